@@ -0,0 +1,255 @@
+use std::{collections::BTreeSet, path::PathBuf, sync::Arc};
+
+use air_codegen_winter::{BaseField, Extension, WinterfellConfig, WinterfellVersion};
+use air_ir::{Pipeline, PipelineOptions};
+use air_parser::{ast::Program, Params};
+use air_pass::Pass;
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler, FileName, Spanned,
+};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Expr, ExprLit, Lit, LitStr, MetaNameValue, Token,
+};
+
+/// The arguments of the [air!] macro: a path, optionally followed by `name = value` options.
+struct AirInput {
+    path: LitStr,
+    config: WinterfellConfig,
+    with_tests: bool,
+    no_std: bool,
+    max_chunk_size: Option<usize>,
+    params: Params,
+}
+impl Parse for AirInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut config = WinterfellConfig::default();
+        let mut with_tests = false;
+        let mut no_std = false;
+        let mut max_chunk_size = None;
+        let mut params = Params::default();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let option = input.parse::<MetaNameValue>()?;
+            if option.path.is_ident("tests") || option.path.is_ident("no_std") {
+                let value = match &option.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Bool(value),
+                        ..
+                    }) => value.value,
+                    value => {
+                        return Err(syn::Error::new_spanned(value, "expected a boolean literal"))
+                    }
+                };
+                if option.path.is_ident("tests") {
+                    with_tests = value;
+                } else {
+                    no_std = value;
+                }
+                continue;
+            }
+            if option.path.is_ident("max_chunk_size") {
+                let value = match &option.value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(value),
+                        ..
+                    }) => value,
+                    value => {
+                        return Err(syn::Error::new_spanned(
+                            value,
+                            "expected an integer literal",
+                        ))
+                    }
+                };
+                let size = value.base10_parse::<usize>()?;
+                if size == 0 {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "the maximum chunk size must be non-zero",
+                    ));
+                }
+                max_chunk_size = Some(size);
+                continue;
+            }
+            let value = match &option.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(value),
+                    ..
+                }) => value,
+                value => return Err(syn::Error::new_spanned(value, "expected a string literal")),
+            };
+            let to_syn_error = |err: anyhow::Error| syn::Error::new_spanned(value, err);
+            if option.path.is_ident("field") {
+                config.base_field = value.value().parse::<BaseField>().map_err(to_syn_error)?;
+            } else if option.path.is_ident("extension") {
+                config.extension = value.value().parse::<Extension>().map_err(to_syn_error)?;
+            } else if option.path.is_ident("winterfell_version") {
+                config.version = value
+                    .value()
+                    .parse::<WinterfellVersion>()
+                    .map_err(to_syn_error)?;
+            } else if option.path.is_ident("param") {
+                let binding = value.value();
+                let (name, param) = binding.split_once('=').ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        value,
+                        "expected a parameter binding of the form 'name=value'",
+                    )
+                })?;
+                let param = param
+                    .parse::<u64>()
+                    .map_err(|err| syn::Error::new_spanned(value, err))?;
+                params.insert(name.to_string(), param);
+            } else {
+                return Err(syn::Error::new_spanned(
+                    option.path,
+                    "unknown option, expected 'field', 'extension', 'winterfell_version', 'tests', 'no_std', 'max_chunk_size', or 'param'",
+                ));
+            }
+        }
+
+        Ok(Self {
+            path,
+            config,
+            with_tests,
+            no_std,
+            max_chunk_size,
+            params,
+        })
+    }
+}
+
+/// Compiles the AirScript file at the given path, and expands to the equivalent implementation of
+/// the Winterfell `Air` trait.
+///
+/// The path is resolved relative to the directory containing the manifest of the crate in which
+/// the macro is invoked. The file, along with the modules it imports and the constant tables it
+/// loads, is tracked as a dependency of that crate, which is recompiled when any of them changes.
+///
+/// The field targeted by the generated code can be selected with the optional `field` (one of
+/// `"f62"`, `"f64"`, or `"f128"`) and `extension` (one of `"none"`, `"quadratic"`, or `"cubic"`)
+/// options, e.g. `air!("system.air", field = "f128", extension = "quadratic")`.
+///
+/// The version of Winterfell targeted by the generated code can be selected with the optional
+/// `winterfell_version` option, one of `"0.5"` (the default) or `"0.6"`.
+///
+/// With `tests = true`, a `#[cfg(test)] mod tests` exercising the generated Air is expanded
+/// alongside it.
+///
+/// With `no_std = true`, the expanded code compiles in a `#![no_std]` crate, which must declare
+/// `extern crate alloc`.
+///
+/// With `max_chunk_size = N`, the transition constraints of each trace segment are evaluated by
+/// helpers of at most `N` constraints each, which keeps very large Airs within what rustc compiles
+/// comfortably.
+///
+/// With `param = "N=16"`, the value 16 is bound to the parameter `N` of the AirScript program,
+/// overriding its default value. The option may be repeated to bind several parameters.
+///
+/// The program is compiled by the same passes as with the `airc` CLI, see [air_ir::Pipeline].
+#[proc_macro]
+pub fn air(input: TokenStream) -> TokenStream {
+    let AirInput {
+        path,
+        config,
+        with_tests,
+        no_std,
+        max_chunk_size,
+        params,
+    } = parse_macro_input!(input as AirInput);
+
+    let mut input_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    input_path.push(path.value());
+
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Never));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+
+    // the files read to compile the program, so that the program is recompiled when they change
+    let mut dependencies = BTreeSet::from([input_path.clone()]);
+    let options = PipelineOptions {
+        params,
+        ..Default::default()
+    };
+    let mut pipeline = Pipeline::new(&diagnostics, codemap.clone(), options);
+    let air = pipeline.parse_file(&input_path).and_then(|ast| {
+        dependencies.extend(dependencies_of(&ast, &codemap));
+        pipeline.run(ast)
+    });
+
+    let air = match air {
+        Ok(air) => air,
+        Err(err) => {
+            diagnostics.emit(err);
+            let message = format!("failed to compile {}", input_path.display());
+            return quote!(compile_error!(#message);).into();
+        }
+    };
+
+    let mut generator = air_codegen_winter::CodeGenerator::new(config)
+        .with_tests(with_tests)
+        .with_no_std(no_std);
+    if let Some(max_chunk_size) = max_chunk_size {
+        generator = generator.with_max_chunk_size(max_chunk_size);
+    }
+    match generator.generate_tokens(&air) {
+        Ok(tokens) => {
+            let dependencies = dependencies.into_iter().map(|path| {
+                let path = std::fs::canonicalize(&path)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string();
+                quote!(
+                    const _: &str = include_str!(#path);
+                )
+            });
+            quote!(#(#dependencies)* #tokens).into()
+        }
+        Err(err) => {
+            let message = format!(
+                "failed to generate code for {}: {err}",
+                input_path.display()
+            );
+            quote!(compile_error!(#message);).into()
+        }
+    }
+}
+
+/// Returns the files, other than the root module, which the provided program was compiled from,
+/// i.e. the imported modules declaring its items, and the files its constant tables were loaded
+/// from.
+fn dependencies_of(program: &Program, codemap: &CodeMap) -> Vec<PathBuf> {
+    let constants = program
+        .constants
+        .values()
+        .chain(program.unused_constants.values());
+    let tables = constants
+        .clone()
+        .filter_map(|constant| constant.table.clone());
+    let spans = constants
+        .map(|constant| constant.span())
+        .chain(
+            program
+                .evaluators
+                .values()
+                .map(|evaluator| evaluator.span()),
+        )
+        .chain(
+            program
+                .periodic_columns
+                .values()
+                .map(|column| column.span()),
+        );
+    let modules = spans.filter_map(|span| match codemap.name(span.source_id()) {
+        Ok(FileName::Real(path)) => Some(path),
+        _ => None,
+    });
+
+    tables.chain(modules).collect()
+}
@@ -0,0 +1,190 @@
+mod binary {
+    air_script_macro::air!("../air-script/tests/binary/binary.air");
+}
+
+mod aux_trace {
+    air_script_macro::air!("../air-script/tests/aux_trace/aux_trace.air");
+}
+
+#[test]
+fn binary_air_expands() {
+    use winter_air::{Air, FieldExtension, ProofOptions, TraceInfo};
+    use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+    let trace_info = TraceInfo::new(2, 8);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 32);
+    let public_inputs = binary::PublicInputs::new([Felt::ZERO; 16]);
+    let air = binary::BinaryAir::new(trace_info, public_inputs, options);
+
+    assert_eq!(air.get_assertions().len(), 1);
+    assert_eq!(air.context().num_main_transition_constraints(), 2);
+}
+
+#[test]
+fn aux_trace_air_expands() {
+    let _ = aux_trace::AuxiliaryAir::last_step;
+}
+
+mod aux_trace_cubic {
+    air_script_macro::air!(
+        "../air-script/tests/aux_trace/aux_trace.air",
+        extension = "cubic",
+        tests = true
+    );
+}
+
+#[test]
+fn aux_trace_air_evaluates_over_cubic_extension() {
+    use winter_air::{Air, AuxTraceRandElements, EvaluationFrame};
+    use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+    type ExtFelt = aux_trace_cubic::ExtFelt;
+
+    let air = aux_trace_cubic::tests::build_air();
+    let mut aux_rand_elements = AuxTraceRandElements::<ExtFelt>::new();
+    let r0 = ExtFelt::new(Felt::new(2), Felt::new(3), Felt::new(5));
+    let r1 = ExtFelt::new(Felt::new(7), Felt::new(11), Felt::new(13));
+    aux_rand_elements.add_segment_elements(vec![r0, r1]);
+
+    // a = 1, b = 2 and c = a + b = 3 in the current row
+    let (a, b, c) = (Felt::new(1), Felt::new(2), Felt::new(3));
+    let main_frame = EvaluationFrame::from_rows(vec![a, b, c], vec![Felt::ZERO; 3]);
+
+    // p0' = p0 * (a + r0 + b + r1) and p1 = p1' * (c + r0) are satisfied over the extension
+    let p0 = ExtFelt::new(Felt::new(17), Felt::new(19), Felt::new(23));
+    let p1_next = ExtFelt::new(Felt::new(29), Felt::new(31), Felt::new(37));
+    let p0_next = p0 * (ExtFelt::from(a) + r0 + ExtFelt::from(b) + r1);
+    let p1 = p1_next * (ExtFelt::from(c) + r0);
+    let aux_frame = EvaluationFrame::from_rows(vec![p0, p1], vec![p0_next, p1_next]);
+
+    let mut result = vec![ExtFelt::ONE; 2];
+    air.evaluate_aux_transition(
+        &main_frame,
+        &aux_frame,
+        &[],
+        &aux_rand_elements,
+        &mut result,
+    );
+    assert_eq!(result, vec![ExtFelt::ZERO; 2]);
+
+    // the constraints no longer hold when the random values are projected on the base field
+    let mut aux_rand_elements = AuxTraceRandElements::<ExtFelt>::new();
+    aux_rand_elements.add_segment_elements(vec![
+        ExtFelt::from(Felt::new(2)),
+        ExtFelt::from(Felt::new(7)),
+    ]);
+    air.evaluate_aux_transition(
+        &main_frame,
+        &aux_frame,
+        &[],
+        &aux_rand_elements,
+        &mut result,
+    );
+    assert!(result.iter().all(|value| *value != ExtFelt::ZERO));
+}
+
+mod pub_inputs {
+    air_script_macro::air!("../air-script/tests/pub_inputs/pub_inputs.air");
+}
+
+#[test]
+fn pub_inputs_to_elements_follow_declaration_order() {
+    use winter_math::fields::f64::BaseElement as Felt;
+    use winter_utils::Serializable;
+
+    fn values<const N: usize>(start: u64) -> [Felt; N] {
+        core::array::from_fn(|i| Felt::new(start + i as u64))
+    }
+    // program_hash, stack_inputs, stack_outputs and overflow_addrs, as declared
+    let inputs = pub_inputs::PublicInputs::new(values(0), values(4), values(8), values(28));
+
+    let elements = inputs.to_elements();
+    assert_eq!(elements, (0..32).map(Felt::new).collect::<Vec<_>>());
+    // the public inputs are serialized in the same order
+    assert_eq!(inputs.to_bytes(), elements.as_slice().to_bytes());
+}
+
+mod constants {
+    air_script_macro::air!("../air-script/tests/constants/constants.air");
+}
+
+mod round_constants {
+    // the constant table is read from `tests/round_constants.json`, which is tracked as a
+    // dependency of this crate along with the AirScript file
+    air_script_macro::air!("tests/round_constants.air");
+}
+
+#[test]
+fn constant_table_air_expands() {
+    use winter_air::{Air, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo};
+    use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+    let trace_info = TraceInfo::new(4, 8);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 32);
+    let public_inputs = round_constants::PublicInputs::new([Felt::ZERO; 4]);
+    let air = round_constants::RoundConstantsAir::new(trace_info, public_inputs, options);
+
+    // each element of the state is incremented by the matching round constant
+    let current = vec![Felt::new(10), Felt::new(20), Felt::new(30), Felt::new(40)];
+    let next = vec![Felt::new(11), Felt::new(22), Felt::new(33), Felt::new(44)];
+    let frame = EvaluationFrame::from_rows(current, next);
+    let mut result = vec![Felt::ONE; 4];
+    air.evaluate_transition(&frame, &[], &mut result);
+    assert_eq!(result, vec![Felt::ZERO; 4]);
+}
+
+mod fibonacci_f64 {
+    air_script_macro::air!("tests/fibonacci.air");
+}
+
+mod fibonacci_f128 {
+    air_script_macro::air!(
+        "tests/fibonacci.air",
+        field = "f128",
+        extension = "quadratic"
+    );
+}
+
+#[test]
+fn fibonacci_air_expands_for_f64() {
+    use winter_air::{Air, FieldExtension, ProofOptions, TraceInfo};
+    use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+    let trace_info = TraceInfo::new(2, 8);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 32);
+    let public_inputs = fibonacci_f64::PublicInputs::new([Felt::new(21)]);
+    let air = fibonacci_f64::FibonacciAir::new(trace_info, public_inputs, options);
+
+    let assertions = air.get_assertions();
+    assert_eq!(assertions.len(), 3);
+    assert_eq!(assertions[2].values(), &[Felt::new(21)]);
+    assert_eq!(assertions[0].values(), &[Felt::ONE]);
+    assert_eq!(air.context().num_main_transition_constraints(), 2);
+}
+
+#[test]
+fn fibonacci_air_expands_for_f128() {
+    use winter_air::{Air, FieldExtension, ProofOptions, TraceInfo};
+    use winter_math::{fields::f128::BaseElement as Felt, FieldElement};
+
+    let trace_info = TraceInfo::new(2, 8);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::Quadratic, 4, 32);
+    let public_inputs = fibonacci_f128::PublicInputs::new([Felt::new(21)]);
+    let air = fibonacci_f128::FibonacciAir::new(trace_info, public_inputs, options);
+
+    let assertions = air.get_assertions();
+    assert_eq!(assertions.len(), 3);
+    assert_eq!(assertions[2].values(), &[Felt::new(21)]);
+    assert_eq!(air.context().num_main_transition_constraints(), 2);
+
+    let one = fibonacci_f128::ExtFelt::ONE;
+    assert_eq!(one + one, fibonacci_f128::ExtFelt::from(2_u64));
+}
+
+#[test]
+fn constants_air_expands() {
+    use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+    assert_eq!(constants::constants::A, Felt::ONE);
+    assert_eq!(constants::constants::C[0][1], Felt::new(2));
+}
@@ -0,0 +1,145 @@
+//! Expands the example AIRs with the generated test module, so that the generated tests are
+//! compiled and run alongside the tests of this crate.
+//!
+//! The examples which declare no main integrity constraints, no main boundary constraints, or an
+//! aux segment without boundary constraints are omitted, as Winterfell cannot instantiate them.
+
+mod aux_trace {
+    air_script_macro::air!("../air-script/tests/aux_trace/aux_trace.air", tests = true);
+}
+
+mod binary {
+    air_script_macro::air!("../air-script/tests/binary/binary.air", tests = true);
+}
+
+mod bitwise {
+    air_script_macro::air!("../air-script/tests/bitwise/bitwise.air", tests = true);
+}
+
+mod constants {
+    air_script_macro::air!("../air-script/tests/constants/constants.air", tests = true);
+}
+
+mod bitwise_chunked {
+    air_script_macro::air!(
+        "../air-script/tests/bitwise/bitwise.air",
+        tests = true,
+        max_chunk_size = 4
+    );
+}
+
+mod empty_aux {
+    air_script_macro::air!("../air-script/tests/empty_aux/empty_aux.air", tests = true);
+}
+
+mod evaluators {
+    air_script_macro::air!(
+        "../air-script/tests/evaluators/evaluators.air",
+        tests = true
+    );
+}
+
+mod exemptions {
+    air_script_macro::air!(
+        "../air-script/tests/exemptions/exemptions.air",
+        tests = true
+    );
+}
+
+mod params {
+    air_script_macro::air!(
+        "../air-script/tests/params/params.air",
+        tests = true,
+        param = "N=4"
+    );
+}
+
+mod periodic_columns {
+    air_script_macro::air!(
+        "../air-script/tests/periodic_columns/periodic_columns.air",
+        tests = true
+    );
+}
+
+mod pub_inputs {
+    air_script_macro::air!(
+        "../air-script/tests/pub_inputs/pub_inputs.air",
+        tests = true
+    );
+}
+
+mod random_values_groups {
+    air_script_macro::air!(
+        "../air-script/tests/random_values/random_values_groups.air",
+        tests = true
+    );
+}
+
+mod selectors {
+    air_script_macro::air!("../air-script/tests/selectors/selectors.air", tests = true);
+}
+
+mod selectors_with_evaluators {
+    air_script_macro::air!(
+        "../air-script/tests/selectors/selectors_with_evaluators.air",
+        tests = true
+    );
+}
+
+mod system {
+    air_script_macro::air!("../air-script/tests/system/system.air", tests = true);
+}
+
+#[test]
+fn evaluate_transition_on_provided_frame() {
+    use winter_air::EvaluationFrame;
+    use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+    // both columns of the binary AIR must hold binary values
+    let frame = EvaluationFrame::from_rows(vec![Felt::ONE, Felt::ZERO], vec![Felt::ZERO; 2]);
+    assert_eq!(
+        binary::tests::evaluate_transition(&frame),
+        vec![Felt::ZERO; 2]
+    );
+
+    let frame = EvaluationFrame::from_rows(vec![Felt::new(2), Felt::ONE], vec![Felt::ZERO; 2]);
+    assert_eq!(
+        binary::tests::evaluate_transition(&frame),
+        vec![Felt::new(2), Felt::ZERO]
+    );
+}
+
+#[test]
+fn chunked_evaluate_transition_matches_unchunked() {
+    use winter_air::EvaluationFrame;
+    use winter_math::fields::f64::BaseElement as Felt;
+
+    let current = (0..14).map(|i| Felt::new(i * 7 + 3)).collect::<Vec<_>>();
+    let next = (0..14).map(|i| Felt::new(i * i + 5)).collect::<Vec<_>>();
+    let frame = EvaluationFrame::from_rows(current, next);
+    assert_eq!(
+        bitwise_chunked::tests::evaluate_transition(&frame),
+        bitwise::tests::evaluate_transition(&frame)
+    );
+}
+
+#[test]
+fn params_are_bound_to_provided_values() {
+    use winter_air::EvaluationFrame;
+    use winter_math::fields::f64::BaseElement as Felt;
+
+    // with N = 4, the clock column is followed by 4 stack columns, each of which must stay equal
+    let current = (0..5).map(Felt::new).collect::<Vec<_>>();
+    let next = [1, 1, 2, 3, 5]
+        .into_iter()
+        .map(Felt::new)
+        .collect::<Vec<_>>();
+    let frame = EvaluationFrame::from_rows(current, next);
+    assert_eq!(
+        params::tests::evaluate_transition(&frame),
+        [0, 0, 0, 0, 1]
+            .into_iter()
+            .map(Felt::new)
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,35 @@
+//! Compiles the prover scaffold generated for the Fibonacci Air, which is checked in by the
+//! `prover_scaffold` test of `air-script`, against the Air expanded by the `air!` macro.
+
+mod fibonacci {
+    air_script_macro::air!("tests/fibonacci.air");
+}
+
+mod fibonacci_prover {
+    include!("scaffold/fibonacci_prover.rs");
+}
+
+#[test]
+fn trace_is_assembled_in_declaration_order() {
+    use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+    use winter_prover::Trace;
+
+    let mut trace = fibonacci_prover::FibonacciTrace::new(8);
+    trace.b[3] = Felt::ONE;
+    let table = trace.into_table();
+
+    assert_eq!(table.width(), fibonacci_prover::FibonacciTrace::WIDTH);
+    assert_eq!(table.length(), 8);
+    assert_eq!(table.get(0, 3), Felt::ZERO);
+    assert_eq!(table.get(1, 3), Felt::ONE);
+}
+
+#[test]
+#[should_panic(expected = "set the values of the columns at row 0")]
+fn prover_runs_until_the_rows_are_filled() {
+    use fibonacci_prover::{FibonacciProver, FibonacciTrace};
+    use winter_prover::{FieldExtension, ProofOptions, Prover};
+
+    let prover = FibonacciProver::new(ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 32));
+    let _ = prover.prove(FibonacciTrace::build(8).into_table());
+}
@@ -0,0 +1,62 @@
+use super::fibonacci::{FibonacciAir, PublicInputs};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_math::FieldElement;
+use winter_utils::collections::Vec;
+use winter_prover::crypto::hashers::Blake3_256;
+use winter_prover::{ProofOptions, Prover, TraceTable};
+/// The columns of the main trace of [FibonacciAir], by their names in AirScript.
+pub struct FibonacciTrace {
+    pub a: Vec<Felt>,
+    pub b: Vec<Felt>,
+}
+impl FibonacciTrace {
+    pub const WIDTH: usize = 2;
+    /// Returns a trace of the specified length, with every column set to zero.
+    pub fn new(length: usize) -> Self {
+        Self {
+            a: vec![Felt::ZERO; length],
+            b: vec![Felt::ZERO; length],
+        }
+    }
+    /// Builds the trace of the specified length, filling its rows in order.
+    pub fn build(length: usize) -> Self {
+        let mut trace = Self::new(length);
+        for row in 0..length {
+            trace.fill_row(row);
+        }
+        trace
+    }
+    /// Sets the values of the columns at the specified row.
+    fn fill_row(&mut self, row: usize) {
+        todo!("set the values of the columns at row {row}")
+    }
+    /// Assembles the columns into a `TraceTable`, in the order in which they are declared.
+    pub fn into_table(self) -> TraceTable<Felt> {
+        let mut columns = Vec::with_capacity(Self::WIDTH);
+        columns.push(self.a);
+        columns.push(self.b);
+        TraceTable::init(columns)
+    }
+}
+/// A prover for [FibonacciAir].
+pub struct FibonacciProver {
+    options: ProofOptions,
+}
+impl FibonacciProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+}
+impl Prover for FibonacciProver {
+    type BaseField = Felt;
+    type Air = FibonacciAir;
+    type Trace = TraceTable<Felt>;
+    type HashFn = Blake3_256<Felt>;
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        // PublicInputs::new(result: [Felt; 1])
+        todo!("read the public inputs from the trace")
+    }
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
@@ -1,7 +1,10 @@
 use std::{fs, path::PathBuf, sync::Arc};
 
-use air_ir::{CodeGenerator, CompileError};
-use air_pass::Pass;
+use air_codegen_masm::{CodegenConfig, FieldExtension, OutputMode};
+use air_codegen_winter::{
+    BaseField, Extension, WinterfellConfig, WinterfellPaths, WinterfellVersion,
+};
+use air_ir::{CodeGenerator, JsonEmitter, Pipeline, PipelineOptions};
 
 use clap::{Args, ValueEnum};
 use miden_diagnostics::{
@@ -12,16 +15,31 @@ use miden_diagnostics::{
 pub enum Target {
     Winterfell,
     Masm,
+    /// A Rust implementation of the `Air` trait of Plonky3
+    Plonky3,
+    /// The constraints re-emitted as AirScript, with all bindings substituted
+    Airscript,
+    /// The constraint graph as a textual IR in static single assignment form
+    Ssa,
 }
 impl Target {
     pub fn extension(&self) -> &'static str {
         match self {
             Self::Winterfell => "rs",
             Self::Masm => "masm",
+            Self::Plonky3 => "rs",
+            Self::Airscript => "flat.air",
+            Self::Ssa => "ssa",
         }
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum ErrorFormat {
+    Human,
+    Json,
+}
+
 #[derive(Args)]
 pub struct Transpile {
     /// Path to input file
@@ -30,7 +48,7 @@ pub struct Transpile {
     #[arg(
         short,
         long,
-        help = "Output filename, defaults to the input file with the .rs extension for Winterfell or .masm for MASM"
+        help = "Output filename, defaults to the input file with the .rs extension for Winterfell and Plonky3, .masm for MASM, .flat.air for AirScript, or .ssa for SSA"
     )]
     output: Option<PathBuf>,
 
@@ -40,54 +58,331 @@ pub struct Transpile {
         help = "Defines the target language, defaults to Winterfell"
     )]
     target: Option<Target>,
+
+    #[arg(
+        long,
+        value_parser = parse_winterfell_paths,
+        help = "Comma-separated 'group=path' mappings of the paths from which the Winterfell code imports each group of items ('air', 'math', or 'utils'), e.g. 'air=miden_air,math=miden_core'"
+    )]
+    winterfell_paths: Option<WinterfellPaths>,
+
+    #[arg(
+        long,
+        value_parser = parse_field,
+        help = "Base field of the generated Winterfell code, one of 'f62', 'f64', or 'f128', defaults to 'f64'. The MASM target only supports 'f64'"
+    )]
+    field: Option<BaseField>,
+
+    #[arg(
+        long,
+        value_parser = parse_extension,
+        help = "Field extension over which the generated Winterfell code evaluates the aux trace, one of 'none', 'quadratic', or 'cubic', defaults to 'none'. The MASM target supports 'quadratic', its default, and 'cubic'"
+    )]
+    extension: Option<Extension>,
+
+    #[arg(
+        long,
+        value_parser = parse_winterfell_version,
+        help = "Version of the Winterfell API targeted by the generated code, one of '0.5' or '0.6', defaults to '0.5'"
+    )]
+    winterfell_version: Option<WinterfellVersion>,
+
+    #[arg(
+        long,
+        value_parser = parse_blowup_factor,
+        help = "Blowup factor targeted by the prover, against which constraints whose degree approaches or exceeds the supported maximum (the blowup factor minus 1) are reported as warnings"
+    )]
+    blowup_factor: Option<usize>,
+
+    #[arg(
+        long,
+        value_parser = parse_trace_length,
+        requires = "blowup_factor",
+        help = "Trace length targeted by the prover, for which the constraints whose quotient exceeds the degree bound of the composition polynomial (the trace length times the blowup factor) are reported as errors. Requires '--blowup-factor'"
+    )]
+    trace_length: Option<usize>,
+
+    #[arg(
+        long,
+        value_parser = parse_constraint_limit,
+        value_delimiter = ',',
+        help = "Comma-separated maximum numbers of boundary and integrity constraints of each trace segment, in order, e.g. '255,64', of which each segment exceeding its limit is reported as an error. The limit of a segment may be left empty to leave it unbounded, e.g. ',64'"
+    )]
+    constraint_limits: Vec<Option<usize>>,
+
+    #[arg(
+        long,
+        help = "Reports the integrity constraints against auxiliary trace segments which do not reference any random value as warnings, as they can usually be enforced against the main trace segment"
+    )]
+    check_aux_random_values: bool,
+
+    #[arg(
+        long,
+        help = "Rewrites the boolean checks of the integrity constraints, e.g. 'a * (a - 1) = 0' or 'a * (1 - a) = 0', to the canonical form 'a^2 - a = 0'"
+    )]
+    normalize_boolean_checks: bool,
+
+    #[arg(
+        long,
+        help = "Rebalances the long chains of additions and multiplications of the constraints, e.g. 'a + b + c + d', into trees of minimal depth, e.g. '(a + b) + (c + d)'"
+    )]
+    balance_expressions: bool,
+
+    #[arg(
+        long,
+        help = "Removes the constraints which are a constant multiple of another constraint enforced on the same rows, e.g. 'enf 3 * (a - b) = 0' along with 'enf a = b', and reports a warning for each one"
+    )]
+    merge_scalar_multiples: bool,
+
+    #[arg(
+        long,
+        help = "Also generate the scaffold of a Winterfell prover for the Air, next to the output file and named after it with a '_prover' suffix, which imports the Air from a sibling module named after the output file"
+    )]
+    prover: bool,
+
+    #[arg(
+        long,
+        help = "Emit the MASM code as a library module, with a module doc comment, exporting the procedures evaluating the constraints and keeping the other procedures internal"
+    )]
+    library: bool,
+
+    #[arg(
+        long = "param",
+        value_parser = parse_param,
+        help = "Binds a value to a parameter of the AIR declared with 'param', as 'name=value', e.g. 'N=16', overriding its default value. May be repeated to bind several parameters"
+    )]
+    params: Vec<(String, u64)>,
+
+    #[arg(
+        long,
+        help = "Enables the field inverses, written as exponentiations by a negative constant, e.g. 'a^(-1)'. They are not supported by the Winterfell and MASM targets"
+    )]
+    inverses: bool,
+
+    #[arg(
+        long,
+        help = "Permits accesses to public inputs in integrity constraints, in which they are row-invariant values of degree 0. By default, public inputs may only be accessed in boundary constraints"
+    )]
+    integrity_public_inputs: bool,
+
+    #[arg(
+        long,
+        help = "Defines how diagnostics are reported, defaults to human-readable diagnostics on stderr. With 'json', each diagnostic is printed to stdout as a single line of JSON, with its severity, stable code, message, span and notes, and nothing else is printed to stdout"
+    )]
+    error_format: Option<ErrorFormat>,
+}
+
+fn parse_winterfell_paths(paths: &str) -> Result<WinterfellPaths, String> {
+    paths
+        .parse::<WinterfellPaths>()
+        .map_err(|err| err.to_string())
+}
+
+fn parse_field(field: &str) -> Result<BaseField, String> {
+    field.parse::<BaseField>().map_err(|err| err.to_string())
+}
+
+fn parse_extension(extension: &str) -> Result<Extension, String> {
+    extension
+        .parse::<Extension>()
+        .map_err(|err| err.to_string())
+}
+
+fn parse_winterfell_version(version: &str) -> Result<WinterfellVersion, String> {
+    version
+        .parse::<WinterfellVersion>()
+        .map_err(|err| err.to_string())
+}
+
+fn parse_param(param: &str) -> Result<(String, u64), String> {
+    let (name, value) = param.split_once('=').ok_or_else(|| {
+        format!("expected a parameter binding of the form 'name=value', got '{param}'")
+    })?;
+    let value = value.parse::<u64>().map_err(|err| err.to_string())?;
+    Ok((name.to_string(), value))
+}
+
+fn parse_blowup_factor(blowup_factor: &str) -> Result<usize, String> {
+    let blowup_factor = blowup_factor
+        .parse::<usize>()
+        .map_err(|err| err.to_string())?;
+    if blowup_factor < 2 || !blowup_factor.is_power_of_two() {
+        return Err(format!(
+            "the blowup factor must be a power of two greater than 1, but was {blowup_factor}"
+        ));
+    }
+    Ok(blowup_factor)
+}
+
+fn parse_trace_length(trace_length: &str) -> Result<usize, String> {
+    let trace_length = trace_length
+        .parse::<usize>()
+        .map_err(|err| err.to_string())?;
+    if trace_length < 2 || !trace_length.is_power_of_two() {
+        return Err(format!(
+            "the trace length must be a power of two greater than 1, but was {trace_length}"
+        ));
+    }
+    Ok(trace_length)
+}
+
+fn parse_constraint_limit(limit: &str) -> Result<Option<usize>, String> {
+    match limit.trim() {
+        "" => Ok(None),
+        limit => limit
+            .parse::<usize>()
+            .map(Some)
+            .map_err(|err| err.to_string()),
+    }
 }
 
 impl Transpile {
     pub fn execute(&self) -> Result<(), String> {
-        println!("============================================================");
-        println!("Transpiling...");
+        self.report("============================================================");
+        self.report("Transpiling...");
+
+        let target = self.target.unwrap_or(Target::Winterfell);
+        if self.prover && target != Target::Winterfell {
+            return Err("a prover scaffold can only be generated for the Winterfell target".into());
+        }
+        if self.library && target != Target::Masm {
+            return Err("a library module can only be generated for the MASM target".into());
+        }
+        // the MASM code evaluates the constraints over an extension of the 64-bit field, which
+        // the field options must agree with when they are set
+        let masm_extension = match self.extension.unwrap_or(Extension::Quadratic) {
+            Extension::Quadratic => Some(FieldExtension::Quadratic),
+            Extension::Cubic => Some(FieldExtension::Cubic),
+            Extension::None => None,
+        };
+        if target == Target::Masm
+            && (self.field.unwrap_or(BaseField::F64) != BaseField::F64 || masm_extension.is_none())
+        {
+            return Err(
+                "the MASM target only supports the quadratic and cubic extensions of the 64-bit field"
+                    .into(),
+            );
+        }
 
         let input_path = &self.input;
 
+        // in json mode, each diagnostic is printed to stdout as a single line of JSON, which is
+        // then the only output on stdout
         let codemap = Arc::new(CodeMap::new());
-        let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
-        let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
-
-        // Parse from file to internal representation
-        let air = air_parser::parse_file(&diagnostics, codemap, input_path)
-            .map_err(CompileError::Parse)
-            .and_then(|ast| {
-                let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
-                    .chain(air_parser::transforms::Inlining::new(&diagnostics))
-                    .chain(air_ir::passes::AstToAir::new(&diagnostics));
-                pipeline.run(ast)
-            });
-
-        match air {
-            Ok(air) => {
+        let diagnostics = match self.error_format.unwrap_or(ErrorFormat::Human) {
+            ErrorFormat::Human => {
+                let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+                DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter)
+            }
+            ErrorFormat::Json => {
+                let emitter = Arc::new(JsonEmitter::stdout(codemap.clone()));
+                DiagnosticsHandler::new(
+                    JsonEmitter::config(Default::default()),
+                    codemap.clone(),
+                    emitter,
+                )
+            }
+        };
+
+        // the passes run on the program, which are shared with the other frontends, and which
+        // record the time spent in each phase with the `metrics` feature
+        let options = PipelineOptions {
+            params: self.params.iter().cloned().collect(),
+            inverses: self.inverses,
+            integrity_public_inputs: self.integrity_public_inputs,
+            normalize_boolean_checks: self.normalize_boolean_checks,
+            merge_scalar_multiples: self.merge_scalar_multiples,
+            balance_expressions: self.balance_expressions,
+            blowup_factor: self.blowup_factor,
+            trace_length: self.trace_length,
+            constraint_limits: self.constraint_limits.clone(),
+            check_aux_random_values: self.check_aux_random_values,
+        };
+        let mut pipeline = Pipeline::new(&diagnostics, codemap.clone(), options);
+
+        // Parse from file to internal representation, with an Air for each of the root modules
+        let airs = pipeline.compile_programs_file(input_path);
+
+        match airs {
+            Ok(airs) => {
                 // generate Rust code targeting Winterfell
-                let target = self.target.unwrap_or(Target::Winterfell);
-                let backend: Box<dyn CodeGenerator<Output = String>> = match target {
-                    Target::Winterfell => Box::new(air_codegen_winter::CodeGenerator),
-                    Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
+                let config = WinterfellConfig {
+                    base_field: self.field.unwrap_or_default(),
+                    extension: self.extension.unwrap_or_default(),
+                    version: self.winterfell_version.unwrap_or_default(),
                 };
+                let winterfell = air_codegen_winter::CodeGenerator::new(config)
+                    .with_source_map(codemap.clone())
+                    .with_paths(self.winterfell_paths.clone().unwrap_or_default());
 
-                // write transpiled output to the output path
-                let output_path = match &self.output {
-                    Some(path) => path.clone(),
-                    None => {
-                        let mut path = input_path.clone();
-                        path.set_extension(target.extension());
-                        path
+                for (name, air) in airs.iter() {
+                    // write transpiled output to the output path, which is suffixed with the name
+                    // of the program when the input defines several of them
+                    let program = if airs.len() > 1 {
+                        Some(name.as_str())
+                    } else {
+                        None
+                    };
+                    let output_path = self.output_path(target, program);
+                    let code = pipeline
+                        .timings()
+                        .time("codegen", || match target {
+                            Target::Winterfell => winterfell.generate(air),
+                            Target::Masm => air_codegen_masm::CodeGenerator::new(CodegenConfig {
+                                extension: masm_extension.unwrap_or_default(),
+                                output_mode: if self.library {
+                                    OutputMode::Library
+                                } else {
+                                    OutputMode::Executable
+                                },
+                                ..Default::default()
+                            })
+                            .with_source_map(codemap.clone())
+                            .generate(air),
+                            Target::Plonky3 => {
+                                air_codegen_plonky3::CodeGenerator::new().generate(air)
+                            }
+                            Target::Airscript => air_ir::FlatAirScript::new().generate(air),
+                            Target::Ssa => air_ir::SsaForm::new().generate(air),
+                        })
+                        .map_err(|err| format!("code generation failed: {err}"))?;
+                    if let Err(err) = fs::write(&output_path, code) {
+                        return Err(format!("{err:?}"));
+                    }
+
+                    self.report(format!("Success! Transpiled to {}", output_path.display()));
+
+                    // write the prover scaffold next to the output, importing the Air from a
+                    // sibling module named after the output
+                    if self.prover {
+                        let module = output_path
+                            .file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .ok_or_else(|| {
+                                format!("invalid output path {}", output_path.display())
+                            })?;
+                        let prover_path = output_path.with_file_name(format!("{module}_prover.rs"));
+                        let code = winterfell
+                            .generate_prover(air, &format!("super::{module}"))
+                            .map_err(|err| format!("code generation failed: {err}"))?;
+                        if let Err(err) = fs::write(&prover_path, code) {
+                            return Err(format!("{err:?}"));
+                        }
+
+                        self.report(format!(
+                            "Generated the prover scaffold in {}",
+                            prover_path.display()
+                        ));
                     }
-                };
-                let code = backend.generate(&air).expect("code generation failed");
-                if let Err(err) = fs::write(&output_path, code) {
-                    return Err(format!("{err:?}"));
                 }
 
-                println!("Success! Transpiled to {}", output_path.display());
-                println!("============================================================");
+                #[cfg(feature = "metrics")]
+                self.report(format!(
+                    "Timings:\n{}",
+                    pipeline.timings().report().trim_end()
+                ));
+
+                self.report("============================================================");
 
                 Ok(())
             }
@@ -97,4 +392,32 @@ impl Transpile {
             }
         }
     }
+
+    /// Prints a progress message to stdout, unless the diagnostics are printed there as JSON
+    fn report(&self, message: impl std::fmt::Display) {
+        if self.error_format.unwrap_or(ErrorFormat::Human) == ErrorFormat::Human {
+            println!("{message}");
+        }
+    }
+
+    /// Returns the path of the output generated for `program`, which is only given when the input
+    /// defines several programs, and is then appended to the name of the output file
+    fn output_path(&self, target: Target, program: Option<&str>) -> PathBuf {
+        let (path, extension) = match &self.output {
+            Some(path) => match program {
+                Some(_) => (path, path.extension().map(|ext| ext.to_os_string())),
+                None => return path.clone(),
+            },
+            None => (&self.input, Some(target.extension().into())),
+        };
+        let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+        if let Some(program) = program {
+            file_name.push(format!("_{program}"));
+        }
+        if let Some(extension) = extension {
+            file_name.push(".");
+            file_name.push(extension);
+        }
+        path.with_file_name(file_name)
+    }
 }
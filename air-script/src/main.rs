@@ -30,6 +30,7 @@ pub fn main() {
     };
 
     if let Err(error) = res {
-        println!("{error}");
+        eprintln!("{error}");
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,32 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+/// The constraints of `BinaryAir`, enforced against a Plonky3 `AirBuilder`.
+///
+/// The public values are the public inputs, in declaration order: `stack_inputs` at 0..16.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BinaryAir;
+impl<F> BaseAir<F> for BinaryAir {
+    fn width(&self) -> usize {
+        2
+    }
+}
+impl<AB: AirBuilderWithPublicValues> Air<AB> for BinaryAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();
+        builder
+            .when_first_row()
+            .assert_zero(local[0].clone() - AB::Expr::from_canonical_u64(0));
+        builder
+            .assert_zero(
+                ((local[0].clone().exp_u64(2)) - local[0].clone())
+                    - AB::Expr::from_canonical_u64(0),
+            );
+        builder
+            .assert_zero(
+                ((local[1].clone().exp_u64(2)) - local[1].clone())
+                    - AB::Expr::from_canonical_u64(0),
+            );
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// Runs `airc transpile` on the AirScript file at `input`, writing the output to a temporary file
+/// named after `output`, with the provided extra arguments.
+fn transpile(input: &str, output: &str, args: &[&str]) -> Output {
+    let output_path: PathBuf = std::env::temp_dir().join(output);
+    Command::new(env!("CARGO_BIN_EXE_airc"))
+        .arg("transpile")
+        .arg(input)
+        .arg("--output")
+        .arg(output_path)
+        .args(args)
+        .output()
+        .expect("failed to run airc")
+}
+
+/// Asserts that `output` is the one of a failed run reporting `error`, rather than of a panic
+fn assert_error(output: Output, error: &str) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(
+        output.status.code(),
+        Some(1),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(stderr.contains(error), "stderr: {stderr}");
+    assert!(!stderr.contains("panicked"), "stderr: {stderr}");
+}
+
+#[test]
+fn transpile_succeeds() {
+    let output = transpile(
+        "tests/binary/binary.air",
+        "airc_cli_binary.rs",
+        &["--target", "winterfell"],
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn err_inverses_with_masm_target() {
+    let output = transpile(
+        "tests/inverses/inverses.air",
+        "airc_cli_inverses.masm",
+        &["--target", "masm", "--inverses"],
+    );
+    assert_error(
+        output,
+        "code generation failed: field inverses are not supported by the Miden assembly backend",
+    );
+}
+
+#[test]
+fn err_exemptions_with_masm_target() {
+    let output = transpile(
+        "tests/exemptions/exemptions.air",
+        "airc_cli_exemptions.masm",
+        &["--target", "masm"],
+    );
+    assert_error(
+        output,
+        "code generation failed: integrity constraints exempted from rows of the trace are not supported by the Miden assembly backend",
+    );
+}
+
+#[test]
+fn err_composition_degree_exceeded() {
+    let output = transpile(
+        "tests/selectors/selectors.air",
+        "airc_cli_composition_degree.rs",
+        &["--blowup-factor", "2", "--trace-length", "8"],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_error(output, "compilation failed");
+    assert!(
+        stderr
+            .contains("constraint quotient exceeds the degree bound of the composition polynomial"),
+        "stderr: {stderr}"
+    );
+
+    // the bound is met with a larger blowup factor
+    let output = transpile(
+        "tests/selectors/selectors.air",
+        "airc_cli_composition_degree.rs",
+        &["--blowup-factor", "4", "--trace-length", "8"],
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn err_constraint_limit_exceeded() {
+    let output = transpile(
+        "tests/binary/binary.air",
+        "airc_cli_constraint_limit.rs",
+        &["--constraint-limits", "2"],
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_error(output, "compilation failed");
+    assert!(stderr.contains("too many constraints"), "stderr: {stderr}");
+
+    // segments without a limit are unbounded
+    let output = transpile(
+        "tests/binary/binary.air",
+        "airc_cli_constraint_limit.rs",
+        &["--constraint-limits", "3,"],
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn transpile_multiple_programs() {
+    let output = transpile(
+        "tests/programs/programs.air",
+        "airc_cli_programs.rs",
+        &["--target", "winterfell"],
+    );
+    assert!(output.status.success());
+
+    // each of the programs is written to the output file suffixed with its name
+    for program in ["BinaryAir", "CounterAir"] {
+        let output_path = std::env::temp_dir().join(format!("airc_cli_programs_{program}.rs"));
+        let code = std::fs::read_to_string(&output_path).expect("missing output");
+        assert!(code.contains(&format!("pub struct {program} ")), "{code}");
+    }
+}
+
+#[test]
+fn json_diagnostics() {
+    let input_path = std::env::temp_dir().join("airc_cli_undefined.air");
+    std::fs::write(
+        &input_path,
+        "def test
+trace_columns:
+    main: [clk]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints:
+    enf clk.first = 0
+integrity_constraints:
+    enf clk' = clk + x
+",
+    )
+    .unwrap();
+    let output = transpile(
+        input_path.to_str().unwrap(),
+        "airc_cli_undefined.rs",
+        &["--error-format", "json"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert_error(output, "compilation failed");
+
+    // stdout only holds the diagnostics, one per line, starting with the undefined variable
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2, "stdout: {stdout}");
+    assert!(lines
+        .iter()
+        .all(|line| line.starts_with('{') && line.ends_with('}')));
+    assert!(
+        lines[0].starts_with(
+            r#"{"severity":"error","code":"E0507","message":"reference to undefined variable","span":{"#
+        ),
+        "stdout: {stdout}"
+    );
+    assert!(
+        lines[0].contains(r#""start":166,"end":null,"line":9,"column":22}"#),
+        "stdout: {stdout}"
+    );
+    assert!(lines[1].contains(r#""code":"E0211""#), "stdout: {stdout}");
+    assert!(!stderr.contains("undefined variable"), "stderr: {stderr}");
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use air_ir::{CodeGenerator, CompileError};
+use air_ir::{Air, CodeGenerator, CompileError};
 use air_pass::Pass;
 use miden_diagnostics::{
     term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
@@ -9,6 +9,7 @@ use miden_diagnostics::{
 pub enum Target {
     Winterfell,
     Masm,
+    Plonky3,
 }
 
 pub struct Test {
@@ -20,26 +21,42 @@ impl Test {
     }
 
     pub fn transpile(&self, target: Target) -> Result<String, CompileError> {
+        let air = self.compile()?;
+
+        let backend: Box<dyn CodeGenerator<Output = String>> = match target {
+            Target::Winterfell => Box::<air_codegen_winter::CodeGenerator>::default(),
+            Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
+            Target::Plonky3 => Box::<air_codegen_plonky3::CodeGenerator>::default(),
+        };
+
+        // generate Rust code targeting Winterfell
+        Ok(backend.generate(&air).expect("code generation failed"))
+    }
+
+    pub fn compile(&self) -> Result<Air, CompileError> {
+        self.compile_with_codemap().map(|(air, _)| air)
+    }
+
+    /// Compiles the input, and returns the IR along with the [CodeMap] containing its source
+    pub fn compile_with_codemap(&self) -> Result<(Air, Arc<CodeMap>), CompileError> {
         let codemap = Arc::new(CodeMap::new());
         let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
         let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
 
         // Parse from file to internal representation
-        let air = air_parser::parse_file(&diagnostics, codemap, &self.input_path)
+        let air = air_parser::parse_file(&diagnostics, codemap.clone(), &self.input_path)
             .map_err(CompileError::Parse)
             .and_then(|ast| {
+                // the passes run by the CLI by default, so that the generated code is the one of
+                // `airc transpile`
                 let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
                     .chain(air_parser::transforms::Inlining::new(&diagnostics))
-                    .chain(air_ir::passes::AstToAir::new(&diagnostics));
+                    .chain(air_ir::passes::AstToAir::new(&diagnostics))
+                    .chain(air_ir::passes::UnusedDeclarations::new(&diagnostics))
+                    .chain(air_ir::passes::NextRowOnlyColumns::new(&diagnostics));
                 pipeline.run(ast)
             })?;
 
-        let backend: Box<dyn CodeGenerator<Output = String>> = match target {
-            Target::Winterfell => Box::new(air_codegen_winter::CodeGenerator),
-            Target::Masm => Box::<air_codegen_masm::CodeGenerator>::default(),
-        };
-
-        // generate Rust code targeting Winterfell
-        Ok(backend.generate(&air).expect("code generation failed"))
+        Ok((air, codemap))
     }
 }
@@ -24,6 +24,17 @@ fn binary() {
     expected.assert_eq(&generated_masm);
 }
 
+#[test]
+fn empty_aux() {
+    // an aux trace declared without columns generates the same code as no aux trace
+    let generated_masm = Test::new("tests/empty_aux/empty_aux.air".to_string())
+        .transpile(Target::Masm)
+        .unwrap();
+
+    let expected = expect_file!["../empty_aux/empty_aux.masm"];
+    expected.assert_eq(&generated_masm);
+}
+
 #[test]
 fn periodic_columns() {
     let generated_masm = Test::new("tests/periodic_columns/periodic_columns.air".to_string())
@@ -185,3 +196,64 @@ fn constraint_comprehension() {
     let expected = expect_file!["../constraint_comprehension/constraint_comprehension.masm"];
     expected.assert_eq(&generated_masm);
 }
+
+#[test]
+fn source_comments() {
+    use air_ir::CodeGenerator;
+
+    let (air, codemap) = Test::new("tests/binary/binary.air".to_string())
+        .compile_with_codemap()
+        .unwrap();
+    let generated_masm = air_codegen_masm::CodeGenerator::default()
+        .with_source_map(codemap)
+        .generate(&air)
+        .unwrap();
+
+    // each constraint is preceded by its source
+    let integrity =
+        "# integrity constraint 0 for main, degree 2\n    # tests/binary/binary.air:13:9: enf a^2 - a = 0\n";
+    assert!(generated_masm.contains(integrity));
+    let boundary =
+        "# boundary constraint 0 for main, degree 1\n    # tests/binary/binary.air:10:9: enf a.first = 0\n";
+    assert!(generated_masm.contains(boundary));
+
+    // comments are only emitted when the source is available
+    let generated_masm = air_codegen_masm::CodeGenerator::default()
+        .generate(&air)
+        .unwrap();
+    assert!(!generated_masm.contains(".air:"));
+}
+
+#[test]
+fn constraint_comments_are_ordered() {
+    use air_ir::CodeGenerator;
+
+    let (air, codemap) = Test::new("tests/binary/binary.air".to_string())
+        .compile_with_codemap()
+        .unwrap();
+    let generated_masm = air_codegen_masm::CodeGenerator::default()
+        .with_source_map(codemap)
+        .generate(&air)
+        .unwrap();
+
+    // the procedures summarize the constraints they cover, which are then emitted in order
+    let expected = [
+        "# Covers the main integrity constraints 0 to 1.",
+        "proc.compute_integrity_constraints",
+        "# integrity constraint 0 for main, degree 2",
+        "# tests/binary/binary.air:13:9: enf a^2 - a = 0",
+        "# integrity constraint 1 for main, degree 2",
+        "# tests/binary/binary.air:14:9: enf b^2 - b = 0",
+        "# Covers the boundary constraints 0.",
+        "proc.compute_boundary_constraints_main_first",
+        "# boundary constraint 0 for main, degree 1",
+        "# tests/binary/binary.air:10:9: enf a.first = 0",
+    ];
+    let mut position = 0;
+    for comment in expected {
+        let offset = generated_masm[position..]
+            .find(comment)
+            .unwrap_or_else(|| panic!("missing or misplaced comment: {comment}"));
+        position += offset + comment.len();
+    }
+}
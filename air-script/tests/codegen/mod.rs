@@ -1,3 +1,4 @@
 mod helpers;
 mod masm;
+mod plonky3;
 mod winterfell;
@@ -0,0 +1,51 @@
+use super::helpers::{Target, Test};
+use expect_test::expect_file;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn binary() {
+    let generated_air = Test::new("tests/binary/binary.air".to_string())
+        .transpile(Target::Plonky3)
+        .unwrap();
+
+    let expected = expect_file!["../binary/binary_plonky3.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn exemptions() {
+    // the exempted rows widen the transition window of the constraint
+    let generated_air = Test::new("tests/exemptions/exemptions.air".to_string())
+        .transpile(Target::Plonky3)
+        .unwrap();
+
+    let expected = expect_file!["../exemptions/exemptions_plonky3.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn pub_inputs() {
+    // the public inputs are laid out one after the other in the public values
+    let generated_air = Test::new("tests/pub_inputs/pub_inputs.air".to_string())
+        .transpile(Target::Plonky3)
+        .unwrap();
+
+    let expected = expect_file!["../pub_inputs/pub_inputs_plonky3.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn aux_trace_is_rejected() {
+    let air = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .compile()
+        .unwrap();
+
+    let err = air_ir::CodeGenerator::generate(&air_codegen_plonky3::CodeGenerator::new(), &air)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "auxiliary trace segments are not supported by the Plonky3 backend"
+    );
+}
@@ -24,6 +24,21 @@ fn binary() {
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn empty_aux() {
+    // an aux trace declared without columns generates the same code as no aux trace, without
+    // the methods of the aux trace
+    let generated_air = Test::new("tests/empty_aux/empty_aux.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+    assert!(!generated_air.contains("fn evaluate_aux_transition"));
+    assert!(!generated_air.contains("fn get_aux_assertions"));
+    assert!(!generated_air.contains("AuxTraceRandElements"));
+
+    let expected = expect_file!["../empty_aux/empty_aux.rs"];
+    expected.assert_eq(&generated_air);
+}
+
 #[test]
 fn periodic_columns() {
     let generated_air = Test::new("tests/periodic_columns/periodic_columns.air".to_string())
@@ -128,10 +143,39 @@ fn random_values() {
         .transpile(Target::Winterfell)
         .unwrap();
 
-    let expected = expect_file!["../random_values/random_values.rs"];
+    let expected = expect_file!["../random_values/random_values_bindings.rs"];
     expected.assert_eq(&generated_air);
 }
 
+#[test]
+fn random_values_struct() {
+    let generated_air = Test::new("tests/random_values/random_values_groups.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    // each binding is sliced from the random values in declaration order, regardless of its name
+    assert!(generated_air.contains("pub struct RandValues<'a, E> {"));
+    assert!(generated_air.contains("impl<'a, E> RandValues<'a, E> {"));
+    assert!(generated_air.contains("betas: &rand_elements[0..2],"));
+    assert!(generated_air.contains("gamma: &rand_elements[2..3],"));
+    assert!(generated_air.contains("alphas: &rand_elements[3..6],"));
+
+    // accesses go through the struct, including flat accesses of elements declared by a binding
+    let declaration =
+        "let rand_values = RandValues::new(aux_rand_elements.get_segment_elements(0));";
+    assert_eq!(generated_air.matches(declaration).count(), 2);
+    assert!(generated_air.contains("rand_values.betas[1]"));
+    assert!(generated_air.contains("rand_values.alphas[2]"));
+    assert!(generated_air.contains("rand_values.gamma[0]"));
+    assert!(!generated_air.contains("aux_rand_elements.get_segment_elements(0)["));
+
+    // random values declared without bindings have no struct
+    let generated_air = Test::new("tests/random_values/random_values_simple.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+    assert!(!generated_air.contains("RandValues"));
+}
+
 #[test]
 fn list_comprehension() {
     let generated_air = Test::new("tests/list_comprehension/list_comprehension.air".to_string())
@@ -187,3 +231,596 @@ fn constraint_comprehension() {
     let expected = expect_file!["../constraint_comprehension/constraint_comprehension.rs"];
     expected.assert_eq(&generated_air);
 }
+
+#[test]
+fn exemptions() {
+    let generated_air = Test::new("tests/exemptions/exemptions.air".to_string())
+        .transpile(Target::Winterfell)
+        .unwrap();
+
+    let expected = expect_file!["../exemptions/exemptions.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn token_generation() {
+    use air_ir::CodeGenerator;
+
+    let sources = [
+        "tests/aux_trace/aux_trace.air",
+        "tests/binary/binary.air",
+        "tests/bitwise/bitwise.air",
+        "tests/constants/constants.air",
+        "tests/constraint_comprehension/cc_with_evaluators.air",
+        "tests/constraint_comprehension/constraint_comprehension.air",
+        "tests/docs/docs.air",
+        "tests/empty_aux/empty_aux.air",
+        "tests/evaluators/evaluators.air",
+        "tests/exemptions/exemptions.air",
+        "tests/indexed_trace_access/indexed_trace_access.air",
+        "tests/list_comprehension/list_comprehension.air",
+        "tests/list_folding/list_folding.air",
+        "tests/periodic_columns/periodic_columns.air",
+        "tests/pub_inputs/pub_inputs.air",
+        "tests/random_values/random_values_bindings.air",
+        "tests/random_values/random_values_groups.air",
+        "tests/random_values/random_values_simple.air",
+        "tests/selectors/selectors.air",
+        "tests/selectors/selectors_with_evaluators.air",
+        "tests/system/system.air",
+        "tests/trace_col_groups/trace_col_groups.air",
+        "tests/variables/variables.air",
+    ];
+
+    for source in sources {
+        let air = Test::new(source.to_string()).compile().unwrap();
+
+        let generator = air_codegen_winter::CodeGenerator::default();
+        let from_tokens = syn::parse2::<syn::File>(generator.generate_tokens(&air).unwrap())
+            .unwrap_or_else(|err| panic!("invalid tokens generated for {source}: {err}"));
+        let from_string = generator.generate(&air).unwrap();
+        let from_string = syn::parse_file(&from_string).unwrap();
+
+        assert_eq!(
+            prettyplease::unparse(&from_tokens),
+            prettyplease::unparse(&from_string),
+            "token output differs from string output for {source}"
+        );
+    }
+}
+
+#[test]
+fn doc_strings() {
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/docs/docs.air".to_string())
+        .compile()
+        .unwrap();
+    let generated_air = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap();
+
+    // the doc strings of the public inputs document the fields of the PublicInputs struct
+    assert!(generated_air.contains(
+        "pub struct PublicInputs {\n    /// hash of the executed program\n    program_hash: [Felt; 4],\n    stack_inputs: [Felt; 16],"
+    ));
+    // and the doc strings of the trace columns document the Air struct
+    assert!(generated_air.contains(
+        "/// Trace columns:\n/// main[0] clk: clock cycle\n/// main[1..3] fmp: free memory pointer\n/// aux[0] p: running product\npub struct DocsAir {"
+    ));
+}
+
+#[test]
+fn field_configuration() {
+    use air_codegen_winter::{BaseField, Extension, WinterfellConfig};
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/binary/binary.air".to_string())
+        .compile()
+        .unwrap();
+    let config = WinterfellConfig {
+        base_field: BaseField::F128,
+        extension: Extension::Quadratic,
+        ..Default::default()
+    };
+    let generator = air_codegen_winter::CodeGenerator::new(config);
+
+    let from_string = generator.generate(&air).unwrap();
+    assert!(from_string.contains("use winter_math::fields::f128::BaseElement as Felt;"));
+    assert!(from_string.contains("use winter_math::fields::QuadExtension;"));
+    assert!(from_string.contains("pub type ExtFelt = QuadExtension<Felt>;"));
+
+    let from_tokens = syn::parse2::<syn::File>(generator.generate_tokens(&air).unwrap()).unwrap();
+    let from_string = syn::parse_file(&from_string).unwrap();
+    assert_eq!(
+        prettyplease::unparse(&from_tokens),
+        prettyplease::unparse(&from_string)
+    );
+
+    // the cubic extension is not supported for the 128-bit field
+    let generator = air_codegen_winter::CodeGenerator::new(WinterfellConfig {
+        base_field: BaseField::F128,
+        extension: Extension::Cubic,
+        ..Default::default()
+    });
+    assert!(generator.generate(&air).is_err());
+}
+
+#[test]
+fn winterfell_version() {
+    use air_codegen_winter::{WinterfellConfig, WinterfellVersion};
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/pub_inputs/pub_inputs.air".to_string())
+        .compile()
+        .unwrap();
+
+    // Winterfell 0.5 requires the public inputs to be serializable
+    let generator = air_codegen_winter::CodeGenerator::default();
+    let from_string = generator.generate(&air).unwrap();
+    assert!(from_string.contains("impl Serializable for PublicInputs {"));
+    assert!(from_string.contains("pub fn to_elements(&self) -> Vec<Felt> {"));
+    assert!(!from_string.contains("ToElements"));
+
+    // Winterfell 0.6 requires them to be convertible to field elements instead
+    let config = WinterfellConfig {
+        version: WinterfellVersion::V0_6,
+        ..Default::default()
+    };
+    let generator = air_codegen_winter::CodeGenerator::new(config);
+    let from_string = generator.generate(&air).unwrap();
+    assert!(from_string.contains("use winter_math::{FieldElement, ToElements};"));
+    assert!(from_string.contains("impl ToElements<Felt> for PublicInputs {"));
+    assert!(!from_string.contains("Serializable"));
+    assert!(!from_string.contains("pub fn to_elements"));
+
+    let from_tokens = syn::parse2::<syn::File>(generator.generate_tokens(&air).unwrap()).unwrap();
+    let parsed = syn::parse_file(&from_string).unwrap();
+    assert_eq!(
+        prettyplease::unparse(&from_tokens),
+        prettyplease::unparse(&parsed)
+    );
+
+    // and provers to select their random coin
+    let generated_prover = generator.generate_prover(&air, "crate::air").unwrap();
+    assert!(generated_prover.contains("use winter_prover::crypto::DefaultRandomCoin;"));
+    assert!(generated_prover.contains("type RandomCoin = DefaultRandomCoin<Self::HashFn>;"));
+}
+
+#[test]
+fn constants_must_fit_in_field() {
+    use air_codegen_winter::{BaseField, WinterfellConfig};
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/fields/large_constant.air".to_string())
+        .compile()
+        .unwrap();
+
+    // 2^62 fits in the 64-bit field, but not in the 62-bit field
+    let generator = air_codegen_winter::CodeGenerator::new(WinterfellConfig {
+        base_field: BaseField::F64,
+        ..Default::default()
+    });
+    assert!(generator.generate(&air).is_ok());
+
+    let generator = air_codegen_winter::CodeGenerator::new(WinterfellConfig {
+        base_field: BaseField::F62,
+        ..Default::default()
+    });
+    let err = generator.generate(&air).unwrap_err();
+    assert!(err.to_string().contains("does not fit in the f62 field"));
+}
+
+#[test]
+fn rational_constants_require_64_bit_field() {
+    use air_codegen_winter::{BaseField, WinterfellConfig};
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/fields/rational_constant.air".to_string())
+        .compile()
+        .unwrap();
+
+    // `1/2` is evaluated in the 64-bit field, so its value is wrong in any other field
+    let generator = air_codegen_winter::CodeGenerator::new(WinterfellConfig {
+        base_field: BaseField::F64,
+        ..Default::default()
+    });
+    assert!(generator.generate(&air).is_ok());
+
+    let generator = air_codegen_winter::CodeGenerator::new(WinterfellConfig {
+        base_field: BaseField::F128,
+        ..Default::default()
+    });
+    let err = generator.generate(&air).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("the constant 'RationalConstantAir::HALF' contains a rational number"));
+}
+
+#[test]
+fn generated_test_module() {
+    use air_ir::CodeGenerator;
+
+    let generator = air_codegen_winter::CodeGenerator::default().with_tests(true);
+    for path in [
+        "tests/binary/binary.air",
+        "tests/aux_trace/aux_trace.air",
+        "tests/periodic_columns/periodic_columns.air",
+    ] {
+        let air = Test::new(path.to_string()).compile().unwrap();
+
+        let from_string = generator.generate(&air).unwrap();
+        assert!(from_string.contains("pub mod tests {"));
+
+        let from_tokens =
+            syn::parse2::<syn::File>(generator.generate_tokens(&air).unwrap()).unwrap();
+        let from_string = syn::parse_file(&from_string).unwrap();
+        assert_eq!(
+            prettyplease::unparse(&from_tokens),
+            prettyplease::unparse(&from_string)
+        );
+    }
+
+    // the test module is only generated on request
+    let air = Test::new("tests/binary/binary.air".to_string())
+        .compile()
+        .unwrap();
+    let generated_air = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap();
+    assert!(!generated_air.contains("mod tests"));
+}
+
+#[test]
+fn source_comments() {
+    use air_ir::CodeGenerator;
+
+    let (air, codemap) = Test::new("tests/binary/binary.air".to_string())
+        .compile_with_codemap()
+        .unwrap();
+    let generated_air = air_codegen_winter::CodeGenerator::default()
+        .with_source_map(codemap)
+        .generate(&air)
+        .unwrap();
+
+    // the summary lists every constraint by index, ahead of the Air implementation
+    let summary = generated_air.find("// Constraint sources:").unwrap();
+    assert!(summary < generated_air.find("impl Air for BinaryAir").unwrap());
+    assert!(generated_air.contains("//   [0] tests/binary/binary.air:10: enf a.first = 0"));
+    assert!(generated_air.contains("//   [1] tests/binary/binary.air:14: enf b^2 - b = 0"));
+
+    // each constraint is preceded by its source
+    let transition = "// tests/binary/binary.air:13: enf a^2 - a = 0\n        result[0] = ";
+    assert!(generated_air.contains(transition));
+    let assertion = "// tests/binary/binary.air:10: enf a.first = 0\n        result.push(";
+    assert!(generated_air.contains(assertion));
+
+    // comments are only emitted when the source is available
+    let generated_air = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap();
+    assert!(!generated_air.contains(".air:"));
+    assert!(!generated_air.contains("// Constraint sources:"));
+}
+
+#[test]
+fn annotated_output() {
+    use air_ir::CodeGenerator;
+
+    // covers the formatting of source comments and of the test module, so that changes to the
+    // layout of the generated code show up in review
+    let (air, codemap) = Test::new("tests/system/system.air".to_string())
+        .compile_with_codemap()
+        .unwrap();
+    let generated_air = air_codegen_winter::CodeGenerator::default()
+        .with_tests(true)
+        .with_source_map(codemap)
+        .generate(&air)
+        .unwrap();
+
+    let expected = expect_file!["../system/system_annotated.rs"];
+    expected.assert_eq(&generated_air);
+}
+
+#[test]
+fn deterministic_output() {
+    use air_ir::CodeGenerator;
+
+    // each program is compiled from scratch for every generation, so that any output depending on
+    // the iteration order of hashed collections would differ between the two generations
+    let sources = [
+        "tests/aux_trace/aux_trace.air",
+        "tests/constraint_comprehension/constraint_comprehension.air",
+        "tests/evaluators/evaluators.air",
+        "tests/periodic_columns/periodic_columns.air",
+        "tests/selectors/selectors.air",
+    ];
+    for source in sources {
+        let generate = || {
+            let (air, codemap) = Test::new(source.to_string())
+                .compile_with_codemap()
+                .unwrap();
+            air_codegen_winter::CodeGenerator::default()
+                .with_tests(true)
+                .with_source_map(codemap)
+                .generate(&air)
+                .unwrap()
+        };
+        assert_eq!(
+            generate(),
+            generate(),
+            "output differs between runs for {source}"
+        );
+    }
+}
+
+#[test]
+fn prover_scaffold() {
+    // the scaffold is compiled against the Air expanded by the `air!` macro in the tests of
+    // `air-script-macro`, as a sibling module named `fibonacci`
+    let air = Test::new("../air-script-macro/tests/fibonacci.air".to_string())
+        .compile()
+        .unwrap();
+    let generator = air_codegen_winter::CodeGenerator::default();
+    let generated_prover = generator.generate_prover(&air, "super::fibonacci").unwrap();
+
+    let expected = expect_file!["../../../air-script-macro/tests/scaffold/fibonacci_prover.rs"];
+    expected.assert_eq(&generated_prover);
+
+    // groups of columns are held as arrays of columns, and the aux trace is left to the user
+    let air = Test::new("tests/trace_col_groups/trace_col_groups.air".to_string())
+        .compile()
+        .unwrap();
+    let generated_prover = generator.generate_prover(&air, "crate::air").unwrap();
+    assert!(generated_prover.contains("use crate::air::{TraceColGroupAir, PublicInputs};"));
+    assert!(generated_prover.contains("pub fmp: [Vec<Felt>; 2],"));
+    assert!(generated_prover.contains("columns.extend(self.fmp);"));
+    assert!(generated_prover.contains("pub const WIDTH: usize = 4;"));
+    assert!(generated_prover.contains("impl Prover for TraceColGroupProver {"));
+    assert!(generated_prover.contains("proving requires a custom `Trace` implementation"));
+}
+
+#[test]
+fn import_paths() {
+    use air_codegen_winter::{BaseField, Extension, WinterfellConfig, WinterfellPaths};
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/aux_trace/aux_trace.air".to_string())
+        .compile()
+        .unwrap();
+    let paths = "air=miden_air::winter, math=miden_core::math, utils=miden_core::utils"
+        .parse::<WinterfellPaths>()
+        .unwrap();
+    let config = WinterfellConfig {
+        base_field: BaseField::F64,
+        extension: Extension::Quadratic,
+        ..Default::default()
+    };
+    let generator = air_codegen_winter::CodeGenerator::new(config)
+        .with_tests(true)
+        .with_paths(paths);
+
+    let from_string = generator.generate(&air).unwrap();
+    for import in [
+        "use miden_air::winter::{\n    Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame,\n    ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo,\n};",
+        "use miden_core::math::fields::f64::BaseElement as Felt;",
+        "use miden_core::math::fields::QuadExtension;",
+        "use miden_core::math::{ExtensionOf, FieldElement};",
+        "use miden_core::utils::collections::Vec;",
+        "use miden_core::utils::{ByteWriter, Serializable};",
+        "use miden_air::winter::{FieldExtension, TraceLayout};",
+    ] {
+        assert!(from_string.contains(import), "missing import: {import}");
+    }
+    // the Winterfell crates are never referenced directly
+    assert!(!from_string.contains("winter_"));
+    // the test module instantiates the Air with the configured extension
+    assert!(from_string.contains("FieldExtension::Quadratic"));
+    assert!(from_string.contains("AuxTraceRandElements::<ExtFelt>::new()"));
+
+    let from_tokens = syn::parse2::<syn::File>(generator.generate_tokens(&air).unwrap()).unwrap();
+    let from_string = syn::parse_file(&from_string).unwrap();
+    assert_eq!(
+        prettyplease::unparse(&from_tokens),
+        prettyplease::unparse(&from_string)
+    );
+
+    // groups which are not mapped keep their default path
+    let paths = "math=miden_core::math".parse::<WinterfellPaths>().unwrap();
+    assert_eq!(paths.air, "winter_air");
+    assert_eq!(paths.utils, "winter_utils");
+
+    // mappings must name a known group and a valid path
+    assert!("maths=miden_core".parse::<WinterfellPaths>().is_err());
+    assert!("air=miden air".parse::<WinterfellPaths>().is_err());
+    assert!("air".parse::<WinterfellPaths>().is_err());
+}
+
+#[test]
+fn chunked_transition_constraints() {
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/large_air/large_air.air".to_string())
+        .compile()
+        .unwrap();
+    let generator = air_codegen_winter::CodeGenerator::default().with_max_chunk_size(256);
+
+    let from_string = generator.generate(&air).unwrap();
+    let from_tokens = syn::parse2::<syn::File>(generator.generate_tokens(&air).unwrap()).unwrap();
+    let from_string_file = syn::parse_file(&from_string).unwrap();
+    assert_eq!(
+        prettyplease::unparse(&from_tokens),
+        prettyplease::unparse(&from_string_file)
+    );
+
+    // returns the body of the generated function with the given signature prefix
+    let body = |name: &str| {
+        let (_, body) = from_string
+            .split_once(&format!("fn {name}"))
+            .unwrap_or_else(|| panic!("missing function {name}"));
+        let (_, body) = body.split_once('{').unwrap();
+        body.split_once("\n    }\n").unwrap().0.to_string()
+    };
+    // removes the whitespace of the provided code, so that calls can be matched however they are
+    // wrapped
+    let compact = |code: &str| code.split_whitespace().collect::<String>();
+
+    // 3000 main constraints are split in 12 chunks of at most 256 constraints, which together
+    // assign every entry of the result exactly once, in order
+    let evaluate_transition = body("evaluate_transition<");
+    let mut next_index = 0;
+    for chunk in 0..12 {
+        let call = format!(
+            "self.evaluate_transition_chunk_{chunk}(main_current,main_next,periodic_values,result"
+        );
+        assert!(
+            compact(&evaluate_transition).contains(&call),
+            "missing call: {call}"
+        );
+
+        let chunk_body = body(&format!("evaluate_transition_chunk_{chunk}<"));
+        let assignments = chunk_body.matches("result[").count();
+        assert!(assignments <= 256);
+        assert!(chunk_body.contains(&format!("result[{next_index}] =")));
+        next_index += assignments;
+    }
+    assert_eq!(next_index, 3000);
+    assert!(!evaluate_transition.contains("result["));
+    assert!(!from_string.contains("evaluate_transition_chunk_12"));
+
+    // the calls are emitted in order
+    let positions = (0..12)
+        .map(|chunk| {
+            evaluate_transition
+                .find(&format!("evaluate_transition_chunk_{chunk}("))
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // 500 aux constraints are split in 2 chunks
+    let evaluate_aux_transition = body("evaluate_aux_transition<");
+    assert!(compact(&evaluate_aux_transition).contains("self.evaluate_aux_transition_chunk_0(main_current,main_next,aux_current,aux_next,aux_rand_elements,result"));
+    assert!(evaluate_aux_transition.contains("self.evaluate_aux_transition_chunk_1("));
+    assert!(!from_string.contains("evaluate_aux_transition_chunk_2"));
+    assert_eq!(
+        body("evaluate_aux_transition_chunk_1<")
+            .matches("result[")
+            .count(),
+        244
+    );
+
+    // without a maximum chunk size, the constraints are evaluated inline
+    let unchunked = air_codegen_winter::CodeGenerator::default()
+        .generate(&air)
+        .unwrap();
+    assert!(!unchunked.contains("_chunk_"));
+    assert_eq!(unchunked.matches("result[").count(), 3500);
+}
+
+#[test]
+fn aux_exemptions_are_rejected() {
+    use air_ir::CodeGenerator;
+
+    let air = Test::new("tests/exemptions/aux_exemptions.air".to_string())
+        .compile()
+        .unwrap();
+
+    let generator = air_codegen_winter::CodeGenerator::default();
+    let err = generator.generate(&air).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("aux trace constraints exempted from more than 2 rows are not supported"));
+    assert!(generator.generate_tokens(&air).is_err());
+}
+
+mod exemptions_air {
+    // the prover depends on Winterfell 0.6, which requires the public inputs to implement
+    // `ToElements`
+    air_script_macro::air!(
+        "tests/exemptions/exemptions.air",
+        winterfell_version = "0.6"
+    );
+}
+
+/// A prover for the AIR of `tests/exemptions/exemptions.air`, whose counter `a` is exempted from
+/// its transition constraint on the last 4 rows of the trace
+struct ExemptionsProver {
+    options: winter_prover::ProofOptions,
+}
+impl ExemptionsProver {
+    fn new() -> Self {
+        use winter_prover::{FieldExtension, ProofOptions};
+
+        Self {
+            options: ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 31),
+        }
+    }
+
+    /// Builds a trace of the specified length, on which the counter is reset from the specified
+    /// row onwards, while `b' = b + a` holds on every row.
+    fn build_trace(
+        trace_length: usize,
+        reset_row: usize,
+    ) -> winter_prover::TraceTable<winter_math::fields::f64::BaseElement> {
+        use winter_math::{fields::f64::BaseElement as Felt, FieldElement};
+
+        let mut trace = winter_prover::TraceTable::new(2, trace_length);
+        trace.fill(
+            |state| {
+                state[0] = Felt::ZERO;
+                state[1] = Felt::new(5);
+            },
+            |step, state| {
+                state[1] += state[0];
+                state[0] = if step + 1 < reset_row {
+                    state[0] + Felt::ONE
+                } else {
+                    Felt::ZERO
+                };
+            },
+        );
+        trace
+    }
+}
+impl winter_prover::Prover for ExemptionsProver {
+    type BaseField = winter_math::fields::f64::BaseElement;
+    type Air = exemptions_air::ExemptionsAir;
+    type Trace = winter_prover::TraceTable<Self::BaseField>;
+    type HashFn = winter_prover::crypto::hashers::Blake3_256<Self::BaseField>;
+    type RandomCoin = winter_prover::crypto::DefaultRandomCoin<Self::HashFn>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> exemptions_air::PublicInputs {
+        exemptions_air::PublicInputs::new([trace.get(1, 0)])
+    }
+
+    fn options(&self) -> &winter_prover::ProofOptions {
+        &self.options
+    }
+}
+
+#[test]
+fn prove_with_violations_in_exempted_rows() {
+    use winter_prover::Prover;
+
+    // the counter is reset on the last 3 rows, so that `a' = a + 1` is only violated by the
+    // transition from the 4th row from the end, which is exempted
+    for trace_length in [8, 32, 128] {
+        let trace = ExemptionsProver::build_trace(trace_length, trace_length - 3);
+        ExemptionsProver::new()
+            .prove(trace)
+            .expect("proving failed");
+    }
+}
+
+// the trace is only validated against the constraints by the prover in debug builds
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "main transition constraint 0 did not evaluate to ZERO at step 27")]
+fn prove_with_violations_in_enforced_rows() {
+    use winter_prover::Prover;
+
+    // the transition from the 5th row from the end is not exempted
+    let trace = ExemptionsProver::build_trace(32, 32 - 4);
+    let _ = ExemptionsProver::new().prove(trace);
+}
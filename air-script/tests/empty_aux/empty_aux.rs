@@ -0,0 +1,89 @@
+use winter_air::{
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions as WinterProofOptions,
+    TransitionConstraintDegree, TraceInfo,
+};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_math::FieldElement;
+use winter_utils::collections::Vec;
+use winter_utils::{ByteWriter, Serializable};
+pub struct PublicInputs {
+    stack_inputs: [Felt; 16],
+}
+impl PublicInputs {
+    pub fn new(stack_inputs: [Felt; 16]) -> Self {
+        Self { stack_inputs }
+    }
+    /// Returns the values of the public inputs, concatenated in the order in which they are declared.
+    pub fn to_elements(&self) -> Vec<Felt> {
+        let mut elements = Vec::with_capacity(16);
+        elements.extend_from_slice(&self.stack_inputs);
+        elements
+    }
+}
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.stack_inputs.as_slice());
+    }
+}
+pub struct EmptyAuxAir {
+    context: AirContext<Felt>,
+    stack_inputs: [Felt; 16],
+}
+impl EmptyAuxAir {
+    pub fn last_step(&self) -> usize {
+        self.trace_length() - self.context().num_transition_exemptions()
+    }
+}
+impl Air for EmptyAuxAir {
+    type BaseField = Felt;
+    type PublicInputs = PublicInputs;
+    fn context(&self) -> &AirContext<Felt> {
+        &self.context
+    }
+    fn new(
+        trace_info: TraceInfo,
+        public_inputs: PublicInputs,
+        options: WinterProofOptions,
+    ) -> Self {
+        let main_degrees = vec![
+            TransitionConstraintDegree::new(1), TransitionConstraintDegree::new(1)
+        ];
+        let aux_degrees = vec![];
+        let num_main_assertions = 2;
+        let num_aux_assertions = 0;
+        let context = AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                num_main_assertions,
+                num_aux_assertions,
+                options,
+            )
+            .set_num_transition_exemptions(2);
+        Self {
+            context,
+            stack_inputs: public_inputs.stack_inputs,
+        }
+    }
+    fn get_periodic_column_values(&self) -> Vec<Vec<Felt>> {
+        vec![]
+    }
+    fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        let mut result = Vec::new();
+        result.push(Assertion::single(0, 0, Felt::ZERO));
+        result.push(Assertion::single(1, 0, self.stack_inputs[0]));
+        result
+    }
+    fn evaluate_transition<E: FieldElement<BaseField = Felt>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let main_current = frame.current();
+        let main_next = frame.next();
+        // transition constraints, which reference the next row
+        result[0] = main_next[0] - (main_current[0] + E::ONE);
+        result[1] = main_next[1] - (main_current[1] + main_current[0]);
+    }
+}
@@ -0,0 +1,39 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+/// The constraints of `ExemptionsAir`, enforced against a Plonky3 `AirBuilder`.
+///
+/// The public values are the public inputs, in declaration order: `stack_inputs` at 0..1.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ExemptionsAir;
+impl<F> BaseAir<F> for ExemptionsAir {
+    fn width(&self) -> usize {
+        2
+    }
+}
+impl<AB: AirBuilderWithPublicValues> Air<AB> for ExemptionsAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();
+        let next: Vec<AB::Expr> = main.row_slice(1).iter().map(|&v| v.into()).collect();
+        let public_values: Vec<AB::Expr> = builder
+            .public_values()
+            .iter()
+            .map(|&v| v.into())
+            .collect();
+        builder
+            .when_first_row()
+            .assert_zero(local[0].clone() - AB::Expr::from_canonical_u64(0));
+        builder
+            .when_first_row()
+            .assert_zero(local[1].clone() - public_values[0].clone());
+        builder
+            .when_transition_window(5)
+            .assert_zero(
+                next[0].clone() - (local[0].clone() + AB::Expr::from_canonical_u64(1)),
+            );
+        builder
+            .when_transition()
+            .assert_zero(next[1].clone() - (local[1].clone() + local[0].clone()));
+    }
+}
@@ -1 +1,3 @@
+mod cli;
 mod codegen;
+mod metrics;
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use air_ir::{CodeGenerator, CompileError};
+use air_pass::{Pass, PhaseTimings};
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+};
+
+#[test]
+fn phase_timings_are_populated_and_ordered() {
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+    let timings = PhaseTimings::new();
+
+    let air = timings
+        .time("parse", || {
+            air_parser::parse_file(&diagnostics, codemap, "tests/binary/binary.air")
+        })
+        .map_err(CompileError::Parse)
+        .and_then(|ast| {
+            let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
+                .timed("constant_propagation", &timings)
+                .chain(
+                    air_parser::transforms::Inlining::new(&diagnostics).timed("inlining", &timings),
+                )
+                .chain(air_ir::passes::AstToAir::new(&diagnostics).timed("ast_to_air", &timings));
+            pipeline.run(ast)
+        })
+        .unwrap();
+    timings
+        .time("codegen", || {
+            air_codegen_winter::CodeGenerator::default().generate(&air)
+        })
+        .unwrap();
+
+    let phases = timings.phases();
+    let names = phases.iter().map(|phase| phase.name).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "parse",
+            "constant_propagation",
+            "inlining",
+            "ast_to_air",
+            "codegen"
+        ]
+    );
+
+    // each phase starts after the previous one ended
+    for pair in phases.windows(2) {
+        assert!(pair[1].start >= pair[0].start + pair[0].duration);
+    }
+    assert!(phases.iter().any(|phase| !phase.duration.is_zero()));
+
+    // the report has a line per phase, in order
+    let report = timings.report();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), phases.len());
+    assert!(lines[0].starts_with("parse"));
+    assert!(lines[4].starts_with("codegen"));
+}
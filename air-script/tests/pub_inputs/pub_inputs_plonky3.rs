@@ -0,0 +1,48 @@
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+/// The constraints of `PubInputsAir`, enforced against a Plonky3 `AirBuilder`.
+///
+/// The public values are the public inputs, in declaration order: `program_hash` at 0..4, `stack_inputs` at 4..8, `stack_outputs` at 8..28, `overflow_addrs` at 28..32.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PubInputsAir;
+impl<F> BaseAir<F> for PubInputsAir {
+    fn width(&self) -> usize {
+        4
+    }
+}
+impl<AB: AirBuilderWithPublicValues> Air<AB> for PubInputsAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();
+        let next: Vec<AB::Expr> = main.row_slice(1).iter().map(|&v| v.into()).collect();
+        let public_values: Vec<AB::Expr> = builder
+            .public_values()
+            .iter()
+            .map(|&v| v.into())
+            .collect();
+        builder
+            .when_first_row()
+            .assert_zero(local[0].clone() - public_values[4].clone());
+        builder
+            .when_first_row()
+            .assert_zero(local[1].clone() - public_values[5].clone());
+        builder
+            .when_first_row()
+            .assert_zero(local[2].clone() - public_values[6].clone());
+        builder
+            .when_first_row()
+            .assert_zero(local[3].clone() - public_values[7].clone());
+        builder.when_last_row().assert_zero(local[0].clone() - public_values[8].clone());
+        builder.when_last_row().assert_zero(local[1].clone() - public_values[9].clone());
+        builder
+            .when_last_row()
+            .assert_zero(local[2].clone() - public_values[10].clone());
+        builder
+            .when_last_row()
+            .assert_zero(local[3].clone() - public_values[11].clone());
+        builder
+            .when_transition()
+            .assert_zero(next[0].clone() - (local[1].clone() + local[2].clone()));
+    }
+}
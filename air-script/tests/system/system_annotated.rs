@@ -0,0 +1,129 @@
+use winter_air::{
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions as WinterProofOptions,
+    TransitionConstraintDegree, TraceInfo,
+};
+use winter_math::fields::f64::BaseElement as Felt;
+use winter_math::FieldElement;
+use winter_utils::collections::Vec;
+use winter_utils::{ByteWriter, Serializable};
+pub struct PublicInputs {
+    stack_inputs: [Felt; 16],
+}
+impl PublicInputs {
+    pub fn new(stack_inputs: [Felt; 16]) -> Self {
+        Self { stack_inputs }
+    }
+    /// Returns the values of the public inputs, concatenated in the order in which they are declared.
+    pub fn to_elements(&self) -> Vec<Felt> {
+        let mut elements = Vec::with_capacity(16);
+        elements.extend_from_slice(&self.stack_inputs);
+        elements
+    }
+}
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.stack_inputs.as_slice());
+    }
+}
+pub struct SystemAir {
+    context: AirContext<Felt>,
+    stack_inputs: [Felt; 16],
+}
+impl SystemAir {
+    pub fn last_step(&self) -> usize {
+        self.trace_length() - self.context().num_transition_exemptions()
+    }
+}
+// Constraint sources:
+// main assertions:
+//   [0] tests/system/system.air:13: enf clk.first = 0
+// main transition constraints:
+//   [0] tests/system/system.air:10: enf clk' = clk + 1
+impl Air for SystemAir {
+    type BaseField = Felt;
+    type PublicInputs = PublicInputs;
+    fn context(&self) -> &AirContext<Felt> {
+        &self.context
+    }
+    fn new(
+        trace_info: TraceInfo,
+        public_inputs: PublicInputs,
+        options: WinterProofOptions,
+    ) -> Self {
+        let main_degrees = vec![TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![];
+        let num_main_assertions = 1;
+        let num_aux_assertions = 0;
+        let context = AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                num_main_assertions,
+                num_aux_assertions,
+                options,
+            )
+            .set_num_transition_exemptions(2);
+        Self {
+            context,
+            stack_inputs: public_inputs.stack_inputs,
+        }
+    }
+    fn get_periodic_column_values(&self) -> Vec<Vec<Felt>> {
+        vec![]
+    }
+    fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+        let mut result = Vec::new();
+        // tests/system/system.air:13: enf clk.first = 0
+        result.push(Assertion::single(0, 0, Felt::ZERO));
+        result
+    }
+    fn evaluate_transition<E: FieldElement<BaseField = Felt>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let main_current = frame.current();
+        let main_next = frame.next();
+        // transition constraints, which reference the next row
+        // tests/system/system.air:10: enf clk' = clk + 1
+        result[0] = main_next[0] - (main_current[0] + E::ONE);
+    }
+}
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use winter_air::{FieldExtension, TraceLayout};
+    /// Returns an instance of the Air, instantiated with zeroed public inputs.
+    pub fn build_air() -> SystemAir {
+        let layout = TraceLayout::new(3, [0], [0]);
+        let trace_info = TraceInfo::new_multi_segment(layout, 8, vec![]);
+        let options = WinterProofOptions::new(32, 8, 0, FieldExtension::None, 4, 32);
+        let public_inputs = PublicInputs::new([Felt::ZERO; 16]);
+        SystemAir::new(trace_info, public_inputs, options)
+    }
+    /// Evaluates the main transition constraints of the Air against the provided frame.
+    pub fn evaluate_transition(frame: &EvaluationFrame<Felt>) -> Vec<Felt> {
+        let air = build_air();
+        let periodic_values = vec![Felt::ZERO; 0];
+        let mut result = vec![Felt::ZERO; 1];
+        air.evaluate_transition(frame, &periodic_values, &mut result);
+        result
+    }
+    #[test]
+    fn air_can_be_constructed() {
+        let air = build_air();
+        assert_eq!(air.context().num_main_transition_constraints(), 1);
+        assert_eq!(air.context().num_aux_transition_constraints(), 0);
+    }
+    #[test]
+    fn assertion_counts() {
+        let air = build_air();
+        assert_eq!(air.get_assertions().len(), 1);
+    }
+    #[test]
+    fn evaluate_transition_on_zero_frame() {
+        let frame = EvaluationFrame::new(3);
+        assert_eq!(evaluate_transition(& frame).len(), 1);
+    }
+}
@@ -1,9 +1,9 @@
-use crate::config::CodegenConfig;
+use crate::config::{CodegenConfig, FieldExtension, OutputMode};
 use crate::constants::{AUX_TRACE, MAIN_TRACE};
 use crate::error::CodegenError;
 use crate::utils::{
-    boundary_group_to_procedure_name, load_quadratic_element, periodic_group_to_memory_offset,
-    quadratic_element_square,
+    boundary_divisor_procedure_name, boundary_group_to_procedure_name, load_element,
+    load_quadratic_element, periodic_group_to_memory_offset,
 };
 use crate::visitor::{
     walk_boundary_constraints, walk_integrity_constraints, walk_periodic_columns, AirVisitor,
@@ -13,25 +13,42 @@ use air_ir::{
     Air, ConstraintDomain, ConstraintRoot, Identifier, NodeIndex, Operation, PeriodicColumn,
     TraceSegmentId, Value,
 };
-use miden_core::{Felt, StarkField};
+use miden_core::{Felt, FieldElement, StarkField};
+use miden_diagnostics::CodeMap;
+use std::borrow::Cow;
 use std::collections::btree_map::BTreeMap;
 use std::mem::{replace, take};
+use std::ops::Range;
+use std::sync::Arc;
 use winter_math::fft;
 
 #[derive(Default)]
 pub struct CodeGenerator {
     config: CodegenConfig,
+    codemap: Option<Arc<CodeMap>>,
 }
 impl CodeGenerator {
     pub fn new(config: CodegenConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            codemap: None,
+        }
+    }
+
+    /// Sets the [CodeMap] containing the AirScript program from which the IR was compiled.
+    ///
+    /// When set, the source location and text of each constraint is emitted as a
+    /// `# <file>:<line>:<column>: enf ...` comment above the code evaluating it.
+    pub fn with_source_map(mut self, codemap: Arc<CodeMap>) -> Self {
+        self.codemap = Some(codemap);
+        self
     }
 }
 impl air_ir::CodeGenerator for CodeGenerator {
     type Output = String;
 
     fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
-        let generator = Backend::new(ir, self.config);
+        let generator = Backend::new(ir, self.config, self.codemap.as_deref());
         generator.generate()
     }
 }
@@ -73,15 +90,30 @@ struct Backend<'ast> {
     /// Maps the public input to their start offset.
     public_input_to_offset: BTreeMap<Identifier, usize>,
 
+    /// Number of intermediate values kept on the stack by the expression being evaluated, i.e.
+    /// the evaluated left operands waiting for their right operands.
+    stack_depth: usize,
+
+    /// Number of intermediate values currently spilled to the scratch memory, the counter is the
+    /// scratch slot of the next spilled value.
+    spilled_values: u32,
+
+    /// Maps the index of each shared subexpression evaluated by the procedure being emitted to
+    /// the position of its value in the scratch memory, as expected by [load_element].
+    shared_values: BTreeMap<usize, u32>,
+
     /// The [Air] to visit.
     ir: &'ast Air,
 
     /// Configuration for the codegen.
     config: CodegenConfig,
+
+    /// The [CodeMap] containing the source of the constraints, if available.
+    codemap: Option<&'ast CodeMap>,
 }
 
 impl<'ast> Backend<'ast> {
-    fn new(ir: &'ast Air, config: CodegenConfig) -> Self {
+    fn new(ir: &'ast Air, config: CodegenConfig, codemap: Option<&'ast CodeMap>) -> Self {
         // remove duplicates and sort period lengths in descending order, since larger periods will
         // have smaller number of cycles (which means a smaller number of exponentiations)
         let mut periods: Vec<usize> = ir.periodic_columns().map(|e| e.period()).collect();
@@ -127,9 +159,319 @@ impl<'ast> Backend<'ast> {
             boundary_contraints: 0,
             boundary_constraint_count,
             public_input_to_offset,
+            stack_depth: 0,
+            spilled_values: 0,
+            shared_values: BTreeMap::new(),
             ir,
             config,
+            codemap,
+        }
+    }
+
+    /// Emits a comment with the `<file>:<line>:<column>: enf <source>` description of the provided
+    /// constraint, if its source is known.
+    fn gen_source_comment(&mut self, constraint: &ConstraintRoot) {
+        let Some(codemap) = self.codemap else {
+            return;
+        };
+        let (Ok(file), Ok(location), Ok(source)) = (
+            codemap.name_for_spanned(constraint),
+            codemap.location(constraint),
+            codemap.source_slice(constraint),
+        ) else {
+            return;
+        };
+        // constraints spanning multiple lines are collapsed onto a single line
+        let source = source.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        self.writer.header(format!(
+            "{file}:{}:{}: enf {source}",
+            location.line.number(),
+            location.column.number()
+        ));
+    }
+
+    /// Returns the description of the degree of the provided constraint, including the cycles
+    /// of the periodic columns it depends on, if any.
+    fn degree_description(&self, constraint: &ConstraintRoot) -> String {
+        let degree = self.ir.constraint_graph().degree(constraint.node_index());
+        if degree.cycles().is_empty() {
+            format!("degree {}", degree.base())
+        } else {
+            format!(
+                "degree {}, periodic cycles {:?}",
+                degree.base(),
+                degree.cycles()
+            )
+        }
+    }
+
+    /// Emits the code to raise the extension element at the top of the stack to the power `exp`,
+    /// which must be at least 2, via square-and-multiply.
+    ///
+    /// The base is squared once per bit of the exponent, and multiplied into an accumulator once
+    /// per set bit, so `exp` is computed with less than `2 * log2(exp)` multiplications. The
+    /// accumulator is initialized with the first power of the base it needs, and is skipped
+    /// altogether if `exp` is a power of two.
+    fn gen_square_and_multiply(&mut self, exp: usize) {
+        let extension = self.config.extension;
+        let mut e = exp;
+        let mut has_accumulator = false;
+        while e != 0 {
+            self.writer
+                .header(format!("square {} times", e.trailing_zeros()));
+            extension.square(&mut self.writer, e.trailing_zeros());
+
+            // account for the exponentiations done above
+            e >>= e.trailing_zeros();
+            assert!(
+                e & 1 == 1,
+                "this loop is only executed if the number is non-zero"
+            );
+            e ^= 1;
+
+            if has_accumulator {
+                self.writer.header("multiply");
+                extension.dup(&mut self.writer);
+                extension.movdn2(&mut self.writer);
+                self.writer.header("=> [b, r, b, ...]");
+
+                extension.mul(&mut self.writer);
+                extension.swap(&mut self.writer);
+                self.writer.header("=> [b, r', ...]");
+            } else if e != 0 {
+                self.writer
+                    .header("initialize the accumulator with the base");
+                extension.dup(&mut self.writer);
+                self.writer.header("=> [b, r, ...]");
+                has_accumulator = true;
+            }
+        }
+
+        if has_accumulator {
+            self.writer.header("clean stack");
+            extension.drop(&mut self.writer);
+            self.writer.header("=> [r, ...]");
+        }
+    }
+
+    /// Returns the value of the specified node, if it is a constant of the base field.
+    fn constant(&self, index: &NodeIndex) -> Option<Felt> {
+        match self.ir.constraint_graph().node(index).op() {
+            Operation::Value(Value::Constant(value)) => Some(Felt::new(*value)),
+            _ => None,
+        }
+    }
+
+    /// Emits the code of an operation with a constant operand, and returns true, or returns false
+    /// without emitting anything if none of its operands is a constant.
+    ///
+    /// The operations of constants are folded into a single constant, while the other operand of
+    /// an addition, a subtraction or a multiplication by a constant is evaluated and combined
+    /// with the constant through the immediate form of the instructions of the base field, e.g.
+    /// `mul.6`, instead of pushing the constant as an extension element. The identities `x + 0`,
+    /// `x - 0` and `x * 1` only evaluate `x`, and `x * 0` doesn't evaluate `x` at all. This is
+    /// disabled by [CodegenConfig::fold_constants].
+    fn gen_constant_operation(&mut self, op: &'ast Operation) -> Result<bool, CodegenError> {
+        if !self.config.fold_constants {
+            return Ok(false);
+        }
+
+        let extension = self.config.extension;
+        let (left, right) = match op {
+            Operation::Add(left, right)
+            | Operation::Sub(left, right)
+            | Operation::Mul(left, right) => (left, right),
+            Operation::Exp(base, exp) => {
+                let Some(base) = self.constant(base) else {
+                    return Ok(false);
+                };
+                let value = base.exp((*exp).try_into().or(Err(CodegenError::InvalidIndex))?);
+                extension.push_base(&mut self.writer, value.as_int());
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+
+        match (op, self.constant(left), self.constant(right)) {
+            (_, Some(left), Some(right)) => {
+                let value = match op {
+                    Operation::Add(_, _) => left + right,
+                    Operation::Sub(_, _) => left - right,
+                    _ => left * right,
+                };
+                extension.push_base(&mut self.writer, value.as_int());
+            }
+            (Operation::Mul(_, _), left, right)
+                if left == Some(Felt::ZERO) || right == Some(Felt::ZERO) =>
+            {
+                extension.push_base(&mut self.writer, 0);
+            }
+            (Operation::Add(_, _), Some(constant), None)
+            | (Operation::Mul(_, _), Some(constant), None) => {
+                self.visit_node_index(right)?;
+                self.gen_immediate_operation(op, constant);
+            }
+            (_, None, Some(constant)) => {
+                self.visit_node_index(left)?;
+                self.gen_immediate_operation(op, constant);
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Emits the code applying the binary operation `op` to the element at the top of the stack
+    /// and to `constant`, with the immediate form of the instructions of the base field.
+    fn gen_immediate_operation(&mut self, op: &Operation, constant: Felt) {
+        let extension = self.config.extension;
+        match (op, constant.as_int()) {
+            (Operation::Add(_, _) | Operation::Sub(_, _), 0) | (Operation::Mul(_, _), 1) => {}
+            (Operation::Add(_, _), value) => extension.add_base(&mut self.writer, value),
+            (Operation::Sub(_, _), value) => extension.sub_base(&mut self.writer, value),
+            (_, value) => extension.mul_base(&mut self.writer, value),
+        }
+    }
+
+    /// Emits the code to evaluate the operands of a binary operation, leaving the stack as
+    /// `[right, left, ...]`.
+    ///
+    /// While the right operand is evaluated the left one is kept on the stack, unless that would
+    /// exceed the configured maximum stack depth, in which case the left operand is spilled to the
+    /// scratch memory and loaded back once the right operand is on the stack.
+    fn visit_operands(
+        &mut self,
+        left: &'ast NodeIndex,
+        right: &'ast NodeIndex,
+    ) -> Result<(), CodegenError> {
+        self.visit_node_index(left)?;
+
+        if self.stack_depth < self.config.max_stack_depth {
+            self.stack_depth += 1;
+            self.visit_node_index(right)?;
+            self.stack_depth -= 1;
+            return Ok(());
+        }
+
+        // each scratch address holds a single value
+        let extension = self.config.extension;
+        let slot = self.spilled_values;
+        self.writer
+            .header("Spill the left operand to the scratch memory");
+        let element = extension.store(&mut self.writer, self.config.scratch_address, slot);
+
+        self.spilled_values += 1;
+        let depth = std::mem::replace(&mut self.stack_depth, 0);
+        self.visit_node_index(right)?;
+        self.stack_depth = depth;
+        self.spilled_values -= 1;
+
+        self.writer
+            .header("Load the spilled left operand below the right operand");
+        load_element(
+            &mut self.writer,
+            extension,
+            self.config.scratch_address,
+            element,
+        )?;
+        extension.swap(&mut self.writer);
+        self.writer.header("=> [right, left, ...]");
+
+        Ok(())
+    }
+
+    /// Emits the code evaluating the subexpressions shared by the constraints rooted at `roots`,
+    /// storing each value in the scratch memory, so the constraints load it instead of evaluating
+    /// it again.
+    ///
+    /// A node is shared if it has multiple parents in the subgraphs of `roots`. The shared nodes
+    /// are visited in topological order, i.e. by increasing index, so a shared node is evaluated
+    /// with the loads of the shared nodes it depends on. Each is kept only if evaluating it for
+    /// every use takes more instructions than evaluating and storing it once and loading it for
+    /// every use. The spilled values are stored after the shared ones.
+    ///
+    /// This must be called at the start of a procedure, and [Self::clear_shared_values] at its
+    /// end.
+    fn gen_shared_values<'a>(
+        &mut self,
+        roots: impl IntoIterator<Item = &'a NodeIndex>,
+    ) -> Result<(), CodegenError> {
+        if !self.config.reuse_shared_subexpressions {
+            return Ok(());
+        }
+
+        let ir = self.ir;
+        let graph = ir.constraint_graph();
+        let operands = |index: &NodeIndex| match graph.node(index).op() {
+            Operation::Exp(_, 0) => [None, None],
+            _ => graph.children(index),
+        };
+
+        // count the parents of each node reachable from the roots
+        let mut parents = vec![0usize; graph.num_nodes()];
+        let mut visited = vec![false; graph.num_nodes()];
+        let mut stack: Vec<NodeIndex> = roots.into_iter().copied().collect();
+        while let Some(index) = stack.pop() {
+            if std::mem::replace(&mut visited[index.as_usize()], true) {
+                continue;
+            }
+            for operand in operands(&index).into_iter().flatten() {
+                parents[operand.as_usize()] += 1;
+                stack.push(operand);
+            }
+        }
+
+        let extension = self.config.extension;
+        let mut measure = Writer::new();
+        measure.proc("size");
+        extension.store(&mut measure, self.config.scratch_address, 0);
+        let store_size = measure.instruction_count();
+        let mut measure = Writer::new();
+        measure.proc("size");
+        load_element(&mut measure, extension, self.config.scratch_address, 0)?;
+        let load_size = measure.instruction_count();
+
+        let mut slot = 0;
+        for (index, node) in graph.nodes_indexed() {
+            let uses = parents[index.as_usize()];
+            if uses < 2 || matches!(node.op(), Operation::Value(_)) {
+                continue;
+            }
+            let size = self.operation_size(node.op())?;
+            if uses * size <= size + store_size + uses * load_size {
+                continue;
+            }
+
+            if slot == 0 {
+                self.writer
+                    .header("Evaluate the shared subexpressions into the scratch memory");
+            }
+            self.spilled_values = slot;
+            self.visit_operation(node.op())?;
+            let element = extension.store(&mut self.writer, self.config.scratch_address, slot);
+            self.shared_values.insert(index.as_usize(), element);
+            slot += 1;
         }
+        self.spilled_values = slot;
+
+        Ok(())
+    }
+
+    /// Forgets the shared subexpressions evaluated by [Self::gen_shared_values], at the end of the
+    /// procedure which evaluated them.
+    fn clear_shared_values(&mut self) {
+        self.shared_values.clear();
+        self.spilled_values = 0;
+    }
+
+    /// Returns the number of instructions of the code evaluating `op`, without emitting it.
+    fn operation_size(&mut self, op: &'ast Operation) -> Result<usize, CodegenError> {
+        let writer = replace(&mut self.writer, Writer::new());
+        self.writer.proc("size");
+        let result = self.visit_operation(op);
+        let measured = replace(&mut self.writer, writer);
+        result.map(|_| measured.instruction_count())
     }
 
     /// Emits the Miden Assembly code  after visiting the [AirIR].
@@ -138,6 +480,103 @@ impl<'ast> Backend<'ast> {
         Ok(self.writer.into_code())
     }
 
+    /// Starts the codegen for a procedure which is an entry point of the module, which is only
+    /// exported in the [OutputMode::Library] mode.
+    fn entry_point(&mut self, name: impl Into<Cow<'static, str>>) {
+        match self.config.output_mode {
+            OutputMode::Executable => self.writer.proc(name),
+            OutputMode::Library => self.writer.export(name),
+        }
+    }
+
+    /// Emits the doc comment of the module in the [OutputMode::Library] mode.
+    fn gen_module_doc(&mut self) {
+        self.writer.module_doc(format!(
+            "Evaluation of the constraints of the {} AIR over the {} extension field, generated by AirScript.",
+            self.ir.name(),
+            self.config.extension.name()
+        ));
+        self.writer.module_doc("");
+        self.writer.module_doc(
+            "The exported procedures evaluate the constraints at the out-of-domain point, reading their inputs",
+        );
+        self.writer
+            .module_doc("from the memory regions documented below.");
+        self.writer.new_line();
+    }
+
+    /// Emits the header documenting the memory layout expected by the generated procedures, as
+    /// configured by [CodegenConfig].
+    fn gen_memory_layout_header(&mut self) {
+        self.writer.header("MEMORY LAYOUT");
+        self.writer.header("");
+        self.writer.header(
+            "The procedures below read and write the following memory regions, each address holding a word:",
+        );
+        self.writer.header("");
+        for (region, address) in self.config.memory_regions() {
+            self.writer.header(format!("- {region}: {address}"));
+        }
+        self.writer.header("");
+        let elements = match self.config.extension {
+            FieldExtension::Quadratic => "two quadratic extension elements per word",
+            FieldExtension::Cubic => "one cubic extension element per word",
+        };
+        self.writer.header(format!(
+            "The OOD frames use the {:?} layout, {elements}.",
+            self.config.frame_layout
+        ));
+        if self.ir.public_inputs().next().is_some() {
+            self.writer.header("");
+            self.writer.header(format!(
+                "The public inputs are flattened in declaration order, {elements}, at the offsets:"
+            ));
+            self.writer.header("");
+            for input in self.ir.public_inputs() {
+                let start = self.public_input_to_offset[&input.name];
+                let doc = input.doc().map_or(String::new(), |doc| format!(", {doc}"));
+                self.writer.header(format!(
+                    "- {}: {} to {}{doc}",
+                    input.name,
+                    start,
+                    start + input.size - 1
+                ));
+            }
+        }
+
+        let documented = self
+            .ir
+            .trace_columns
+            .iter()
+            .flat_map(|segment| segment.bindings.iter())
+            .filter_map(|binding| Some((binding, binding.name?, binding.doc()?)))
+            .collect::<Vec<_>>();
+        if !documented.is_empty() {
+            self.writer.header("");
+            self.writer.header("The trace columns are documented as:");
+            self.writer.header("");
+            for (binding, name, doc) in documented {
+                let segment = if binding.segment == MAIN_TRACE {
+                    "main"
+                } else {
+                    "aux"
+                };
+                let columns = if binding.is_scalar() {
+                    format!("column {}", binding.offset)
+                } else {
+                    format!(
+                        "columns {} to {}",
+                        binding.offset,
+                        binding.offset + binding.size - 1
+                    )
+                };
+                self.writer
+                    .header(format!("- {name}, {segment} {columns}: {doc}"));
+            }
+        }
+        self.writer.new_line();
+    }
+
     /// Emits code for the procedure `cache_z_exp`.
     ///
     /// The procedure computes and caches the necessary exponentiation of `z`. These values are
@@ -202,7 +641,7 @@ impl<'ast> Backend<'ast> {
                     // the VM (IOW, counting up is faster than counting down).
                     self.load_log2_trace_len();
                     self.writer.neg();
-                    self.writer.add(period.ilog2().into());
+                    self.writer.add_imm(period.ilog2().into());
                     self.writer.header(format!(
                         "=> [count, z_1, z_0, ...] where count = -log2(trace_len) + {}",
                         period.ilog2()
@@ -395,6 +834,161 @@ impl<'ast> Backend<'ast> {
         Ok(())
     }
 
+    /// Emits the procedures computing the divisors of the boundary constraints, one for each
+    /// domain which has boundary constraints.
+    ///
+    /// The constraints of a domain share the same divisor, so [Self::boundary_constraint_group]
+    /// sums their numerators and divides the sum once.
+    fn gen_compute_boundary_constraint_divisors(&mut self) -> Result<(), CodegenError> {
+        for domain in [ConstraintDomain::FirstRow, ConstraintDomain::LastRow] {
+            if !self
+                .boundary_constraint_count
+                .contains_key(&(MAIN_TRACE, domain))
+                && !self
+                    .boundary_constraint_count
+                    .contains_key(&(AUX_TRACE, domain))
+            {
+                continue;
+            }
+
+            let (row, point) = match domain {
+                ConstraintDomain::FirstRow => ("first", "1"),
+                ConstraintDomain::LastRow => ("last", "g^{trace_len-2}"),
+                _ => panic!("unexpected constraint domain"),
+            };
+            self.writer.header(format!(
+                "Procedure to compute the divisor of the boundary constraints of the {row} row."
+            ));
+            self.writer.header("");
+            self.writer
+                .header(format!("The divisor is defined as `z - {point}`"));
+            if domain == ConstraintDomain::LastRow {
+                self.writer.header(
+                    "Procedure `compute_integrity_constraint_divisor` must have been called prior to this.",
+                );
+            }
+            self.writer.header("");
+            self.writer.header("Input: [...]");
+            self.writer.header("Output: [divisor_1, divisor_0, ...]");
+
+            self.writer.proc(boundary_divisor_procedure_name(domain));
+            self.load_z();
+            self.writer.header("=> [z_1, z_0, ...]");
+
+            match domain {
+                ConstraintDomain::FirstRow => self.writer.push(1),
+                _ => {
+                    self.writer.mem_load(self.config.exemption_two_address);
+                    self.writer.comment("load g^{trace_len-2}");
+                }
+            }
+            self.writer.push(0);
+            self.writer.ext2sub();
+            self.writer.header("=> [divisor_1, divisor_0, ...]");
+            self.writer.end();
+        }
+
+        Ok(())
+    }
+
+    /// Emits the procedures evaluating the integrity constraints of `segment` in chunks, if the
+    /// code of all of them doesn't fit in a procedure of `budget` instructions, and returns their
+    /// names.
+    ///
+    /// The chunks are named `{name}_0..n`, and evaluate consecutive constraints, so executing
+    /// them in order leaves the results on the stack exactly as a single procedure evaluating all
+    /// the constraints would. If all the constraints fit in `budget` nothing is emitted, and an
+    /// empty list is returned.
+    ///
+    /// The composition coefficient and constraint counters must be set to the ones of the first
+    /// constraint of `segment`.
+    fn gen_integrity_constraint_chunks(
+        &mut self,
+        segment: TraceSegmentId,
+        name: &str,
+        budget: usize,
+    ) -> Result<Vec<String>, CodegenError> {
+        let constraints = self.ir.integrity_constraints(segment);
+
+        let mut ranges: Vec<Range<usize>> = vec![];
+        let mut start = 0;
+        let mut size = 0;
+        for (index, constraint) in constraints.iter().enumerate() {
+            let constraint_size = self.integrity_constraint_size(constraint, segment)?;
+            if index > start && size + constraint_size > budget {
+                ranges.push(start..index);
+                start = index;
+                size = 0;
+            }
+            size += constraint_size;
+        }
+        if ranges.is_empty() {
+            return Ok(vec![]);
+        }
+        ranges.push(start..constraints.len());
+
+        let segment_name = if segment == MAIN_TRACE {
+            "main"
+        } else {
+            "auxiliary"
+        };
+        let mut chunks = Vec::with_capacity(ranges.len());
+        for (chunk, range) in ranges.into_iter().enumerate() {
+            self.writer.header(format!(
+                "Procedure to evaluate numerators of the integrity constraints {} of the {segment_name} trace.",
+                constraint_range(range.start, range.len())
+            ));
+            self.writer.header("");
+            self.writer.header("Input: [...]");
+            let r = self.config.extension.stack_notation("r_");
+            self.writer.header(format!("Output: [{r}*, ...]"));
+            self.writer.header(format!(
+                "where: {r} is the result of the evaluation of each constraint, {} elements are pushed to the stack",
+                range.len()
+            ));
+
+            let chunk_name = format!("{name}_{chunk}");
+            self.writer.proc(chunk_name.clone());
+            let constraints = &constraints[range];
+            self.gen_shared_values(constraints.iter().map(ConstraintRoot::node_index))?;
+            for constraint in constraints {
+                self.visit_integrity_constraint(constraint, segment)?;
+            }
+            self.clear_shared_values();
+            self.writer.end();
+            chunks.push(chunk_name);
+        }
+
+        Ok(chunks)
+    }
+
+    /// Returns the number of instructions of the code evaluating `constraint`, without emitting
+    /// it.
+    fn integrity_constraint_size(
+        &mut self,
+        constraint: &'ast ConstraintRoot,
+        segment: TraceSegmentId,
+    ) -> Result<usize, CodegenError> {
+        let writer = replace(&mut self.writer, Writer::new());
+        let counters = (
+            self.composition_coefficient_count,
+            self.integrity_contraints,
+        );
+
+        self.writer.proc("size");
+        let result = self
+            .gen_shared_values([constraint.node_index()])
+            .and_then(|_| self.visit_integrity_constraint(constraint, segment));
+        self.clear_shared_values();
+
+        (
+            self.composition_coefficient_count,
+            self.integrity_contraints,
+        ) = counters;
+        let measured = replace(&mut self.writer, writer);
+        result.map(|_| measured.instruction_count())
+    }
+
     /// Emits code for the procedure `compute_integrity_constraints`.
     ///
     /// This procedure evaluates each top-level integrity constraint and leaves the result on the
@@ -403,6 +997,18 @@ impl<'ast> Backend<'ast> {
         let main_trace_count = self.ir.integrity_constraints(MAIN_TRACE).len();
         let aux_trace_count = self.ir.integrity_constraints(AUX_TRACE).len();
 
+        // the procedure also executes `evaluate_aux_transitions`
+        let budget = self
+            .config
+            .max_procedure_instructions
+            .saturating_sub((aux_trace_count > 0) as usize);
+        self.composition_coefficient_count = 0;
+        let chunks = self.gen_integrity_constraint_chunks(
+            MAIN_TRACE,
+            "compute_integrity_constraints",
+            budget,
+        )?;
+
         self.writer
             .header("Procedure to evaluate numerators of all integrity constraints.");
         self.writer.header("");
@@ -410,6 +1016,15 @@ impl<'ast> Backend<'ast> {
             "All the {} main and {} auxiliary constraints are evaluated.",
             main_trace_count, aux_trace_count
         ));
+        let aux = if aux_trace_count > 0 {
+            ", and the auxiliary ones through evaluate_aux_transitions"
+        } else {
+            ""
+        };
+        self.writer.header(format!(
+            "Covers the main integrity constraints {}{aux}.",
+            constraint_range(0, main_trace_count)
+        ));
         self.writer.header(
             "The result of each evaluation is kept on the stack, with the top of the stack",
         );
@@ -418,19 +1033,113 @@ impl<'ast> Backend<'ast> {
         );
         self.writer.header("");
         self.writer.header("Input: [...]");
-        self.writer.header("Output: [(r_1, r_0)*, ...]");
+        let r = self.config.extension.stack_notation("r_");
+        let extension = self.config.extension.name();
+        self.writer.header(format!("Output: [{r}*, ...]"));
+        self.writer.header(format!(
+            "where: {r} is the {extension} extension element resulting from the integrity constraint evaluation.",
+        ));
+        self.writer.header(format!(
+            "       This procedure pushes {} {extension} extension field elements to the stack",
+            main_trace_count + aux_trace_count
+        ));
+
+        self.entry_point("compute_integrity_constraints");
+        if chunks.is_empty() {
+            self.composition_coefficient_count = 0;
+            let constraints = self.ir.integrity_constraints(MAIN_TRACE);
+            self.gen_shared_values(constraints.iter().map(ConstraintRoot::node_index))?;
+            walk_integrity_constraints(self, self.ir, MAIN_TRACE)?;
+            self.clear_shared_values();
+        }
+        for chunk in chunks {
+            self.writer.exec(chunk);
+        }
+        if aux_trace_count > 0 {
+            self.writer.exec("evaluate_aux_transitions");
+        }
+        self.writer.end();
+
+        // the coefficients of the aux trace follow the ones of the main trace, and are used by
+        // `evaluate_aux_transitions`
+        self.composition_coefficient_count = (main_trace_count + aux_trace_count)
+            .try_into()
+            .or(Err(CodegenError::InvalidIndex))?;
+
+        Ok(())
+    }
+
+    /// Emits code for the procedure `evaluate_aux_transitions`, if the AIR has integrity
+    /// constraints for the auxiliary trace.
+    ///
+    /// This procedure reads the auxiliary OOD frame and the random values from their memory
+    /// regions, and evaluates each top-level integrity constraint of the auxiliary trace, leaving
+    /// the results on the stack in the same format as `compute_integrity_constraints`, which
+    /// executes it after evaluating the constraints of the main trace.
+    ///
+    /// Since procedures must be defined before they are used, this is emitted before
+    /// `compute_integrity_constraints`, starting from the composition coefficient following the
+    /// ones of the main trace.
+    fn gen_evaluate_aux_transitions(&mut self) -> Result<(), CodegenError> {
+        let main_trace_count = self.ir.integrity_constraints(MAIN_TRACE).len();
+        let aux_trace_count = self.ir.integrity_constraints(AUX_TRACE).len();
+        if aux_trace_count == 0 {
+            return Ok(());
+        }
+
+        self.composition_coefficient_count = main_trace_count
+            .try_into()
+            .or(Err(CodegenError::InvalidIndex))?;
+        self.integrity_contraints = 0;
+        let budget = self.config.max_procedure_instructions;
+        let chunks =
+            self.gen_integrity_constraint_chunks(AUX_TRACE, "evaluate_aux_transitions", budget)?;
+
         self.writer.header(
-            "where: (r_1, r_0) is the quadratic extension element resulting from the integrity constraint evaluation.",
+            "Procedure to evaluate numerators of the integrity constraints of the auxiliary trace.",
         );
+        self.writer.header("");
         self.writer.header(format!(
-            "       This procedure pushes {} quadratic extension field elements to the stack",
-            main_trace_count + aux_trace_count
+            "All the {} auxiliary constraints are evaluated.",
+            aux_trace_count
+        ));
+        self.writer.header(format!(
+            "Covers the auxiliary integrity constraints {}.",
+            constraint_range(0, aux_trace_count)
+        ));
+        self.writer.header(
+            "The result of each evaluation is kept on the stack, with the top of the stack",
+        );
+        self.writer
+            .header("containing the evaluation of the last constraint.");
+        self.writer.header("");
+        self.writer.header("Input: [...]");
+        let r = self.config.extension.stack_notation("r_");
+        let extension = self.config.extension.name();
+        self.writer.header(format!("Output: [{r}*, ...]"));
+        self.writer.header(format!(
+            "where: {r} is the {extension} extension element resulting from the integrity constraint evaluation.",
+        ));
+        self.writer.header(format!(
+            "       This procedure pushes {} {extension} extension field elements to the stack",
+            aux_trace_count
         ));
 
-        self.writer.proc("compute_integrity_constraints");
-        walk_integrity_constraints(self, self.ir, MAIN_TRACE)?;
-        self.integrity_contraints = 0; // reset counter for the aux trace
-        walk_integrity_constraints(self, self.ir, AUX_TRACE)?;
+        self.entry_point("evaluate_aux_transitions");
+        if chunks.is_empty() {
+            self.composition_coefficient_count = main_trace_count
+                .try_into()
+                .or(Err(CodegenError::InvalidIndex))?;
+            self.integrity_contraints = 0;
+            let constraints = self.ir.integrity_constraints(AUX_TRACE);
+            self.gen_shared_values(constraints.iter().map(ConstraintRoot::node_index))?;
+            walk_integrity_constraints(self, self.ir, AUX_TRACE)?;
+            self.clear_shared_values();
+        }
+        for chunk in chunks {
+            self.writer.exec(chunk);
+        }
+        self.integrity_contraints = 0; // reset counter for the main trace
         self.writer.end();
 
         Ok(())
@@ -451,86 +1160,128 @@ impl<'ast> Backend<'ast> {
     fn gen_compute_boundary_constraints(&mut self) -> Result<(), CodegenError> {
         // The boundary constraints have a natural order defined as (trace, domain, column_pos).
         // The code below iterates using that order
-
-        if self
-            .boundary_constraint_count
-            .contains_key(&(MAIN_TRACE, ConstraintDomain::FirstRow))
-        {
-            let name = boundary_group_to_procedure_name(MAIN_TRACE, ConstraintDomain::FirstRow);
-            self.writer.header(
-                "Procedure to evaluate the boundary constraint numerator for the first row of the main trace",
-            );
-            self.writer.header("");
-            self.writer.header("Input: [...]");
-            self.writer.header("Output: [(r_1, r_0)*, ...]");
-            self.writer.header(
-                "Where: (r_1, r_0) is one quadratic extension field element for each constraint",
-            );
-            self.writer.proc(name);
-            walk_boundary_constraints(self, self.ir, MAIN_TRACE, ConstraintDomain::FirstRow)?;
-            self.writer.end();
+        for segment in [MAIN_TRACE, AUX_TRACE] {
+            for domain in [ConstraintDomain::FirstRow, ConstraintDomain::LastRow] {
+                if self
+                    .boundary_constraint_count
+                    .contains_key(&(segment, domain))
+                {
+                    self.gen_compute_boundary_constraint_group(segment, domain)?;
+                }
+            }
         }
 
-        if self
-            .boundary_constraint_count
-            .contains_key(&(MAIN_TRACE, ConstraintDomain::LastRow))
-        {
-            let name = boundary_group_to_procedure_name(MAIN_TRACE, ConstraintDomain::LastRow);
-            self.writer.header(
-                "Procedure to evaluate the boundary constraint numerator for the last row of the main trace",
-            );
-            self.writer.header("");
-            self.writer.header("Input: [...]");
-            self.writer.header("Output: [(r_1, r_0)*, ...]");
-            self.writer.header(
-                "Where: (r_1, r_0) is one quadratic extension field element for each constraint",
-            );
-            self.writer.proc(name);
-            walk_boundary_constraints(self, self.ir, MAIN_TRACE, ConstraintDomain::LastRow)?;
-            self.writer.end();
-        }
+        Ok(())
+    }
 
-        if self
-            .boundary_constraint_count
-            .contains_key(&(AUX_TRACE, ConstraintDomain::FirstRow))
-        {
-            let name = boundary_group_to_procedure_name(AUX_TRACE, ConstraintDomain::FirstRow);
-            self.writer.header(
-            "Procedure to evaluate the boundary constraint numerator for the first row of the auxiliary trace",
-        );
-            self.writer.header("");
-            self.writer.header("Input: [...]");
-            self.writer.header("Output: [(r_1, r_0)*, ...]");
-            self.writer.header(
-                "Where: (r_1, r_0) is one quadratic extension field element for each constraint",
-            );
-            self.writer.proc(name);
-            walk_boundary_constraints(self, self.ir, AUX_TRACE, ConstraintDomain::FirstRow)?;
-            self.writer.end();
-        }
+    /// Emits the procedure evaluating the numerators of the boundary constraints of the given
+    /// group, documenting the layout of its results and the memory regions it reads.
+    fn gen_compute_boundary_constraint_group(
+        &mut self,
+        segment: TraceSegmentId,
+        domain: ConstraintDomain,
+    ) -> Result<(), CodegenError> {
+        let (segment_name, frame_address) = if segment == MAIN_TRACE {
+            ("main", self.config.ood_frame_address)
+        } else {
+            ("auxiliary", self.config.ood_aux_frame_address)
+        };
+        let row = match domain {
+            ConstraintDomain::FirstRow => "first",
+            ConstraintDomain::LastRow => "last",
+            _ => panic!("unexpected constraint domain"),
+        };
+        let columns = self.boundary_constrained_columns(segment, domain);
 
-        if self
-            .boundary_constraint_count
-            .contains_key(&(AUX_TRACE, ConstraintDomain::LastRow))
-        {
-            let name = boundary_group_to_procedure_name(AUX_TRACE, ConstraintDomain::LastRow);
-            self.writer.header(
-            "Procedure to evaluate the boundary constraint numerator for the last row of the auxiliary trace",
-        );
-            self.writer.header("");
-            self.writer.header("Input: [...]");
-            self.writer.header("Output: [(r_1, r_0)*, ...]");
-            self.writer.header(
-                "Where: (r_1, r_0) is one quadratic extension field element for each constraint",
-            );
+        self.writer.header(format!(
+            "Procedure to evaluate the boundary constraint numerator for the {row} row of the {segment_name} trace"
+        ));
+        self.writer.header(format!(
+            "Covers the boundary constraints {}.",
+            constraint_range(self.boundary_contraints, columns.len())
+        ));
+        self.writer.header("");
+        self.writer.header(format!(
+            "Each numerator is the difference of the current value of the constrained column in the OOD frame at {frame_address}"
+        ));
+        self.writer.header(format!(
+            "and of its expected value, which may read the public inputs at {} and the random values at {},",
+            self.config.public_inputs_address, self.config.aux_rand_address
+        ));
+        self.writer.header(format!(
+            "multiplied by its composition coefficient, read from {}.",
+            self.config.composition_coef_address
+        ));
+        self.writer.header("");
+        self.writer.header("Input: [...]");
+        self.writer.header(format!(
+            "Output: [{}, ...]",
+            (0..columns.len())
+                .rev()
+                .map(|i| self.config.extension.stack_notation(&format!("r{i}_")))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        self.writer.header(format!(
+            "Where: {} is one {} extension field element for each constraint",
+            self.config.extension.stack_notation("r_"),
+            self.config.extension.name()
+        ));
+        for (i, column) in columns.iter().enumerate() {
+            let column = column.map_or("an expression".to_string(), |column| {
+                format!("column {column}")
+            });
+            self.writer.header(format!(
+                "       r{i} constrains {column} of the {segment_name} trace"
+            ));
+        }
+        // over the cubic extension, the boundary constraints are only evaluated by these procedures
+        let name = boundary_group_to_procedure_name(segment, domain);
+        if self.config.extension == FieldExtension::Cubic {
+            self.entry_point(name);
+        } else {
             self.writer.proc(name);
-            walk_boundary_constraints(self, self.ir, AUX_TRACE, ConstraintDomain::LastRow)?;
-            self.writer.end();
         }
+        let constraints = self.ir.boundary_constraints(segment);
+        self.gen_shared_values(
+            constraints
+                .iter()
+                .filter(|constraint| constraint.domain() == domain)
+                .map(ConstraintRoot::node_index),
+        )?;
+        walk_boundary_constraints(self, self.ir, segment, domain)?;
+        self.clear_shared_values();
+        self.writer.end();
 
         Ok(())
     }
 
+    /// Returns the column constrained by each boundary constraint of the given group, in the
+    /// order in which they are evaluated, if the constraint is of the form `column - value`, or
+    /// `column` when the expected value is zero.
+    fn boundary_constrained_columns(
+        &self,
+        segment: TraceSegmentId,
+        domain: ConstraintDomain,
+    ) -> Vec<Option<usize>> {
+        let graph = self.ir.constraint_graph();
+        self.ir
+            .boundary_constraints(segment)
+            .iter()
+            .filter(|constraint| constraint.domain() == domain)
+            .map(
+                |constraint| match graph.node(constraint.node_index()).op() {
+                    Operation::Value(Value::TraceAccess(access)) => Some(access.column),
+                    Operation::Sub(lhs, _) => match graph.node(lhs).op() {
+                        Operation::Value(Value::TraceAccess(access)) => Some(access.column),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+            )
+            .collect()
+    }
+
     /// Emits code for the procedure `get_exemptions_points`.
     ///
     /// Generate code to push the exemption points to the top of the stack.
@@ -573,7 +1324,7 @@ impl<'ast> Backend<'ast> {
         self.writer
             .header("Where: (r_1, r_0) is the final result with the divisor applied");
 
-        self.writer.proc("evaluate_integrity_constraints");
+        self.entry_point("evaluate_integrity_constraints");
 
         if !self.ir.periodic_columns.is_empty() {
             self.writer.exec("cache_periodic_polys");
@@ -616,7 +1367,7 @@ impl<'ast> Backend<'ast> {
         self.writer
             .header("Where: (r_1, r_0) is the final result with the divisor applied");
 
-        self.writer.proc("evaluate_boundary_constraints");
+        self.entry_point("evaluate_boundary_constraints");
 
         let last = self.boundary_constraint_group(ConstraintDomain::LastRow);
         let first = self.boundary_constraint_group(ConstraintDomain::FirstRow);
@@ -672,21 +1423,7 @@ impl<'ast> Backend<'ast> {
             self.writer
                 .header(format!("Compute the denominator for domain {:?}", domain));
 
-            match domain {
-                ConstraintDomain::FirstRow => {
-                    self.load_z();
-                    self.writer.push(1);
-                    self.writer.push(0);
-                    self.writer.ext2sub();
-                }
-                ConstraintDomain::LastRow => {
-                    self.load_z();
-                    self.writer.mem_load(self.config.exemption_two_address);
-                    self.writer.push(0);
-                    self.writer.ext2sub();
-                }
-                _ => panic!("unexpected constraint domain"),
-            };
+            self.writer.exec(boundary_divisor_procedure_name(domain));
 
             self.writer
                 .header(format!("Compute numerator/denominator for {name} row"));
@@ -720,6 +1457,75 @@ impl<'ast> Backend<'ast> {
         }
     }
 
+    /// Emits code for the procedure `combine_constraints`.
+    ///
+    /// This computes the random linear combination of all the integrity and boundary constraints,
+    /// each numerator being multiplied by its composition coefficient and divided by the divisor
+    /// of its domain, and documents the order in which the coefficients are read, which is the
+    /// order in which the constraints are emitted.
+    fn gen_combine_constraints(&mut self) {
+        self.writer
+            .header("Procedure to compute the random linear combination of all the constraints.");
+        self.writer.header("");
+        self.writer.header(format!(
+            "The composition coefficients are read from the memory starting at {}, two per word, in the order:",
+            self.config.composition_coef_address
+        ));
+        let mut start = 0;
+        let mut groups = vec![
+            (
+                self.ir.integrity_constraints(MAIN_TRACE).len(),
+                "integrity constraints of the main trace".to_string(),
+            ),
+            (
+                self.ir.integrity_constraints(AUX_TRACE).len(),
+                "integrity constraints of the auxiliary trace".to_string(),
+            ),
+        ];
+        for (segment, segment_name) in [(MAIN_TRACE, "main"), (AUX_TRACE, "auxiliary")] {
+            for (domain, row) in [
+                (ConstraintDomain::FirstRow, "first"),
+                (ConstraintDomain::LastRow, "last"),
+            ] {
+                let count = self
+                    .boundary_constraint_count
+                    .get(&(segment, domain))
+                    .copied()
+                    .unwrap_or(0);
+                groups.push((
+                    count,
+                    format!("boundary constraints for the {row} row of the {segment_name} trace"),
+                ));
+            }
+        }
+        for (count, description) in groups.into_iter().filter(|(count, _)| *count > 0) {
+            self.writer.header(format!(
+                "- coefficients {start}..{}: {description}",
+                start + count
+            ));
+            start += count;
+        }
+        self.writer.header("");
+        self.writer
+            .header("Procedure `cache_z_exp` must have been called prior to this.");
+        self.writer.header("");
+        self.writer.header("Input: [...]");
+        self.writer.header("Output: [(r_1, r_0), ...]");
+        self.writer.header(
+            "Where: (r_1, r_0) is the sum of the integrity and boundary constraints, each divided by its divisor",
+        );
+
+        self.entry_point("combine_constraints");
+
+        // `evaluate_integrity_constraints` caches the exemption points used by the boundary
+        // constraint divisors, so it must be executed first
+        self.writer.exec("evaluate_integrity_constraints");
+        self.writer.exec("evaluate_boundary_constraints");
+        self.writer.ext2add();
+
+        self.writer.end();
+    }
+
     /// Emits code for the procedure `evaluate_constraints`.
     ///
     /// This will compute and cache values, the transition and boundary constraints for both the main and auxiliary traces.
@@ -732,13 +1538,9 @@ impl<'ast> Backend<'ast> {
 
         self.writer.export("evaluate_constraints");
 
-        // The order of execution below is important. These are the dependencies:
-        // - `z^trace_len` is computed and cached to be used by integrity contraints
-        // - `g^{trace_len-2}` is computed and cached to be used by boundary constraints
+        // `z^trace_len` is computed and cached to be used by the integrity constraint divisor
         self.writer.exec("cache_z_exp");
-        self.writer.exec("evaluate_integrity_constraints");
-        self.writer.exec("evaluate_boundary_constraints");
-        self.writer.ext2add();
+        self.writer.exec("combine_constraints");
 
         self.writer.end();
     }
@@ -776,6 +1578,9 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
         if !constraint.domain().is_integrity() {
             return Err(CodegenError::InvalidIntegrityConstraint);
         }
+        if constraint.exemptions() > 0 {
+            return Err(CodegenError::UnsupportedExemptions);
+        }
 
         let segment = if trace_segment == MAIN_TRACE {
             "main"
@@ -784,21 +1589,25 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
         };
 
         self.writer.header(format!(
-            "integrity constraint {} for {}",
-            self.integrity_contraints, segment
+            "integrity constraint {} for {}, {}",
+            self.integrity_contraints,
+            segment,
+            self.degree_description(constraint)
         ));
+        self.gen_source_comment(constraint);
 
         self.visit_node_index(constraint.node_index())?;
 
         self.writer
             .header("Multiply by the composition coefficient");
 
-        load_quadratic_element(
+        load_element(
             &mut self.writer,
+            self.config.extension,
             self.config.composition_coef_address,
             self.composition_coefficient_count,
         )?;
-        self.writer.ext2mul();
+        self.config.extension.mul(&mut self.writer);
         self.composition_coefficient_count += 1;
 
         self.integrity_contraints += 1;
@@ -821,9 +1630,12 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
         };
 
         self.writer.header(format!(
-            "boundary constraint {} for {}",
-            self.boundary_contraints, segment
+            "boundary constraint {} for {}, {}",
+            self.boundary_contraints,
+            segment,
+            self.degree_description(constraint)
         ));
+        self.gen_source_comment(constraint);
 
         // Note: AirScript's boundary constraints are only defined for the first or last row.
         // Meaning they are implemented as an assertion for a single element. Visiting the
@@ -836,12 +1648,13 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
 
         // Note: The correctness of the load below relies on the integrity constraint being
         // iterated first _and_ the boundary constraints being iterated in natural order.
-        load_quadratic_element(
+        load_element(
             &mut self.writer,
+            self.config.extension,
             self.config.composition_coef_address,
             self.composition_coefficient_count,
         )?;
-        self.writer.ext2mul();
+        self.config.extension.mul(&mut self.writer);
         self.composition_coefficient_count += 1;
 
         self.boundary_contraints += 1;
@@ -849,6 +1662,22 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
     }
 
     fn visit_air(&mut self) -> Result<Self::Value, Self::Error> {
+        if self.config.output_mode == OutputMode::Library {
+            self.gen_module_doc();
+        }
+        self.gen_memory_layout_header();
+
+        // only the numerators of the constraints are evaluated over the cubic extension
+        if self.config.extension == FieldExtension::Cubic {
+            if !self.ir.periodic_columns.is_empty() {
+                return Err(CodegenError::UnsupportedCubicPeriodicColumns);
+            }
+            self.gen_evaluate_aux_transitions()?;
+            self.gen_compute_integrity_constraints()?;
+            self.gen_compute_boundary_constraints()?;
+            return Ok(());
+        }
+
         self.gen_cache_z_exp()?;
         self.gen_get_exemptions_points()?;
 
@@ -858,8 +1687,10 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
 
         self.gen_compute_integrity_constraint_divisor()?;
 
+        self.gen_evaluate_aux_transitions()?;
         self.gen_compute_integrity_constraints()?;
         self.gen_compute_boundary_constraints()?;
+        self.gen_compute_boundary_constraint_divisors()?;
 
         // NOTE: Order of the following two methods is important! The iteration order is used to
         // determine the composition coefficient index. The correct order is:
@@ -870,6 +1701,7 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
         self.gen_evaluate_integrity_constraints()?;
         self.gen_evaluate_boundary_constraints()?;
 
+        self.gen_combine_constraints();
         self.gen_evaluate_constraints();
 
         Ok(())
@@ -879,6 +1711,15 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
         &mut self,
         node_index: &'ast NodeIndex,
     ) -> Result<Self::Value, Self::Error> {
+        if let Some(&element) = self.shared_values.get(&node_index.as_usize()) {
+            return load_element(
+                &mut self.writer,
+                self.config.extension,
+                self.config.scratch_address,
+                element,
+            );
+        }
+
         let op = self.ir.constraint_graph().node(node_index).op();
         self.visit_operation(op)
     }
@@ -889,70 +1730,44 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
                 self.visit_value(value)?;
             }
             Operation::Add(left, right) => {
-                self.visit_node_index(left)?;
-                self.visit_node_index(right)?;
-                self.writer.ext2add();
+                if !self.gen_constant_operation(op)? {
+                    self.visit_operands(left, right)?;
+                    self.config.extension.add(&mut self.writer);
+                }
             }
             Operation::Sub(left, right) => {
-                self.visit_node_index(left)?;
-                self.visit_node_index(right)?;
-                self.writer.ext2sub();
+                if !self.gen_constant_operation(op)? {
+                    self.visit_operands(left, right)?;
+                    self.config.extension.sub(&mut self.writer);
+                }
             }
             Operation::Mul(left, right) => {
-                self.visit_node_index(left)?;
-                self.visit_node_index(right)?;
-                self.writer.ext2mul();
+                if !self.gen_constant_operation(op)? {
+                    self.visit_operands(left, right)?;
+                    self.config.extension.mul(&mut self.writer);
+                }
             }
+            Operation::Exp(_, _) if self.gen_constant_operation(op)? => {}
             Operation::Exp(left, exp) => {
                 // NOTE: The VM doesn't support exponentiation of extension elements.
                 //
                 // Ref: https://github.com/facebook/winterfell/blob/0acb2a148e2e8445d5f6a3511fa9d852e54818dd/math/src/field/traits.rs#L124-L150
 
-                self.visit_node_index(left)?;
-
-                self.writer.header("push the accumulator to the stack");
-                self.writer.push(1);
-                self.writer.movdn(2);
-                self.writer.push(0);
-                self.writer.movdn(2);
-                self.writer.header("=> [b1, b0, r1, r0, ...]");
-
-                // emitted code computes exponentiation via square-and-multiply
-                let mut e: usize = *exp;
-                while e != 0 {
-                    self.writer
-                        .header(format!("square {} times", e.trailing_zeros()));
-                    quadratic_element_square(&mut self.writer, e.trailing_zeros());
-
-                    // account for the exponentiations done above
-                    e = e >> e.trailing_zeros();
-
-                    self.writer.header("multiply");
-                    self.writer.dup(1);
-                    self.writer.dup(1);
-                    self.writer.movdn(5);
-                    self.writer.movdn(5);
-                    self.writer
-                        .header("=> [b1, b0, r1, r0, b1, b0, ...] (4 cycles)");
-
-                    self.writer.ext2mul();
-                    self.writer.movdn(3);
-                    self.writer.movdn(3);
-                    self.writer.header("=> [b1, b0, r1', r0', ...] (5 cycles)");
-
-                    // account for the multiply done above
-                    assert!(
-                        e & 1 == 1,
-                        "this loop is only executed if the number is non-zero"
-                    );
-                    e ^= 1;
+                match *exp {
+                    0 => {
+                        self.writer.header("x^0 = 1");
+                        self.config.extension.push_base(&mut self.writer, 1);
+                    }
+                    1 => {
+                        self.visit_node_index(left)?;
+                    }
+                    exp => {
+                        self.visit_node_index(left)?;
+                        self.gen_square_and_multiply(exp);
+                    }
                 }
-
-                self.writer.header("clean stack");
-                self.writer.drop();
-                self.writer.drop();
-                self.writer.header("=> [r1, r0, ...] (2 cycles)");
             }
+            Operation::Inv(_) => return Err(CodegenError::UnsupportedInverse),
         };
 
         Ok(())
@@ -994,13 +1809,15 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
         // convert coefficients from Montgomery form (Masm uses plain integers).
         let coef: Vec<u64> = poly.iter().map(|e| e.as_int()).collect();
 
-        // periodic columns have at least 2 values, push the first as the accumulator
-        self.writer.push(coef[0]);
+        // periodic columns have at least 2 values, push the leading coefficient as the accumulator
+        let (leading, rest) = coef.split_last().expect("periodic columns are not empty");
+        self.writer.push(*leading);
         self.writer.push(0);
         self.writer.header("=> [a_1, a_0, z_exp_1, z_exp_0, ...]");
 
-        // Evaluate the periodic polynomial at point z**exp using Horner's algorithm
-        for c in coef.iter().skip(1) {
+        // Evaluate the periodic polynomial at point z**exp using Horner's algorithm, from the
+        // highest to the lowest degree coefficient
+        for c in rest.iter().rev() {
             self.writer.header("duplicate z_exp");
             self.writer.dup(3);
             self.writer.dup(3);
@@ -1037,8 +1854,7 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
     fn visit_value(&mut self, value: &'ast Value) -> Result<Self::Value, Self::Error> {
         match value {
             Value::Constant(value) => {
-                self.writer.push(*value);
-                self.writer.push(0);
+                self.config.extension.push_base(&mut self.writer, *value);
             }
             Value::TraceAccess(access) => {
                 // eventually larger offsets will be supported
@@ -1046,19 +1862,23 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
                     return Err(CodegenError::InvalidRowOffset);
                 }
 
-                // Compute the target address for this variable. Each memory address contains the
-                // curr and next values of a single variable.
+                // Compute the target address for this variable. Each memory address contains two
+                // values, whose position in the frame depends on the configured layout.
                 //
-                // Layout defined at: https://github.com/0xPolygonMiden/miden-vm/issues/875
-                let target_word: u32 = access
+                // Default layout defined at: https://github.com/0xPolygonMiden/miden-vm/issues/875
+                let column: u32 = access
                     .column
                     .try_into()
                     .map_err(|_| CodegenError::InvalidIndex)?;
-                let el_pos: u32 = access
+                let row_offset: u32 = access
                     .row_offset
                     .try_into()
                     .or(Err(CodegenError::InvalidIndex))?;
-                let target_element = target_word * 2 + el_pos;
+                let width: u32 = self.ir.trace_segment_widths[access.segment].into();
+                let target_element = self
+                    .config
+                    .frame_layout
+                    .element_position(column, row_offset, width);
 
                 let base_address = if access.segment == MAIN_TRACE {
                     self.config.ood_frame_address
@@ -1066,20 +1886,28 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
                     self.config.ood_aux_frame_address
                 };
 
-                load_quadratic_element(&mut self.writer, base_address, target_element)?;
+                load_element(
+                    &mut self.writer,
+                    self.config.extension,
+                    base_address,
+                    target_element,
+                )?;
             }
             Value::PeriodicColumn(access) => {
-                let group: u32 = self
-                    .periods
-                    .iter()
-                    .position(|&p| p == access.cycle)
-                    .expect("All periods are added in the constructor")
+                // the values are cached by `cache_periodic_polys` in the order the columns are
+                // visited, one column per word
+                let column: u32 = self
+                    .ir
+                    .periodic_columns
+                    .keys()
+                    .position(|name| name == &access.name)
+                    .expect("periodic column accesses refer to declared columns")
                     .try_into()
-                    .expect("periods are u32");
+                    .expect("periodic columns are u32");
                 load_quadratic_element(
                     &mut self.writer,
                     self.config.periodic_values_address,
-                    periodic_group_to_memory_offset(group),
+                    periodic_group_to_memory_offset(column),
                 )?;
             }
             Value::PublicInput(access) => {
@@ -1088,22 +1916,27 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
                     .get(&access.name)
                     .unwrap_or_else(|| panic!("public input {} unknown", access.name));
 
+                let offset = start_offset + access.index;
                 self.writer.header(format!(
                     "Load public input {} pos {} with final offset {}",
-                    access.name, access.index, start_offset,
+                    access.name, access.index, offset,
                 ));
-                let index: u32 = (start_offset + access.index)
-                    .try_into()
-                    .or(Err(CodegenError::InvalidIndex))?;
-                load_quadratic_element(&mut self.writer, self.config.public_inputs_address, index)?;
+                let index: u32 = offset.try_into().or(Err(CodegenError::InvalidIndex))?;
+                load_element(
+                    &mut self.writer,
+                    self.config.extension,
+                    self.config.public_inputs_address,
+                    index,
+                )?;
             }
             Value::RandomValue(element) => {
                 // Compute the target address for the random value. Each memory address contains
                 // two values.
                 //
                 // Layout defined at: https://github.com/0xPolygonMiden/miden-vm/blob/next/stdlib/asm/crypto/stark/random_coin.masm#L169-L172
-                load_quadratic_element(
+                load_element(
                     &mut self.writer,
+                    self.config.extension,
                     self.config.aux_rand_address,
                     (*element).try_into().or(Err(CodegenError::InvalidIndex))?,
                 )?;
@@ -1113,3 +1946,12 @@ impl<'ast> AirVisitor<'ast> for Backend<'ast> {
         Ok(())
     }
 }
+
+/// Returns the description of the `count` consecutive constraint indices starting at `start`.
+fn constraint_range(start: usize, count: usize) -> String {
+    match count {
+        0 => "(none)".to_string(),
+        1 => start.to_string(),
+        _ => format!("{} to {}", start, start + count - 1),
+    }
+}
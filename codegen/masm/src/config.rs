@@ -1,5 +1,96 @@
 use crate::constants;
 
+/// The layout of the out-of-domain frames in memory.
+///
+/// Each memory address holds two quadratic extension elements, and the frame of a trace segment
+/// is stored as a contiguous sequence of elements, two per address, starting at the frame address
+/// of the segment. The layout determines the position of each value in that sequence.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum FrameLayout {
+    /// The current and next values of each column are stored next to each other, i.e. each
+    /// address holds the values of a single column:
+    ///
+    /// ```text
+    /// [frame_address+i] => [curr_i_0, curr_i_1, next_i_0, next_i_1]
+    /// ```
+    ///
+    /// This is the layout used by the Miden VM.
+    #[default]
+    RowInterleaved,
+    /// The current values of all columns are stored first, followed by the next values of all
+    /// columns, i.e. the value of column `i` in row `r` is the element `r * width + i`, where
+    /// `width` is the number of columns in the trace segment:
+    ///
+    /// ```text
+    /// [frame_address+0] => [curr_0_0, curr_0_1, curr_1_0, curr_1_1]
+    /// ```
+    ColumnsGrouped,
+}
+impl FrameLayout {
+    /// Returns the position of the value of `column` at `row_offset` in the frame of a trace
+    /// segment with `width` columns.
+    pub fn element_position(&self, column: u32, row_offset: u32, width: u32) -> u32 {
+        match self {
+            Self::RowInterleaved => column * 2 + row_offset,
+            Self::ColumnsGrouped => row_offset * width + column,
+        }
+    }
+}
+
+/// The extension of the base field over which the constraints are evaluated.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum FieldExtension {
+    /// The quadratic extension over `x^2 - x + 2`, which is natively supported by the `ext2*`
+    /// instructions of the VM. Each memory address holds two extension elements.
+    ///
+    /// This is the extension used by the Miden VM.
+    #[default]
+    Quadratic,
+    /// The cubic extension over `x^3 - x - 1`, whose arithmetic is emulated with instructions of
+    /// the base field. Each memory address holds a single extension element, as `[e0, e1, e2, 0]`.
+    ///
+    /// Only the procedures evaluating the numerators of the constraints are generated for this
+    /// extension, i.e. `compute_integrity_constraints`, `evaluate_aux_transitions` and
+    /// `compute_boundary_constraints_*`, and periodic columns are not supported.
+    Cubic,
+}
+impl FieldExtension {
+    /// Returns the number of base field elements of an extension element.
+    pub fn degree(&self) -> u32 {
+        match self {
+            Self::Quadratic => 2,
+            Self::Cubic => 3,
+        }
+    }
+
+    /// Returns the number of extension elements held by each memory address.
+    pub fn elements_per_word(&self) -> u32 {
+        match self {
+            Self::Quadratic => 2,
+            Self::Cubic => 1,
+        }
+    }
+}
+
+/// The shape of the generated module.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OutputMode {
+    /// A listing of procedures to be included in a program, e.g. by a test harness, in which
+    /// only `evaluate_constraints` is exported.
+    #[default]
+    Executable,
+    /// A library module, documented by a module doc comment, which exports the entry points
+    /// evaluating the constraints, i.e. `evaluate_constraints`, `combine_constraints`,
+    /// `evaluate_integrity_constraints`, `evaluate_boundary_constraints`, as well as
+    /// `compute_integrity_constraints` and `evaluate_aux_transitions` evaluating the numerators of
+    /// the integrity constraints, while the other procedures are kept internal.
+    ///
+    /// Over the cubic extension, the exported entry points are the procedures evaluating the
+    /// numerators of the constraints, i.e. `compute_integrity_constraints`,
+    /// `evaluate_aux_transitions` and `compute_boundary_constraints_*`.
+    Library,
+}
+
 #[derive(Copy, Clone)]
 pub struct CodegenConfig {
     // Memory location of the trace length using the following format:
@@ -31,6 +122,14 @@ pub struct CodegenConfig {
     //      [ood_aux_frame_address+0] => [ood_aux_curr_0, ood_aux_curr_1, ood_aux_next_0, ood_aux_next_1]
     pub ood_aux_frame_address: u32,
 
+    // Layout of the values of the OOD main and auxiliary frames, the formats above correspond to
+    // the default `FrameLayout::RowInterleaved` layout.
+    pub frame_layout: FrameLayout,
+
+    // Extension field over which the constraints are evaluated, the formats in this struct
+    // correspond to the default `FieldExtension::Quadratic` extension.
+    pub extension: FieldExtension,
+
     // Memory range for the composition coefficients.
     //
     // The coefficients are organized as follows:
@@ -45,6 +144,11 @@ pub struct CodegenConfig {
     // Memory range for the public inputs.
     pub public_inputs_address: u32,
 
+    // Memory range for the random values used by the auxiliary constraints, indexed by their
+    // position in the random values array, through the named bindings or not. Each memory
+    // location contains two random values:
+    //
+    //      [aux_rand_address+0] => [rand_0_0, rand_0_1, rand_1_0, rand_1_1]
     pub aux_rand_address: u32,
     pub periodic_values_address: u32,
 
@@ -60,6 +164,35 @@ pub struct CodegenConfig {
     /// Address to cache the point `g^{trace_len-2}`, which is used by the divisor of the boundary
     /// constraints.
     pub exemption_two_address: u32,
+
+    /// Memory range used to store the shared subexpressions and to spill the intermediate values
+    /// of deep expressions, each address contains a single quadratic extension element.
+    pub scratch_address: u32,
+
+    /// Maximum number of intermediate values kept on the stack while evaluating an expression,
+    /// once reached the left operand of a binary operation is spilled to the scratch memory
+    /// while its right operand is evaluated.
+    pub max_stack_depth: usize,
+
+    /// Maximum number of instructions in the body of the procedures evaluating the integrity
+    /// constraints. When the constraints of a trace segment don't fit in a single procedure, they
+    /// are evaluated by a sequence of procedures, each within this budget unless it contains a
+    /// single constraint exceeding it.
+    pub max_procedure_instructions: usize,
+
+    /// Whether the subexpressions shared by the constraints evaluated in a procedure are
+    /// evaluated once into the scratch memory, and loaded where they are used, whenever that takes
+    /// fewer instructions than evaluating them at each use.
+    pub reuse_shared_subexpressions: bool,
+
+    /// Whether the operations of constants are folded while emitting the code, and the
+    /// operations with a single constant operand use the immediate form of the instructions of
+    /// the base field, e.g. `mul.6`, rather than pushing the constant as an extension element.
+    pub fold_constants: bool,
+
+    /// Whether the procedures are emitted as a listing to be included in a program, or as a
+    /// library module exporting its entry points.
+    pub output_mode: OutputMode,
 }
 
 impl Default for CodegenConfig {
@@ -70,6 +203,8 @@ impl Default for CodegenConfig {
             z_address: constants::Z_ADDRESS,
             ood_frame_address: constants::OOD_FRAME_ADDRESS,
             ood_aux_frame_address: constants::OOD_AUX_FRAME_ADDRESS,
+            frame_layout: FrameLayout::default(),
+            extension: FieldExtension::default(),
             composition_coef_address: constants::COMPOSITION_COEF_ADDRESS,
             public_inputs_address: constants::PUBLIC_INPUTS_ADDRESS,
             aux_rand_address: constants::AUX_RAND_ELEM_PTR,
@@ -77,6 +212,37 @@ impl Default for CodegenConfig {
             z_exp_address: constants::Z_EXP_ADDRESS,
             trace_domain_generator_address: constants::TRACE_DOMAIN_GENERATOR_ADDRESS,
             exemption_two_address: constants::EXEMPTION_TWO_ADDRESS,
+            scratch_address: constants::SCRATCH_ADDRESS,
+            max_stack_depth: constants::MAX_STACK_DEPTH,
+            max_procedure_instructions: constants::MAX_PROCEDURE_INSTRUCTIONS,
+            reuse_shared_subexpressions: true,
+            fold_constants: true,
+            output_mode: OutputMode::default(),
         }
     }
 }
+
+impl CodegenConfig {
+    /// Returns the name and start address of each memory region read or written by the generated
+    /// code, in the order in which they are documented in the generated module.
+    pub fn memory_regions(&self) -> [(&'static str, u32); 13] {
+        [
+            ("trace_len", self.trace_len_address),
+            ("log2(trace_len)", self.log2_trace_len_address),
+            ("z", self.z_address),
+            (
+                "trace domain generator",
+                self.trace_domain_generator_address,
+            ),
+            ("OOD main frame", self.ood_frame_address),
+            ("OOD aux frame", self.ood_aux_frame_address),
+            ("composition coefficients", self.composition_coef_address),
+            ("public inputs", self.public_inputs_address),
+            ("aux random values", self.aux_rand_address),
+            ("periodic values", self.periodic_values_address),
+            ("z exponentiations", self.z_exp_address),
+            ("g^{trace_len-2}", self.exemption_two_address),
+            ("scratch", self.scratch_address),
+        ]
+    }
+}
@@ -32,3 +32,12 @@ pub const TRACE_DOMAIN_GENERATOR_ADDRESS: u32 = 4294799999;
 pub const PERIODIC_VALUES_ADDRESS: u32 = 500000000;
 pub const Z_EXP_ADDRESS: u32 = 500000100;
 pub const EXEMPTION_TWO_ADDRESS: u32 = 500000101;
+pub const SCRATCH_ADDRESS: u32 = 500000200;
+
+/// Number of intermediate values an expression may keep on the operand stack before the
+/// generated code spills them to memory. Each value is a quadratic extension element, so the
+/// default keeps the evaluation within the 16 directly accessible stack elements.
+pub const MAX_STACK_DEPTH: usize = 8;
+
+/// Maximum number of instructions in the body of a procedure accepted by the Miden assembler.
+pub const MAX_PROCEDURE_INSTRUCTIONS: usize = u16::MAX as usize;
@@ -12,4 +12,12 @@ pub enum CodegenError {
     InvalidBoundaryConstraint,
     #[error("invalid integrity constraint")]
     InvalidIntegrityConstraint,
+    #[error("integrity constraints exempted from rows of the trace are not supported by the Miden assembly backend")]
+    UnsupportedExemptions,
+    #[error(
+        "periodic columns are not supported over the cubic extension by the Miden assembly backend"
+    )]
+    UnsupportedCubicPeriodicColumns,
+    #[error("field inverses are not supported by the Miden assembly backend")]
+    UnsupportedInverse,
 }
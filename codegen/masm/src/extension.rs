@@ -0,0 +1,221 @@
+use crate::config::FieldExtension;
+use crate::writer::Writer;
+
+/// Emits the instructions operating on the elements of the extension field.
+///
+/// Extension elements are kept on the stack with their highest coefficient at the top, e.g. the
+/// quadratic element `a0 + a1 * x` as `[a1, a0, ...]` and the cubic element
+/// `a0 + a1 * x + a2 * x^2` as `[a2, a1, a0, ...]`. The arithmetic of the quadratic extension
+/// uses the `ext2*` instructions of the VM, while the arithmetic of the cubic extension is emulated
+/// with instructions of the base field.
+impl FieldExtension {
+    /// Returns the name of the extension, as used in the comments of the generated code.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Quadratic => "quadratic",
+            Self::Cubic => "cubic",
+        }
+    }
+
+    /// Returns the notation of an element on the stack, with coefficients named after `prefix`,
+    /// e.g. `(r_1, r_0)` for the prefix `r_` of a quadratic element.
+    pub(crate) fn stack_notation(&self, prefix: &str) -> String {
+        let coefficients = (0..self.degree())
+            .rev()
+            .map(|i| format!("{prefix}{i}"))
+            .collect::<Vec<_>>();
+        format!("({})", coefficients.join(", "))
+    }
+
+    /// Pushes the base field element `value` to the stack, as an extension element.
+    pub(crate) fn push_base(&self, writer: &mut Writer, value: u64) {
+        writer.push(value);
+        for _ in 1..self.degree() {
+            writer.push(0);
+        }
+    }
+
+    /// Duplicates the element at the top of the stack.
+    pub(crate) fn dup(&self, writer: &mut Writer) {
+        let n = self.degree();
+        for _ in 0..n {
+            writer.dup((n - 1).into());
+        }
+    }
+
+    /// Drops the element at the top of the stack.
+    pub(crate) fn drop(&self, writer: &mut Writer) {
+        for _ in 0..self.degree() {
+            writer.drop();
+        }
+    }
+
+    /// Swaps the two elements at the top of the stack, i.e. `[b, a, ...] => [a, b, ...]`.
+    pub(crate) fn swap(&self, writer: &mut Writer) {
+        let n = self.degree();
+        for _ in 0..n {
+            writer.movdn((2 * n - 1).into());
+        }
+    }
+
+    /// Moves the element at the top of the stack below the two following elements, i.e.
+    /// `[c, b, a, ...] => [b, a, c, ...]`.
+    pub(crate) fn movdn2(&self, writer: &mut Writer) {
+        let n = self.degree();
+        for _ in 0..n {
+            writer.movdn((3 * n - 1).into());
+        }
+    }
+
+    /// Adds the two elements at the top of the stack, i.e. `[b, a, ...] => [a + b, ...]`.
+    pub(crate) fn add(&self, writer: &mut Writer) {
+        match self {
+            Self::Quadratic => writer.ext2add(),
+            Self::Cubic => {
+                // combine the coefficients pairwise, from the highest to the lowest
+                for _ in 0..3 {
+                    writer.movup(3);
+                    writer.add();
+                    writer.movdn(2);
+                }
+            }
+        }
+    }
+
+    /// Subtracts the two elements at the top of the stack, i.e. `[b, a, ...] => [a - b, ...]`.
+    pub(crate) fn sub(&self, writer: &mut Writer) {
+        match self {
+            Self::Quadratic => writer.ext2sub(),
+            Self::Cubic => {
+                // combine the coefficients pairwise, from the highest to the lowest
+                for _ in 0..3 {
+                    writer.movup(3);
+                    writer.swap();
+                    writer.sub();
+                    writer.movdn(2);
+                }
+            }
+        }
+    }
+
+    /// Multiplies the two elements at the top of the stack, i.e. `[b, a, ...] => [a * b, ...]`.
+    pub(crate) fn mul(&self, writer: &mut Writer) {
+        match self {
+            Self::Quadratic => writer.ext2mul(),
+            Self::Cubic => {
+                // with x^3 = x + 1, the coefficients of the product are:
+                //
+                // c0 = a0*b0 + a1*b2 + a2*b1
+                // c1 = a0*b1 + a1*b0 + a1*b2 + a2*b1 + a2*b2
+                // c2 = a0*b2 + a1*b1 + a2*b0 + a2*b2
+                //
+                // where the position of each coefficient in `[b2, b1, b0, a2, a1, a0, ...]` is
+                // used below to refer to it.
+                const A: [u64; 3] = [5, 4, 3];
+                const B: [u64; 3] = [2, 1, 0];
+                let products: [&[(usize, usize)]; 3] = [
+                    &[(0, 0), (1, 2), (2, 1)],
+                    &[(0, 1), (1, 0), (1, 2), (2, 1), (2, 2)],
+                    &[(0, 2), (1, 1), (2, 0), (2, 2)],
+                ];
+
+                // the number of values pushed above the operands
+                let mut pushed = 0;
+                for terms in products {
+                    for (i, (a, b)) in terms.iter().enumerate() {
+                        writer.dup(A[*a] + pushed);
+                        writer.dup(B[*b] + pushed + 1);
+                        writer.mul();
+                        if i == 0 {
+                            pushed += 1;
+                        } else {
+                            writer.add();
+                        }
+                    }
+                }
+                // => [c2, c1, c0, b2, b1, b0, a2, a1, a0, ...]
+
+                for _ in 0..6 {
+                    writer.movup(3);
+                    writer.drop();
+                }
+            }
+        }
+    }
+
+    /// Adds the base field element `value` to the element at the top of the stack, i.e. to its
+    /// lowest coefficient, using the immediate form of the instruction.
+    pub(crate) fn add_base(&self, writer: &mut Writer, value: u64) {
+        self.with_lowest_coefficient(writer, |writer| writer.add_imm(value));
+    }
+
+    /// Subtracts the base field element `value` from the element at the top of the stack, i.e.
+    /// from its lowest coefficient, using the immediate form of the instruction.
+    pub(crate) fn sub_base(&self, writer: &mut Writer, value: u64) {
+        self.with_lowest_coefficient(writer, |writer| writer.sub_imm(value));
+    }
+
+    /// Multiplies the element at the top of the stack by the base field element `value`, i.e.
+    /// each of its coefficients, using the immediate form of the instruction.
+    pub(crate) fn mul_base(&self, writer: &mut Writer, value: u64) {
+        // each coefficient is multiplied at the top of the stack, and rotated below the others
+        for _ in 0..self.degree() {
+            writer.mul_imm(value);
+            match self {
+                Self::Quadratic => writer.swap(),
+                Self::Cubic => writer.movdn(2),
+            }
+        }
+    }
+
+    /// Emits `op` with the lowest coefficient of the element at the top of the stack moved to the
+    /// top, and moves it back below the other coefficients.
+    fn with_lowest_coefficient<F>(&self, writer: &mut Writer, op: F)
+    where
+        F: FnOnce(&mut Writer),
+    {
+        match self {
+            Self::Quadratic => {
+                writer.swap();
+                op(writer);
+                writer.swap();
+            }
+            Self::Cubic => {
+                writer.movup(2);
+                op(writer);
+                writer.movdn(2);
+            }
+        }
+    }
+
+    /// Squares the element at the top of the stack `n` times.
+    pub(crate) fn square(&self, writer: &mut Writer, n: u32) {
+        for _ in 0..n {
+            self.dup(writer);
+            self.mul(writer);
+        }
+    }
+
+    /// Stores the element at the top of the stack alone in the word at `base_addr + offset`,
+    /// removing it from the stack, and returns the position of the stored element in the memory
+    /// range starting at `base_addr`, as expected by [crate::utils::load_element].
+    pub(crate) fn store(&self, writer: &mut Writer, base_addr: u32, offset: u32) -> u32 {
+        match self {
+            Self::Quadratic => {
+                // the element is stored in the lower half of the word, i.e. as an odd element
+                writer.push(0);
+                writer.push(0);
+                writer.mem_storew(base_addr + offset);
+                writer.dropw();
+                offset * 2 + 1
+            }
+            Self::Cubic => {
+                writer.push(0);
+                writer.movdn(3);
+                writer.mem_storew(base_addr + offset);
+                writer.dropw();
+                offset
+            }
+        }
+    }
+}
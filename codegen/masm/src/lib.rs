@@ -2,9 +2,12 @@ pub mod codegen;
 mod config;
 pub mod constants;
 pub mod error;
+mod extension;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod utils;
 pub mod visitor;
 mod writer;
 
 pub use codegen::CodeGenerator;
-pub use config::CodegenConfig;
+pub use config::{CodegenConfig, FieldExtension, FrameLayout, OutputMode};
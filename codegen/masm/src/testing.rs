@@ -0,0 +1,423 @@
+//! Utilities to test the Miden assembly generated for an AIR, by executing it in the Miden VM.
+//!
+//! This module is only available with the `testing` feature. It is used by the tests of this
+//! crate, and lets the users of the generated code test their own AIRs in the same way.
+//!
+//! # Test programs
+//!
+//! The generated code is a listing of procedures, see [crate::OutputMode::Executable]. [test_code]
+//! appends a `begin ... end` block to it, which initializes the memory read by the procedures, and
+//! then executes the requested [Procedure]s, whose results are left on the stack. The memory is
+//! initialized as follows:
+//!
+//! - the trace length, its base 2 logarithm, the generator of the trace domain and the
+//!   out-of-domain point `z` are stored at the addresses of the [CodegenConfig] of the code,
+//! - each [Data] region, e.g. the out-of-domain frames, the composition coefficients, the random
+//!   values or the public inputs, is stored word by word from its address, padded with zeros to a
+//!   whole number of words.
+//!
+//! # Stack and memory conventions
+//!
+//! Extension elements are kept on the stack with their highest coefficient at the top, e.g. the
+//! quadratic element `a0 + a1 * x` as `[a1, a0, ...]`. The procedures evaluating several
+//! constraints push one element per constraint, so the result of the last constraint is at the
+//! top of the stack. [to_stack_order] converts elements to that order, with the coefficients of
+//! the first element first, which is also the order of the stack returned by [execute].
+//!
+//! In memory, each word holds two quadratic extension elements, in stack order, or a single cubic
+//! extension element padded with a zero, as returned by [to_memory_order]. The out-of-domain
+//! frames are stored in the layout selected by [CodegenConfig::frame_layout], e.g. with the
+//! default [crate::FrameLayout::RowInterleaved], the current and next values of each column are
+//! stored next to each other.
+//!
+//! # Example
+//!
+//! ```
+//! use air_codegen_masm::testing::{codegen, execute, test_code, to_stack_order, Data, Procedure};
+//! use air_codegen_masm::constants;
+//! use miden_processor::{
+//!     math::{Felt, FieldElement},
+//!     QuadExtension,
+//! };
+//!
+//! let code = codegen(
+//!     "
+//! def Counter
+//!
+//! trace_columns:
+//!     main: [a]
+//!
+//! public_inputs:
+//!     stack_inputs: [16]
+//!
+//! boundary_constraints:
+//!     enf a.first = 0
+//!
+//! integrity_constraints:
+//!     enf a' = a + 1
+//! ",
+//! );
+//!
+//! // the current and next values of `a` are stored in the first word of the main frame
+//! let a = QuadExtension::new(Felt::new(3), Felt::ZERO);
+//! let a_next = QuadExtension::new(Felt::new(5), Felt::ZERO);
+//! let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+//! let code = test_code(
+//!     code,
+//!     vec![
+//!         Data {
+//!             data: to_stack_order(&[a, a_next]),
+//!             address: constants::OOD_FRAME_ADDRESS,
+//!             descriptor: "main_trace",
+//!         },
+//!         Data {
+//!             data: to_stack_order(&[one]),
+//!             address: constants::COMPOSITION_COEF_ADDRESS,
+//!             descriptor: "composition_coefficients",
+//!         },
+//!     ],
+//!     2u64.pow(4),
+//!     one,
+//!     &[Procedure::ComputeIntegrityConstraints],
+//! );
+//!
+//! // `a' - (a + 1)` is multiplied by its composition coefficient
+//! let stack = execute(code);
+//! assert_eq!(&stack[..2], &to_stack_order(&[a_next - (a + one)])[..]);
+//! ```
+
+use air_ir::{ConstraintDomain, TraceSegmentId};
+use miden_assembly::Assembler;
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+};
+use miden_processor::{
+    math::{Felt, FieldElement, StarkField},
+    AdviceInputs, Kernel, MemAdviceProvider, Process, StackInputs,
+};
+use std::{fmt, sync::Arc};
+
+use crate::{utils::boundary_group_to_procedure_name, CodegenConfig};
+
+/// A region of the memory initialized by the test code, e.g. the out-of-domain frame of a trace
+/// segment.
+pub struct Data<'a, T>
+where
+    T: Default + std::fmt::Display,
+{
+    /// The values stored in the region, four per word, e.g. as returned by [to_stack_order]
+    pub data: Vec<T>,
+    /// The address of the first word of the region
+    pub address: u32,
+    /// The description of the region, emitted as a comment of the test code
+    pub descriptor: &'a str,
+}
+
+/// A procedure of the generated code which can be executed by the test code.
+///
+/// The procedures evaluating the divisors and combining the constraints read the powers of `z`
+/// cached by [Procedure::CacheZExp], which must be executed first, or through
+/// [Procedure::EvaluateConstraints].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Procedure {
+    /// `cache_z_exp`, which caches the powers of `z` used by the other procedures
+    CacheZExp,
+    /// `compute_integrity_constraints`, which pushes the numerator of each integrity constraint
+    /// of the main trace, followed by the ones of the auxiliary trace
+    ComputeIntegrityConstraints,
+    /// `evaluate_aux_transitions`, which pushes the numerator of each integrity constraint of the
+    /// auxiliary trace
+    EvaluateAuxTransitions,
+    /// `compute_boundary_constraints_*`, which pushes the numerator of each boundary constraint
+    /// of a trace segment applied to the first or the last row
+    ComputeBoundaryConstraints(TraceSegmentId, ConstraintDomain),
+    /// `evaluate_integrity_constraints`, which pushes the sum of the integrity constraints divided
+    /// by their divisor
+    EvaluateIntegrityConstraints,
+    /// `evaluate_boundary_constraints`, which pushes the sum of the boundary constraints, each
+    /// divided by its divisor
+    EvaluateBoundaryConstraints,
+    /// `combine_constraints`, which pushes the sum of all the constraints divided by their
+    /// divisors
+    CombineConstraints,
+    /// `evaluate_constraints`, which caches the powers of `z` and combines the constraints
+    EvaluateConstraints,
+}
+impl fmt::Display for Procedure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::CacheZExp => "cache_z_exp",
+            Self::ComputeIntegrityConstraints => "compute_integrity_constraints",
+            Self::EvaluateAuxTransitions => "evaluate_aux_transitions",
+            Self::ComputeBoundaryConstraints(segment, domain) => {
+                boundary_group_to_procedure_name(*segment, *domain)
+            }
+            Self::EvaluateIntegrityConstraints => "evaluate_integrity_constraints",
+            Self::EvaluateBoundaryConstraints => "evaluate_boundary_constraints",
+            Self::CombineConstraints => "combine_constraints",
+            Self::EvaluateConstraints => "evaluate_constraints",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Compiles the AirScript program `source` and returns the Miden assembly generated for it with
+/// the default [CodegenConfig], as a listing of procedures suited to [test_code].
+///
+/// # Panics
+/// Panics if the program doesn't compile, or if the code can't be generated.
+pub fn codegen(source: &str) -> String {
+    codegen_with_config(source, CodegenConfig::default())
+}
+
+/// Same as [codegen], but generates the code with the provided [CodegenConfig].
+pub fn codegen_with_config(source: &str, config: CodegenConfig) -> String {
+    try_codegen_with_config(source, config).expect("codegen failed")
+}
+
+/// Same as [codegen_with_config], but returns the error of the code generator instead of
+/// panicking.
+///
+/// The exported procedures are turned into regular ones, so that they can be executed by the
+/// test code, see [crate::OutputMode::Executable].
+pub fn try_codegen_with_config(source: &str, config: CodegenConfig) -> anyhow::Result<String> {
+    generate(source, config).map(|code| code.replace("export", "proc"))
+}
+
+/// Same as [try_codegen_with_config], but keeps the procedures exported by the generated code,
+/// e.g. to assemble it as a library module with [crate::OutputMode::Library].
+///
+/// # Panics
+/// Panics if the program doesn't compile.
+pub fn generate(source: &str, config: CodegenConfig) -> anyhow::Result<String> {
+    use air_ir::CodeGenerator;
+    use air_pass::Pass;
+
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Auto));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+
+    let air = air_parser::parse(&diagnostics, codemap, source)
+        .map_err(air_ir::CompileError::Parse)
+        .and_then(|ast| {
+            let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
+                .chain(air_parser::transforms::Inlining::new(&diagnostics))
+                // inverses are enabled so that their rejection by the backend can be tested
+                .chain(air_ir::passes::AstToAir::new(&diagnostics).with_inverses(true));
+            pipeline.run(ast)
+        })
+        .expect("lowering failed");
+
+    crate::CodeGenerator::new(config).generate(&air)
+}
+
+/// Returns the coefficients of the extension elements `values`, with the highest coefficient of
+/// each element first.
+pub fn to_stack_order<E>(values: &[E]) -> Vec<u64>
+where
+    E: FieldElement<BaseField = Felt>,
+{
+    E::slice_as_base_elements(values)
+        .chunks(E::EXTENSION_DEGREE)
+        .flat_map(|el| el.iter().rev().map(|b| b.as_int()))
+        .collect()
+}
+
+/// Returns the coefficients of the extension elements `values` in the layout of the memory,
+/// i.e. in stack order, with each cubic extension element padded to a word.
+pub fn to_memory_order<E>(values: &[E]) -> Vec<u64>
+where
+    E: FieldElement<BaseField = Felt>,
+{
+    match E::EXTENSION_DEGREE {
+        3 => to_stack_order(values)
+            .chunks(3)
+            .flat_map(|el| [el[0], el[1], el[2], 0])
+            .collect(),
+        _ => to_stack_order(values),
+    }
+}
+
+/// If necessary pad with zeros the data vector to the word size
+fn pad_to_word_len<T>(data: &mut Vec<T>)
+where
+    T: Default,
+{
+    let last = data.len() % 4;
+    if last != 0 {
+        let extra = 4 - last;
+        data.resize_with(data.len() + extra, || T::default());
+    }
+}
+
+/// Helper to initialize the VM's memory.
+///
+/// This writes the data directly to the assembly code instead of using procedures and advice
+/// inputs. This makes it easy to print the code and debug directly it in the VM.
+fn push_to_memory<T>(code: &mut String, memory: &mut Data<T>)
+where
+    T: Default + std::fmt::Display,
+{
+    code.push_str(&format!("    # initialize {}\n", memory.descriptor));
+    code.push_str(&format!("    push.{} # memory address\n", memory.address));
+
+    pad_to_word_len(&mut memory.data);
+    for (i, data) in memory.data.chunks(4).enumerate() {
+        code.push_str(&format!(
+            "        push.{}.{}.{}.{} dup.4 add.{} mem_storew dropw # row {}\n",
+            data[3], data[2], data[1], data[0], i, i,
+        ));
+    }
+
+    code.push_str("    drop # clean address\n");
+    code.push_str(&format!("    # finished {}\n\n", memory.descriptor));
+}
+
+/// Given the generated procedures as `code`, returns the test code initializing the `memory`
+/// regions for a trace of length `trace_len` and the out-of-domain point `z`, and then executing
+/// the procedures `execs`, either [Procedure]s or the names of procedures of `code`.
+///
+/// # Panics
+/// Panics if `trace_len` isn't a power of two, if the memory regions overlap, or if the
+/// out-of-domain frame of the main trace isn't initialized.
+pub fn test_code<T, E, P>(
+    code: String,
+    memory: Vec<Data<T>>,
+    trace_len: u64,
+    z: E,
+    execs: &[P],
+) -> String
+where
+    T: Default + std::fmt::Display,
+    E: FieldElement<BaseField = Felt>,
+    P: fmt::Display,
+{
+    test_code_with_config(code, memory, trace_len, z, execs, &CodegenConfig::default())
+}
+
+/// Same as [test_code], but stores the trace length, `z` and the trace domain generator at the
+/// addresses of the given `config`, which must be the one used to generate `code`.
+pub fn test_code_with_config<T, E, P>(
+    mut code: String,
+    memory: Vec<Data<T>>,
+    trace_len: u64,
+    z: E,
+    execs: &[P],
+    config: &CodegenConfig,
+) -> String
+where
+    T: Default + std::fmt::Display,
+    E: FieldElement<BaseField = Felt>,
+    P: fmt::Display,
+{
+    assert!(
+        trace_len.is_power_of_two(),
+        "trace_len must be a power of two"
+    );
+
+    // asserts there is no overlap between the memory ranges
+    let mut ranges: Vec<(u32, u32)> = memory
+        .iter()
+        .map(|data| {
+            let len: u32 = data.data.len().try_into().unwrap();
+            (data.address, data.address + len)
+        })
+        .collect();
+    ranges.sort();
+
+    for check in ranges.windows(2) {
+        let first = check[0];
+        let second = check[1];
+        assert!(
+            first.1 <= second.0,
+            "memory ranges overlap {:?} {:?}",
+            first,
+            second
+        );
+    }
+
+    let main_memory_pos = ranges
+        .binary_search_by_key(&config.ood_frame_address, |&(address, _)| address)
+        .expect("main trace memory missing");
+
+    assert!(ranges[main_memory_pos].1 > 0, "main trace memory is empty");
+
+    code.push_str("# END CODEGEN | START TESTCODE\n");
+    code.push_str("begin\n");
+
+    // save the trace length
+    code.push_str(&format!(
+        "    push.{} push.{} mem_store # trace_len\n",
+        trace_len, config.trace_len_address
+    ));
+    code.push_str(&format!(
+        "    push.{} push.{} mem_store # log2(trace_len)\n",
+        trace_len.ilog2(),
+        config.log2_trace_len_address,
+    ));
+
+    let g = Felt::get_root_of_unity(trace_len.ilog2());
+    code.push_str(&format!(
+        "    push.{} push.{} mem_store # trace domain generator `g`\n",
+        g, config.trace_domain_generator_address,
+    ));
+
+    // save the out-of-domain element
+    let mut z_word = [0; 4];
+    for (word, z) in z_word.iter_mut().zip(E::slice_as_base_elements(&[z])) {
+        *word = z.as_int();
+    }
+    code.push_str(&format!(
+        "    push.{}.{}.{}.{} push.{} mem_storew dropw # z\n\n",
+        z_word[0], z_word[1], z_word[2], z_word[3], config.z_address,
+    ));
+
+    // initialize the memory
+    for mut data in memory {
+        push_to_memory(&mut code, &mut data);
+    }
+
+    // call procedures to test
+    for proc in execs {
+        code.push_str(&format!("    exec.{}\n", proc));
+    }
+    code.push_str("end\n");
+
+    code
+}
+
+/// Compiles and executes the provided test code, e.g. as returned by [test_code], and returns the
+/// resulting stack.
+pub fn execute(code: String) -> Vec<u64> {
+    execute_with_assembler(code, Assembler::default())
+}
+
+/// Same as [execute], but compiles the test code with the provided [Assembler], e.g. to link it
+/// against a library.
+pub fn execute_with_assembler(code: String, assembler: Assembler) -> Vec<u64> {
+    let program = assembler.compile(code).unwrap();
+
+    let mut process: Process<MemAdviceProvider> = Process::new(
+        Kernel::new(&[]),
+        StackInputs::new(vec![]),
+        AdviceInputs::default().into(),
+    );
+    let program_outputs = process.execute(&program).expect("execution failed");
+    program_outputs.stack().to_vec()
+}
+
+/// Returns the bodies of the procedures of `code` whose name starts with `prefix`.
+pub fn procedures<'a>(code: &'a str, prefix: &str) -> Vec<&'a str> {
+    code.split(&format!("proc.{prefix}"))
+        .skip(1)
+        .map(|proc| &proc[..proc.find("\nend").expect("unterminated procedure")])
+        .collect()
+}
+
+/// Returns the number of instructions in the body of a procedure.
+pub fn instruction_count(body: &str) -> usize {
+    body.lines()
+        .skip(1)
+        .map(|line| line.split('#').next().unwrap().split_whitespace().count())
+        .sum()
+}
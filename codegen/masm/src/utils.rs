@@ -1,3 +1,4 @@
+use crate::config::FieldExtension;
 use crate::constants::AUX_TRACE;
 use crate::writer::Writer;
 use crate::{constants::MAIN_TRACE, error::CodegenError};
@@ -62,12 +63,25 @@ pub fn load_quadratic_element(
     Ok(())
 }
 
-/// Assumes a quadratic extension field element is at the top of the stack and square it `n` times.
-pub fn quadratic_element_square(writer: &mut Writer, n: u32) {
-    for _ in 0..n {
-        writer.dup(1);
-        writer.dup(1);
-        writer.ext2mul();
+/// Loads the `element` of the `extension` from a memory range starting at `base_addr`.
+///
+/// Quadratic extension elements are loaded with [load_quadratic_element], while each cubic
+/// extension element is stored alone in a word, as `[e0, e1, e2, 0]`.
+pub fn load_element(
+    writer: &mut Writer,
+    extension: FieldExtension,
+    base_addr: u32,
+    element: u32,
+) -> Result<(), CodegenError> {
+    match extension {
+        FieldExtension::Quadratic => load_quadratic_element(writer, base_addr, element),
+        FieldExtension::Cubic => {
+            writer.padw();
+            writer.mem_loadw(base_addr + element);
+            writer.movup(3);
+            writer.drop();
+            Ok(())
+        }
     }
 }
 
@@ -83,3 +97,13 @@ pub fn boundary_group_to_procedure_name(
         _ => panic!("Invalid boundary constraint"),
     }
 }
+
+/// Returns the name of the procedure computing the divisor of the boundary constraints of the
+/// given domain.
+pub fn boundary_divisor_procedure_name(domain: ConstraintDomain) -> &'static str {
+    match domain {
+        ConstraintDomain::FirstRow => "compute_boundary_constraint_divisor_first",
+        ConstraintDomain::LastRow => "compute_boundary_constraint_divisor_last",
+        _ => panic!("Invalid boundary constraint domain"),
+    }
+}
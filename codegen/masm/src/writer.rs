@@ -33,6 +33,7 @@ pub struct Writer {
     procedure: Option<Cow<'static, str>>,
     stack: Vec<ControlFlow>,
     state: LineState,
+    instructions: usize,
 }
 
 macro_rules! simple_ins {
@@ -60,6 +61,11 @@ impl Writer {
         self.code
     }
 
+    /// Returns the number of instructions written so far.
+    pub fn instruction_count(&self) -> usize {
+        self.instructions
+    }
+
     pub fn new_line(&mut self) {
         self.code.push('\n');
         self.state = LineState::Start;
@@ -86,6 +92,23 @@ impl Writer {
         self.state = LineState::Idented;
     }
 
+    /// Adds a line to the doc comment of the module, which must precede any other code.
+    pub fn module_doc(&mut self, doc: impl Borrow<str>) {
+        assert!(
+            self.procedure.is_none() && self.code.lines().all(|line| line.starts_with("#!")),
+            "The module doc comment must precede any other code"
+        );
+
+        let doc = doc.borrow();
+        if !doc.is_empty() {
+            self.code.push_str("#! ");
+            self.code.push_str(doc);
+        } else {
+            self.code.push_str("#!");
+        }
+        self.new_line();
+    }
+
     /// Ensures the comment is on a new line.
     pub fn header(&mut self, comment: impl Borrow<str>) {
         self.maybe_new_line_and_indent();
@@ -148,6 +171,7 @@ impl Writer {
     pub fn exec(&mut self, name: impl Into<Cow<'static, str>>) {
         self.maybe_new_line_and_indent();
         self.code.push_str(&format!("exec.{}", name.into()));
+        self.instructions += 1;
         self.new_line();
     }
 
@@ -196,6 +220,7 @@ impl Writer {
         }
 
         self.state = LineState::Instructions;
+        self.instructions += 1;
         let ins = ins.into();
         self.code.push_str(&ins);
     }
@@ -211,11 +236,21 @@ impl Writer {
     simple_ins!(swap);
     simple_ins!(div);
     simple_ins!(mul);
+    simple_ins!(add);
+    simple_ins!(sub);
 
-    pub(crate) fn add(&mut self, arg: u64) {
+    pub(crate) fn add_imm(&mut self, arg: u64) {
         self.ins(format!("add.{}", arg));
     }
 
+    pub(crate) fn sub_imm(&mut self, arg: u64) {
+        self.ins(format!("sub.{}", arg));
+    }
+
+    pub(crate) fn mul_imm(&mut self, arg: u64) {
+        self.ins(format!("mul.{}", arg));
+    }
+
     pub fn dup(&mut self, arg: u64) {
         assert!(
             arg <= 15,
@@ -305,7 +340,7 @@ impl Writer {
         self.header("=> [(e_1, e_0)^n, i, ...]");
 
         self.movup(2);
-        self.add(1);
+        self.add_imm(1);
         self.dup(0);
         self.neq(0);
         self.header("=> [b, i+1, (e_1, e_0)^n, ...]");
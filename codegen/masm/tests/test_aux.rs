@@ -5,8 +5,7 @@ use miden_processor::{
     AdviceInputs, Kernel, MemAdviceProvider, Process, QuadExtension, StackInputs,
 };
 
-mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use air_codegen_masm::testing::{codegen, execute, test_code, to_stack_order, Data};
 
 static SIMPLE_AUX_AIR: &str = "
 def SimpleAux
@@ -97,3 +96,160 @@ fn test_simple_aux() {
         expected,
     );
 }
+
+static RUNNING_PRODUCT_AIR: &str = "
+def RunningProduct
+
+trace_columns:
+    main: [a]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [16]
+
+random_values:
+    rand: [2]
+
+boundary_constraints:
+    enf a.first = 0
+    enf p.first = 1
+
+integrity_constraints:
+    enf a' = a + 1
+    enf p' = p * (a + $rand[0])
+";
+
+#[test]
+fn test_running_product_aux_transition() {
+    let code = codegen(RUNNING_PRODUCT_AIR);
+
+    let trace_len = 2u64.pow(4);
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let z = one;
+    let a = QuadExtension::new(Felt::new(3), Felt::ZERO);
+    let a_prime = a + one;
+    let p = QuadExtension::new(Felt::new(5), Felt::new(2));
+    let p_prime = QuadExtension::new(Felt::new(7), Felt::new(11));
+    let rand = [
+        QuadExtension::new(Felt::new(13), Felt::new(17)),
+        QuadExtension::new(Felt::new(19), Felt::new(23)),
+    ];
+    // the coefficient of the aux transition follows the one of the main transition
+    let coefficients = [one, QuadExtension::new(Felt::new(2), Felt::new(3))];
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&[a, a_prime]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[p, p_prime]),
+                address: constants::OOD_AUX_FRAME_ADDRESS,
+                descriptor: "aux_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+            Data {
+                data: to_stack_order(&rand),
+                address: constants::AUX_RAND_ELEM_PTR,
+                descriptor: "aux_random_elements",
+            },
+        ],
+        trace_len,
+        z,
+        &["evaluate_aux_transitions"],
+    );
+    let result_stack = execute(code);
+
+    // (p' - p * (a + rand[0])) * coefficients[1], where p * (a + rand[0]) is
+    // (5 + 2x) * (16 + 17x) = 80 + 117x + 34x^2, and x^2 = x - 2 in the quadratic extension, so
+    // the difference is (7 + 11x) - (12 + 151x) = -5 - 140x
+    let difference = QuadExtension::new(-Felt::new(5), -Felt::new(140));
+    assert_eq!(difference, p_prime - p * (a + rand[0]));
+    let expected = to_stack_order(&[difference * coefficients[1]]);
+
+    assert_eq!(&result_stack[..expected.len()], expected.as_slice());
+
+    // the aux transitions are also evaluated along with the main transitions, on top of them
+    let code = codegen(RUNNING_PRODUCT_AIR);
+    assert!(code.contains("exec.evaluate_aux_transitions"));
+}
+
+static NAMED_RANDOM_VALUES_AIR: &str = "
+def NamedRandomValues
+
+trace_columns:
+    main: [a]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [16]
+
+random_values:
+    rand: [alpha, betas[2]]
+
+boundary_constraints:
+    enf a.first = 0
+    enf p.first = 1
+
+integrity_constraints:
+    enf a' = a + 1
+    enf p' = p * (a + betas[1]) + alpha
+";
+
+#[test]
+fn test_named_random_values() {
+    let code = codegen(NAMED_RANDOM_VALUES_AIR);
+
+    let trace_len = 2u64.pow(4);
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let z = one;
+    let a = QuadExtension::new(Felt::new(3), Felt::new(1));
+    let p = QuadExtension::new(Felt::new(5), Felt::new(2));
+    let p_prime = QuadExtension::new(Felt::new(7), Felt::new(11));
+    let rand: Vec<QuadExtension<Felt>> = (0..3)
+        .map(|i| QuadExtension::new(Felt::new(13 + i), Felt::new(17 + i)))
+        .collect();
+    let coefficients = [one, one];
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&[a, a + one]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[p, p_prime]),
+                address: constants::OOD_AUX_FRAME_ADDRESS,
+                descriptor: "aux_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+            Data {
+                data: to_stack_order(&rand),
+                address: constants::AUX_RAND_ELEM_PTR,
+                descriptor: "aux_random_elements",
+            },
+        ],
+        trace_len,
+        z,
+        &["evaluate_aux_transitions"],
+    );
+    let result_stack = execute(code);
+
+    // the bindings are loaded by their index in the random values array, i.e. `alpha` is the
+    // element 0 and `betas[1]` the element 2
+    let expected = to_stack_order(&[p_prime - (p * (a + rand[2]) + rand[0])]);
+    assert_eq!(&result_stack[..expected.len()], expected.as_slice());
+}
@@ -1,12 +1,23 @@
-use air_codegen_masm::constants;
+use air_codegen_masm::{constants, error::CodegenError, CodegenConfig, FieldExtension};
 use miden_assembly::Assembler;
 use miden_processor::{
     math::{Felt, FieldElement},
     AdviceInputs, Kernel, MemAdviceProvider, Process, QuadExtension, StackInputs,
 };
+use winter_math::fields::CubeExtension;
 
-mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use air_codegen_masm::testing::{
+    codegen, codegen_with_config, execute, test_code, to_memory_order, to_stack_order,
+    try_codegen_with_config, Data,
+};
+
+/// The default configuration, over the cubic extension.
+fn cubic() -> CodegenConfig {
+    CodegenConfig {
+        extension: FieldExtension::Cubic,
+        ..Default::default()
+    }
+}
 
 static ARITH_AIR: &str = "
 def SimpleArithmetic
@@ -51,7 +62,7 @@ fn test_simple_arithmetic() {
                 descriptor: "main_trace",
             },
             Data {
-                data: to_stack_order(&[]),
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
                 address: constants::OOD_AUX_FRAME_ADDRESS,
                 descriptor: "aux_trace",
             },
@@ -97,6 +108,184 @@ fn test_simple_arithmetic() {
     );
 }
 
+#[test]
+fn test_simple_arithmetic_cubic() {
+    let code = codegen_with_config(ARITH_AIR, cubic());
+
+    let trace_len = 2u64.pow(4);
+    let one = CubeExtension::new(Felt::new(1), Felt::ZERO, Felt::ZERO);
+    let z = one;
+    let a = CubeExtension::new(Felt::new(3), Felt::new(5), Felt::new(11));
+    let b = CubeExtension::new(Felt::new(7), Felt::new(13), Felt::new(17));
+    let a_prime = a;
+    let b_prime = b;
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_memory_order(&[a, a_prime, b, b_prime]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_memory_order(&[one; 6]),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        trace_len,
+        z,
+        &["compute_integrity_constraints"],
+    );
+    let result_stack = execute(code);
+
+    // results are in stack-order, only the top of the stack is compared
+    #[rustfmt::skip]
+    let expected = to_stack_order(&[
+        b * a,
+        b - a,
+        b + a,
+        a * a,
+        a - a,
+        a + a,
+    ]);
+
+    assert_eq!(&result_stack[..16], &expected[..16]);
+}
+
+static CONSTANT_AIR: &str = "
+def ConstantArithmetic
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a + 6 = 0
+    enf a - 5 = 0
+    enf 7 * a = b
+    enf 3 - b = 0
+    enf b * 1 + 0 = a * 0
+    enf b' = 2 * a + b * 5 - 9
+";
+
+/// Returns the number of `push` instructions in `code`.
+fn push_count(code: &str) -> usize {
+    code.lines()
+        .filter(|line| line.trim_start().starts_with("push."))
+        .count()
+}
+
+/// Evaluates the 6 integrity constraints of `source`, over the trace columns `a` and `b`, over the
+/// quadratic extension.
+fn evaluate_arithmetic(source: &str, config: CodegenConfig) -> Vec<u64> {
+    let code = codegen_with_config(source, config);
+
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let frame = [
+        QuadExtension::new(Felt::new(3), Felt::new(2)),
+        QuadExtension::new(Felt::new(11), Felt::new(13)),
+        QuadExtension::new(Felt::new(7), Felt::new(5)),
+        QuadExtension::new(Felt::new(17), Felt::new(19)),
+    ];
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&frame),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[one; 6]),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        2u64.pow(4),
+        one,
+        &["compute_integrity_constraints"],
+    );
+    execute(code)
+}
+
+#[test]
+fn test_constant_folding() {
+    let unfolded = CodegenConfig {
+        fold_constants: false,
+        ..Default::default()
+    };
+
+    let code = codegen(CONSTANT_AIR);
+    let unfolded_code = codegen_with_config(CONSTANT_AIR, unfolded);
+    assert!(push_count(&code) < push_count(&unfolded_code));
+
+    // folding doesn't change the evaluation of the constraints
+    let expected = evaluate_arithmetic(CONSTANT_AIR, unfolded);
+    assert_ne!(&expected[..12], &[0; 12]);
+    assert_eq!(
+        &evaluate_arithmetic(CONSTANT_AIR, CodegenConfig::default())[..12],
+        &expected[..12]
+    );
+
+    // the constants are applied through the immediate form of the instructions
+    assert!(code.contains("add.6"));
+    assert!(code.contains("sub.5"));
+    assert!(code.contains("mul.7"));
+    assert!(!codegen_with_config(CONSTANT_AIR, unfolded).contains("mul.7"));
+}
+
+#[test]
+fn test_constant_folding_cubic() {
+    let folded = cubic();
+    let unfolded = CodegenConfig {
+        fold_constants: false,
+        ..cubic()
+    };
+    let folded_code = codegen_with_config(CONSTANT_AIR, folded);
+    let unfolded_code = codegen_with_config(CONSTANT_AIR, unfolded);
+    assert!(push_count(&folded_code) < push_count(&unfolded_code));
+
+    let one = CubeExtension::new(Felt::new(1), Felt::ZERO, Felt::ZERO);
+    let frame = [
+        CubeExtension::new(Felt::new(3), Felt::new(5), Felt::new(11)),
+        CubeExtension::new(Felt::new(2), Felt::new(4), Felt::new(8)),
+        CubeExtension::new(Felt::new(7), Felt::new(13), Felt::new(17)),
+        CubeExtension::new(Felt::new(19), Felt::new(23), Felt::new(29)),
+    ];
+    let evaluate = |code: String| {
+        execute(test_code(
+            code,
+            vec![
+                Data {
+                    data: to_memory_order(&frame),
+                    address: constants::OOD_FRAME_ADDRESS,
+                    descriptor: "main_trace",
+                },
+                Data {
+                    data: to_memory_order(&[one; 6]),
+                    address: constants::COMPOSITION_COEF_ADDRESS,
+                    descriptor: "composition_coefficients",
+                },
+            ],
+            2u64.pow(4),
+            one,
+            &["compute_integrity_constraints"],
+        ))
+    };
+
+    // the stack holds the 16 coefficients of the last constraints
+    let expected = evaluate(unfolded_code);
+    assert_ne!(&expected[..16], &[0; 16]);
+    assert_eq!(&evaluate(folded_code)[..16], &expected[..16]);
+}
+
 static EXP_AIR: &str = "
 def Exp
 
@@ -115,12 +304,28 @@ integrity_constraints:
     enf b^3 = 0
     enf b^4 = 0
     enf b^5 = 0
+    enf b^64 = 0
+    enf b^1000 = 0
 ";
 
 #[test]
 fn test_exp() {
     let code = codegen(EXP_AIR);
 
+    // square-and-multiply uses less than `2 * log2(exp)` multiplications for each exponentiation,
+    // instead of `exp - 1` for a chain of multiplications, plus one multiplication by the
+    // composition coefficient for each constraint
+    let exponents = [1u32, 2, 3, 4, 5, 64, 1000];
+    let naive: u32 = exponents.iter().map(|exp| exp - 1).sum();
+    let square_and_multiply: u32 = exponents.iter().map(|exp| 2 * exp.ilog2()).sum();
+    let start = code
+        .find("proc.compute_integrity_constraints")
+        .expect("missing procedure");
+    let end = start + code[start..].find("\nend").expect("unterminated procedure");
+    let ext2mul = code[start..end].matches("ext2mul").count() as u32;
+    assert!(ext2mul <= square_and_multiply + exponents.len() as u32);
+    assert!(ext2mul < naive);
+
     let trace_len = 2u64.pow(4);
     let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
     let z = one;
@@ -138,12 +343,12 @@ fn test_exp() {
                 descriptor: "main_trace",
             },
             Data {
-                data: to_stack_order(&[]),
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
                 address: constants::OOD_AUX_FRAME_ADDRESS,
                 descriptor: "aux_trace",
             },
             Data {
-                data: to_stack_order(&[one; 5]),
+                data: to_stack_order(&[one; 7]),
                 address: constants::COMPOSITION_COEF_ADDRESS,
                 descriptor: "composition_coefficients",
             },
@@ -165,6 +370,8 @@ fn test_exp() {
     // results are in stack-order
     #[rustfmt::skip]
     let expected = to_stack_order(&[
+        b.exp(1000),
+        b.exp(64),
         b.exp(5),
         b.exp(4),
         b.exp(3),
@@ -183,6 +390,53 @@ fn test_exp() {
     );
 }
 
+#[test]
+fn test_exp_cubic() {
+    let code = codegen_with_config(EXP_AIR, cubic());
+
+    let trace_len = 2u64.pow(4);
+    let one = CubeExtension::new(Felt::new(1), Felt::ZERO, Felt::ZERO);
+    let z = one;
+    let a = CubeExtension::<Felt>::ZERO;
+    let b = CubeExtension::new(Felt::new(3), Felt::new(5), Felt::new(7));
+    let a_prime = a;
+    let b_prime = b;
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_memory_order(&[a, a_prime, b, b_prime]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_memory_order(&[one; 7]),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        trace_len,
+        z,
+        &["compute_integrity_constraints"],
+    );
+    let result_stack = execute(code);
+
+    // results are in stack-order, only the top of the stack is compared
+    #[rustfmt::skip]
+    let expected = to_stack_order(&[
+        b.exp(1000),
+        b.exp(64),
+        b.exp(5),
+        b.exp(4),
+        b.exp(3),
+        b.exp(2),
+        b.exp(1),
+    ]);
+
+    assert_eq!(&result_stack[..16], &expected[..16]);
+}
+
 static LONG_TRACE: &str = "
 def LongTrace
 
@@ -226,7 +480,7 @@ fn test_long_trace() {
                 descriptor: "main_trace",
             },
             Data {
-                data: to_stack_order(&[]),
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
                 address: constants::OOD_AUX_FRAME_ADDRESS,
                 descriptor: "aux_trace",
             },
@@ -292,7 +546,7 @@ fn test_vector() {
     let fmp_0_prime = fmp_0;
     let fmp_1_prime = fmp_1;
     let main_frame = to_stack_order(&[clk, clk_prime, fmp_0, fmp_0_prime, fmp_1, fmp_1_prime]);
-    let aux_frame = to_stack_order(&[]);
+    let aux_frame = to_stack_order::<QuadExtension<Felt>>(&[]);
 
     let code = test_code(
         code,
@@ -378,7 +632,7 @@ fn test_multiple_rows() {
                 descriptor: "main_trace",
             },
             Data {
-                data: to_stack_order(&[]),
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
                 address: constants::OOD_AUX_FRAME_ADDRESS,
                 descriptor: "aux_trace",
             },
@@ -419,3 +673,33 @@ fn test_multiple_rows() {
         expected,
     );
 }
+
+static INVERSE_AIR: &str = "
+def InverseAir
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf b = a^(-1)
+";
+
+#[test]
+fn test_inverse_is_rejected() {
+    let err = try_codegen_with_config(INVERSE_AIR, CodegenConfig::default())
+        .expect_err("field inverses should be rejected");
+    assert!(matches!(
+        err.downcast_ref::<CodegenError>(),
+        Some(CodegenError::UnsupportedInverse)
+    ));
+    assert_eq!(
+        err.to_string(),
+        "field inverses are not supported by the Miden assembly backend"
+    );
+}
@@ -1,12 +1,12 @@
-use air_codegen_masm::constants;
+use air_codegen_masm::constants::{self, AUX_TRACE, MAIN_TRACE};
+use air_ir::ConstraintDomain;
 use miden_assembly::Assembler;
 use miden_processor::{
     math::{Felt, FieldElement},
     AdviceInputs, Kernel, MemAdviceProvider, Process, QuadExtension, StackInputs,
 };
 
-mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use air_codegen_masm::testing::{codegen, execute, test_code, to_stack_order, Data, Procedure};
 
 static SIMPLE_BOUNDARY_AIR: &str = "
 def SimpleBoundary
@@ -212,10 +212,10 @@ fn test_complex_boundary() {
         trace_len,
         z,
         &[
-            "compute_boundary_constraints_main_first",
-            "compute_boundary_constraints_main_last",
-            "compute_boundary_constraints_aux_first",
-            "compute_boundary_constraints_aux_last",
+            Procedure::ComputeBoundaryConstraints(MAIN_TRACE, ConstraintDomain::FirstRow),
+            Procedure::ComputeBoundaryConstraints(MAIN_TRACE, ConstraintDomain::LastRow),
+            Procedure::ComputeBoundaryConstraints(AUX_TRACE, ConstraintDomain::FirstRow),
+            Procedure::ComputeBoundaryConstraints(AUX_TRACE, ConstraintDomain::LastRow),
         ],
     );
     let program = Assembler::default().compile(code).unwrap();
@@ -270,3 +270,179 @@ fn test_complex_boundary() {
         expected,
     );
 }
+
+static PUBLIC_INPUTS_BOUNDARY_AIR: &str = "
+def PublicInputsBoundary
+
+trace_columns:
+    main: [a, b]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [2]
+
+random_values:
+    rand: [2]
+
+boundary_constraints:
+    enf a.first = stack_inputs[0]
+    enf b.last = stack_inputs[1] + 1
+    enf p.first = $rand[1] * stack_inputs[0]
+
+integrity_constraints:
+    enf a' = a + b
+    enf p' = p * $rand[0]
+";
+
+#[test]
+fn test_boundary_with_public_inputs_and_random_values() {
+    let code = codegen(PUBLIC_INPUTS_BOUNDARY_AIR);
+    // the layout of the results is documented in the header of each procedure
+    assert!(code.contains("#        r0 constrains column 1 of the main trace"));
+    assert!(code.contains("#        r0 constrains column 0 of the auxiliary trace"));
+
+    let trace_len = 16u64;
+    let one = QuadExtension::ONE;
+    let z = one;
+    let stack_inputs = [
+        QuadExtension::new(Felt::new(3), Felt::ZERO),
+        QuadExtension::new(Felt::new(5), Felt::ZERO),
+    ];
+    let rand = [
+        QuadExtension::new(Felt::new(7), Felt::new(11)),
+        QuadExtension::new(Felt::new(13), Felt::new(17)),
+    ];
+    let a = QuadExtension::new(Felt::new(19), Felt::new(23));
+    let b = QuadExtension::new(Felt::new(29), Felt::new(31));
+    let p = QuadExtension::new(Felt::new(37), Felt::new(41));
+    // the coefficients of the boundary constraints follow the ones of the 2 integrity
+    // constraints, in the order main first row, main last row, aux first row
+    let coefficients = [
+        one,
+        one,
+        QuadExtension::new(Felt::new(2), Felt::new(3)),
+        QuadExtension::new(Felt::new(5), Felt::new(7)),
+        QuadExtension::new(Felt::new(11), Felt::new(13)),
+    ];
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&[a, a + b, b, b]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[p, p * rand[0]]),
+                address: constants::OOD_AUX_FRAME_ADDRESS,
+                descriptor: "aux_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+            Data {
+                data: to_stack_order(&stack_inputs),
+                address: constants::PUBLIC_INPUTS_ADDRESS,
+                descriptor: "public_inputs",
+            },
+            Data {
+                data: to_stack_order(&rand),
+                address: constants::AUX_RAND_ELEM_PTR,
+                descriptor: "aux_random_elements",
+            },
+        ],
+        trace_len,
+        z,
+        &[
+            "compute_boundary_constraints_main_first",
+            "compute_boundary_constraints_main_last",
+            "compute_boundary_constraints_aux_first",
+        ],
+    );
+    let result_stack = execute(code);
+
+    // results are in stack-order
+    #[rustfmt::skip]
+    let expected = to_stack_order(&[
+        (p - rand[1] * stack_inputs[0]) * coefficients[4], // enf p.first = $rand[1] * stack_inputs[0]
+        (b - (stack_inputs[1] + one)) * coefficients[3],   // enf b.last = stack_inputs[1] + 1
+        (a - stack_inputs[0]) * coefficients[2],           // enf a.first = stack_inputs[0]
+    ]);
+
+    assert_eq!(&result_stack[..expected.len()], expected.as_slice());
+}
+
+static PUBLIC_INPUT_OFFSET_AIR: &str = "
+def PublicInputOffset
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    program_hash: [4]
+    stack_inputs: [4]
+
+boundary_constraints:
+    enf a.first = stack_inputs[3]
+    enf b.last = program_hash[1]
+
+integrity_constraints:
+    enf a' = a + b
+";
+
+#[test]
+fn test_public_input_offset() {
+    let code = codegen(PUBLIC_INPUT_OFFSET_AIR);
+    // the public inputs are flattened in declaration order
+    assert!(code.contains("# - program_hash: 0 to 3\n# - stack_inputs: 4 to 7\n"));
+    assert!(code.contains("# Load public input stack_inputs pos 3 with final offset 7"));
+
+    let trace_len = 16u64;
+    let one = QuadExtension::ONE;
+    let z = one;
+    let public_inputs: Vec<QuadExtension<Felt>> = (0..8)
+        .map(|i| QuadExtension::new(Felt::new(100 + i), Felt::ZERO))
+        .collect();
+    let a = QuadExtension::new(Felt::new(3), Felt::new(5));
+    let b = QuadExtension::new(Felt::new(7), Felt::new(11));
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&[a, a + b, b, b]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[one; 3]),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+            Data {
+                data: to_stack_order(&public_inputs),
+                address: constants::PUBLIC_INPUTS_ADDRESS,
+                descriptor: "public_inputs",
+            },
+        ],
+        trace_len,
+        z,
+        &[
+            "compute_boundary_constraints_main_first",
+            "compute_boundary_constraints_main_last",
+        ],
+    );
+    let result_stack = execute(code);
+
+    // results are in stack-order
+    #[rustfmt::skip]
+    let expected = to_stack_order(&[
+        b - public_inputs[1], // enf b.last = program_hash[1]
+        a - public_inputs[7], // enf a.first = stack_inputs[3]
+    ]);
+
+    assert_eq!(&result_stack[..expected.len()], expected.as_slice());
+}
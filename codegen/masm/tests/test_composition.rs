@@ -0,0 +1,126 @@
+use air_codegen_masm::constants;
+use miden_processor::{
+    math::{Felt, FieldElement},
+    QuadExtension,
+};
+use winter_air::{Assertion, ConstraintDivisor};
+
+use air_codegen_masm::testing::{codegen, execute, test_code, to_stack_order, Data, Procedure};
+
+static RUNNING_PRODUCT_AIR: &str = "
+def RunningProduct
+
+trace_columns:
+    main: [a, b]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [16]
+
+random_values:
+    rand: [2]
+
+boundary_constraints:
+    enf a.first = 0
+    enf b.last = 7
+    enf p.first = 1
+    enf p.last = 1
+
+integrity_constraints:
+    enf a' = a + 1
+    enf b' = b * a
+    enf p' = p * (a + $rand[0])
+";
+
+#[test]
+fn test_combine_constraints() {
+    let code = codegen(RUNNING_PRODUCT_AIR);
+
+    let exemptions = 2;
+    let trace_len = 2u64.pow(5);
+    let z = QuadExtension::new(Felt::new(29), Felt::new(31));
+    let a = QuadExtension::new(Felt::new(3), Felt::ZERO);
+    let a_prime = QuadExtension::new(Felt::new(4), Felt::ZERO);
+    let b = QuadExtension::new(Felt::new(5), Felt::ZERO);
+    let b_prime = QuadExtension::new(Felt::new(16), Felt::ZERO);
+    let p = QuadExtension::new(Felt::new(5), Felt::new(2));
+    let p_prime = QuadExtension::new(Felt::new(7), Felt::new(11));
+    let rand = [
+        QuadExtension::new(Felt::new(13), Felt::new(17)),
+        QuadExtension::new(Felt::new(19), Felt::new(23)),
+    ];
+    let coefficients: Vec<QuadExtension<Felt>> = (0..7)
+        .map(|i| QuadExtension::new(Felt::new(2 * i + 3), Felt::new(i + 1)))
+        .collect();
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&[a, a_prime, b, b_prime]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[p, p_prime]),
+                address: constants::OOD_AUX_FRAME_ADDRESS,
+                descriptor: "aux_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+            Data {
+                data: to_stack_order(&rand),
+                address: constants::AUX_RAND_ELEM_PTR,
+                descriptor: "aux_random_elements",
+            },
+        ],
+        trace_len,
+        z,
+        &[Procedure::CacheZExp, Procedure::CombineConstraints],
+    );
+    let result_stack = execute(code);
+
+    // the coefficients are used in the order the constraints are emitted: the integrity
+    // constraints of the main and aux traces, then the boundary constraints of the main trace for
+    // the first and last rows, followed by the ones of the aux trace
+    let one = QuadExtension::ONE;
+    let integrity = [
+        a_prime - (a + one),
+        b_prime - b * a,
+        p_prime - p * (a + rand[0]),
+    ];
+    let divisor = ConstraintDivisor::<Felt>::from_transition(trace_len as usize, exemptions);
+    let mut expected = integrity
+        .iter()
+        .zip(coefficients.iter())
+        .fold(QuadExtension::ZERO, |acc, (c, alpha)| acc + *c * *alpha)
+        / divisor.evaluate_at(z);
+
+    let first_step = 0;
+    let last_step = (trace_len - exemptions as u64) as usize;
+    let boundary = [
+        (Assertion::<Felt>::single(0, first_step, Felt::ZERO), a),
+        (
+            Assertion::<Felt>::single(1, last_step, Felt::new(7)),
+            b - QuadExtension::from(Felt::new(7)),
+        ),
+        (Assertion::<Felt>::single(0, first_step, Felt::ONE), p - one),
+        (Assertion::<Felt>::single(0, last_step, Felt::ONE), p - one),
+    ];
+    for ((assertion, numerator), alpha) in boundary.into_iter().zip(coefficients[3..].iter()) {
+        let divisor = ConstraintDivisor::<Felt>::from_assertion(&assertion, trace_len as usize);
+        expected += numerator * *alpha / divisor.evaluate_at(z);
+    }
+
+    let expected = to_stack_order(&[expected]);
+    assert_eq!(&result_stack[..expected.len()], expected.as_slice());
+
+    // the whole evaluation is the combination of the constraints
+    let code = codegen(RUNNING_PRODUCT_AIR);
+    assert!(code
+        .contains("- coefficients 3..4: boundary constraints for the first row of the main trace"));
+    assert!(code.contains("exec.combine_constraints"));
+}
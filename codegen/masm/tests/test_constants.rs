@@ -5,8 +5,7 @@ use miden_processor::{
     AdviceInputs, Kernel, MemAdviceProvider, Process, QuadExtension, StackInputs,
 };
 
-mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use air_codegen_masm::testing::{codegen, test_code, to_stack_order, Data};
 
 static CONSTANTS_AIR: &str = "
 def ConstantsAir
@@ -58,7 +57,7 @@ fn test_constants() {
                 descriptor: "main_trace",
             },
             Data {
-                data: to_stack_order(&[]),
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
                 address: constants::OOD_AUX_FRAME_ADDRESS,
                 descriptor: "aux_trace",
             },
@@ -6,8 +6,7 @@ use miden_processor::{
 };
 use winter_air::{Assertion, ConstraintDivisor};
 
-mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use air_codegen_masm::testing::{codegen, execute, test_code, to_stack_order, Data};
 
 static SIMPLE_INTEGRITY_AIR: &str = "
 def SimpleIntegrityAux
@@ -320,3 +319,74 @@ fn test_mixed_boundary_divisor() {
         );
     }
 }
+
+#[test]
+fn test_divisor_procedures() {
+    let code = codegen(SIMPLE_BOUNDARY_AIR);
+
+    let exemptions: usize = 2;
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let a = QuadExtension::new(Felt::new(13), Felt::ZERO);
+    let z = QuadExtension::new(Felt::new(29), Felt::new(31));
+
+    for power in [3, 10, 20] {
+        let trace_len = 2u64.pow(power);
+        let last_step: usize = trace_len as usize - exemptions;
+
+        let code = test_code(
+            code.clone(),
+            vec![
+                Data {
+                    data: to_stack_order(&[a, a]),
+                    address: constants::OOD_FRAME_ADDRESS,
+                    descriptor: "main_trace",
+                },
+                Data {
+                    data: to_stack_order(&[one; 5]),
+                    address: constants::COMPOSITION_COEF_ADDRESS,
+                    descriptor: "composition_coefficients",
+                },
+            ],
+            trace_len,
+            z,
+            &[
+                "cache_z_exp",
+                "compute_integrity_constraint_divisor",
+                "compute_boundary_constraint_divisor_first",
+                "compute_boundary_constraint_divisor_last",
+            ],
+        );
+
+        let trace_len: usize = trace_len.try_into().unwrap();
+        let transition = ConstraintDivisor::<Felt>::from_transition(trace_len, exemptions);
+        let first = ConstraintDivisor::<Felt>::from_assertion(
+            &Assertion::single(0, 0, Felt::ZERO),
+            trace_len,
+        );
+        let last = ConstraintDivisor::<Felt>::from_assertion(
+            &Assertion::single(0, last_step, Felt::ZERO),
+            trace_len,
+        );
+
+        // results are in stack-order
+        let expected = to_stack_order(&[
+            last.evaluate_at(z),
+            first.evaluate_at(z),
+            transition.evaluate_at(z),
+        ]);
+
+        let result = execute(code);
+        assert_eq!(&result[..6], &expected[..], "trace_len={trace_len}");
+    }
+}
+
+#[test]
+fn test_boundary_divisor_procedures_are_emitted_per_domain() {
+    let code = codegen(MIXED_BOUNDARY_AIR);
+    assert!(code.contains("proc.compute_boundary_constraint_divisor_first"));
+    assert!(code.contains("proc.compute_boundary_constraint_divisor_last"));
+
+    let code = codegen(SIMPLE_INTEGRITY_AIR);
+    assert!(code.contains("proc.compute_boundary_constraint_divisor_first"));
+    assert!(!code.contains("proc.compute_boundary_constraint_divisor_last"));
+}
@@ -1,12 +1,13 @@
-use air_codegen_masm::constants;
+use air_codegen_masm::{constants, error::CodegenError, CodegenConfig};
 use miden_assembly::Assembler;
 use miden_processor::{
     math::{Felt, FieldElement, StarkField},
     AdviceInputs, Kernel, MemAdviceProvider, Process, QuadExtension, StackInputs,
 };
 
-mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use air_codegen_masm::testing::{
+    codegen, test_code, to_stack_order, try_codegen_with_config, Data,
+};
 
 static SIMPLE_AIR: &str = "
 def Simple
@@ -73,3 +74,29 @@ fn test_exemption_points() {
         );
     }
 }
+
+static EXEMPTED_AIR: &str = "
+def ExemptedAir
+
+trace_columns:
+    main: [a]
+
+public_inputs:
+    stack_inputs: [1]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a' = a + 1 except last 4
+";
+
+#[test]
+fn test_exempted_constraints_are_rejected() {
+    let err = try_codegen_with_config(EXEMPTED_AIR, CodegenConfig::default())
+        .expect_err("exempted constraints should be rejected");
+    assert!(matches!(
+        err.downcast_ref::<CodegenError>(),
+        Some(CodegenError::UnsupportedExemptions)
+    ));
+}
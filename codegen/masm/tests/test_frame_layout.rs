@@ -0,0 +1,130 @@
+use air_codegen_masm::{constants, CodegenConfig, FrameLayout};
+use miden_assembly::Assembler;
+use miden_processor::{
+    math::{Felt, FieldElement},
+    AdviceInputs, Kernel, MemAdviceProvider, Process, QuadExtension, StackInputs,
+};
+
+use air_codegen_masm::testing::{codegen, codegen_with_config, test_code, to_stack_order, Data};
+
+static FRAME_LAYOUT_AIR: &str = "
+def FrameLayout
+
+trace_columns:
+    main: [a, b, c]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a' = a + b
+    enf b' = b * c
+    enf c' = c - a
+";
+
+/// Returns the values of the `current` and `next` rows of a frame, in the order in which they
+/// are stored in memory for the given layout.
+fn frame_to_stack_order(
+    layout: FrameLayout,
+    current: &[QuadExtension<Felt>],
+    next: &[QuadExtension<Felt>],
+) -> Vec<u64> {
+    let values: Vec<QuadExtension<Felt>> = match layout {
+        FrameLayout::RowInterleaved => current
+            .iter()
+            .zip(next.iter())
+            .flat_map(|(curr, next)| [*curr, *next])
+            .collect(),
+        FrameLayout::ColumnsGrouped => current.iter().chain(next.iter()).copied().collect(),
+    };
+    to_stack_order(&values)
+}
+
+fn evaluate_with_layout(layout: FrameLayout) {
+    let config = CodegenConfig {
+        frame_layout: layout,
+        ..Default::default()
+    };
+    let code = codegen_with_config(FRAME_LAYOUT_AIR, config);
+
+    let trace_len = 2u64.pow(4);
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let z = one;
+    let current = [
+        QuadExtension::new(Felt::new(3), Felt::ZERO),
+        QuadExtension::new(Felt::new(5), Felt::ZERO),
+        QuadExtension::new(Felt::new(7), Felt::ZERO),
+    ];
+    let next = [
+        QuadExtension::new(Felt::new(11), Felt::ZERO),
+        QuadExtension::new(Felt::new(13), Felt::ZERO),
+        QuadExtension::new(Felt::new(17), Felt::ZERO),
+    ];
+    let [a, b, c] = current;
+    let [a_prime, b_prime, c_prime] = next;
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: frame_to_stack_order(layout, &current, &next),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[one; 4]),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        trace_len,
+        z,
+        &["compute_integrity_constraints"],
+    );
+    let program = Assembler::default().compile(code).unwrap();
+
+    let mut process: Process<MemAdviceProvider> = Process::new(
+        Kernel::new(&[]),
+        StackInputs::new(vec![]),
+        AdviceInputs::default().into(),
+    );
+    let program_outputs = process.execute(&program).expect("execution failed");
+    let result_stack = program_outputs.stack();
+
+    // results are in stack-order
+    let expected = to_stack_order(&[c_prime - (c - a), b_prime - b * c, a_prime - (a + b)]);
+
+    assert!(
+        result_stack
+            .iter()
+            .zip(expected.iter())
+            .all(|(l, r)| l == r),
+        "results don't match for {:?} result={:?} expected={:?}",
+        layout,
+        result_stack,
+        expected,
+    );
+}
+
+#[test]
+fn test_row_interleaved_layout() {
+    // the row-interleaved layout is the default
+    let config = CodegenConfig {
+        frame_layout: FrameLayout::RowInterleaved,
+        ..Default::default()
+    };
+    assert_eq!(
+        codegen(FRAME_LAYOUT_AIR),
+        codegen_with_config(FRAME_LAYOUT_AIR, config)
+    );
+
+    evaluate_with_layout(FrameLayout::RowInterleaved);
+}
+
+#[test]
+fn test_columns_grouped_layout() {
+    evaluate_with_layout(FrameLayout::ColumnsGrouped);
+}
@@ -0,0 +1,188 @@
+use air_codegen_masm::{CodegenConfig, OutputMode};
+use miden_assembly::{
+    ast::ModuleAst, Assembler, Library, LibraryNamespace, LibraryPath, Module, Version,
+};
+use miden_processor::{
+    math::{Felt, FieldElement},
+    QuadExtension,
+};
+
+use air_codegen_masm::testing::{
+    codegen, execute, execute_with_assembler, generate, test_code, to_stack_order, Data,
+};
+
+static AIR: &str = "
+def Library
+
+trace_columns:
+    main: [a, b]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [16]
+
+random_values:
+    rand: [2]
+
+boundary_constraints:
+    enf a.first = 0
+    enf b.last = 7
+    enf p.first = 1
+
+integrity_constraints:
+    enf a' = a + 1
+    enf b' = b * a
+    enf p' = p * (a + $rand[0])
+";
+
+/// The procedures exported by the library mode, when the AIR has an auxiliary trace.
+const EXPORTS: [&str; 6] = [
+    "evaluate_aux_transitions",
+    "compute_integrity_constraints",
+    "evaluate_integrity_constraints",
+    "evaluate_boundary_constraints",
+    "combine_constraints",
+    "evaluate_constraints",
+];
+
+/// A library holding the single module `air::constraints`.
+struct AirLibrary {
+    namespace: LibraryNamespace,
+    version: Version,
+    modules: Vec<Module>,
+}
+impl AirLibrary {
+    fn new(code: &str) -> Self {
+        let path = LibraryPath::new("air::constraints").unwrap();
+        let ast = ModuleAst::parse(code).expect("the library module doesn't parse");
+        Self {
+            namespace: LibraryNamespace::new("air").unwrap(),
+            version: Version::MIN,
+            modules: vec![Module::new(path, ast)],
+        }
+    }
+}
+impl Library for AirLibrary {
+    type ModuleIterator<'a> = std::slice::Iter<'a, Module>;
+
+    fn root_ns(&self) -> &LibraryNamespace {
+        &self.namespace
+    }
+
+    fn version(&self) -> &Version {
+        &self.version
+    }
+
+    fn modules(&self) -> Self::ModuleIterator<'_> {
+        self.modules.iter()
+    }
+
+    fn dependencies(&self) -> &[LibraryNamespace] {
+        &[]
+    }
+}
+
+fn library_config() -> CodegenConfig {
+    CodegenConfig {
+        output_mode: OutputMode::Library,
+        ..Default::default()
+    }
+}
+
+/// Returns the test code evaluating all the constraints with `evaluate_constraints`, executed
+/// from `code`, which defines it or imports it as `procedure`.
+fn evaluate_constraints(code: String, procedure: &str) -> String {
+    let trace_len = 2u64.pow(5);
+    let z = QuadExtension::new(Felt::new(29), Felt::new(31));
+    let frame: Vec<QuadExtension<Felt>> = (0..4)
+        .map(|i| QuadExtension::new(Felt::new(i + 3), Felt::ZERO))
+        .collect();
+    let aux_frame = [
+        QuadExtension::new(Felt::new(5), Felt::new(2)),
+        QuadExtension::new(Felt::new(7), Felt::new(11)),
+    ];
+    let rand = [
+        QuadExtension::new(Felt::new(13), Felt::new(17)),
+        QuadExtension::new(Felt::new(19), Felt::new(23)),
+    ];
+    let coefficients: Vec<QuadExtension<Felt>> = (0..6)
+        .map(|i| QuadExtension::new(Felt::new(2 * i + 3), Felt::new(i + 1)))
+        .collect();
+    let config = CodegenConfig::default();
+
+    test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&frame),
+                address: config.ood_frame_address,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&aux_frame),
+                address: config.ood_aux_frame_address,
+                descriptor: "aux_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: config.composition_coef_address,
+                descriptor: "composition_coefficients",
+            },
+            Data {
+                data: to_stack_order(&rand),
+                address: config.aux_rand_address,
+                descriptor: "aux_random_elements",
+            },
+        ],
+        trace_len,
+        z,
+        &[procedure],
+    )
+}
+
+#[test]
+fn test_library_exports() {
+    let code = generate(AIR, library_config()).unwrap();
+    assert!(code.starts_with("#! Evaluation of the constraints of the Library AIR"));
+    assert!(!code.contains("begin"));
+    for export in EXPORTS {
+        assert!(code.contains(&format!("\nexport.{export}\n")), "{export}");
+    }
+    let exports = code
+        .lines()
+        .filter(|line| line.starts_with("export."))
+        .count();
+    assert_eq!(exports, EXPORTS.len());
+    assert!(code.contains("\nproc.cache_z_exp\n"));
+    assert!(code.contains("\nproc.compute_integrity_constraint_divisor\n"));
+}
+
+#[test]
+fn test_executable_mode_is_unchanged() {
+    let code = generate(AIR, CodegenConfig::default()).unwrap();
+    assert!(!code.contains("#!"));
+    let exports = code
+        .lines()
+        .filter(|line| line.starts_with("export."))
+        .collect::<Vec<_>>();
+    assert_eq!(exports, ["export.evaluate_constraints"]);
+}
+
+#[test]
+fn test_library_assembles() {
+    let library = AirLibrary::new(&generate(AIR, library_config()).unwrap());
+    let assembler = Assembler::default()
+        .with_library(&library)
+        .expect("the library doesn't assemble");
+
+    let code = evaluate_constraints(
+        "use.air::constraints\n".to_string(),
+        "constraints::evaluate_constraints",
+    );
+    let stack = execute_with_assembler(code, assembler);
+
+    // the library evaluates the constraints as the procedures of the executable mode
+    let expected = execute(evaluate_constraints(codegen(AIR), "evaluate_constraints"));
+    assert_ne!(&expected[..2], &[0, 0]);
+    assert_eq!(&stack[..2], &expected[..2]);
+}
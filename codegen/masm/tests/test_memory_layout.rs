@@ -0,0 +1,179 @@
+use air_codegen_masm::{constants, CodegenConfig};
+use miden_processor::{
+    math::{Felt, FieldElement},
+    QuadExtension,
+};
+
+use air_codegen_masm::testing::{
+    codegen_with_config, execute, test_code_with_config, to_stack_order, Data,
+};
+
+static MEMORY_LAYOUT_AIR: &str = "
+def MemoryLayout
+
+trace_columns:
+    main: [a, b]
+    aux: [p]
+
+periodic_columns:
+    k: [1, 2, 3, 4]
+
+public_inputs:
+    stack_inputs: [4]
+
+random_values:
+    rand: [2]
+
+boundary_constraints:
+    enf a.first = stack_inputs[2]
+    enf b.last = 7
+    enf p.first = 1
+
+integrity_constraints:
+    enf a' = a + k
+    enf p' = p * (b + $rand[1])
+";
+
+/// A layout which moves every region away from its default address.
+fn relocated() -> CodegenConfig {
+    CodegenConfig {
+        trace_len_address: 1000,
+        log2_trace_len_address: 1001,
+        z_address: 1002,
+        trace_domain_generator_address: 1003,
+        ood_frame_address: 2000,
+        ood_aux_frame_address: 2100,
+        composition_coef_address: 3000,
+        public_inputs_address: 4000,
+        aux_rand_address: 5000,
+        periodic_values_address: 6000,
+        z_exp_address: 7000,
+        exemption_two_address: 8000,
+        scratch_address: 9000,
+        ..Default::default()
+    }
+}
+
+/// Evaluates all the constraints of [MEMORY_LAYOUT_AIR] with the memory regions at the addresses
+/// of the given `config`.
+fn evaluate(config: CodegenConfig) -> Vec<u64> {
+    let code = codegen_with_config(MEMORY_LAYOUT_AIR, config);
+
+    let trace_len = 2u64.pow(4);
+    let z = QuadExtension::new(Felt::new(41), Felt::new(43));
+    let main = [3, 5, 11, 13].map(|v| QuadExtension::new(Felt::new(v), Felt::ZERO));
+    let aux = [
+        QuadExtension::new(Felt::new(5), Felt::new(2)),
+        QuadExtension::new(Felt::new(7), Felt::new(11)),
+    ];
+    let rand = [
+        QuadExtension::new(Felt::new(13), Felt::new(17)),
+        QuadExtension::new(Felt::new(19), Felt::new(23)),
+    ];
+    let coefficients: Vec<QuadExtension<Felt>> = (0..5)
+        .map(|i| QuadExtension::new(Felt::new(i + 2), Felt::new(i + 3)))
+        .collect();
+
+    let code = test_code_with_config(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&main),
+                address: config.ood_frame_address,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&aux),
+                address: config.ood_aux_frame_address,
+                descriptor: "aux_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: config.composition_coef_address,
+                descriptor: "composition_coefficients",
+            },
+            Data {
+                data: to_stack_order(&rand),
+                address: config.aux_rand_address,
+                descriptor: "aux_random_elements",
+            },
+            Data {
+                data: vec![29, 31, 37, 41],
+                address: config.public_inputs_address,
+                descriptor: "public_inputs",
+            },
+        ],
+        trace_len,
+        z,
+        &["cache_z_exp", "combine_constraints"],
+        &config,
+    );
+
+    execute(code)
+}
+
+#[test]
+fn test_relocated_memory_layout() {
+    let expected = evaluate(CodegenConfig::default());
+    let result = evaluate(relocated());
+
+    assert_ne!(&expected[..2], &[0, 0]);
+    assert_eq!(&result[..2], &expected[..2]);
+}
+
+#[test]
+fn test_memory_layout_header() {
+    let config = relocated();
+    let code = codegen_with_config(MEMORY_LAYOUT_AIR, config);
+
+    assert!(code.starts_with("# MEMORY LAYOUT\n"));
+    for (region, address) in config.memory_regions() {
+        assert!(code.contains(&format!("# - {region}: {address}\n")));
+    }
+
+    // none of the default addresses are used
+    for address in [
+        constants::OOD_FRAME_ADDRESS,
+        constants::OOD_AUX_FRAME_ADDRESS,
+        constants::COMPOSITION_COEF_ADDRESS,
+        constants::PUBLIC_INPUTS_ADDRESS,
+        constants::AUX_RAND_ELEM_PTR,
+        constants::Z_ADDRESS,
+        constants::TRACE_DOMAIN_GENERATOR_ADDRESS,
+    ] {
+        assert!(!code.contains(&address.to_string()), "{address} is used");
+    }
+}
+
+#[test]
+fn test_memory_layout_header_docs() {
+    let source = r#"
+def Documented
+
+trace_columns:
+    main: [clk "clock cycle", fmp[2] "free memory pointer", a]
+    aux: [p "running product"]
+
+public_inputs:
+    stack_inputs: [4] "initial stack"
+
+random_values:
+    rand: [2]
+
+boundary_constraints:
+    enf clk.first = 0
+
+integrity_constraints:
+    enf clk' = clk + 1
+    enf p' = p * $rand[0]
+"#;
+    let code = codegen_with_config(source, CodegenConfig::default());
+
+    assert!(code.contains("# - stack_inputs: 0 to 3, initial stack\n"));
+    assert!(code.contains(
+        "# - clk, main column 0: clock cycle\n\
+         # - fmp, main columns 1 to 2: free memory pointer\n\
+         # - p, aux column 0: running product\n"
+    ));
+    assert!(!code.contains("# - a,"));
+}
@@ -1,12 +1,14 @@
-use air_codegen_masm::constants;
+use air_codegen_masm::{constants, error::CodegenError, CodegenConfig, FieldExtension};
 use miden_assembly::Assembler;
 use miden_processor::{
     math::{Felt, FieldElement},
     AdviceInputs, Kernel, MemAdviceProvider, Process, QuadExtension, StackInputs,
 };
+use winter_math::{fft, polynom};
 
-mod utils;
-use utils::{codegen, test_code, to_stack_order, Data};
+use air_codegen_masm::testing::{
+    codegen, test_code, to_stack_order, try_codegen_with_config, Data,
+};
 
 static SIMPLE_AUX_AIR: &str = "
 def SimpleAux
@@ -46,7 +48,7 @@ fn test_simple_periodic() {
                 descriptor: "main_trace",
             },
             Data {
-                data: to_stack_order(&[]),
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
                 address: constants::OOD_AUX_FRAME_ADDRESS,
                 descriptor: "aux_trace",
             },
@@ -137,7 +139,7 @@ fn test_multiple_periodic() {
                 descriptor: "main_trace",
             },
             Data {
-                data: to_stack_order(&[]),
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
                 address: constants::OOD_AUX_FRAME_ADDRESS,
                 descriptor: "aux_trace",
             },
@@ -183,3 +185,127 @@ fn test_multiple_periodic() {
         expected,
     );
 }
+
+static CYCLE_AIR: &str = "
+def CycleAir
+
+trace_columns:
+    main: [a, b, c]
+
+periodic_columns:
+    k: [1, 0]
+    m: [1, 2, 3, 4]
+    n: [5, 7, 11, 13]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf a * m = 0
+    enf b * n = 0
+    enf c * k = 0
+";
+
+/// Evaluates the periodic column with the given cycle at the out-of-domain point `z`, i.e. the
+/// polynomial interpolating the cycle at `z^(trace_len / cycle_len)`, as winterfell does.
+fn eval_periodic_column(
+    cycle: &[u64],
+    trace_len: u64,
+    z: QuadExtension<Felt>,
+) -> QuadExtension<Felt> {
+    let inv_twiddles = fft::get_inv_twiddles::<Felt>(cycle.len());
+    let mut poly: Vec<Felt> = cycle.iter().map(|e| Felt::new(*e)).collect();
+    fft::interpolate_poly(&mut poly, &inv_twiddles);
+    let poly: Vec<QuadExtension<Felt>> = poly.into_iter().map(QuadExtension::from).collect();
+
+    let num_cycles = trace_len / cycle.len() as u64;
+    polynom::eval(&poly, z.exp(num_cycles))
+}
+
+#[test]
+fn test_periodic_cycle_values() {
+    let code = codegen(CYCLE_AIR);
+
+    let trace_len = 2u64.pow(4);
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let z = QuadExtension::new(Felt::new(7), Felt::new(11));
+    let a = QuadExtension::new(Felt::new(3), Felt::ZERO);
+    let b = QuadExtension::new(Felt::new(5), Felt::ZERO);
+    let c = QuadExtension::new(Felt::new(9), Felt::ZERO);
+
+    let code = test_code(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&[a, a, b, b, c, c]),
+                address: constants::OOD_FRAME_ADDRESS,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order::<QuadExtension<Felt>>(&[]),
+                address: constants::OOD_AUX_FRAME_ADDRESS,
+                descriptor: "aux_trace",
+            },
+            Data {
+                data: to_stack_order(&[one; 3]),
+                address: constants::COMPOSITION_COEF_ADDRESS,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        trace_len,
+        z,
+        &[
+            "cache_z_exp",
+            "cache_periodic_polys",
+            "compute_integrity_constraints",
+        ],
+    );
+    let program = Assembler::default().compile(code).unwrap();
+
+    let mut process: Process<MemAdviceProvider> = Process::new(
+        Kernel::new(&[]),
+        StackInputs::new(vec![]),
+        AdviceInputs::default().into(),
+    );
+    let program_outputs = process.execute(&program).expect("execution failed");
+    let result_stack = program_outputs.stack();
+
+    let k = eval_periodic_column(&[1, 0], trace_len, z);
+    let m = eval_periodic_column(&[1, 2, 3, 4], trace_len, z);
+    let n = eval_periodic_column(&[5, 7, 11, 13], trace_len, z);
+
+    // results are in stack-order
+    #[rustfmt::skip]
+    let expected = to_stack_order(&[
+        c * k,
+        b * n,
+        a * m,
+    ]);
+
+    assert!(
+        result_stack
+            .iter()
+            .zip(expected.iter())
+            .all(|(l, r)| l == r),
+        "results don't match result={:?} expected={:?}",
+        result_stack,
+        expected,
+    );
+}
+
+#[test]
+fn test_cubic_periodic_columns_are_rejected() {
+    let config = CodegenConfig {
+        extension: FieldExtension::Cubic,
+        ..Default::default()
+    };
+    let err = try_codegen_with_config(SIMPLE_AUX_AIR, config)
+        .expect_err("periodic columns should be rejected over the cubic extension");
+    assert!(matches!(
+        err.downcast_ref::<CodegenError>(),
+        Some(CodegenError::UnsupportedCubicPeriodicColumns)
+    ));
+}
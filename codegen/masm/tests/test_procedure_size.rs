@@ -0,0 +1,116 @@
+use air_codegen_masm::CodegenConfig;
+use miden_processor::{
+    math::{Felt, FieldElement},
+    QuadExtension,
+};
+
+use air_codegen_masm::testing::{
+    codegen_with_config, execute, instruction_count, procedures, test_code_with_config,
+    to_stack_order, Data,
+};
+
+const NUM_COLUMNS: usize = 40;
+const NUM_CONSTRAINTS: usize = 500;
+
+/// Returns an AIR with [NUM_CONSTRAINTS] integrity constraints over [NUM_COLUMNS] columns.
+fn many_constraints_air() -> String {
+    let constraints = (0..NUM_CONSTRAINTS)
+        .map(|i| {
+            let a = i % NUM_COLUMNS;
+            let b = (i + 1) % NUM_COLUMNS;
+            format!("    enf c[{a}]' = c[{a}] * c[{b}] + {i}")
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "
+def ManyConstraints
+
+trace_columns:
+    main: [c[{NUM_COLUMNS}]]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf c[0].first = 0
+
+integrity_constraints:
+{}
+",
+        constraints.join("\n")
+    )
+}
+
+/// Evaluates the integrity constraints of [many_constraints_air] generated with `config`.
+fn evaluate(config: CodegenConfig) -> Vec<u64> {
+    let code = codegen_with_config(&many_constraints_air(), config);
+
+    let trace_len = 2u64.pow(4);
+    let z = QuadExtension::new(Felt::new(41), Felt::new(43));
+    let frame: Vec<QuadExtension<Felt>> = (0..NUM_COLUMNS as u64)
+        .flat_map(|i| {
+            [
+                QuadExtension::new(Felt::new(i + 1), Felt::new(i + 2)),
+                QuadExtension::new(Felt::new(i + 3), Felt::ZERO),
+            ]
+        })
+        .collect();
+    let coefficients: Vec<QuadExtension<Felt>> = (0..NUM_CONSTRAINTS as u64)
+        .map(|i| QuadExtension::new(Felt::new(i + 5), Felt::new(i + 7)))
+        .collect();
+
+    let code = test_code_with_config(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&frame),
+                address: config.ood_frame_address,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: config.composition_coef_address,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        trace_len,
+        z,
+        &["evaluate_integrity_constraints"],
+        &config,
+    );
+
+    execute(code)
+}
+
+#[test]
+fn test_split_integrity_constraints() {
+    // with the default budget all the constraints are evaluated by a single procedure
+    let code = codegen_with_config(&many_constraints_air(), CodegenConfig::default());
+    assert!(!code.contains("proc.compute_integrity_constraints_0"));
+
+    let config = CodegenConfig {
+        max_procedure_instructions: 1000,
+        ..Default::default()
+    };
+    let code = codegen_with_config(&many_constraints_air(), config);
+    let chunks = procedures(&code, "compute_integrity_constraints_");
+    assert!(chunks.len() > 1);
+    for chunk in &chunks {
+        assert!(instruction_count(chunk) <= config.max_procedure_instructions);
+    }
+
+    // the top-level procedure executes every chunk in order
+    let [compute] = procedures(&code, "compute_integrity_constraints\n")[..] else {
+        panic!("expected a single compute_integrity_constraints procedure");
+    };
+    let execs = (0..chunks.len())
+        .map(|i| format!("    exec.compute_integrity_constraints_{i}\n"))
+        .collect::<String>();
+    assert_eq!(compute.trim(), execs.trim());
+
+    // splitting the constraints doesn't change their evaluation
+    let expected = evaluate(CodegenConfig::default());
+    assert_ne!(&expected[..2], &[0, 0]);
+    assert_eq!(&evaluate(config)[..2], &expected[..2]);
+}
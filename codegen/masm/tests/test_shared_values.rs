@@ -0,0 +1,118 @@
+use air_codegen_masm::CodegenConfig;
+use miden_processor::{
+    math::{Felt, FieldElement},
+    QuadExtension,
+};
+
+use air_codegen_masm::testing::{
+    codegen_with_config, execute, instruction_count, procedures, test_code_with_config,
+    to_stack_order, Data,
+};
+
+/// An AIR whose constraints all use the subexpression `s`, which is also used several times by
+/// each of them.
+static SHARED_AIR: &str = "
+def Shared
+
+trace_columns:
+    main: [a, b, c, d]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    let s = (a + b) * (a - b) + a * b * c
+    enf c' = s * s + s
+    enf d' = s^3 + s * c
+    enf a' = s * d - s
+";
+
+/// Evaluates the integrity constraints of [SHARED_AIR] generated with `config`.
+fn evaluate(config: CodegenConfig) -> Vec<u64> {
+    let code = codegen_with_config(SHARED_AIR, config);
+
+    let trace_len = 2u64.pow(4);
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let frame: Vec<QuadExtension<Felt>> = (0..8)
+        .map(|i| QuadExtension::new(Felt::new(i + 3), Felt::new(2 * i + 5)))
+        .collect();
+    let coefficients: Vec<QuadExtension<Felt>> = (0..3)
+        .map(|i| QuadExtension::new(Felt::new(i + 7), Felt::new(i + 11)))
+        .collect();
+
+    let code = test_code_with_config(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&frame),
+                address: config.ood_frame_address,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&coefficients),
+                address: config.composition_coef_address,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        trace_len,
+        one,
+        &["compute_integrity_constraints"],
+        &config,
+    );
+
+    execute(code)
+}
+
+#[test]
+fn test_shared_subexpressions() {
+    let shared = CodegenConfig::default();
+    let recomputed = CodegenConfig {
+        reuse_shared_subexpressions: false,
+        ..Default::default()
+    };
+
+    let code = codegen_with_config(SHARED_AIR, shared);
+    assert!(code.contains("Evaluate the shared subexpressions into the scratch memory"));
+    let [body] = procedures(&code, "compute_integrity_constraints\n")[..] else {
+        panic!("expected a single compute_integrity_constraints procedure");
+    };
+
+    let code = codegen_with_config(SHARED_AIR, recomputed);
+    assert!(!code.contains("Evaluate the shared subexpressions into the scratch memory"));
+    let [recomputed_body] = procedures(&code, "compute_integrity_constraints\n")[..] else {
+        panic!("expected a single compute_integrity_constraints procedure");
+    };
+
+    assert!(instruction_count(body) < instruction_count(recomputed_body));
+
+    // loading the shared subexpressions doesn't change the evaluation of the constraints
+    let expected = evaluate(recomputed);
+    assert_ne!(&expected[..6], &[0; 6]);
+    assert_eq!(&evaluate(shared)[..6], &expected[..6]);
+}
+
+#[test]
+fn test_cheap_subexpressions_are_recomputed() {
+    // `a + b` is used twice, which is cheaper than storing it once and loading it twice
+    let source = "
+def Cheap
+
+trace_columns:
+    main: [a, b, c, d]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 0
+
+integrity_constraints:
+    enf c' = a + b + d
+    enf d' = (a + b) * c
+";
+    let code = codegen_with_config(source, CodegenConfig::default());
+    assert!(!code.contains("Evaluate the shared subexpressions into the scratch memory"));
+}
@@ -0,0 +1,124 @@
+use air_codegen_masm::{constants, CodegenConfig};
+use miden_processor::{
+    math::{Felt, FieldElement},
+    QuadExtension,
+};
+
+use air_codegen_masm::testing::{
+    codegen_with_config, execute, test_code_with_config, to_stack_order, Data,
+};
+
+const NUM_TERMS: usize = 30;
+
+/// Returns an AIR with a single integrity constraint summing [NUM_TERMS] columns, the sum is
+/// nested to the right and alternates additions and subtractions, i.e.
+/// `c[0] + (c[1] - (c[2] + ...))`, so every left operand has to be kept while the remaining terms
+/// are evaluated.
+fn deep_sum_air() -> String {
+    let mut expr = format!("c[{}]", NUM_TERMS - 1);
+    for i in (0..NUM_TERMS - 1).rev() {
+        let op = if i % 2 == 0 { "+" } else { "-" };
+        expr = format!("c[{i}] {op} ({expr})");
+    }
+
+    format!(
+        "
+def DeepSum
+
+trace_columns:
+    main: [c[{NUM_TERMS}]]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf c[0].first = 0
+
+integrity_constraints:
+    enf {expr} = 0
+"
+    )
+}
+
+/// The value of the column `i` in the current row.
+fn column(i: usize) -> QuadExtension<Felt> {
+    QuadExtension::new(Felt::new(i as u64 + 1), Felt::new(3 * i as u64 + 7))
+}
+
+/// Evaluates the integrity constraint of [deep_sum_air] with the given `config`.
+fn evaluate(config: CodegenConfig) -> Vec<u64> {
+    let code = codegen_with_config(&deep_sum_air(), config);
+
+    let trace_len = 2u64.pow(4);
+    let one = QuadExtension::new(Felt::new(1), Felt::ZERO);
+    let frame: Vec<QuadExtension<Felt>> = (0..NUM_TERMS)
+        .flat_map(|i| [column(i), QuadExtension::ZERO])
+        .collect();
+
+    let code = test_code_with_config(
+        code,
+        vec![
+            Data {
+                data: to_stack_order(&frame),
+                address: config.ood_frame_address,
+                descriptor: "main_trace",
+            },
+            Data {
+                data: to_stack_order(&[one; 2]),
+                address: config.composition_coef_address,
+                descriptor: "composition_coefficients",
+            },
+        ],
+        trace_len,
+        one,
+        &["compute_integrity_constraints"],
+        &config,
+    );
+
+    execute(code)
+}
+
+#[test]
+fn test_deep_expression() {
+    let mut expected = column(NUM_TERMS - 1);
+    for i in (0..NUM_TERMS - 1).rev() {
+        expected = if i % 2 == 0 {
+            column(i) + expected
+        } else {
+            column(i) - expected
+        };
+    }
+    let expected = to_stack_order(&[expected]);
+
+    let result = evaluate(CodegenConfig::default());
+    assert_eq!(&result[..2], &expected[..]);
+
+    for max_stack_depth in [0, 1, 3] {
+        let result = evaluate(CodegenConfig {
+            max_stack_depth,
+            ..Default::default()
+        });
+        assert_eq!(
+            &result[..2],
+            &expected[..],
+            "max_stack_depth={max_stack_depth}"
+        );
+    }
+}
+
+#[test]
+fn test_spill_to_scratch_memory() {
+    let scratch = format!("mem_storew.{}", constants::SCRATCH_ADDRESS);
+
+    let code = codegen_with_config(&deep_sum_air(), CodegenConfig::default());
+    assert!(code.contains(&scratch));
+
+    let code = codegen_with_config(
+        &deep_sum_air(),
+        CodegenConfig {
+            max_stack_depth: NUM_TERMS,
+            ..Default::default()
+        },
+    );
+    assert!(!code.contains(&scratch));
+}
@@ -0,0 +1,120 @@
+//! Compiles the Plonky3 code generated for the Fibonacci Air against a pinned version of the
+//! Plonky3 crates, and checks its constraints against traces row by row.
+
+/// The Fibonacci Air, as generated by the Plonky3 code generator in the build script.
+pub mod fibonacci {
+    include!(concat!(env!("OUT_DIR"), "/fibonacci.rs"));
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+    use p3_field::AbstractField;
+    use p3_goldilocks::Goldilocks;
+    use p3_matrix::{dense::RowMajorMatrix, Matrix};
+
+    use super::fibonacci::FibonacciAir;
+
+    /// An [AirBuilder] which evaluates the constraints on a single row of a trace, and the row
+    /// following it, and counts the constraints which do not evaluate to zero.
+    struct RowChecker<'a> {
+        trace: &'a RowMajorMatrix<Goldilocks>,
+        row: usize,
+        public_values: &'a [Goldilocks],
+        failures: usize,
+    }
+    impl<'a> AirBuilder for RowChecker<'a> {
+        type F = Goldilocks;
+        type Expr = Goldilocks;
+        type Var = Goldilocks;
+        type M = RowMajorMatrix<Goldilocks>;
+
+        fn main(&self) -> Self::M {
+            let next = (self.row + 1) % self.trace.height();
+            let mut values = self.trace.row_slice(self.row).to_vec();
+            values.extend_from_slice(&self.trace.row_slice(next));
+            RowMajorMatrix::new(values, self.trace.width())
+        }
+
+        fn is_first_row(&self) -> Self::Expr {
+            Goldilocks::from_bool(self.row == 0)
+        }
+
+        fn is_last_row(&self) -> Self::Expr {
+            Goldilocks::from_bool(self.row == self.trace.height() - 1)
+        }
+
+        fn is_transition_window(&self, size: usize) -> Self::Expr {
+            Goldilocks::from_bool(self.row + size <= self.trace.height())
+        }
+
+        fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I) {
+            if x.into() != Goldilocks::zero() {
+                self.failures += 1;
+            }
+        }
+    }
+    impl<'a> AirBuilderWithPublicValues for RowChecker<'a> {
+        type PublicVar = Goldilocks;
+
+        fn public_values(&self) -> &[Self::PublicVar] {
+            self.public_values
+        }
+    }
+
+    /// Returns the number of constraints of the Fibonacci Air which fail over all rows of `trace`
+    fn failures(trace: &RowMajorMatrix<Goldilocks>, result: u64) -> usize {
+        let public_values = [Goldilocks::from_canonical_u64(result)];
+        (0..trace.height())
+            .map(|row| {
+                let mut builder = RowChecker {
+                    trace,
+                    row,
+                    public_values: &public_values,
+                    failures: 0,
+                };
+                FibonacciAir.eval(&mut builder);
+                builder.failures
+            })
+            .sum()
+    }
+
+    /// Returns the trace of the Fibonacci sequence over `height` rows, and its last term
+    fn fibonacci_trace(height: usize) -> (RowMajorMatrix<Goldilocks>, u64) {
+        let (mut a, mut b) = (1u64, 1u64);
+        let mut values = Vec::with_capacity(height * 2);
+        for row in 0..height {
+            values.push(Goldilocks::from_canonical_u64(a));
+            values.push(Goldilocks::from_canonical_u64(b));
+            if row + 1 < height {
+                (a, b) = (b, a + b);
+            }
+        }
+        (RowMajorMatrix::new(values, 2), b)
+    }
+
+    #[test]
+    fn width_is_main_segment_width() {
+        assert_eq!(BaseAir::<Goldilocks>::width(&FibonacciAir), 2);
+    }
+
+    #[test]
+    fn valid_trace_satisfies_constraints() {
+        let (trace, result) = fibonacci_trace(8);
+        assert_eq!(failures(&trace, result), 0);
+    }
+
+    #[test]
+    fn wrong_result_fails_boundary_constraint() {
+        let (trace, result) = fibonacci_trace(8);
+        assert_eq!(failures(&trace, result + 1), 1);
+    }
+
+    #[test]
+    fn wrong_transition_fails_integrity_constraint() {
+        let (mut trace, result) = fibonacci_trace(8);
+        // corrupting `b` in a middle row breaks both transitions into and out of it
+        trace.values[2 * 3 + 1] += Goldilocks::one();
+        assert_eq!(failures(&trace, result), 3);
+    }
+}
@@ -0,0 +1,186 @@
+use air_ir::{
+    Air, ConstraintDomain, ConstraintRoot, NodeIndex, Operation, PublicInputAccess, TraceAccess,
+    Value, ValueCollector, Visit,
+};
+use codegen::{Function, Scope};
+
+/// Validates that the IR only uses features which can be expressed by the generated code, i.e.
+/// that it has a single trace segment, no periodic columns, no field inverses, and no row offsets
+/// beyond the next row.
+pub(crate) fn validate(ir: &Air) -> anyhow::Result<()> {
+    if ir.trace_segment_widths.len() > 1 {
+        anyhow::bail!("auxiliary trace segments are not supported by the Plonky3 backend");
+    }
+    if ir.periodic_columns().next().is_some() {
+        anyhow::bail!("periodic columns are not supported by the Plonky3 backend");
+    }
+
+    let graph = ir.constraint_graph();
+    let mut stack = constraints(ir)
+        .map(|constraint| *constraint.node_index())
+        .collect::<Vec<_>>();
+    while let Some(index) = stack.pop() {
+        match graph.node(&index).op() {
+            Operation::Inv(_) => {
+                anyhow::bail!("field inverses are not supported by the Plonky3 backend")
+            }
+            Operation::Value(Value::TraceAccess(access)) if access.row_offset > 1 => {
+                anyhow::bail!("row offsets greater than 1 are not supported by the Plonky3 backend")
+            }
+            _ => (),
+        }
+        stack.extend(graph.children(&index).into_iter().flatten());
+    }
+
+    Ok(())
+}
+
+/// Updates the provided scope with a struct named after the AIR, which implements the `BaseAir`
+/// and `Air` traits of Plonky3.
+pub(crate) fn add_air(scope: &mut Scope, ir: &Air) {
+    let name = ir.name();
+
+    let layout = ir
+        .public_inputs()
+        .scan(0, |offset, input| {
+            let start = *offset;
+            *offset += input.size;
+            Some(format!("`{}` at {start}..{}", input.name, *offset))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    scope
+        .new_struct(name)
+        .vis("pub")
+        .derive("Debug")
+        .derive("Default")
+        .derive("Clone")
+        .derive("Copy")
+        .doc(&format!(
+            "The constraints of `{name}`, enforced against a Plonky3 `AirBuilder`.\n\nThe public values are the public inputs, in declaration order: {layout}."
+        ));
+
+    scope
+        .new_impl(name)
+        .generic("F")
+        .impl_trait("BaseAir<F>")
+        .new_fn("width")
+        .arg_ref_self()
+        .ret("usize")
+        .line(ir.trace_segment_widths[0].to_string());
+
+    let eval = scope
+        .new_impl(name)
+        .generic("AB: AirBuilderWithPublicValues")
+        .impl_trait("Air<AB>")
+        .new_fn("eval")
+        .arg_ref_self()
+        .arg("builder", "&mut AB");
+    add_fn_eval_body(eval, ir);
+}
+
+/// Adds the body of the `eval` method, which binds the rows of the main trace and the public
+/// values as expressions, and enforces each constraint on the rows of its domain.
+fn add_fn_eval_body(eval: &mut Function, ir: &Air) {
+    let mut collector = ValueCollector::default();
+    collector.visit_postorder(
+        ir.constraint_graph(),
+        constraints(ir).map(|constraint| *constraint.node_index()),
+    );
+    let values = collector.values();
+    let uses_next = values
+        .iter()
+        .any(|value| matches!(value, Value::TraceAccess(access) if access.row_offset == 1));
+    let uses_public_values = values
+        .iter()
+        .any(|value| matches!(value, Value::PublicInput(_)));
+
+    eval.line("let main = builder.main();");
+    eval.line("let local: Vec<AB::Expr> = main.row_slice(0).iter().map(|&v| v.into()).collect();");
+    if uses_next {
+        eval.line(
+            "let next: Vec<AB::Expr> = main.row_slice(1).iter().map(|&v| v.into()).collect();",
+        );
+    }
+    if uses_public_values {
+        eval.line("let public_values: Vec<AB::Expr> = builder.public_values().iter().map(|&v| v.into()).collect();");
+    }
+
+    for constraint in constraints(ir) {
+        let expr = expr(ir, constraint.node_index());
+        eval.line(format!(
+            "{}.assert_zero({expr});",
+            guarded_builder(constraint)
+        ));
+    }
+}
+
+/// Returns the constraints of the main trace segment, boundary constraints first
+fn constraints(ir: &Air) -> impl Iterator<Item = &ConstraintRoot> {
+    ir.boundary_constraints(0)
+        .iter()
+        .chain(ir.integrity_constraints(0))
+}
+
+/// Returns the builder against which `constraint` is enforced, restricted to the rows of its
+/// domain which are not exempted.
+///
+/// Integrity constraints are not enforced on the last rows of the trace from which their frame
+/// would extend beyond its end, nor on their exempted rows.
+fn guarded_builder(constraint: &ConstraintRoot) -> String {
+    let excluded_rows = match constraint.domain() {
+        ConstraintDomain::FirstRow => return "builder.when_first_row()".to_string(),
+        ConstraintDomain::LastRow => return "builder.when_last_row()".to_string(),
+        ConstraintDomain::EveryRow => constraint.exemptions(),
+        ConstraintDomain::EveryFrame(size) => constraint.exemptions().max(size - 1),
+    };
+    match excluded_rows {
+        0 => "builder".to_string(),
+        1 => "builder.when_transition()".to_string(),
+        rows => format!("builder.when_transition_window({})", rows + 1),
+    }
+}
+
+/// Returns the Rust expression of type `AB::Expr` evaluating the subgraph rooted at `index`.
+fn expr(ir: &Air, index: &NodeIndex) -> String {
+    let operand = |index: &NodeIndex| match ir.constraint_graph().node(index).op() {
+        Operation::Value(_) => expr(ir, index),
+        _ => format!("({})", expr(ir, index)),
+    };
+    match ir.constraint_graph().node(index).op() {
+        Operation::Value(value) => value_expr(ir, value),
+        Operation::Add(lhs, rhs) => format!("{} + {}", operand(lhs), operand(rhs)),
+        Operation::Sub(lhs, rhs) => format!("{} - {}", operand(lhs), operand(rhs)),
+        Operation::Mul(lhs, rhs) => format!("{} * {}", operand(lhs), operand(rhs)),
+        // x^0 = 1
+        Operation::Exp(_, 0) => "AB::Expr::one()".to_string(),
+        Operation::Exp(lhs, exp) => format!("{}.exp_u64({exp})", operand(lhs)),
+        Operation::Inv(_) => unreachable!("field inverses are rejected by validation"),
+    }
+}
+
+/// Returns the Rust expression of type `AB::Expr` of a leaf of the constraint graph.
+fn value_expr(ir: &Air, value: &Value) -> String {
+    match value {
+        Value::Constant(value) => {
+            format!("AB::Expr::from_canonical_u64({value})")
+        }
+        Value::TraceAccess(TraceAccess {
+            column, row_offset, ..
+        }) => match row_offset {
+            0 => format!("local[{column}].clone()"),
+            _ => format!("next[{column}].clone()"),
+        },
+        Value::PublicInput(PublicInputAccess { name, index }) => {
+            let offset: usize = ir
+                .public_inputs()
+                .take_while(|input| input.name != *name)
+                .map(|input| input.size)
+                .sum();
+            format!("public_values[{}].clone()", offset + index)
+        }
+        Value::PeriodicColumn(_) | Value::RandomValue(_) => {
+            unreachable!("periodic columns and aux segments are rejected by validation")
+        }
+    }
+}
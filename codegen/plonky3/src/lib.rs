@@ -0,0 +1,49 @@
+use air_ir::Air;
+use anyhow::Context;
+use codegen::Scope;
+
+mod air;
+
+// GENERATE RUST CODE FOR PLONKY3 AIR
+// ================================================================================================
+
+/// CodeGenerator is used to generate a Rust implementation of the `Air` trait of the Plonky3
+/// `p3-air` crate, in which the constraints of the provided [Air] are enforced against a generic
+/// `AirBuilder`.
+///
+/// The generated code targets version 0.2 of the Plonky3 crates. It only supports AIRs with a
+/// single trace segment, and without periodic columns or field inverses.
+#[derive(Default)]
+pub struct CodeGenerator;
+impl CodeGenerator {
+    /// Create a new instance of this code generator
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+impl air_ir::CodeGenerator for CodeGenerator {
+    type Output = String;
+
+    fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        air::validate(ir)?;
+
+        let mut scope = Scope::new();
+
+        // add the Plonky3 imports.
+        scope.import("p3_air", "Air");
+        scope.import("p3_air", "AirBuilder");
+        scope.import("p3_air", "AirBuilderWithPublicValues");
+        scope.import("p3_air", "BaseAir");
+        scope.import("p3_field", "AbstractField");
+        scope.import("p3_matrix", "Matrix");
+
+        // add the Air struct and its Plonky3 trait implementations.
+        air::add_air(&mut scope, ir);
+
+        // format the generated code, so that it does not depend on the layout of the scope.
+        let file =
+            syn::parse_file(&scope.to_string()).context("the generated code is not valid Rust")?;
+        Ok(prettyplease::unparse(&file))
+    }
+}
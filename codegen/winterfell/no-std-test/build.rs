@@ -0,0 +1,41 @@
+use std::{env, fs, path::PathBuf, sync::Arc};
+
+use air_codegen_winter::{CodeGenerator, WinterfellConfig};
+use air_ir::CodeGenerator as _;
+use air_pass::Pass;
+use miden_diagnostics::{
+    term::termcolor::ColorChoice, CodeMap, DefaultEmitter, DiagnosticsHandler,
+};
+
+/// The AirScript program compiled by this crate, shared with the tests of the `air!` macro
+const FIBONACCI: &str = "../../../air-script-macro/tests/fibonacci.air";
+
+/// Generates the no_std Winterfell code for the Fibonacci Air with the string-based generator, and
+/// writes it to `$OUT_DIR/fibonacci.rs`.
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let input_path = manifest_dir.join(FIBONACCI);
+    println!("cargo:rerun-if-changed={}", input_path.display());
+
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(DefaultEmitter::new(ColorChoice::Never));
+    let diagnostics = DiagnosticsHandler::new(Default::default(), codemap.clone(), emitter);
+
+    let ast = air_parser::parse_file(&diagnostics, codemap, &input_path)
+        .expect("failed to parse the Fibonacci Air");
+    let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
+        .chain(air_parser::transforms::Inlining::new(&diagnostics))
+        .chain(air_ir::passes::AstToAir::new(&diagnostics));
+    let air = pipeline
+        .run(ast)
+        .expect("failed to compile the Fibonacci Air");
+
+    let code = CodeGenerator::new(WinterfellConfig::default())
+        .with_tests(true)
+        .with_no_std(true)
+        .generate(&air)
+        .expect("failed to generate the Fibonacci Air");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("fibonacci.rs");
+    fs::write(out_path, code).expect("failed to write the generated Fibonacci Air");
+}
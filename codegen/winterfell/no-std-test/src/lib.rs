@@ -0,0 +1,29 @@
+//! Compiles the Winterfell code generated for the Fibonacci Air, with the `no_std` option, in a
+//! `#![no_std]` crate. The standard library is only linked when building the generated tests.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+/// The Fibonacci Air, as generated by the string-based code generator in the build script.
+///
+/// The generated code is written for simplicity rather than idiom, e.g. it declares frame bindings
+/// for every segment, whether or not they are used.
+#[allow(
+    unused_mut,
+    unused_variables,
+    clippy::let_and_return,
+    clippy::vec_init_then_push
+)]
+pub mod fibonacci {
+    include!(concat!(env!("OUT_DIR"), "/fibonacci.rs"));
+}
+
+/// The Fibonacci Air, as expanded by the `air!` macro.
+pub mod fibonacci_macro {
+    air_script_macro::air!(
+        "../../../air-script-macro/tests/fibonacci.air",
+        tests = true,
+        no_std = true
+    );
+}
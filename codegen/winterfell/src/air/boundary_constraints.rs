@@ -4,7 +4,7 @@ use air_ir::{
     Air, AlgebraicGraph, ConstraintDomain, NodeIndex, Operation, TraceAccess, TraceSegmentId, Value,
 };
 
-use super::{Codegen, ElemType, Impl};
+use super::{rand_values_declaration, Codegen, ElemType, Impl, SourceComments};
 
 // HELPERS TO GENERATE THE WINTERFELL BOUNDARY CONSTRAINT METHODS
 // ================================================================================================
@@ -12,7 +12,7 @@ use super::{Codegen, ElemType, Impl};
 /// Adds an implementation of the "get_assertions" method to the referenced Air implementation
 /// based on the data in the provided IR.
 /// TODO: add result types to these functions.
-pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &Air) {
+pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &Air, sources: SourceComments) {
     // define the function
     let get_assertions = impl_ref
         .new_fn("get_assertions")
@@ -20,7 +20,7 @@ pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &Air) {
         .ret("Vec<Assertion<Felt>>");
 
     // add the boundary constraints
-    add_assertions(get_assertions, ir, 0);
+    add_assertions(get_assertions, ir, 0, sources);
 
     // return the result
     get_assertions.line("result");
@@ -28,7 +28,7 @@ pub(super) fn add_fn_get_assertions(impl_ref: &mut Impl, ir: &Air) {
 
 /// Adds an implementation of the "get_aux_assertions" method to the referenced Air implementation
 /// based on the data in the provided IR.
-pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &Air) {
+pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &Air, sources: SourceComments) {
     // define the function
     let get_aux_assertions = impl_ref
         .new_fn("get_aux_assertions")
@@ -38,15 +38,20 @@ pub(super) fn add_fn_get_aux_assertions(impl_ref: &mut Impl, ir: &Air) {
         .ret("Vec<Assertion<E>>");
 
     // add the boundary constraints
-    add_assertions(get_aux_assertions, ir, 1);
+    add_assertions(get_aux_assertions, ir, 1, sources);
 
     // return the result
     get_aux_assertions.line("result");
 }
 
 /// Declares a result vector and adds assertions for boundary constraints to it for the specified
-/// trace segment
-fn add_assertions(func_body: &mut codegen::Function, ir: &Air, trace_segment: TraceSegmentId) {
+/// trace segment, each preceded by the source of the constraint, if it is known.
+fn add_assertions(
+    func_body: &mut codegen::Function,
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+    sources: SourceComments,
+) {
     let elem_type = if trace_segment == 0 {
         ElemType::Base
     } else {
@@ -56,12 +61,21 @@ fn add_assertions(func_body: &mut codegen::Function, ir: &Air, trace_segment: Tr
     // declare the result vector to be returned.
     func_body.line("let mut result = Vec::new();");
 
+    // instantiate the struct holding the random values by their names, if they are accessed so.
+    if let Some(declaration) = rand_values_declaration(ir, ir.boundary_constraints(trace_segment)) {
+        func_body.line(declaration);
+    }
+
     // add the boundary constraints
     for constraint in ir.boundary_constraints(trace_segment) {
         let (trace_access, expr_root) =
             split_boundary_constraint(ir.constraint_graph(), constraint.node_index());
         debug_assert_eq!(trace_access.segment, trace_segment);
 
+        if let Some(comment) = sources.comment(constraint) {
+            func_body.line(comment);
+        }
+
         let assertion = format!(
             "result.push(Assertion::single({}, {}, {}));",
             trace_access.column,
@@ -0,0 +1,77 @@
+use air_ir::{Air, ConstantAccess, ConstantExpr, QualifiedIdentifier};
+
+use super::Scope;
+
+/// Updates the provided scope with a `constants` module declaring a Rust constant for each named
+/// constant referenced by the IR.
+pub(super) fn add_constants_module(scope: &mut Scope, ir: &Air) {
+    if ir.constants.is_empty() {
+        return;
+    }
+
+    let mut declarations = vec![];
+    for (qid, constant) in ir.constants.iter() {
+        let name = constant_name(ir, qid);
+        let declaration = match &constant.value {
+            ConstantExpr::Scalar(value) => format!("pub const {name}: Felt = {};", felt(*value)),
+            ConstantExpr::Vector(values) => format!(
+                "pub const {name}: [Felt; {}] = [{}];",
+                values.len(),
+                felts(values)
+            ),
+            ConstantExpr::Matrix(rows) => format!(
+                "pub const {name}: [[Felt; {}]; {}] = [{}];",
+                rows.first().map(|row| row.len()).unwrap_or_default(),
+                rows.len(),
+                rows.iter()
+                    .map(|row| format!("[{}]", felts(row)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        declarations.push(declaration);
+    }
+
+    let module = scope.new_module("constants").vis("pub");
+    module.import("super", "Felt");
+    module.scope().raw(declarations.join("\n"));
+}
+
+/// Returns the name of the Rust constant declared for the named constant `qid`.
+///
+/// Constants are named after their declaration, unless constants with the same name are declared
+/// in several modules, in which case the name is prefixed with the name of the module.
+pub(crate) fn constant_name(ir: &Air, qid: &QualifiedIdentifier) -> String {
+    let name = qid.name();
+    let is_ambiguous = ir
+        .constants
+        .keys()
+        .any(|other| other != qid && other.name() == name);
+    if is_ambiguous {
+        format!("{}_{name}", qid.module.as_str().to_uppercase())
+    } else {
+        name.to_string()
+    }
+}
+
+/// Returns the path of the element of a named constant referenced by `access`, e.g.
+/// `constants::A`, `constants::B[1]`, or `constants::C[1][2]`.
+pub(crate) fn constant_path(ir: &Air, access: &ConstantAccess) -> String {
+    let mut path = format!("constants::{}", constant_name(ir, &access.name));
+    for index in access.index.iter().chain(access.column.iter()) {
+        path.push_str(&format!("[{index}]"));
+    }
+    path
+}
+
+fn felt(value: u64) -> String {
+    format!("Felt::new({value})")
+}
+
+fn felts(values: &[u64]) -> String {
+    values
+        .iter()
+        .map(|value| felt(*value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
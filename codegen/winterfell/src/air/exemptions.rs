@@ -0,0 +1,109 @@
+use air_ir::{Air, ConstraintRoot, TraceSegmentId, AUX_SEGMENT, DEFAULT_SEGMENT};
+
+use super::Impl;
+
+// HELPERS TO EMULATE THE EXEMPTIONS OF INTEGRITY CONSTRAINTS
+// ================================================================================================
+
+/// The number of rows at the end of the trace on which Winterfell does not enforce any transition
+/// constraint, as configured by the generated `set_num_transition_exemptions(2)`.
+///
+/// The targeted version of Winterfell only supports a single number of exemptions shared by all
+/// transition constraints, so constraints exempted from more rows than this are multiplied by a
+/// selector periodic column as long as the trace, which is non-zero on the rows where the
+/// constraint is enforced, and zero on its other exempted rows.
+///
+/// The value of the selector on the penultimate row, which is exempted from every transition
+/// constraint, is chosen to cancel the leading coefficient of its polynomial, so that its degree
+/// is two less than the trace length. This is the degree of a periodic column with a cycle of half
+/// the trace length, which is how the selector is declared in the degree of the constraint, and
+/// keeps the degree of the constraint composition polynomial within the bound assumed by
+/// Winterfell.
+pub(crate) const NUM_TRANSITION_EXEMPTIONS: usize = 2;
+
+/// Validates that the exemptions of the integrity constraints in the IR can be emulated, i.e. that
+/// no constraint of the aux trace segment is exempted from more rows than Winterfell exempts from
+/// all transition constraints, as periodic values are not passed to the evaluation of the aux
+/// transition constraints.
+pub(crate) fn validate_exemptions(ir: &Air) -> anyhow::Result<()> {
+    let max_exemptions = ir
+        .integrity_constraints(AUX_SEGMENT)
+        .iter()
+        .map(ConstraintRoot::exemptions)
+        .max()
+        .unwrap_or_default();
+    if max_exemptions > NUM_TRANSITION_EXEMPTIONS {
+        anyhow::bail!(
+            "aux trace constraints exempted from more than {NUM_TRANSITION_EXEMPTIONS} rows are not supported by the Winterfell backend, but one is exempted from {max_exemptions} rows"
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the number of rows exempted by each selector column, in the order in which the selector
+/// columns follow the periodic columns of the IR.
+///
+/// A selector column is generated for each distinct number of exemptions of the main trace
+/// constraints which exceeds [NUM_TRANSITION_EXEMPTIONS].
+pub(crate) fn exemption_selectors(ir: &Air) -> Vec<usize> {
+    let mut selectors = ir
+        .integrity_constraints(DEFAULT_SEGMENT)
+        .iter()
+        .map(ConstraintRoot::exemptions)
+        .filter(|exemptions| *exemptions > NUM_TRANSITION_EXEMPTIONS)
+        .collect::<Vec<_>>();
+    selectors.sort_unstable();
+    selectors.dedup();
+    selectors
+}
+
+/// Returns the index in the periodic values of the selector column by which the provided
+/// constraint of the specified trace segment is multiplied, or `None` if its exemptions are
+/// already covered by the exemptions shared by all transition constraints.
+pub(crate) fn exemption_selector(
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+    constraint: &ConstraintRoot,
+) -> Option<usize> {
+    if trace_segment != DEFAULT_SEGMENT || constraint.exemptions() <= NUM_TRANSITION_EXEMPTIONS {
+        return None;
+    }
+
+    exemption_selectors(ir)
+        .iter()
+        .position(|exemptions| *exemptions == constraint.exemptions())
+        .map(|position| ir.periodic_columns.len() + position)
+}
+
+/// Adds a method returning the values of the selector column exempting constraints from the last
+/// `exemptions` rows of the trace to the referenced implementation, which is expected to be the
+/// base implementation of the custom Air struct, if any constraint requires a selector column.
+///
+/// The leading coefficient of the polynomial of the selector is proportional to the sum of
+/// `values[i] * g^i` over the rows of the trace, where `g` is the generator of the trace domain,
+/// so it is cancelled by setting the value of the penultimate row to `-sum * g^2`, as
+/// `g^(trace_length - 2) = g^-2`.
+pub(super) fn add_fn_exemption_selector(impl_ref: &mut Impl, ir: &Air) {
+    if exemption_selectors(ir).is_empty() {
+        return;
+    }
+
+    impl_ref
+        .new_fn("exemption_selector")
+        .arg_ref_self()
+        .arg("exemptions", "usize")
+        .ret("Vec<Felt>")
+        .line("let trace_length = self.trace_length();")
+        .line("let g = self.trace_domain_generator();")
+        .line("let mut values = vec![Felt::ZERO; trace_length];")
+        .line("let mut sum = Felt::ZERO;")
+        .line("let mut x = Felt::ONE;")
+        .line("for value in values.iter_mut().take(trace_length.saturating_sub(exemptions)) {")
+        .line("    *value = Felt::ONE;")
+        .line("    sum += x;")
+        .line("    x *= g;")
+        .line("}")
+        .line("values[trace_length - 2] = -sum * g.square();")
+        .line("values");
+}
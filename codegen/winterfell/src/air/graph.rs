@@ -2,7 +2,30 @@ use air_ir::{
     Air, IntegrityConstraintDegree, NodeIndex, Operation, TraceAccess, TraceSegmentId, Value,
 };
 
-use super::ElemType;
+use super::{constant_path, ElemType, RAND_VALUES_VAR};
+
+/// Validates that the constraints of the IR don't use field inverses, which are not polynomials
+/// of the trace columns, so they can't be represented by Winterfell transition constraints and
+/// assertions.
+pub(crate) fn validate_inverses(ir: &Air) -> anyhow::Result<()> {
+    let graph = ir.constraint_graph();
+    let mut stack = (0..ir.trace_segment_widths.len())
+        .flat_map(|segment| {
+            ir.integrity_constraints(segment)
+                .iter()
+                .chain(ir.boundary_constraints(segment))
+                .map(|constraint| *constraint.node_index())
+        })
+        .collect::<Vec<_>>();
+    while let Some(index) = stack.pop() {
+        if let Operation::Inv(_) = graph.node(&index).op() {
+            anyhow::bail!("field inverses are not supported by the Winterfell backend");
+        }
+        stack.extend(graph.children(&index).into_iter().flatten());
+    }
+
+    Ok(())
+}
 
 // RUST STRING GENERATION FOR THE CONSTRAINT GRAPH
 // ================================================================================================
@@ -57,6 +80,13 @@ impl Codegen for TraceAccess {
 impl Codegen for NodeIndex {
     fn to_string(&self, ir: &Air, elem_type: ElemType, trace_segment: TraceSegmentId) -> String {
         let op = ir.constraint_graph().node(self).op();
+        // named constants are emitted as paths to the constants of the generated module
+        if let (Operation::Value(Value::Constant(_)), Some(access)) = (op, ir.constant_name(self)) {
+            return match elem_type {
+                ElemType::Base => constant_path(ir, access),
+                ElemType::Ext => format!("E::from({})", constant_path(ir, access)),
+            };
+        }
         op.to_string(ir, elem_type, trace_segment)
     }
 }
@@ -91,6 +121,7 @@ impl Codegen for Operation {
                     },
                 }
             }
+            Operation::Inv(_) => unreachable!("field inverses are rejected by validation"),
         }
     }
 }
@@ -122,12 +153,16 @@ impl Codegen for Value {
                     .unwrap();
                 format!("periodic_values[{index}]")
             }
-            Value::PublicInput(air_ir::PublicInputAccess { name, index }) => {
-                format!("self.{name}[{index}]")
-            }
-            Value::RandomValue(idx) => {
-                format!("aux_rand_elements.get_segment_elements(0)[{idx}]")
-            }
+            Value::PublicInput(air_ir::PublicInputAccess { name, index }) => match elem_type {
+                ElemType::Base => format!("self.{name}[{index}]"),
+                ElemType::Ext => format!("E::from(self.{name}[{index}])"),
+            },
+            Value::RandomValue(idx) => match ir.random_value_binding(*idx) {
+                Some(air_ir::RandomValueAccess { name, index, .. }) => {
+                    format!("{RAND_VALUES_VAR}.{name}[{index}]")
+                }
+                None => format!("aux_rand_elements.get_segment_elements(0)[{idx}]"),
+            },
         }
     }
 }
@@ -1,3 +1,7 @@
+mod constants;
+use constants::add_constants_module;
+pub(crate) use constants::{constant_name, constant_path};
+
 mod public_inputs;
 use public_inputs::add_public_inputs_struct;
 
@@ -5,17 +9,43 @@ mod periodic_columns;
 use periodic_columns::add_fn_get_periodic_column_values;
 
 mod graph;
+pub(crate) use graph::validate_inverses;
 use graph::Codegen;
 
+mod random_values;
+use random_values::{add_rand_values_struct, rand_values_declaration};
+pub(crate) use random_values::{
+    binding_bounds, rand_bindings, uses_rand_values, RAND_VALUES_STRUCT, RAND_VALUES_VAR,
+};
+
+mod source_comments;
+use source_comments::add_constraint_summary;
+pub(crate) use source_comments::SourceComments;
+
+mod test_module;
+use test_module::add_test_module;
+pub(crate) use test_module::TestParameters;
+
+mod exemptions;
+use exemptions::add_fn_exemption_selector;
+pub(crate) use exemptions::{
+    exemption_selector, exemption_selectors, validate_exemptions, NUM_TRANSITION_EXEMPTIONS,
+};
+
 mod boundary_constraints;
+pub(crate) use boundary_constraints::split_boundary_constraint;
 use boundary_constraints::{add_fn_get_assertions, add_fn_get_aux_assertions};
 
 mod transition_constraints;
-use transition_constraints::{add_fn_evaluate_aux_transition, add_fn_evaluate_transition};
+use transition_constraints::{
+    add_fn_evaluate_aux_transition, add_fn_evaluate_aux_transition_chunks,
+    add_fn_evaluate_transition, add_fn_evaluate_transition_chunks,
+};
+pub(crate) use transition_constraints::{constraint_range, num_chunks};
 
-use air_ir::{Air, TraceSegmentId};
+use air_ir::{Air, ConstraintRoot, TraceSegmentId, AUX_SEGMENT};
 
-use super::{Impl, Scope};
+use super::{CodeGenerator, Impl, Scope};
 
 // HELPER TYPES
 // ================================================================================================
@@ -30,27 +60,56 @@ pub enum ElemType {
 // ================================================================================================
 
 /// Updates the provided scope with a new Air struct and Winterfell Air trait implementation
-/// which are equivalent the provided AirIR.
-pub(super) fn add_air(scope: &mut Scope, ir: &Air) {
+/// which are equivalent the provided AirIR, using the options of the provided generator.
+///
+/// The evaluation of the transition constraints is split across helper functions if it exceeds the
+/// maximum chunk size, a test module is added if requested, and the source of each constraint is
+/// emitted as a comment if the source map is available.
+pub(super) fn add_air(scope: &mut Scope, ir: &Air, generator: &CodeGenerator) {
+    let sources = SourceComments::new(generator.codemap.as_deref());
+    let max_chunk_size = generator.max_chunk_size;
+
+    // add the module declaring the named constants.
+    add_constants_module(scope, ir);
+
     // add the Public Inputs struct and its base implementation.
-    add_public_inputs_struct(scope, ir);
+    add_public_inputs_struct(scope, ir, generator.config);
+
+    // add the struct holding the random values by their names, if they are named.
+    add_rand_values_struct(scope, ir);
 
     let name = ir.name();
 
     // add the Air struct and its base implementation.
-    add_air_struct(scope, ir, name);
+    add_air_struct(scope, ir, name, max_chunk_size, sources);
+
+    // add a summary of the constraint sources ahead of the Air trait implementation.
+    add_constraint_summary(scope, ir, sources);
 
     // add Winterfell Air trait implementation for the provided AirIR.
-    add_air_trait(scope, ir, name);
+    add_air_trait(scope, ir, name, max_chunk_size, sources);
+
+    // add the test module exercising the generated Air, if requested.
+    if generator.with_tests {
+        add_test_module(scope, ir, generator.config, &generator.paths);
+    }
 }
 
 /// Updates the provided scope with a custom Air struct.
-fn add_air_struct(scope: &mut Scope, ir: &Air, name: &str) {
-    // define the custom Air struct.
-    let air_struct = scope
-        .new_struct(name)
-        .vis("pub")
-        .field("context", "AirContext<Felt>");
+fn add_air_struct(
+    scope: &mut Scope,
+    ir: &Air,
+    name: &str,
+    max_chunk_size: Option<usize>,
+    sources: SourceComments,
+) {
+    // define the custom Air struct, documenting the trace columns which have a doc string.
+    let air_struct = scope.new_struct(name).vis("pub");
+    let docs = trace_column_docs(ir);
+    if !docs.is_empty() {
+        air_struct.doc(&docs.join("\n"));
+    }
+    air_struct.field("context", "AirContext<Felt>");
 
     // add public inputs
     for public_input in ir.public_inputs() {
@@ -69,11 +128,24 @@ fn add_air_struct(scope: &mut Scope, ir: &Air, name: &str) {
         .vis("pub")
         .ret("usize")
         .line("self.trace_length() - self.context().num_transition_exemptions()");
+
+    // add the helper computing the selector columns emulating exemptions, if any are needed.
+    add_fn_exemption_selector(base_impl, ir);
+
+    // add the helpers evaluating chunks of the transition constraints, if they are chunked.
+    add_fn_evaluate_transition_chunks(base_impl, ir, max_chunk_size, sources);
+    add_fn_evaluate_aux_transition_chunks(base_impl, ir, max_chunk_size, sources);
 }
 
 /// Updates the provided scope with the custom Air struct and an Air trait implementation based on
 /// the provided AirIR.
-fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str) {
+fn add_air_trait(
+    scope: &mut Scope,
+    ir: &Air,
+    name: &str,
+    max_chunk_size: Option<usize>,
+    sources: SourceComments,
+) {
     // add the implementation block for the Air trait.
     let air_impl = scope
         .new_impl(name)
@@ -93,13 +165,19 @@ fn add_air_trait(scope: &mut Scope, ir: &Air, name: &str) {
 
     add_fn_get_periodic_column_values(air_impl, ir);
 
-    add_fn_get_assertions(air_impl, ir);
+    add_fn_get_assertions(air_impl, ir, sources);
 
-    add_fn_get_aux_assertions(air_impl, ir);
+    // the methods of the aux trace are only implemented if it has columns, as their default
+    // implementations are never called otherwise.
+    if has_aux_trace(ir) {
+        add_fn_get_aux_assertions(air_impl, ir, sources);
+    }
 
-    add_fn_evaluate_transition(air_impl, ir);
+    add_fn_evaluate_transition(air_impl, ir, max_chunk_size, sources);
 
-    add_fn_evaluate_aux_transition(air_impl, ir);
+    if has_aux_trace(ir) {
+        add_fn_evaluate_aux_transition(air_impl, ir, max_chunk_size, sources);
+    }
 }
 
 /// Adds an implementation of the "new" method to the referenced Air implementation based on the
@@ -132,7 +210,8 @@ fn add_fn_new(impl_ref: &mut Impl, ir: &Air) {
     ));
 
     // define the context.
-    let context = "
+    let context = format!(
+        "
 let context = AirContext::new_multi_segment(
     trace_info,
     main_degrees,
@@ -141,7 +220,8 @@ let context = AirContext::new_multi_segment(
     num_aux_assertions,
     options,
 )
-.set_num_transition_exemptions(2);";
+.set_num_transition_exemptions({NUM_TRANSITION_EXEMPTIONS});"
+    );
 
     new.line(context);
 
@@ -162,10 +242,73 @@ fn add_constraint_degrees(
     trace_segment: TraceSegmentId,
     decl_name: &str,
 ) {
-    let degrees = ir
-        .integrity_constraint_degrees(trace_segment)
-        .iter()
-        .map(|degree| degree.to_string(ir, ElemType::Ext, trace_segment))
+    let degrees = integrity_constraints(ir, trace_segment)
+        .map(|constraint| {
+            let degree = ir.constraint_graph().degree(constraint.node_index());
+            if exemption_selector(ir, trace_segment, constraint).is_none() {
+                return degree.to_string(ir, ElemType::Ext, trace_segment);
+            }
+
+            // the degree of the selector column emulating the exemptions of the constraint is
+            // that of a cycle of half the trace length, which is only known once the Air is
+            // instantiated.
+            let cycles = degree
+                .cycles()
+                .iter()
+                .map(|cycle_len| cycle_len.to_string())
+                .chain(["trace_info.length() / 2".to_string()])
+                .collect::<Vec<_>>();
+            format!(
+                "TransitionConstraintDegree::with_cycles({}, vec![{}])",
+                degree.base(),
+                cycles.join(", ")
+            )
+        })
         .collect::<Vec<_>>();
     func_body.line(format!("let {decl_name} = vec![{}];", degrees.join(", ")));
 }
+
+/// Returns the integrity constraints of the specified trace segment.
+///
+/// The validity constraints, which only reference the current row, come first, followed by the
+/// transition constraints, each group in declaration order. Every part of the generated Air
+/// indexing the constraints goes through this function, so that the order of the evaluations
+/// always matches the order of the degrees.
+pub(crate) fn integrity_constraints(
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+) -> impl Iterator<Item = &ConstraintRoot> + '_ {
+    ir.validity_constraints(trace_segment)
+        .chain(ir.transition_constraints(trace_segment))
+}
+
+/// Returns true if the IR declares an aux trace with at least one column.
+pub(crate) fn has_aux_trace(ir: &Air) -> bool {
+    ir.trace_segment_widths
+        .get(AUX_SEGMENT)
+        .map_or(false, |width| *width > 0)
+}
+
+/// Returns the lines documenting the trace columns declared with a doc string, e.g.
+/// `main[1..3] fmp: free memory pointer`, or nothing if no column has one.
+pub(crate) fn trace_column_docs(ir: &Air) -> Vec<String> {
+    let mut lines = vec![];
+    for segment in ir.trace_columns.iter() {
+        let segment_name = segment.name.as_str().trim_start_matches('$');
+        for binding in segment.bindings.iter() {
+            let (Some(name), Some(doc)) = (binding.name, binding.doc()) else {
+                continue;
+            };
+            let columns = if binding.is_scalar() {
+                binding.offset.to_string()
+            } else {
+                format!("{}..{}", binding.offset, binding.offset + binding.size)
+            };
+            lines.push(format!("{segment_name}[{columns}] {name}: {doc}"));
+        }
+    }
+    if !lines.is_empty() {
+        lines.insert(0, "Trace columns:".to_string());
+    }
+    lines
+}
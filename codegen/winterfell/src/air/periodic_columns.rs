@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use air_ir::{Air, PeriodicColumn, QualifiedIdentifier};
 
-use super::Impl;
+use super::{exemption_selectors, Impl};
 
 pub(super) fn add_fn_get_periodic_column_values(impl_ref: &mut Impl, ir: &Air) {
     // define the function.
@@ -11,9 +11,23 @@ pub(super) fn add_fn_get_periodic_column_values(impl_ref: &mut Impl, ir: &Air) {
         .arg_ref_self()
         .ret("Vec<Vec<Felt>>");
 
-    // output the periodic columns.
+    // output the periodic columns, followed by the selector columns emulating exemptions.
     let periodic_columns = &ir.periodic_columns;
-    get_periodic_column_values.line(periodic_columns.codegen());
+    let selectors = exemption_selectors(ir);
+    if selectors.is_empty() {
+        get_periodic_column_values.line(periodic_columns.codegen());
+        return;
+    }
+
+    let selectors = selectors
+        .iter()
+        .map(|exemptions| format!("self.exemption_selector({exemptions})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    get_periodic_column_values
+        .line(format!("let mut columns = {};", periodic_columns.codegen()))
+        .line(format!("columns.extend([{selectors}]);"))
+        .line("columns");
 }
 
 /// Code generation trait for generating Rust code strings from Periodic Columns.
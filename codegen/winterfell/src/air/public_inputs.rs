@@ -1,18 +1,25 @@
 use air_ir::Air;
+use codegen::Function;
+
+use crate::{WinterfellConfig, WinterfellVersion};
 
 use super::Scope;
 
-/// Updates the provided scope with a public inputs.
-pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air) {
+/// Updates the provided scope with a struct holding the public inputs, in the order in which they
+/// are declared.
+pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air, config: WinterfellConfig) {
     let name = "PublicInputs";
     // define the PublicInputs struct.
     let pub_inputs_struct = scope.new_struct(name).vis("pub");
 
     for public_input in ir.public_inputs() {
-        pub_inputs_struct.field(
+        let field = pub_inputs_struct.new_field(
             public_input.name.as_str(),
             format!("[Felt; {}]", public_input.size),
         );
+        if let Some(doc) = public_input.doc() {
+            field.doc(doc);
+        }
     }
 
     // add the public inputs implementation block
@@ -36,10 +43,44 @@ pub(super) fn add_public_inputs_struct(scope: &mut Scope, ir: &Air) {
         );
     }
 
-    add_serializable_impl(scope, pub_inputs_values)
+    // add the conversion of the public inputs to field elements, or their serialization, as
+    // required by the targeted version of Winterfell.
+    match config.version {
+        WinterfellVersion::V0_5 => {
+            let to_elements_fn = base_impl
+                .new_fn("to_elements")
+                .vis("pub")
+                .doc("Returns the values of the public inputs, concatenated in the order in which they are declared.")
+                .arg_ref_self()
+                .ret("Vec<Felt>");
+            add_to_elements_body(to_elements_fn, ir);
+            add_serializable_impl(scope, pub_inputs_values)
+        }
+        WinterfellVersion::V0_6 => add_to_elements_impl(scope, ir),
+    }
+}
+
+/// Adds the body of a function returning the values of the public inputs, concatenated in the
+/// order in which they are declared.
+fn add_to_elements_body(function: &mut Function, ir: &Air) {
+    let num_elements = ir.public_inputs().map(|input| input.size).sum::<usize>();
+    if num_elements == 0 {
+        function.line("Vec::new()");
+        return;
+    }
+    function.line(format!(
+        "let mut elements = Vec::with_capacity({num_elements});"
+    ));
+    for public_input in ir.public_inputs() {
+        function.line(format!(
+            "elements.extend_from_slice(&self.{});",
+            public_input.name
+        ));
+    }
+    function.line("elements");
 }
 
-/// Adds Serialization implementation for PublicInputs to the scope
+/// Adds Serialization implementation for PublicInputs to the scope, as required by Winterfell 0.5
 fn add_serializable_impl(scope: &mut Scope, pub_input_values: Vec<String>) {
     let serializable_impl = scope.new_impl("PublicInputs").impl_trait("Serializable");
     let write_into_fn = serializable_impl
@@ -51,3 +92,15 @@ fn add_serializable_impl(scope: &mut Scope, pub_input_values: Vec<String>) {
         write_into_fn.line(format!("target.write(self.{pub_input_value}.as_slice());"));
     }
 }
+
+/// Adds the conversion of PublicInputs to field elements to the scope, as required by
+/// Winterfell 0.6
+fn add_to_elements_impl(scope: &mut Scope, ir: &Air) {
+    let to_elements_fn = scope
+        .new_impl("PublicInputs")
+        .impl_trait("ToElements<Felt>")
+        .new_fn("to_elements")
+        .arg_ref_self()
+        .ret("Vec<Felt>");
+    add_to_elements_body(to_elements_fn, ir);
+}
@@ -0,0 +1,102 @@
+use air_ir::{Air, ConstraintRoot, RandBinding, Value, ValueCollector, Visit};
+
+use super::Scope;
+
+/// The name of the struct holding the random values by the names of their bindings in AirScript
+pub(crate) const RAND_VALUES_STRUCT: &str = "RandValues";
+
+/// The name of the variable holding an instance of [RAND_VALUES_STRUCT] in the generated methods
+pub(crate) const RAND_VALUES_VAR: &str = "rand_values";
+
+// HELPERS TO GENERATE THE ACCESSOR STRUCT OF THE RANDOM VALUES
+// ================================================================================================
+
+/// Updates the provided scope with a struct holding a slice of the random values for each binding
+/// of the `random_values` declaration in the IR, if it declares any, along with a constructor
+/// slicing the random values of the aux trace segment accordingly.
+///
+/// Random values accessed through one of their bindings are evaluated through the field of this
+/// struct named after the binding, e.g. `rand_values.alphas[2]`, so that the generated code keeps
+/// the structure of the `random_values` declaration.
+pub(super) fn add_rand_values_struct(scope: &mut Scope, ir: &Air) {
+    let bindings = rand_bindings(ir);
+    if bindings.is_empty() {
+        return;
+    }
+
+    let rand_values_struct = scope
+        .new_struct(RAND_VALUES_STRUCT)
+        .vis("pub")
+        .doc("The random values of the aux trace segment, by the names of their bindings in AirScript.")
+        .generic("'a")
+        .generic("E");
+    for binding in bindings {
+        rand_values_struct.field(&format!("pub {}", binding.name), "&'a [E]");
+    }
+
+    let new = scope
+        .new_impl(RAND_VALUES_STRUCT)
+        .generic("'a")
+        .generic("E")
+        .target_generic("'a")
+        .target_generic("E")
+        .new_fn("new")
+        .vis("pub")
+        .doc("Slices the provided random values according to the declaration of their bindings.")
+        .arg("rand_elements", "&'a [E]")
+        .ret("Self")
+        .line("Self {");
+    for binding in bindings {
+        let (start, end) = binding_bounds(binding);
+        new.line(format!(
+            "    {}: &rand_elements[{start}..{end}],",
+            binding.name
+        ));
+    }
+    new.line("}");
+}
+
+/// Returns the line instantiating [RAND_VALUES_STRUCT] from the random values of the aux trace
+/// segment if the evaluation of the provided constraints requires it, i.e. if any of them accesses
+/// a random value through one of its bindings.
+pub(crate) fn rand_values_declaration<'a>(
+    ir: &Air,
+    constraints: impl IntoIterator<Item = &'a ConstraintRoot>,
+) -> Option<String> {
+    uses_rand_values(ir, constraints).then(|| {
+        format!(
+            "let {RAND_VALUES_VAR} = {RAND_VALUES_STRUCT}::new(aux_rand_elements.get_segment_elements(0));"
+        )
+    })
+}
+
+/// Returns true if any of the provided constraints accesses a random value through one of its
+/// bindings.
+pub(crate) fn uses_rand_values<'a>(
+    ir: &Air,
+    constraints: impl IntoIterator<Item = &'a ConstraintRoot>,
+) -> bool {
+    let mut collector = ValueCollector::default();
+    let roots = constraints
+        .into_iter()
+        .map(|constraint| *constraint.node_index());
+    collector.visit_postorder(ir.constraint_graph(), roots);
+    collector.values().iter().any(|value| match value {
+        Value::RandomValue(element) => ir.random_value_binding(*element).is_some(),
+        _ => false,
+    })
+}
+
+/// Returns the bindings of the random values declared in the IR, which are empty if the random
+/// values are declared without bindings.
+pub(crate) fn rand_bindings(ir: &Air) -> &[RandBinding] {
+    ir.random_values
+        .as_ref()
+        .map_or(&[], |random_values| random_values.bindings.as_slice())
+}
+
+/// Returns the range of indices of the random values bound by the provided binding, as its start
+/// and exclusive end.
+pub(crate) fn binding_bounds(binding: &RandBinding) -> (usize, usize) {
+    (binding.offset, binding.offset + binding.size)
+}
@@ -0,0 +1,78 @@
+use air_ir::{Air, ConstraintRoot, TraceSegmentId, AUX_SEGMENT, DEFAULT_SEGMENT};
+use miden_diagnostics::CodeMap;
+
+use super::{integrity_constraints, Scope};
+
+/// Renders the AirScript source of constraints as comments in the generated code, so that a
+/// constraint reported by index as unsatisfied can be traced back to its source.
+///
+/// Comments are only rendered if the [CodeMap] containing the source program is available.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct SourceComments<'a> {
+    codemap: Option<&'a CodeMap>,
+}
+impl<'a> SourceComments<'a> {
+    pub fn new(codemap: Option<&'a CodeMap>) -> Self {
+        Self { codemap }
+    }
+
+    /// Returns the `<file>:<line>: enf <source>` description of the provided constraint, or `None`
+    /// if its source is unknown.
+    pub fn describe(&self, constraint: &ConstraintRoot) -> Option<String> {
+        let codemap = self.codemap?;
+        let file = codemap.name_for_spanned(constraint).ok()?;
+        let location = codemap.location(constraint).ok()?;
+        let source = codemap.source_slice(constraint).ok()?;
+        // constraints spanning multiple lines are collapsed onto a single line.
+        let source = source.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        Some(format!("{file}:{}: enf {source}", location.line.number()))
+    }
+
+    /// Returns the comment to emit above the generated code of the provided constraint, if its
+    /// source is known.
+    pub fn comment(&self, constraint: &ConstraintRoot) -> Option<String> {
+        self.describe(constraint)
+            .map(|description| format!("// {description}"))
+    }
+}
+
+/// Updates the provided scope with a comment block listing the index and source of every
+/// constraint, in the order in which they are evaluated by the generated code.
+pub(super) fn add_constraint_summary(scope: &mut Scope, ir: &Air, sources: SourceComments) {
+    if sources.codemap.is_none() {
+        return;
+    }
+
+    let mut lines = vec!["// Constraint sources:".to_string()];
+    let mut add_section = |title: &str, constraints: Vec<&ConstraintRoot>| {
+        if constraints.is_empty() {
+            return;
+        }
+        lines.push(format!("// {title}:"));
+        for (idx, constraint) in constraints.into_iter().enumerate() {
+            let description = sources
+                .describe(constraint)
+                .unwrap_or_else(|| "unknown source".to_string());
+            lines.push(format!("//   [{idx}] {description}"));
+        }
+    };
+
+    let segments: [(&str, TraceSegmentId); 2] = [("main", DEFAULT_SEGMENT), ("aux", AUX_SEGMENT)];
+    for (segment_name, segment) in segments {
+        add_section(
+            &format!("{segment_name} assertions"),
+            ir.boundary_constraints(segment).iter().collect(),
+        );
+    }
+    add_section(
+        "main transition constraints",
+        integrity_constraints(ir, DEFAULT_SEGMENT).collect(),
+    );
+    add_section(
+        "aux transition constraints",
+        integrity_constraints(ir, AUX_SEGMENT).collect(),
+    );
+
+    scope.raw(lines.join("\n"));
+}
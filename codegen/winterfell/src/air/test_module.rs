@@ -0,0 +1,197 @@
+use air_ir::{Air, TraceSegmentId, AUX_SEGMENT, DEFAULT_SEGMENT};
+
+use crate::{WinterfellConfig, WinterfellPaths};
+
+use super::{exemption_selector, exemption_selectors, integrity_constraints, Scope};
+
+/// The parameters with which the Air is instantiated in the generated test module.
+pub(crate) struct TestParameters {
+    /// The width of the main trace segment
+    pub main_width: usize,
+    /// The width of the aux trace segment, or zero if there is no aux segment
+    pub aux_width: usize,
+    /// The number of random values available to the aux segment, which Winterfell requires to be
+    /// non-zero whenever there is an aux segment
+    pub num_rand_values: usize,
+    /// The shortest valid trace length which accommodates every periodic column
+    pub trace_length: usize,
+    /// The smallest blowup factor which accommodates the degrees of the integrity constraints
+    pub blowup_factor: usize,
+    /// The number of periodic values passed to `evaluate_transition`, including the values of the
+    /// selector columns emulating exemptions
+    pub num_periodic_values: usize,
+    /// The number of integrity constraints evaluated by `evaluate_transition`
+    pub num_main_constraints: usize,
+    /// The number of integrity constraints evaluated by `evaluate_aux_transition`
+    pub num_aux_constraints: usize,
+}
+impl TestParameters {
+    /// The smallest trace length supported by Winterfell
+    const MIN_TRACE_LENGTH: usize = 8;
+    /// The blowup factor used unless the constraint degrees require a larger one
+    const MIN_BLOWUP_FACTOR: usize = 8;
+
+    pub fn new(ir: &Air) -> Self {
+        let segment_width = |segment: TraceSegmentId| {
+            ir.trace_segment_widths
+                .get(segment)
+                .copied()
+                .unwrap_or_default() as usize
+        };
+        let aux_width = segment_width(AUX_SEGMENT);
+
+        let trace_length = ir
+            .periodic_columns()
+            .map(|column| column.values.len().next_power_of_two())
+            .fold(Self::MIN_TRACE_LENGTH, usize::max);
+
+        let blowup_factor = [DEFAULT_SEGMENT, AUX_SEGMENT]
+            .into_iter()
+            .flat_map(|segment| {
+                integrity_constraints(ir, segment).map(move |constraint| (segment, constraint))
+            })
+            .map(|(segment, constraint)| {
+                let degree = ir.constraint_graph().degree(constraint.node_index());
+                // the selector column emulating exemptions is an additional cycle
+                let num_cycles = degree.cycles().len()
+                    + usize::from(exemption_selector(ir, segment, constraint).is_some());
+                (degree.base() + num_cycles - 1).next_power_of_two()
+            })
+            .fold(Self::MIN_BLOWUP_FACTOR, usize::max);
+
+        Self {
+            main_width: segment_width(DEFAULT_SEGMENT),
+            aux_width,
+            num_rand_values: if aux_width == 0 {
+                0
+            } else {
+                (ir.num_random_values as usize).max(1)
+            },
+            trace_length,
+            blowup_factor,
+            num_periodic_values: ir.periodic_columns.len() + exemption_selectors(ir).len(),
+            num_main_constraints: integrity_constraints(ir, DEFAULT_SEGMENT).count(),
+            num_aux_constraints: integrity_constraints(ir, AUX_SEGMENT).count(),
+        }
+    }
+}
+
+/// Updates the provided scope with a public `tests` module, which is only compiled for tests, and
+/// contains:
+/// - a helper which instantiates the Air with zeroed public inputs and the configured field
+///   extension, and smoke tests it.
+/// - a test checking the number of assertions of each trace segment.
+/// - a helper which evaluates the main transition constraints against a caller-provided frame,
+///   and a test which evaluates them against a frame of zeros.
+pub(super) fn add_test_module(
+    scope: &mut Scope,
+    ir: &Air,
+    config: WinterfellConfig,
+    paths: &WinterfellPaths,
+) {
+    let params = TestParameters::new(ir);
+    let ext = config.extension.element_type();
+    let name = ir.name();
+
+    let module = scope.new_module("tests").vis("pub").attr("cfg(test)");
+    module.import("super", "*");
+    module.import(&paths.air, "FieldExtension");
+    module.import(&paths.air, "TraceLayout");
+
+    let public_inputs = ir
+        .public_inputs()
+        .map(|input| format!("[Felt::ZERO; {}]", input.size))
+        .collect::<Vec<_>>();
+    module
+        .new_fn("build_air")
+        .doc("Returns an instance of the Air, instantiated with zeroed public inputs.")
+        .vis("pub")
+        .ret(name)
+        .line(format!(
+            "let layout = TraceLayout::new({}, [{}], [{}]);",
+            params.main_width, params.aux_width, params.num_rand_values
+        ))
+        .line(format!(
+            "let trace_info = TraceInfo::new_multi_segment(layout, {}, vec![]);",
+            params.trace_length
+        ))
+        .line(format!(
+            "let options = WinterProofOptions::new(32, {}, 0, FieldExtension::{}, 4, 32);",
+            params.blowup_factor,
+            config.extension.proof_option()
+        ))
+        .line(format!(
+            "let public_inputs = PublicInputs::new({});",
+            public_inputs.join(", ")
+        ))
+        .line(format!("{name}::new(trace_info, public_inputs, options)"));
+
+    module
+        .new_fn("evaluate_transition")
+        .doc("Evaluates the main transition constraints of the Air against the provided frame.")
+        .vis("pub")
+        .arg("frame", "&EvaluationFrame<Felt>")
+        .ret("Vec<Felt>")
+        .line("let air = build_air();")
+        .line(format!(
+            "let periodic_values = vec![Felt::ZERO; {}];",
+            params.num_periodic_values
+        ))
+        .line(format!(
+            "let mut result = vec![Felt::ZERO; {}];",
+            params.num_main_constraints
+        ))
+        .line("air.evaluate_transition(frame, &periodic_values, &mut result);")
+        .line("result");
+
+    module
+        .new_fn("air_can_be_constructed")
+        .attr("test")
+        .line("let air = build_air();")
+        .line(format!(
+            "assert_eq!(air.context().num_main_transition_constraints(), {});",
+            params.num_main_constraints
+        ))
+        .line(format!(
+            "assert_eq!(air.context().num_aux_transition_constraints(), {});",
+            params.num_aux_constraints
+        ));
+
+    let assertion_counts = module
+        .new_fn("assertion_counts")
+        .attr("test")
+        .line("let air = build_air();");
+    // the aux assertions are only implemented if there is an aux segment
+    if params.aux_width > 0 {
+        assertion_counts
+            .line(format!(
+                "let mut aux_rand_elements = AuxTraceRandElements::<{ext}>::new();"
+            ))
+            .line(format!(
+                "aux_rand_elements.add_segment_elements(vec![{ext}::ZERO; {}]);",
+                params.num_rand_values
+            ));
+    }
+    assertion_counts.line(format!(
+        "assert_eq!(air.get_assertions().len(), {});",
+        ir.num_boundary_constraints(DEFAULT_SEGMENT)
+    ));
+    if params.aux_width > 0 {
+        assertion_counts.line(format!(
+            "assert_eq!(air.get_aux_assertions(&aux_rand_elements).len(), {});",
+            ir.num_boundary_constraints(AUX_SEGMENT)
+        ));
+    }
+
+    module
+        .new_fn("evaluate_transition_on_zero_frame")
+        .attr("test")
+        .line(format!(
+            "let frame = EvaluationFrame::new({});",
+            params.main_width
+        ))
+        .line(format!(
+            "assert_eq!(evaluate_transition(&frame).len(), {});",
+            params.num_main_constraints
+        ));
+}
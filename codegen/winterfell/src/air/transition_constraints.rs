@@ -1,13 +1,26 @@
-use air_ir::{Air, TraceSegmentId};
+use core::ops::Range;
 
-use super::{Codegen, ElemType, Impl};
+use air_ir::{Air, ConstraintDomain, TraceSegmentId};
+
+use super::{
+    exemption_selector, integrity_constraints, rand_values_declaration, Codegen, ElemType, Impl,
+    SourceComments,
+};
 
 // HELPERS TO GENERATE THE WINTERFELL TRANSITION CONSTRAINT METHODS
 // ================================================================================================
 
 /// Adds an implementation of the "evaluate_transition" method to the referenced Air implementation
 /// based on the data in the provided IR.
-pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &Air) {
+///
+/// If the constraints are split into chunks of at most `max_chunk_size` constraints, the method
+/// calls the helper evaluating each chunk in order, instead of evaluating the constraints itself.
+pub(super) fn add_fn_evaluate_transition(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    max_chunk_size: Option<usize>,
+    sources: SourceComments,
+) {
     // define the function.
     let evaluate_transition = impl_ref
         .new_fn("evaluate_transition")
@@ -21,13 +34,61 @@ pub(super) fn add_fn_evaluate_transition(impl_ref: &mut Impl, ir: &Air) {
     evaluate_transition.line("let main_current = frame.current();");
     evaluate_transition.line("let main_next = frame.next();");
 
-    // output the constraints.
-    add_constraints(evaluate_transition, ir, 0);
+    // output the constraints, or the calls to the helpers evaluating them.
+    match num_chunks(ir, 0, max_chunk_size) {
+        Some(num_chunks) => {
+            for chunk in 0..num_chunks {
+                evaluate_transition.line(format!(
+                    "self.evaluate_transition_chunk_{chunk}(main_current, main_next, periodic_values, result);"
+                ));
+            }
+        }
+        None => {
+            let constraints = constraint_range(ir, 0, None, 0);
+            add_constraints(evaluate_transition, ir, 0, constraints, sources);
+        }
+    }
+}
+
+/// Adds a helper evaluating each chunk of the main transition constraints to the referenced
+/// implementation, which is expected to be the base implementation of the custom Air struct, if
+/// the constraints are split into chunks of at most `max_chunk_size` constraints.
+pub(super) fn add_fn_evaluate_transition_chunks(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    max_chunk_size: Option<usize>,
+    sources: SourceComments,
+) {
+    let Some(num_chunks) = num_chunks(ir, 0, max_chunk_size) else {
+        return;
+    };
+
+    for chunk in 0..num_chunks {
+        let evaluate_chunk = impl_ref
+            .new_fn(&format!("evaluate_transition_chunk_{chunk}"))
+            .arg_ref_self()
+            .generic("E: FieldElement<BaseField = Felt>")
+            .arg("main_current", "&[E]")
+            .arg("main_next", "&[E]")
+            .arg("periodic_values", "&[E]")
+            .arg("result", "&mut [E]");
+
+        let constraints = constraint_range(ir, 0, max_chunk_size, chunk);
+        add_constraints(evaluate_chunk, ir, 0, constraints, sources);
+    }
 }
 
 /// Adds an implementation of the "evaluate_aux_transition" method to the referenced Air implementation
 /// based on the data in the provided IR.
-pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &Air) {
+///
+/// If the constraints are split into chunks of at most `max_chunk_size` constraints, the method
+/// calls the helper evaluating each chunk in order, instead of evaluating the constraints itself.
+pub(super) fn add_fn_evaluate_aux_transition(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    max_chunk_size: Option<usize>,
+    sources: SourceComments,
+) {
     // define the function.
     let evaluate_aux_transition = impl_ref
         .new_fn("evaluate_aux_transition")
@@ -47,20 +108,144 @@ pub(super) fn add_fn_evaluate_aux_transition(impl_ref: &mut Impl, ir: &Air) {
     evaluate_aux_transition.line("let aux_current = aux_frame.current();");
     evaluate_aux_transition.line("let aux_next = aux_frame.next();");
 
-    // output the constraints.
-    add_constraints(evaluate_aux_transition, ir, 1);
+    // output the constraints, or the calls to the helpers evaluating them.
+    match num_chunks(ir, 1, max_chunk_size) {
+        Some(num_chunks) => {
+            for chunk in 0..num_chunks {
+                evaluate_aux_transition.line(format!(
+                    "self.evaluate_aux_transition_chunk_{chunk}(main_current, main_next, aux_current, aux_next, aux_rand_elements, result);"
+                ));
+            }
+        }
+        None => {
+            let constraints = constraint_range(ir, 1, None, 0);
+            add_constraints(evaluate_aux_transition, ir, 1, constraints, sources);
+        }
+    }
 }
 
-/// Iterates through the integrity constraints in the IR, and appends a line of generated code to
-/// the provided codegen function body for each constraint.
-fn add_constraints(func_body: &mut codegen::Function, ir: &Air, trace_segment: TraceSegmentId) {
-    for (idx, constraint) in ir.integrity_constraints(trace_segment).iter().enumerate() {
-        func_body.line(format!(
-            "result[{}] = {};",
-            idx,
-            constraint
-                .node_index()
-                .to_string(ir, ElemType::Ext, trace_segment)
-        ));
+/// Adds a helper evaluating each chunk of the aux transition constraints to the referenced
+/// implementation, which is expected to be the base implementation of the custom Air struct, if
+/// the constraints are split into chunks of at most `max_chunk_size` constraints.
+pub(super) fn add_fn_evaluate_aux_transition_chunks(
+    impl_ref: &mut Impl,
+    ir: &Air,
+    max_chunk_size: Option<usize>,
+    sources: SourceComments,
+) {
+    let Some(num_chunks) = num_chunks(ir, 1, max_chunk_size) else {
+        return;
+    };
+
+    for chunk in 0..num_chunks {
+        let evaluate_chunk = impl_ref
+            .new_fn(&format!("evaluate_aux_transition_chunk_{chunk}"))
+            .generic("F, E")
+            .arg_ref_self()
+            .arg("main_current", "&[F]")
+            .arg("main_next", "&[F]")
+            .arg("aux_current", "&[E]")
+            .arg("aux_next", "&[E]")
+            .arg("aux_rand_elements", "&AuxTraceRandElements<E>")
+            .arg("result", "&mut [E]")
+            .bound("F", "FieldElement<BaseField = Felt>")
+            .bound("E", "FieldElement<BaseField = Felt> + ExtensionOf<F>");
+
+        let constraints = constraint_range(ir, 1, max_chunk_size, chunk);
+        add_constraints(evaluate_chunk, ir, 1, constraints, sources);
+    }
+}
+
+/// Returns the number of chunks into which the transition constraints of the specified trace
+/// segment are split, if there are more than `max_chunk_size` of them, or `None` if they are
+/// evaluated by a single function.
+///
+/// The constraints of each chunk only share the rows of the evaluation frame, so splitting them
+/// does not require any other state to be passed between chunks.
+pub(crate) fn num_chunks(
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+    max_chunk_size: Option<usize>,
+) -> Option<usize> {
+    let max_chunk_size = max_chunk_size?;
+    let num_constraints = integrity_constraints(ir, trace_segment).count();
+    if num_constraints <= max_chunk_size {
+        return None;
+    }
+
+    Some((num_constraints + max_chunk_size - 1) / max_chunk_size)
+}
+
+/// Returns the range of indices of the transition constraints of the specified trace segment which
+/// are evaluated in the specified chunk, or of all of them if `max_chunk_size` is `None`.
+pub(crate) fn constraint_range(
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+    max_chunk_size: Option<usize>,
+    chunk: usize,
+) -> Range<usize> {
+    let num_constraints = integrity_constraints(ir, trace_segment).count();
+    match max_chunk_size {
+        Some(max_chunk_size) => {
+            let start = chunk * max_chunk_size;
+            start..num_constraints.min(start + max_chunk_size)
+        }
+        None => 0..num_constraints,
+    }
+}
+
+/// Iterates through the integrity constraints in the IR whose index is in `constraints`, and
+/// appends a line of generated code to the provided codegen function body for each constraint.
+/// The validity and transition constraints are each
+/// preceded by a comment naming their group, and each line is preceded by the source of the
+/// constraint, if it is known. Constraints exempted from more rows than Winterfell exempts from all transition
+/// constraints are multiplied by the selector column emulating their exemptions.
+fn add_constraints(
+    func_body: &mut codegen::Function,
+    ir: &Air,
+    trace_segment: TraceSegmentId,
+    constraints: Range<usize>,
+    sources: SourceComments,
+) {
+    let constraints = integrity_constraints(ir, trace_segment)
+        .enumerate()
+        .skip(constraints.start)
+        .take(constraints.len())
+        .collect::<Vec<_>>();
+
+    // instantiate the struct holding the random values by their names, if they are accessed so.
+    if let Some(declaration) =
+        rand_values_declaration(ir, constraints.iter().map(|(_, constraint)| *constraint))
+    {
+        func_body.line(declaration);
+    }
+
+    // whether the previous constraint is a validity constraint, if any.
+    let mut group = None;
+    for (idx, constraint) in constraints {
+        let is_validity = matches!(constraint.domain(), ConstraintDomain::EveryRow);
+        if group.replace(is_validity) != Some(is_validity) {
+            func_body.line(if is_validity {
+                "// validity constraints, which only reference the current row"
+            } else {
+                "// transition constraints, which reference the next row"
+            });
+        }
+        if let Some(comment) = sources.comment(constraint) {
+            func_body.line(comment);
+        }
+        let expr = constraint
+            .node_index()
+            .to_string(ir, ElemType::Ext, trace_segment);
+        match exemption_selector(ir, trace_segment, constraint) {
+            Some(selector) => {
+                func_body.line(format!(
+                    "result[{idx}] = periodic_values[{selector}] * ({expr});"
+                ));
+            }
+            None => {
+                func_body.line(format!("result[{idx}] = {expr};"));
+            }
+        }
     }
 }
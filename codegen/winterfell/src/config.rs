@@ -0,0 +1,267 @@
+use std::str::FromStr;
+
+use air_ir::{Air, Value, ValueCollector, Visit};
+
+/// The base fields supported by Winterfell.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum BaseField {
+    /// The 62-bit field with modulus 2^62 - 111 * 2^39 + 1
+    F62,
+    /// The 64-bit field with modulus 2^64 - 2^32 + 1
+    #[default]
+    F64,
+    /// The 128-bit field with modulus 2^128 - 45 * 2^40 + 1
+    F128,
+}
+impl BaseField {
+    /// Returns the name of the `winter_math::fields` module in which this field is defined
+    pub const fn module(&self) -> &'static str {
+        match self {
+            Self::F62 => "f62",
+            Self::F64 => "f64",
+            Self::F128 => "f128",
+        }
+    }
+
+    /// Returns the modulus of this field
+    pub const fn modulus(&self) -> u128 {
+        match self {
+            Self::F62 => 4611624995532046337,
+            Self::F64 => 0xFFFFFFFF00000001,
+            Self::F128 => 340282366920938463463374557953744961537,
+        }
+    }
+
+    /// Returns true if Winterfell supports the given extension of this field
+    pub const fn supports(&self, extension: Extension) -> bool {
+        !matches!((self, extension), (Self::F128, Extension::Cubic))
+    }
+}
+
+impl FromStr for BaseField {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f62" => Ok(Self::F62),
+            "f64" => Ok(Self::F64),
+            "f128" => Ok(Self::F128),
+            _ => Err(anyhow::anyhow!(
+                "unknown field '{s}', expected one of 'f62', 'f64', or 'f128'"
+            )),
+        }
+    }
+}
+
+/// The field extensions supported by Winterfell.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Extension {
+    /// No extension is used, i.e. the auxiliary trace is defined over the base field
+    #[default]
+    None,
+    /// A quadratic extension of the base field
+    Quadratic,
+    /// A cubic extension of the base field
+    Cubic,
+}
+impl Extension {
+    /// Returns the name of the `winter_math::fields` type implementing this extension, if any
+    pub const fn type_name(&self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Quadratic => Some("QuadExtension"),
+            Self::Cubic => Some("CubeExtension"),
+        }
+    }
+
+    /// Returns the name of the `FieldExtension` variant selecting this extension in the proof
+    /// options
+    pub const fn proof_option(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Quadratic => "Quadratic",
+            Self::Cubic => "Cubic",
+        }
+    }
+
+    /// Returns the type of the elements over which the aux trace is evaluated in the generated
+    /// code, i.e. the `ExtFelt` alias of the extension, or `Felt` if no extension is used
+    pub const fn element_type(&self) -> &'static str {
+        match self {
+            Self::None => "Felt",
+            Self::Quadratic | Self::Cubic => "ExtFelt",
+        }
+    }
+}
+
+impl FromStr for Extension {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "quadratic" => Ok(Self::Quadratic),
+            "cubic" => Ok(Self::Cubic),
+            _ => Err(anyhow::anyhow!(
+                "unknown extension '{s}', expected one of 'none', 'quadratic', or 'cubic'"
+            )),
+        }
+    }
+}
+
+/// The versions of the Winterfell API which the generated code can target.
+///
+/// The generated `Air` implementation is the same for every version, but the traits required of
+/// the public inputs and of the prover changed between releases.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum WinterfellVersion {
+    /// Winterfell 0.5, which requires the public inputs to be `Serializable`
+    #[default]
+    V0_5,
+    /// Winterfell 0.6, which requires the public inputs to be convertible to field elements with
+    /// `ToElements`, and provers to select the `RandomCoin` drawing their random elements
+    V0_6,
+}
+
+impl FromStr for WinterfellVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0.5" => Ok(Self::V0_5),
+            "0.6" => Ok(Self::V0_6),
+            _ => Err(anyhow::anyhow!(
+                "unknown Winterfell version '{s}', expected one of '0.5' or '0.6'"
+            )),
+        }
+    }
+}
+
+/// Configuration for the Winterfell code generator.
+///
+/// The base field controls the `Felt` type alias and the imports of the generated code. When an
+/// extension is selected, an `ExtFelt` type alias for the extension of the base field is also
+/// generated. The version selects the shape of the Winterfell API targeted by the generated code.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct WinterfellConfig {
+    pub base_field: BaseField,
+    pub extension: Extension,
+    pub version: WinterfellVersion,
+}
+impl WinterfellConfig {
+    /// Validates that the provided IR can be represented using this configuration, i.e. that
+    /// the extension is supported by the base field, and that all constants fit in the base field.
+    pub fn validate(&self, ir: &Air) -> anyhow::Result<()> {
+        if !self.base_field.supports(self.extension) {
+            anyhow::bail!(
+                "the {:?} extension is not supported by the {} field",
+                self.extension,
+                self.base_field.module()
+            );
+        }
+
+        // Rational constants are evaluated in the 64-bit field by the parser, so their values are
+        // meaningless in any other field
+        if self.base_field != BaseField::F64 {
+            if let Some(qid) = ir
+                .constants
+                .iter()
+                .find_map(|(qid, constant)| constant.rational.then_some(qid))
+            {
+                anyhow::bail!(
+                    "the constant '{qid}' contains a rational number, which is only supported by the {} field",
+                    BaseField::F64.module()
+                );
+            }
+        }
+
+        let mut collector = ValueCollector::default();
+        collector.visit_constraints(ir);
+        let constants = collector.values().iter().filter_map(|value| match value {
+            Value::Constant(value) => Some(*value),
+            _ => None,
+        });
+        let periodic_values = ir
+            .periodic_columns()
+            .flat_map(|column| column.values.iter().copied());
+        for value in constants.chain(periodic_values) {
+            if u128::from(value) >= self.base_field.modulus() {
+                anyhow::bail!(
+                    "the constant {value} does not fit in the {} field",
+                    self.base_field.module()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The paths from which the generated code imports the items of each Winterfell crate.
+///
+/// By default, the items are imported from the `winter_air`, `winter_math`, and `winter_utils`
+/// crates, but each group can be mapped to the path at which the same items are re-exported by
+/// another crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WinterfellPaths {
+    /// The path of the Air traits and types, `winter_air` by default
+    pub air: String,
+    /// The path of the field types and traits, `winter_math` by default
+    pub math: String,
+    /// The path of the serialization and collection utilities, `winter_utils` by default
+    pub utils: String,
+}
+impl Default for WinterfellPaths {
+    fn default() -> Self {
+        Self {
+            air: "winter_air".to_string(),
+            math: "winter_math".to_string(),
+            utils: "winter_utils".to_string(),
+        }
+    }
+}
+impl WinterfellPaths {
+    /// Validates that each path is a valid Rust path, e.g. `miden_air::winter`.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for path in [&self.air, &self.math, &self.utils] {
+            let is_valid = path.split("::").all(|segment| {
+                let mut chars = segment.chars();
+                matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+            });
+            if !is_valid {
+                anyhow::bail!("invalid import path '{path}'");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for WinterfellPaths {
+    type Err = anyhow::Error;
+
+    /// Parses a comma-separated list of `group=path` mappings, where `group` is one of `air`,
+    /// `math`, or `utils`, e.g. `air=miden_air,math=miden_core::math`. Groups which are not
+    /// mapped keep their default path.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut paths = Self::default();
+        for mapping in s.split(',') {
+            let Some((group, path)) = mapping.split_once('=') else {
+                anyhow::bail!("invalid import path mapping '{mapping}', expected 'group=path'");
+            };
+            let path = path.trim().to_string();
+            match group.trim() {
+                "air" => paths.air = path,
+                "math" => paths.math = path,
+                "utils" => paths.utils = path,
+                group => anyhow::bail!(
+                    "unknown import group '{group}', expected one of 'air', 'math', or 'utils'"
+                ),
+            }
+        }
+        paths.validate()?;
+
+        Ok(paths)
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Write;
+
+use anyhow::Context;
+use syn::{punctuated::Punctuated, LitStr, Token};
+
+/// The name of the macro standing in for comments while the generated code is formatted, as the
+/// syntax tree of the code does not retain them.
+const COMMENT_MACRO: &str = "__air_script_comments";
+
+// FORMATTING OF THE GENERATED CODE
+// ================================================================================================
+
+/// Formats the provided generated code with [prettyplease], so that the output of the code
+/// generator is consistently formatted without depending on a `rustfmt` binary.
+///
+/// Each run of consecutive `//` comments is replaced by an invocation of [COMMENT_MACRO] taking
+/// the text of the comments as string literals, which is then restored as comments at the
+/// indentation of the invocation once the code is formatted. Doc comments are preserved by the
+/// syntax tree, so they are left as is.
+///
+/// # Errors
+/// Returns an error if the generated code is not valid Rust, which indicates a bug in the code
+/// generator.
+pub(crate) fn format(code: &str) -> anyhow::Result<String> {
+    let file =
+        syn::parse_file(&replace_comments(code)).context("the generated code is not valid Rust")?;
+    restore_comments(&prettyplease::unparse(&file))
+}
+
+/// Replaces each run of consecutive comment lines by an invocation of [COMMENT_MACRO].
+fn replace_comments(code: &str) -> String {
+    let mut output = String::with_capacity(code.len());
+    let mut comments = Vec::new();
+    for line in code.lines() {
+        match comment_text(line) {
+            Some(text) => comments.push(format!("{text:?}")),
+            None => {
+                flush_comments(&mut output, &mut comments);
+                output.push_str(line);
+                output.push('\n');
+            }
+        }
+    }
+    flush_comments(&mut output, &mut comments);
+
+    output
+}
+
+/// Returns the text following the `//` of the provided line if it is a comment, but not a doc
+/// comment.
+fn comment_text(line: &str) -> Option<&str> {
+    line.trim_start()
+        .strip_prefix("//")
+        .filter(|text| !text.starts_with('/') && !text.starts_with('!'))
+}
+
+/// Appends an invocation of [COMMENT_MACRO] with the pending comments to the output, if any.
+fn flush_comments(output: &mut String, comments: &mut Vec<String>) {
+    if comments.is_empty() {
+        return;
+    }
+    writeln!(output, "{COMMENT_MACRO}!({});", comments.join(", "))
+        .expect("writing to a string cannot fail");
+    comments.clear();
+}
+
+/// Replaces each invocation of [COMMENT_MACRO] in the formatted code by the comments it stands in
+/// for, which may span several lines if the formatter wrapped its arguments.
+fn restore_comments(code: &str) -> anyhow::Result<String> {
+    let prefix = format!("{COMMENT_MACRO}!(");
+    let mut output = String::with_capacity(code.len());
+    let mut lines = code.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with(&prefix) {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        let indent = &line[..line.len() - trimmed.len()];
+        let mut invocation = trimmed.to_string();
+        while !invocation.ends_with(");") {
+            let line = lines
+                .next()
+                .context("unterminated comment placeholder in the formatted code")?;
+            invocation.push_str(line.trim());
+        }
+        let arguments = &invocation[prefix.len()..invocation.len() - 2];
+        let comments = syn::parse::Parser::parse_str(
+            Punctuated::<LitStr, Token![,]>::parse_terminated,
+            arguments,
+        )
+        .context("invalid comment placeholder in the formatted code")?;
+        for comment in comments {
+            writeln!(output, "{indent}//{}", comment.value())
+                .expect("writing to a string cannot fail");
+        }
+    }
+
+    Ok(output)
+}
@@ -1,20 +1,64 @@
-use super::Scope;
+use air_ir::Air;
+
+use super::{air::has_aux_trace, Scope, WinterfellConfig, WinterfellPaths, WinterfellVersion};
+
+/// Adds the required imports to the provided scope, importing the Winterfell items from the
+/// provided paths. The items only used by the methods of the aux trace are only imported if `ir`
+/// has an aux trace.
+///
+/// If `no_std` is true, `Vec` and the `vec!` macro are imported from `alloc`, so that the generated
+/// code compiles in a `#![no_std]` crate which declares `extern crate alloc`.
+pub(super) fn add_imports(
+    scope: &mut Scope,
+    ir: &Air,
+    config: WinterfellConfig,
+    no_std: bool,
+    paths: &WinterfellPaths,
+) {
+    let air = paths.air.as_str();
+    let math = paths.math.as_str();
+    let utils = paths.utils.as_str();
 
-/// Adds the required imports to the provided scope.
-pub(super) fn add_imports(scope: &mut Scope) {
     // add winterfell imports
-    scope.import("winter_air", "Air");
-    scope.import("winter_air", "AirContext");
-    scope.import("winter_air", "Assertion");
-    scope.import("winter_air", "AuxTraceRandElements");
-    scope.import("winter_air", "EvaluationFrame");
-    scope.import("winter_air", "ProofOptions as WinterProofOptions");
-    scope.import("winter_air", "TransitionConstraintDegree");
-    scope.import("winter_air", "TraceInfo");
-    scope.import("winter_math::fields::f64", "BaseElement as Felt");
-    scope.import("winter_math", "ExtensionOf");
-    scope.import("winter_math", "FieldElement");
-    scope.import("winter_utils::collections", "Vec");
-    scope.import("winter_utils", "ByteWriter");
-    scope.import("winter_utils", "Serializable");
+    scope.import(air, "Air");
+    scope.import(air, "AirContext");
+    scope.import(air, "Assertion");
+    if has_aux_trace(ir) {
+        scope.import(air, "AuxTraceRandElements");
+    }
+    scope.import(air, "EvaluationFrame");
+    scope.import(air, "ProofOptions as WinterProofOptions");
+    scope.import(air, "TransitionConstraintDegree");
+    scope.import(air, "TraceInfo");
+    scope.import(
+        &format!("{math}::fields::{}", config.base_field.module()),
+        "BaseElement as Felt",
+    );
+    if let Some(extension) = config.extension.type_name() {
+        scope.import(&format!("{math}::fields"), extension);
+    }
+    if has_aux_trace(ir) {
+        scope.import(math, "ExtensionOf");
+    }
+    scope.import(math, "FieldElement");
+    if config.version == WinterfellVersion::V0_6 {
+        scope.import(math, "ToElements");
+    }
+    if no_std {
+        scope.import("alloc", "vec");
+        scope.import("alloc::vec", "Vec");
+    } else {
+        scope.import(&format!("{utils}::collections"), "Vec");
+    }
+    if config.version == WinterfellVersion::V0_5 {
+        scope.import(utils, "ByteWriter");
+        scope.import(utils, "Serializable");
+    }
+}
+
+/// Adds the type aliases for the configured field extension to the provided scope, if any.
+pub(super) fn add_type_aliases(scope: &mut Scope, config: WinterfellConfig) {
+    if let Some(extension) = config.extension.type_name() {
+        scope.raw(format!("pub type ExtFelt = {extension}<Felt>;"));
+    }
 }
@@ -1,8 +1,18 @@
+use std::sync::Arc;
+
 use air_ir::Air;
 use codegen::{Impl, Scope};
+use miden_diagnostics::CodeMap;
+use proc_macro2::TokenStream;
 
 mod air;
+mod config;
+mod format;
 mod imports;
+mod prover;
+mod tokens;
+
+pub use config::{BaseField, Extension, WinterfellConfig, WinterfellPaths, WinterfellVersion};
 
 // GENERATE RUST CODE FOR WINTERFELL AIR
 // ================================================================================================
@@ -10,19 +20,153 @@ mod imports;
 /// CodeGenerator is used to generate a Rust implementation of the Winterfell STARK prover library's
 /// Air trait. The generated Air expresses the constraints specified by the AirIR used to build the
 /// CodeGenerator.
-pub struct CodeGenerator;
+///
+/// The field and extension used by the generated code are selected via [WinterfellConfig], and the
+/// paths from which Winterfell items are imported via [WinterfellPaths].
+#[derive(Default)]
+pub struct CodeGenerator {
+    config: WinterfellConfig,
+    with_tests: bool,
+    no_std: bool,
+    codemap: Option<Arc<CodeMap>>,
+    paths: WinterfellPaths,
+    max_chunk_size: Option<usize>,
+}
+impl CodeGenerator {
+    pub fn new(config: WinterfellConfig) -> Self {
+        Self {
+            config,
+            with_tests: false,
+            no_std: false,
+            codemap: None,
+            paths: WinterfellPaths::default(),
+            max_chunk_size: None,
+        }
+    }
+
+    /// Sets whether a `#[cfg(test)] mod tests` is generated alongside the Air.
+    ///
+    /// The generated module contains a smoke test instantiating the Air with zeroed public
+    /// inputs, a check of the number of assertions, and a test evaluating the main transition
+    /// constraints against a frame of zeros. Its `build_air` and `evaluate_transition` helpers
+    /// are public, so they can be reused by tests evaluating the constraints on other frames.
+    pub fn with_tests(mut self, with_tests: bool) -> Self {
+        self.with_tests = with_tests;
+        self
+    }
+
+    /// Sets whether the generated code must compile in a `#![no_std]` crate.
+    ///
+    /// When set, `Vec` and the `vec!` macro are imported from `alloc` rather than relying on the
+    /// standard library, so the crate embedding the generated code must declare
+    /// `extern crate alloc`.
+    pub fn with_no_std(mut self, no_std: bool) -> Self {
+        self.no_std = no_std;
+        self
+    }
+
+    /// Sets the [CodeMap] containing the AirScript program from which the IR was compiled.
+    ///
+    /// When set, the source text and location of each constraint is emitted as a
+    /// `// <file>:<line>: enf ...` comment above its evaluation in `evaluate_transition`,
+    /// `evaluate_aux_transition`, and the assertion methods, and a summary of the sources of all
+    /// constraints, by index, is emitted ahead of the `Air` implementation. Comments are not
+    /// preserved by [CodeGenerator::generate_tokens].
+    pub fn with_source_map(mut self, codemap: Arc<CodeMap>) -> Self {
+        self.codemap = Some(codemap);
+        self
+    }
+
+    /// Sets the paths from which the generated code imports the items of each Winterfell crate,
+    /// e.g. to use the re-exports of another crate rather than depending on Winterfell directly.
+    ///
+    /// The generated code only refers to Winterfell items through its imports, so no other part
+    /// of it depends on these paths.
+    pub fn with_paths(mut self, paths: WinterfellPaths) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Sets the maximum number of transition constraints evaluated by a single generated function.
+    ///
+    /// When a trace segment has more transition constraints than this, `evaluate_transition` (or
+    /// `evaluate_aux_transition`) only calls helpers named `evaluate_transition_chunk_<n>` (or
+    /// `evaluate_aux_transition_chunk_<n>`) in order, each of which evaluates at most
+    /// `max_chunk_size` constraints into the shared `result` slice. This keeps the size of the
+    /// generated functions manageable for rustc on large AIRs. By default, constraints are never
+    /// split.
+    pub fn with_max_chunk_size(mut self, max_chunk_size: usize) -> Self {
+        assert!(
+            max_chunk_size > 0,
+            "the maximum chunk size must be non-zero"
+        );
+        self.max_chunk_size = Some(max_chunk_size);
+        self
+    }
+
+    /// Generates the scaffold of a Winterfell prover for the Air generated by
+    /// [air_ir::CodeGenerator::generate], as a companion file importing the Air from the module at
+    /// `air_module`, e.g. `super::fibonacci` for a sibling module named `fibonacci`.
+    ///
+    /// The scaffold is named after the Air, without its `Air` suffix. For `FibonacciAir`, it
+    /// contains:
+    /// - `FibonacciTrace`, holding the columns of the main trace by their names in AirScript, whose
+    ///   `build` fills the rows of the trace with a `todo!()` body, and whose `into_table`
+    ///   assembles the columns into a `TraceTable`.
+    /// - `FibonacciProver`, holding the `ProofOptions`, which implements the `Prover` trait of
+    ///   `winter_prover` for the Air, with a `todo!()` body reading the public inputs from the
+    ///   trace.
+    ///
+    /// The field and the paths of the `math` and `utils` items are the same as in the Air.
+    pub fn generate_prover(&self, ir: &Air, air_module: &str) -> anyhow::Result<String> {
+        self.config.validate(ir)?;
+        self.paths.validate()?;
+
+        let mut scope = Scope::new();
+        prover::add_prover_scaffold(
+            &mut scope,
+            ir,
+            air_module,
+            self.config,
+            self.no_std,
+            &self.paths,
+        );
+
+        format::format(&scope.to_string())
+    }
+
+    /// Generates the same Air implementation as [air_ir::CodeGenerator::generate], but as a
+    /// [TokenStream].
+    pub fn generate_tokens(&self, ir: &Air) -> anyhow::Result<TokenStream> {
+        self.config.validate(ir)?;
+        self.paths.validate()?;
+        air::validate_exemptions(ir)?;
+        air::validate_inverses(ir)?;
+
+        Ok(tokens::generate(ir, self))
+    }
+}
 impl air_ir::CodeGenerator for CodeGenerator {
     type Output = String;
 
     fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        self.config.validate(ir)?;
+        self.paths.validate()?;
+        air::validate_exemptions(ir)?;
+        air::validate_inverses(ir)?;
+
         let mut scope = Scope::new();
 
         // add winterfell imports.
-        imports::add_imports(&mut scope);
+        imports::add_imports(&mut scope, ir, self.config, self.no_std, &self.paths);
+
+        // add type aliases for the configured field extension.
+        imports::add_type_aliases(&mut scope, self.config);
 
         // add an Air struct and Winterfell Air trait implementation for the provided AirIR.
-        air::add_air(&mut scope, ir);
+        air::add_air(&mut scope, ir, self);
 
-        Ok(scope.to_string())
+        // format the generated code, so that it does not depend on the layout of the scope.
+        format::format(&scope.to_string())
     }
 }
@@ -0,0 +1,227 @@
+use air_ir::{Air, DEFAULT_SEGMENT};
+
+use super::{Scope, WinterfellConfig, WinterfellPaths, WinterfellVersion};
+
+// HELPERS TO GENERATE A PROVER SCAFFOLD FOR THE GENERATED AIR
+// ================================================================================================
+
+/// Updates the provided scope with the scaffold of a Winterfell prover for the Air generated from
+/// the provided AirIR, which is imported from the module at `air_module`.
+///
+/// The scaffold contains a struct holding the columns of the main trace by their names in AirScript,
+/// which assembles them into a `TraceTable`, and a `Prover` implementation for the Air. The values
+/// of the trace and of the public inputs are left to the user, as `todo!()` bodies.
+pub(super) fn add_prover_scaffold(
+    scope: &mut Scope,
+    ir: &Air,
+    air_module: &str,
+    config: WinterfellConfig,
+    no_std: bool,
+    paths: &WinterfellPaths,
+) {
+    let names = ScaffoldNames::new(ir.name());
+
+    add_imports(scope, air_module, &names, config, no_std, paths);
+    add_trace_struct(scope, ir, &names);
+    add_prover_struct(scope, ir, &names, config);
+}
+
+/// The names of the items of the scaffold generated for an Air.
+struct ScaffoldNames {
+    /// The name of the Air struct
+    air: String,
+    /// The name of the struct holding the columns of the main trace
+    trace: String,
+    /// The name of the prover struct
+    prover: String,
+}
+impl ScaffoldNames {
+    /// Names the items of the scaffold after the Air, without its `Air` suffix, if any, so that
+    /// the prover of `FibonacciAir` is `FibonacciProver`.
+    fn new(air: &str) -> Self {
+        let prefix = air.strip_suffix("Air").unwrap_or(air);
+        Self {
+            air: air.to_string(),
+            trace: format!("{prefix}Trace"),
+            prover: format!("{prefix}Prover"),
+        }
+    }
+}
+
+/// Adds the imports of the Air, of the field and of the Winterfell prover to the provided scope.
+fn add_imports(
+    scope: &mut Scope,
+    air_module: &str,
+    names: &ScaffoldNames,
+    config: WinterfellConfig,
+    no_std: bool,
+    paths: &WinterfellPaths,
+) {
+    let math = paths.math.as_str();
+
+    scope.import(air_module, &names.air);
+    scope.import(air_module, "PublicInputs");
+    scope.import(
+        &format!("{math}::fields::{}", config.base_field.module()),
+        "BaseElement as Felt",
+    );
+    scope.import(math, "FieldElement");
+    if no_std {
+        scope.import("alloc", "vec");
+        scope.import("alloc::vec", "Vec");
+    } else {
+        scope.import(&format!("{}::collections", paths.utils), "Vec");
+    }
+    if config.version == WinterfellVersion::V0_6 {
+        scope.import("winter_prover::crypto", "DefaultRandomCoin");
+    }
+    scope.import("winter_prover::crypto::hashers", "Blake3_256");
+    scope.import("winter_prover", "ProofOptions");
+    scope.import("winter_prover", "Prover");
+    scope.import("winter_prover", "TraceTable");
+}
+
+/// Adds the struct holding the columns of the main trace, with one field per trace binding, and
+/// its implementation filling the rows of the trace and assembling them into a `TraceTable`.
+fn add_trace_struct(scope: &mut Scope, ir: &Air, names: &ScaffoldNames) {
+    let segment = &ir.trace_columns[DEFAULT_SEGMENT];
+    // bindings without a name are named after the index of their first column.
+    let bindings = segment
+        .bindings
+        .iter()
+        .map(|binding| {
+            let name = binding
+                .name
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("column_{}", binding.offset));
+            (name, binding.size)
+        })
+        .collect::<Vec<_>>();
+
+    let mut doc = format!(
+        "The columns of the main trace of [{}], by their names in AirScript.",
+        names.air
+    );
+    if ir.trace_segment_widths.len() > 1 {
+        doc.push_str(
+            "\n\nThe aux trace is not built by a `TraceTable`, so proving requires a custom `Trace` \
+             implementation building it.",
+        );
+    }
+    let trace_struct = scope.new_struct(&names.trace).vis("pub").doc(&doc);
+    for (name, size) in bindings.iter() {
+        let ty = match size {
+            1 => "Vec<Felt>".to_string(),
+            size => format!("[Vec<Felt>; {size}]"),
+        };
+        trace_struct.field(&format!("pub {name}"), ty);
+    }
+
+    let trace_impl = scope.new_impl(&names.trace).associate_const(
+        "WIDTH",
+        "usize",
+        segment.size.to_string(),
+        "pub",
+    );
+
+    // add a constructor allocating the columns.
+    let new = trace_impl
+        .new_fn("new")
+        .vis("pub")
+        .doc("Returns a trace of the specified length, with every column set to zero.")
+        .arg("length", "usize")
+        .ret("Self")
+        .line("Self {");
+    for (name, size) in bindings.iter() {
+        match size {
+            1 => new.line(format!("    {name}: vec![Felt::ZERO; length],")),
+            _ => new.line(format!(
+                "    {name}: core::array::from_fn(|_| vec![Felt::ZERO; length]),"
+            )),
+        };
+    }
+    new.line("}");
+
+    // add the skeleton filling the rows of the trace.
+    trace_impl
+        .new_fn("build")
+        .vis("pub")
+        .doc("Builds the trace of the specified length, filling its rows in order.")
+        .arg("length", "usize")
+        .ret("Self")
+        .line("let mut trace = Self::new(length);")
+        .line("for row in 0..length {")
+        .line("    trace.fill_row(row);")
+        .line("}")
+        .line("trace");
+    trace_impl
+        .new_fn("fill_row")
+        .doc("Sets the values of the columns at the specified row.")
+        .arg_mut_self()
+        .arg("row", "usize")
+        .line("todo!(\"set the values of the columns at row {row}\")");
+
+    // add the assembly of the columns into a trace table, in the order in which they are declared.
+    let into_table = trace_impl
+        .new_fn("into_table")
+        .vis("pub")
+        .doc("Assembles the columns into a `TraceTable`, in the order in which they are declared.")
+        .arg_self()
+        .ret("TraceTable<Felt>")
+        .line("let mut columns = Vec::with_capacity(Self::WIDTH);");
+    for (name, size) in bindings.iter() {
+        match size {
+            1 => into_table.line(format!("columns.push(self.{name});")),
+            _ => into_table.line(format!("columns.extend(self.{name});")),
+        };
+    }
+    into_table.line("TraceTable::init(columns)");
+}
+
+/// Adds the prover struct holding the proof options, and its implementation of the Winterfell
+/// `Prover` trait for the Air, which also names the random coin of the prover from Winterfell 0.6.
+fn add_prover_struct(scope: &mut Scope, ir: &Air, names: &ScaffoldNames, config: WinterfellConfig) {
+    scope
+        .new_struct(&names.prover)
+        .vis("pub")
+        .doc(&format!("A prover for [{}].", names.air))
+        .field("options", "ProofOptions");
+
+    scope
+        .new_impl(&names.prover)
+        .new_fn("new")
+        .vis("pub")
+        .arg("options", "ProofOptions")
+        .ret("Self")
+        .line("Self { options }");
+
+    let prover_impl = scope
+        .new_impl(&names.prover)
+        .impl_trait("Prover")
+        .associate_type("BaseField", "Felt")
+        .associate_type("Air", &names.air)
+        .associate_type("Trace", "TraceTable<Felt>")
+        .associate_type("HashFn", "Blake3_256<Felt>");
+    if config.version == WinterfellVersion::V0_6 {
+        prover_impl.associate_type("RandomCoin", "DefaultRandomCoin<Self::HashFn>");
+    }
+
+    // list the arguments of the constructor of the public inputs, which are read from the trace.
+    let arguments = ir
+        .public_inputs()
+        .map(|input| format!("{}: [Felt; {}]", input.name, input.size))
+        .collect::<Vec<_>>();
+    prover_impl
+        .new_fn("get_pub_inputs")
+        .arg_ref_self()
+        .arg("_trace", "&Self::Trace")
+        .ret("PublicInputs")
+        .line(format!("// PublicInputs::new({})", arguments.join(", ")))
+        .line("todo!(\"read the public inputs from the trace\")");
+
+    prover_impl
+        .new_fn("options")
+        .arg_ref_self()
+        .ret("&ProofOptions")
+        .line("&self.options");
+}
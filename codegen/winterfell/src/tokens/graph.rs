@@ -0,0 +1,242 @@
+use std::str::FromStr;
+
+use air_ir::{
+    Air, IntegrityConstraintDegree, NodeIndex, Operation, TraceAccess, TraceSegmentId, Value,
+};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::air::{constant_name, ElemType, RAND_VALUES_VAR};
+
+// RUST TOKEN GENERATION FOR THE CONSTRAINT GRAPH
+// ================================================================================================
+
+/// Code generation trait for generating Rust tokens from IR types related to constraints and the
+/// [air_ir::AlgebraicGraph].
+///
+/// The tokens produced here are equivalent to the strings produced by the string-based backend.
+pub trait Codegen {
+    fn to_tokens(
+        &self,
+        ir: &Air,
+        elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> TokenStream;
+}
+
+impl Codegen for IntegrityConstraintDegree {
+    fn to_tokens(
+        &self,
+        _ir: &Air,
+        _elem_type: ElemType,
+        _trace_segment: TraceSegmentId,
+    ) -> TokenStream {
+        let base = Literal::usize_unsuffixed(self.base());
+        if self.cycles().is_empty() {
+            quote!(TransitionConstraintDegree::new(#base))
+        } else {
+            let cycles = self.cycles().iter().copied().map(Literal::usize_unsuffixed);
+            quote!(TransitionConstraintDegree::with_cycles(#base, vec![#(#cycles),*]))
+        }
+    }
+}
+
+impl Codegen for TraceAccess {
+    fn to_tokens(
+        &self,
+        _ir: &Air,
+        _elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> TokenStream {
+        let frame = if self.segment == 0 { "main" } else { "aux" };
+        let row = match self.row_offset {
+            0 => "current",
+            1 => "next",
+            _ => panic!("Winterfell doesn't support row offsets greater than 1."),
+        };
+        let row = format_ident!("{frame}_{row}");
+        let column = Literal::usize_unsuffixed(self.column);
+        if self.segment == 0 && self.segment != trace_segment {
+            quote!(E::from(#row[#column]))
+        } else {
+            quote!(#row[#column])
+        }
+    }
+}
+
+impl Codegen for NodeIndex {
+    fn to_tokens(
+        &self,
+        ir: &Air,
+        elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> TokenStream {
+        let op = ir.constraint_graph().node(self).op();
+        // named constants are emitted as paths to the constants of the generated module
+        if let (Operation::Value(Value::Constant(_)), Some(access)) = (op, ir.constant_name(self)) {
+            let name = format_ident!("{}", constant_name(ir, &access.name));
+            let indices = access
+                .index
+                .iter()
+                .chain(access.column.iter())
+                .map(|index| Literal::usize_unsuffixed(*index));
+            let path = quote!(constants::#name #([#indices])*);
+            return match elem_type {
+                ElemType::Base => path,
+                ElemType::Ext => quote!(E::from(#path)),
+            };
+        }
+        op.to_tokens(ir, elem_type, trace_segment)
+    }
+}
+
+impl Codegen for Operation {
+    fn to_tokens(
+        &self,
+        ir: &Air,
+        elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> TokenStream {
+        match self {
+            Operation::Value(value) => value.to_tokens(ir, elem_type, trace_segment),
+            Operation::Add(..) | Operation::Sub(..) | Operation::Mul(..) => {
+                binary_op_to_tokens(ir, self, elem_type, trace_segment)
+            }
+            Operation::Exp(l_idx, r_idx) => {
+                let lhs = l_idx.to_tokens(ir, elem_type, trace_segment);
+                let lhs = if is_leaf(l_idx, ir) {
+                    lhs
+                } else {
+                    quote!((#lhs))
+                };
+                match (r_idx, elem_type) {
+                    // x^0 = 1
+                    (0, ElemType::Base) => quote!(Felt::ONE),
+                    (0, ElemType::Ext) => quote!(E::ONE),
+                    // x^1 = x
+                    (1, _) => lhs,
+                    (_, ElemType::Base) => {
+                        let exp = Literal::usize_unsuffixed(*r_idx);
+                        quote!(#lhs.exp(Felt::new(#exp)))
+                    }
+                    (_, ElemType::Ext) => {
+                        let exp = u64_literal(*r_idx as u64);
+                        quote!(#lhs.exp(E::PositiveInteger::from(#exp)))
+                    }
+                }
+            }
+            Operation::Inv(_) => unreachable!("field inverses are rejected by validation"),
+        }
+    }
+}
+
+impl Codegen for Value {
+    fn to_tokens(
+        &self,
+        ir: &Air,
+        elem_type: ElemType,
+        trace_segment: TraceSegmentId,
+    ) -> TokenStream {
+        match self {
+            Value::Constant(value) => constant_to_tokens(*value, elem_type),
+            Value::TraceAccess(trace_access) => {
+                trace_access.to_tokens(ir, elem_type, trace_segment)
+            }
+            Value::PeriodicColumn(pc) => {
+                let index = ir
+                    .periodic_columns
+                    .iter()
+                    .position(|(qid, _)| qid == &pc.name)
+                    .unwrap();
+                let index = Literal::usize_unsuffixed(index);
+                quote!(periodic_values[#index])
+            }
+            Value::PublicInput(air_ir::PublicInputAccess { name, index }) => {
+                let name = format_ident!("{}", name.as_str());
+                let index = Literal::usize_unsuffixed(*index);
+                quote!(self.#name[#index])
+            }
+            Value::RandomValue(idx) => match ir.random_value_binding(*idx) {
+                Some(air_ir::RandomValueAccess { name, index, .. }) => {
+                    let rand_values = format_ident!("{RAND_VALUES_VAR}");
+                    let name = format_ident!("{}", name.as_str());
+                    let index = Literal::usize_unsuffixed(index);
+                    quote!(#rand_values.#name[#index])
+                }
+                None => {
+                    let idx = Literal::usize_unsuffixed(*idx);
+                    quote!(aux_rand_elements.get_segment_elements(0)[#idx])
+                }
+            },
+        }
+    }
+}
+
+/// Returns the tokens for a constant of the specified element type.
+pub fn constant_to_tokens(value: u64, elem_type: ElemType) -> TokenStream {
+    match (value, elem_type) {
+        (0, ElemType::Base) => quote!(Felt::ZERO),
+        (0, ElemType::Ext) => quote!(E::ZERO),
+        (1, ElemType::Base) => quote!(Felt::ONE),
+        (1, ElemType::Ext) => quote!(E::ONE),
+        (value, ElemType::Base) => {
+            let value = Literal::u64_unsuffixed(value);
+            quote!(Felt::new(#value))
+        }
+        (value, ElemType::Ext) => {
+            let value = u64_literal(value);
+            quote!(E::from(#value))
+        }
+    }
+}
+
+/// Returns a `u64` literal using the same `1_u64` form as the string-based backend.
+fn u64_literal(value: u64) -> Literal {
+    Literal::from_str(&format!("{value}_u64")).expect("invalid integer literal")
+}
+
+/// Returns true if the operation at the specified node index is a leaf node in the constraint graph.
+fn is_leaf(idx: &NodeIndex, ir: &Air) -> bool {
+    !matches!(
+        ir.constraint_graph().node(idx).op(),
+        Operation::Add(_, _) | Operation::Sub(_, _) | Operation::Mul(_, _) | Operation::Exp(_, _)
+    )
+}
+
+/// Returns the tokens for a binary operation.
+fn binary_op_to_tokens(
+    ir: &Air,
+    op: &Operation,
+    elem_type: ElemType,
+    trace_segment: TraceSegmentId,
+) -> TokenStream {
+    let graph = ir.constraint_graph();
+    match op {
+        Operation::Add(l_idx, r_idx) => {
+            let lhs = l_idx.to_tokens(ir, elem_type, trace_segment);
+            let rhs = r_idx.to_tokens(ir, elem_type, trace_segment);
+            quote!(#lhs + #rhs)
+        }
+        Operation::Sub(l_idx, r_idx) => {
+            let lhs = l_idx.to_tokens(ir, elem_type, trace_segment);
+            let rhs = r_idx.to_tokens(ir, elem_type, trace_segment);
+            if graph.node(r_idx).op().precedence() <= op.precedence() {
+                quote!(#lhs - (#rhs))
+            } else {
+                quote!(#lhs - #rhs)
+            }
+        }
+        Operation::Mul(l_idx, r_idx) => {
+            let mut lhs = l_idx.to_tokens(ir, elem_type, trace_segment);
+            if graph.node(l_idx).op().precedence() < op.precedence() {
+                lhs = quote!((#lhs));
+            }
+            let mut rhs = r_idx.to_tokens(ir, elem_type, trace_segment);
+            if graph.node(r_idx).op().precedence() < op.precedence() {
+                rhs = quote!((#rhs));
+            }
+            quote!(#lhs * #rhs)
+        }
+        _ => panic!("unsupported operation"),
+    }
+}
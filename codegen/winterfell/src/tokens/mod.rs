@@ -0,0 +1,733 @@
+mod graph;
+use graph::{constant_to_tokens, Codegen};
+
+use core::ops::Range;
+
+use air_ir::{Air, ConstantExpr, ConstraintDomain, ConstraintRoot, TraceSegmentId};
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::air::{
+    binding_bounds, constant_name, constraint_range, exemption_selector, exemption_selectors,
+    has_aux_trace, integrity_constraints, num_chunks, rand_bindings, split_boundary_constraint,
+    trace_column_docs, uses_rand_values, ElemType, TestParameters, NUM_TRANSITION_EXEMPTIONS,
+    RAND_VALUES_STRUCT, RAND_VALUES_VAR,
+};
+use crate::{CodeGenerator, WinterfellConfig, WinterfellPaths, WinterfellVersion};
+
+// GENERATE RUST TOKENS FOR WINTERFELL AIR
+// ================================================================================================
+
+/// Generates a Rust implementation of the Winterfell `Air` trait for the provided IR as a
+/// [TokenStream], using the options of the provided generator, i.e. its configuration and import
+/// paths, followed by a test module if requested. If `no_std` is set, the generated code compiles
+/// in a `#![no_std]` crate.
+///
+/// The generated code is equivalent to the output of the string-based [crate::CodeGenerator], but
+/// is guaranteed to be syntactically valid, which makes it suitable for use in procedural macros.
+///
+/// The configuration and paths are expected to have been validated against the IR, see
+/// [crate::CodeGenerator::generate_tokens].
+pub(crate) fn generate(ir: &Air, generator: &CodeGenerator) -> TokenStream {
+    let config = generator.config;
+    let imports = imports(ir, config, generator.no_std, &generator.paths);
+    let type_aliases = type_aliases(config);
+    let constants = constants_module(ir);
+    let public_inputs = public_inputs_struct(ir, config);
+    let rand_values = rand_values_struct(ir);
+    let air_struct = air_struct(ir, generator.max_chunk_size);
+    let air_trait = air_trait(ir, generator.max_chunk_size);
+    let tests = if generator.with_tests {
+        test_module(ir, config, &generator.paths)
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        #imports
+        #type_aliases
+        #constants
+        #public_inputs
+        #rand_values
+        #air_struct
+        #air_trait
+        #tests
+    }
+}
+
+/// Returns the required imports of Winterfell items from the provided paths, importing `Vec` and
+/// the `vec!` macro from `alloc` if `no_std` is true. The items only used by the methods of the
+/// aux trace are only imported if the IR has an aux trace.
+fn imports(
+    ir: &Air,
+    config: WinterfellConfig,
+    no_std: bool,
+    paths: &WinterfellPaths,
+) -> TokenStream {
+    let air = path_tokens(&paths.air);
+    let math = path_tokens(&paths.math);
+    let utils = path_tokens(&paths.utils);
+    let field = format_ident!("{}", config.base_field.module());
+    let extension = config.extension.type_name().map(|extension| {
+        let extension = format_ident!("{extension}");
+        quote!(use #math::fields::#extension;)
+    });
+    let vec = if no_std {
+        quote! {
+            use alloc::vec;
+            use alloc::vec::Vec;
+        }
+    } else {
+        quote!(
+            use #utils::collections::Vec;
+        )
+    };
+
+    let (aux_rand_elements, extension_of) = if has_aux_trace(ir) {
+        (quote!(AuxTraceRandElements,), quote!(ExtensionOf,))
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+    let (math_traits, serializable) = match config.version {
+        WinterfellVersion::V0_5 => (
+            quote!(use #math::{#extension_of FieldElement};),
+            quote!(use #utils::{ByteWriter, Serializable};),
+        ),
+        WinterfellVersion::V0_6 => (
+            quote!(use #math::{#extension_of FieldElement, ToElements};),
+            TokenStream::new(),
+        ),
+    };
+
+    quote! {
+        use #air::{Air, AirContext, Assertion, #aux_rand_elements EvaluationFrame, ProofOptions as WinterProofOptions, TransitionConstraintDegree, TraceInfo};
+        use #math::fields::#field::BaseElement as Felt;
+        #extension
+        #math_traits
+        #vec
+        #serializable
+    }
+}
+
+/// Returns the tokens of the provided import path, which is expected to have been validated.
+fn path_tokens(path: &str) -> TokenStream {
+    path.parse().expect("invalid import path")
+}
+
+/// Returns the type aliases for the configured field extension, if any.
+fn type_aliases(config: WinterfellConfig) -> TokenStream {
+    match config.extension.type_name() {
+        Some(extension) => {
+            let extension = format_ident!("{extension}");
+            quote!(pub type ExtFelt = #extension<Felt>;)
+        }
+        None => TokenStream::new(),
+    }
+}
+
+/// Returns the module declaring a Rust constant for each named constant referenced by the IR, if
+/// any.
+fn constants_module(ir: &Air) -> TokenStream {
+    if ir.constants.is_empty() {
+        return TokenStream::new();
+    }
+
+    let constants = ir.constants.iter().map(|(qid, constant)| {
+        let name = format_ident!("{}", constant_name(ir, qid));
+        let felt = |value: &u64| {
+            let value = Literal::u64_unsuffixed(*value);
+            quote!(Felt::new(#value))
+        };
+        match &constant.value {
+            ConstantExpr::Scalar(value) => {
+                let value = felt(value);
+                quote!(pub const #name: Felt = #value;)
+            }
+            ConstantExpr::Vector(values) => {
+                let len = Literal::usize_unsuffixed(values.len());
+                let values = values.iter().map(felt);
+                quote!(pub const #name: [Felt; #len] = [#(#values),*];)
+            }
+            ConstantExpr::Matrix(rows) => {
+                let num_rows = Literal::usize_unsuffixed(rows.len());
+                let num_cols = Literal::usize_unsuffixed(
+                    rows.first().map(|row| row.len()).unwrap_or_default(),
+                );
+                let rows = rows.iter().map(|row| {
+                    let values = row.iter().map(felt);
+                    quote!([#(#values),*])
+                });
+                quote!(pub const #name: [[Felt; #num_cols]; #num_rows] = [#(#rows),*];)
+            }
+        }
+    });
+
+    quote! {
+        pub mod constants {
+            use super::Felt;
+
+            #(#constants)*
+        }
+    }
+}
+
+/// Returns the public inputs struct, its constructor, and either its Serializable implementation
+/// or its ToElements implementation, depending on the targeted version of Winterfell.
+fn public_inputs_struct(ir: &Air, config: WinterfellConfig) -> TokenStream {
+    let (names, sizes) = public_inputs(ir);
+    let to_elements = if names.is_empty() {
+        quote!(Vec::new())
+    } else {
+        let num_elements =
+            Literal::usize_unsuffixed(ir.public_inputs().map(|input| input.size).sum());
+        quote! {
+            let mut elements = Vec::with_capacity(#num_elements);
+            #(elements.extend_from_slice(&self.#names);)*
+            elements
+        }
+    };
+
+    let conversion = match config.version {
+        WinterfellVersion::V0_5 => quote! {
+            impl PublicInputs {
+                pub fn new(#(#names: [Felt; #sizes]),*) -> Self {
+                    Self { #(#names),* }
+                }
+
+                #[doc = " Returns the values of the public inputs, concatenated in the order in which they are declared."]
+                pub fn to_elements(&self) -> Vec<Felt> {
+                    #to_elements
+                }
+            }
+
+            impl Serializable for PublicInputs {
+                fn write_into<W: ByteWriter>(&self, target: &mut W) {
+                    #(target.write(self.#names.as_slice());)*
+                }
+            }
+        },
+        WinterfellVersion::V0_6 => quote! {
+            impl PublicInputs {
+                pub fn new(#(#names: [Felt; #sizes]),*) -> Self {
+                    Self { #(#names),* }
+                }
+            }
+
+            impl ToElements<Felt> for PublicInputs {
+                fn to_elements(&self) -> Vec<Felt> {
+                    #to_elements
+                }
+            }
+        },
+    };
+
+    let docs = ir.public_inputs().map(|input| {
+        input.doc().map(|doc| {
+            let doc = format!(" {doc}");
+            quote!(#[doc = #doc])
+        })
+    });
+
+    quote! {
+        pub struct PublicInputs {
+            #(#docs #names: [Felt; #sizes],)*
+        }
+
+        #conversion
+    }
+}
+
+/// Returns the custom Air struct and its base implementation, which includes the helpers
+/// evaluating each chunk of the transition constraints, if they are split into chunks of at most
+/// `max_chunk_size` constraints.
+fn air_struct(ir: &Air, max_chunk_size: Option<usize>) -> TokenStream {
+    let name = format_ident!("{}", ir.name());
+    let (names, sizes) = public_inputs(ir);
+    let fn_exemption_selector = fn_exemption_selector(ir);
+    let fn_evaluate_transition_chunks = fn_evaluate_transition_chunks(ir, max_chunk_size);
+    let fn_evaluate_aux_transition_chunks = fn_evaluate_aux_transition_chunks(ir, max_chunk_size);
+    let docs = trace_column_docs(ir)
+        .into_iter()
+        .map(|doc| format!(" {doc}"));
+
+    quote! {
+        #(#[doc = #docs])*
+        pub struct #name {
+            context: AirContext<Felt>,
+            #(#names: [Felt; #sizes],)*
+        }
+
+        impl #name {
+            pub fn last_step(&self) -> usize {
+                self.trace_length() - self.context().num_transition_exemptions()
+            }
+            #fn_exemption_selector
+            #fn_evaluate_transition_chunks
+            #fn_evaluate_aux_transition_chunks
+        }
+    }
+}
+
+/// Returns the method computing the values of the selector columns emulating exemptions, if any
+/// constraint requires one.
+fn fn_exemption_selector(ir: &Air) -> TokenStream {
+    if exemption_selectors(ir).is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        fn exemption_selector(&self, exemptions: usize) -> Vec<Felt> {
+            let trace_length = self.trace_length();
+            let g = self.trace_domain_generator();
+            let mut values = vec![Felt::ZERO; trace_length];
+            let mut sum = Felt::ZERO;
+            let mut x = Felt::ONE;
+            for value in values.iter_mut().take(trace_length.saturating_sub(exemptions)) {
+                *value = Felt::ONE;
+                sum += x;
+                x *= g;
+            }
+            values[trace_length - 2] = -sum * g.square();
+            values
+        }
+    }
+}
+
+/// Returns the Winterfell Air trait implementation for the custom Air struct.
+fn air_trait(ir: &Air, max_chunk_size: Option<usize>) -> TokenStream {
+    let name = format_ident!("{}", ir.name());
+    let fn_new = fn_new(ir);
+    let fn_get_periodic_column_values = fn_get_periodic_column_values(ir);
+    let fn_get_assertions = fn_get_assertions(ir);
+    let fn_evaluate_transition = fn_evaluate_transition(ir, max_chunk_size);
+    // the methods of the aux trace are only implemented if it has columns
+    let (fn_get_aux_assertions, fn_evaluate_aux_transition) = if has_aux_trace(ir) {
+        (
+            fn_get_aux_assertions(ir),
+            fn_evaluate_aux_transition(ir, max_chunk_size),
+        )
+    } else {
+        (quote!(), quote!())
+    };
+
+    quote! {
+        impl Air for #name {
+            type BaseField = Felt;
+            type PublicInputs = PublicInputs;
+
+            fn context(&self) -> &AirContext<Felt> {
+                &self.context
+            }
+
+            #fn_new
+            #fn_get_periodic_column_values
+            #fn_get_assertions
+            #fn_get_aux_assertions
+            #fn_evaluate_transition
+            #fn_evaluate_aux_transition
+        }
+    }
+}
+
+fn fn_new(ir: &Air) -> TokenStream {
+    let main_degrees = constraint_degrees(ir, 0);
+    let aux_degrees = constraint_degrees(ir, 1);
+    let num_main_assertions = Literal::usize_unsuffixed(ir.num_boundary_constraints(0));
+    let num_aux_assertions = Literal::usize_unsuffixed(ir.num_boundary_constraints(1));
+    let num_transition_exemptions = Literal::usize_unsuffixed(NUM_TRANSITION_EXEMPTIONS);
+    let (names, _) = public_inputs(ir);
+
+    quote! {
+        fn new(trace_info: TraceInfo, public_inputs: PublicInputs, options: WinterProofOptions) -> Self {
+            let main_degrees = vec![#(#main_degrees),*];
+            let aux_degrees = vec![#(#aux_degrees),*];
+            let num_main_assertions = #num_main_assertions;
+            let num_aux_assertions = #num_aux_assertions;
+
+            let context = AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                num_main_assertions,
+                num_aux_assertions,
+                options,
+            )
+            .set_num_transition_exemptions(#num_transition_exemptions);
+            Self { context, #(#names: public_inputs.#names),* }
+        }
+    }
+}
+
+fn fn_get_periodic_column_values(ir: &Air) -> TokenStream {
+    let columns = ir.periodic_columns().map(|column| {
+        let rows = column
+            .values
+            .iter()
+            .map(|value| constant_to_tokens(*value, ElemType::Base));
+        quote!(vec![#(#rows),*])
+    });
+
+    // the selector columns emulating exemptions follow the periodic columns.
+    let selectors = exemption_selectors(ir);
+    if selectors.is_empty() {
+        return quote! {
+            fn get_periodic_column_values(&self) -> Vec<Vec<Felt>> {
+                vec![#(#columns),*]
+            }
+        };
+    }
+
+    let selectors = selectors.into_iter().map(Literal::usize_unsuffixed);
+    quote! {
+        fn get_periodic_column_values(&self) -> Vec<Vec<Felt>> {
+            let mut columns = vec![#(#columns),*];
+            columns.extend([#(self.exemption_selector(#selectors)),*]);
+            columns
+        }
+    }
+}
+
+/// Returns the struct holding the random values by the names of their bindings, if the random
+/// values are declared with bindings.
+fn rand_values_struct(ir: &Air) -> TokenStream {
+    let bindings = rand_bindings(ir);
+    if bindings.is_empty() {
+        return TokenStream::new();
+    }
+
+    let name = format_ident!("{RAND_VALUES_STRUCT}");
+    let fields = bindings
+        .iter()
+        .map(|binding| format_ident!("{}", binding.name.as_str()))
+        .collect::<Vec<_>>();
+    let slices = bindings.iter().map(|binding| {
+        let (start, end) = binding_bounds(binding);
+        let start = Literal::usize_unsuffixed(start);
+        let end = Literal::usize_unsuffixed(end);
+        quote!(&rand_elements[#start..#end])
+    });
+
+    quote! {
+        #[doc = " The random values of the aux trace segment, by the names of their bindings in AirScript."]
+        pub struct #name<'a, E> {
+            #(pub #fields: &'a [E],)*
+        }
+
+        impl<'a, E> #name<'a, E> {
+            #[doc = " Slices the provided random values according to the declaration of their bindings."]
+            pub fn new(rand_elements: &'a [E]) -> Self {
+                Self {
+                    #(#fields: #slices,)*
+                }
+            }
+        }
+    }
+}
+
+/// Returns the statement instantiating the struct holding the random values by their names if the
+/// evaluation of the provided constraints requires it, i.e. if any of them accesses a random value
+/// through one of its bindings.
+fn rand_values_declaration<'a>(
+    ir: &Air,
+    constraints: impl IntoIterator<Item = &'a ConstraintRoot>,
+) -> TokenStream {
+    if !uses_rand_values(ir, constraints) {
+        return TokenStream::new();
+    }
+
+    let name = format_ident!("{RAND_VALUES_STRUCT}");
+    let var = format_ident!("{RAND_VALUES_VAR}");
+    quote!(let #var = #name::new(aux_rand_elements.get_segment_elements(0));)
+}
+
+fn fn_get_assertions(ir: &Air) -> TokenStream {
+    let assertions = assertions(ir, 0);
+
+    quote! {
+        fn get_assertions(&self) -> Vec<Assertion<Felt>> {
+            #assertions
+        }
+    }
+}
+
+fn fn_get_aux_assertions(ir: &Air) -> TokenStream {
+    let assertions = assertions(ir, 1);
+
+    quote! {
+        fn get_aux_assertions<E: FieldElement<BaseField = Felt>>(&self, aux_rand_elements: &AuxTraceRandElements<E>) -> Vec<Assertion<E>> {
+            #assertions
+        }
+    }
+}
+
+fn fn_evaluate_transition(ir: &Air, max_chunk_size: Option<usize>) -> TokenStream {
+    let constraints = match num_chunks(ir, 0, max_chunk_size) {
+        Some(num_chunks) => {
+            let chunks = (0..num_chunks).map(|chunk| {
+                let evaluate_chunk = format_ident!("evaluate_transition_chunk_{chunk}");
+                quote!(self.#evaluate_chunk(main_current, main_next, periodic_values, result);)
+            });
+            quote!(#(#chunks)*)
+        }
+        None => constraints(ir, 0, constraint_range(ir, 0, None, 0)),
+    };
+
+    quote! {
+        fn evaluate_transition<E: FieldElement<BaseField = Felt>>(&self, frame: &EvaluationFrame<E>, periodic_values: &[E], result: &mut [E]) {
+            let main_current = frame.current();
+            let main_next = frame.next();
+            #constraints
+        }
+    }
+}
+
+/// Returns the helpers evaluating each chunk of the main transition constraints, if they are split
+/// into chunks of at most `max_chunk_size` constraints.
+fn fn_evaluate_transition_chunks(ir: &Air, max_chunk_size: Option<usize>) -> TokenStream {
+    let num_chunks = num_chunks(ir, 0, max_chunk_size).unwrap_or_default();
+    let chunks = (0..num_chunks).map(|chunk| {
+        let evaluate_chunk = format_ident!("evaluate_transition_chunk_{chunk}");
+        let constraints = constraints(
+            ir,
+            0,
+            constraint_range(ir, 0, max_chunk_size, chunk),
+        );
+        quote! {
+            fn #evaluate_chunk<E: FieldElement<BaseField = Felt>>(&self, main_current: &[E], main_next: &[E], periodic_values: &[E], result: &mut [E]) {
+                #constraints
+            }
+        }
+    });
+
+    quote!(#(#chunks)*)
+}
+
+fn fn_evaluate_aux_transition(ir: &Air, max_chunk_size: Option<usize>) -> TokenStream {
+    let constraints = match num_chunks(ir, 1, max_chunk_size) {
+        Some(num_chunks) => {
+            let chunks = (0..num_chunks).map(|chunk| {
+                let evaluate_chunk = format_ident!("evaluate_aux_transition_chunk_{chunk}");
+                quote!(self.#evaluate_chunk(main_current, main_next, aux_current, aux_next, aux_rand_elements, result);)
+            });
+            quote!(#(#chunks)*)
+        }
+        None => constraints(ir, 1, constraint_range(ir, 1, None, 0)),
+    };
+
+    quote! {
+        fn evaluate_aux_transition<F, E>(&self, main_frame: &EvaluationFrame<F>, aux_frame: &EvaluationFrame<E>, _periodic_values: &[F], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
+        where F: FieldElement<BaseField = Felt>,
+              E: FieldElement<BaseField = Felt> + ExtensionOf<F>,
+        {
+            let main_current = main_frame.current();
+            let main_next = main_frame.next();
+            let aux_current = aux_frame.current();
+            let aux_next = aux_frame.next();
+            #constraints
+        }
+    }
+}
+
+/// Returns the helpers evaluating each chunk of the aux transition constraints, if they are split
+/// into chunks of at most `max_chunk_size` constraints.
+fn fn_evaluate_aux_transition_chunks(ir: &Air, max_chunk_size: Option<usize>) -> TokenStream {
+    let num_chunks = num_chunks(ir, 1, max_chunk_size).unwrap_or_default();
+    let chunks = (0..num_chunks).map(|chunk| {
+        let evaluate_chunk = format_ident!("evaluate_aux_transition_chunk_{chunk}");
+        let constraints = constraints(
+            ir,
+            1,
+            constraint_range(ir, 1, max_chunk_size, chunk),
+        );
+        quote! {
+            fn #evaluate_chunk<F, E>(&self, main_current: &[F], main_next: &[F], aux_current: &[E], aux_next: &[E], aux_rand_elements: &AuxTraceRandElements<E>, result: &mut [E])
+            where F: FieldElement<BaseField = Felt>,
+                  E: FieldElement<BaseField = Felt> + ExtensionOf<F>,
+            {
+                #constraints
+            }
+        }
+    });
+
+    quote!(#(#chunks)*)
+}
+
+/// Returns the test module exercising the generated Air, which imports Winterfell items from the
+/// provided paths.
+fn test_module(ir: &Air, config: WinterfellConfig, paths: &WinterfellPaths) -> TokenStream {
+    let air = path_tokens(&paths.air);
+    let ext = format_ident!("{}", config.extension.element_type());
+    let field_extension = format_ident!("{}", config.extension.proof_option());
+    let params = TestParameters::new(ir);
+    let name = format_ident!("{}", ir.name());
+    let (_, sizes) = public_inputs(ir);
+    let main_width = Literal::usize_unsuffixed(params.main_width);
+    let aux_width = Literal::usize_unsuffixed(params.aux_width);
+    let num_rand_values = Literal::usize_unsuffixed(params.num_rand_values);
+    let trace_length = Literal::usize_unsuffixed(params.trace_length);
+    let blowup_factor = Literal::usize_unsuffixed(params.blowup_factor);
+    let num_main_constraints = Literal::usize_unsuffixed(params.num_main_constraints);
+    let num_aux_constraints = Literal::usize_unsuffixed(params.num_aux_constraints);
+    let num_periodic_values = Literal::usize_unsuffixed(params.num_periodic_values);
+    let num_main_assertions = Literal::usize_unsuffixed(ir.num_boundary_constraints(0));
+    let num_aux_assertions = Literal::usize_unsuffixed(ir.num_boundary_constraints(1));
+    // the aux assertions are only implemented if there is an aux segment
+    let (aux_rand_elements, aux_assertion_count) = if params.aux_width > 0 {
+        (
+            quote! {
+                let mut aux_rand_elements = AuxTraceRandElements::<#ext>::new();
+                aux_rand_elements.add_segment_elements(vec![#ext::ZERO; #num_rand_values]);
+            },
+            quote!(assert_eq!(air.get_aux_assertions(&aux_rand_elements).len(), #num_aux_assertions);),
+        )
+    } else {
+        (TokenStream::new(), TokenStream::new())
+    };
+
+    // doc comments are spelled out as attributes, as `///` comments are quoted as raw strings,
+    // which would not match the output of the string-based generator
+    quote! {
+        #[cfg(test)]
+        pub mod tests {
+            use super::*;
+            use #air::{FieldExtension, TraceLayout};
+
+            #[doc = " Returns an instance of the Air, instantiated with zeroed public inputs."]
+            pub fn build_air() -> #name {
+                let layout = TraceLayout::new(#main_width, [#aux_width], [#num_rand_values]);
+                let trace_info = TraceInfo::new_multi_segment(layout, #trace_length, vec![]);
+                let options = WinterProofOptions::new(32, #blowup_factor, 0, FieldExtension::#field_extension, 4, 32);
+                let public_inputs = PublicInputs::new(#([Felt::ZERO; #sizes]),*);
+                #name::new(trace_info, public_inputs, options)
+            }
+
+            #[doc = " Evaluates the main transition constraints of the Air against the provided frame."]
+            pub fn evaluate_transition(frame: &EvaluationFrame<Felt>) -> Vec<Felt> {
+                let air = build_air();
+                let periodic_values = vec![Felt::ZERO; #num_periodic_values];
+                let mut result = vec![Felt::ZERO; #num_main_constraints];
+                air.evaluate_transition(frame, &periodic_values, &mut result);
+                result
+            }
+
+            #[test]
+            fn air_can_be_constructed() {
+                let air = build_air();
+                assert_eq!(air.context().num_main_transition_constraints(), #num_main_constraints);
+                assert_eq!(air.context().num_aux_transition_constraints(), #num_aux_constraints);
+            }
+
+            #[test]
+            fn assertion_counts() {
+                let air = build_air();
+                #aux_rand_elements
+                assert_eq!(air.get_assertions().len(), #num_main_assertions);
+                #aux_assertion_count
+            }
+
+            #[test]
+            fn evaluate_transition_on_zero_frame() {
+                let frame = EvaluationFrame::new(#main_width);
+                assert_eq!(evaluate_transition(&frame).len(), #num_main_constraints);
+            }
+        }
+    }
+}
+
+// HELPERS
+// ================================================================================================
+
+/// Returns the identifiers and sizes of the public inputs in the IR.
+fn public_inputs(ir: &Air) -> (Vec<proc_macro2::Ident>, Vec<Literal>) {
+    ir.public_inputs()
+        .map(|input| {
+            (
+                format_ident!("{}", input.name.as_str()),
+                Literal::usize_unsuffixed(input.size),
+            )
+        })
+        .unzip()
+}
+
+/// Returns the degrees of the integrity constraints of the specified trace segment, including the
+/// cycle of the selector column emulating the exemptions of a constraint, if any.
+fn constraint_degrees(ir: &Air, trace_segment: TraceSegmentId) -> Vec<TokenStream> {
+    integrity_constraints(ir, trace_segment)
+        .map(|constraint| {
+            let degree = ir.constraint_graph().degree(constraint.node_index());
+            if exemption_selector(ir, trace_segment, constraint).is_none() {
+                return degree.to_tokens(ir, ElemType::Ext, trace_segment);
+            }
+
+            let base = Literal::usize_unsuffixed(degree.base());
+            let cycles = degree.cycles().iter().copied().map(Literal::usize_unsuffixed);
+            quote!(TransitionConstraintDegree::with_cycles(#base, vec![#(#cycles,)* trace_info.length() / 2]))
+        })
+        .collect()
+}
+
+/// Returns a block declaring a result vector containing the assertions for the boundary
+/// constraints of the specified trace segment.
+fn assertions(ir: &Air, trace_segment: TraceSegmentId) -> TokenStream {
+    let elem_type = if trace_segment == 0 {
+        ElemType::Base
+    } else {
+        ElemType::Ext
+    };
+
+    let rand_values = rand_values_declaration(ir, ir.boundary_constraints(trace_segment));
+    let assertions = ir
+        .boundary_constraints(trace_segment)
+        .iter()
+        .map(|constraint| {
+            let (trace_access, expr_root) =
+                split_boundary_constraint(ir.constraint_graph(), constraint.node_index());
+            debug_assert_eq!(trace_access.segment, trace_segment);
+
+            let column = Literal::usize_unsuffixed(trace_access.column);
+            let domain = match constraint.domain() {
+                ConstraintDomain::FirstRow => quote!(0),
+                ConstraintDomain::LastRow => quote!(self.last_step()),
+                _ => panic!("invalid constraint domain"),
+            };
+            let expr = expr_root.to_tokens(ir, elem_type, trace_segment);
+            quote!(result.push(Assertion::single(#column, #domain, #expr));)
+        });
+
+    quote! {
+        let mut result = Vec::new();
+        #rand_values
+        #(#assertions)*
+        result
+    }
+}
+
+/// Returns the assignments of the integrity constraints of the specified trace segment whose index
+/// is in `constraints` to the result slice.
+fn constraints(ir: &Air, trace_segment: TraceSegmentId, constraints: Range<usize>) -> TokenStream {
+    let constraints = integrity_constraints(ir, trace_segment)
+        .enumerate()
+        .skip(constraints.start)
+        .take(constraints.len())
+        .collect::<Vec<_>>();
+    let rand_values =
+        rand_values_declaration(ir, constraints.iter().map(|(_, constraint)| *constraint));
+    let constraints = constraints.into_iter().map(|(idx, constraint)| {
+        let idx = Literal::usize_unsuffixed(idx);
+        let expr = constraint
+            .node_index()
+            .to_tokens(ir, ElemType::Ext, trace_segment);
+        match exemption_selector(ir, trace_segment, constraint) {
+            Some(selector) => {
+                let selector = Literal::usize_unsuffixed(selector);
+                quote!(result[#idx] = periodic_values[#selector] * (#expr);)
+            }
+            None => quote!(result[#idx] = #expr;),
+        }
+    });
+
+    quote! {
+        #rand_values
+        #(#constraints)*
+    }
+}
@@ -0,0 +1,365 @@
+use std::sync::Arc;
+
+use miden_diagnostics::{
+    term::{termcolor::Buffer, DisplayStyle},
+    ByteIndex, CodeMap, Diagnostic, DiagnosticsConfig, Emitter, FileName, LabelStyle, LineIndex,
+    Severity, SourceId, ToDiagnostic,
+};
+use serde::Serialize;
+
+/// A machine-readable representation of a [Diagnostic], for consumption by editors and other
+/// tools rather than by humans.
+///
+/// It is serialized to JSON by [JsonDiagnostic::to_json], e.g.:
+///
+/// ```json
+/// {"severity":"error","code":"E0206","message":"name already in use","span":{"file":"<nofile>",
+/// "start":40,"end":41,"line":4,"column":11},"labels":[...],"notes":[]}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonDiagnostic {
+    /// The severity of the diagnostic, i.e. "bug", "error", "warning", "note" or "help"
+    pub severity: &'static str,
+    /// The stable code identifying the kind of diagnostic, if any
+    pub code: Option<String>,
+    /// The main message of the diagnostic
+    pub message: String,
+    /// The location of the primary label of the diagnostic, if any
+    pub span: Option<JsonSpan>,
+    /// The labels of the diagnostic, primary and secondary, in order
+    pub labels: Vec<JsonLabel>,
+    /// The notes attached to the diagnostic
+    pub notes: Vec<String>,
+}
+impl JsonDiagnostic {
+    /// Converts the provided error or diagnostic, whose spans are resolved against the provided
+    /// [CodeMap].
+    ///
+    /// Labels referring to a source which is not in the [CodeMap] are omitted.
+    pub fn new(diagnostic: impl ToDiagnostic, codemap: &CodeMap) -> Self {
+        let diagnostic: Diagnostic = diagnostic.to_diagnostic();
+        let labels = diagnostic
+            .labels
+            .iter()
+            .filter_map(|label| {
+                let span = JsonSpan::new(codemap, label.file_id, label.range.clone())?;
+                Some(JsonLabel {
+                    primary: label.style == LabelStyle::Primary,
+                    message: label.message.clone(),
+                    span,
+                })
+            })
+            .collect::<Vec<_>>();
+        let span = labels
+            .iter()
+            .find(|label| label.primary)
+            .map(|label| label.span.clone());
+
+        Self {
+            severity: match diagnostic.severity {
+                Severity::Bug => "bug",
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note => "note",
+                Severity::Help => "help",
+            },
+            code: diagnostic.code,
+            message: diagnostic.message,
+            span,
+            labels,
+            notes: diagnostic.notes,
+        }
+    }
+
+    /// Serializes this diagnostic as a single line of JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("diagnostics are always serializable")
+    }
+}
+
+/// A label of a [JsonDiagnostic], pointing at a location in the source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonLabel {
+    /// Whether this is the primary label of the diagnostic
+    pub primary: bool,
+    /// The message attached to the label, which may be empty
+    pub message: String,
+    /// The location the label points at
+    pub span: JsonSpan,
+}
+
+/// A location in the source, as both a range of byte offsets and the line and column at which it
+/// starts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonSpan {
+    /// The name of the source file
+    pub file: String,
+    /// The byte offset of the start of the span in the source file
+    pub start: usize,
+    /// The byte offset of the end of the span in the source file, exclusive, which is unknown for
+    /// the diagnostics printed by a [JsonEmitter]
+    pub end: Option<usize>,
+    /// The line at which the span starts, starting from 1
+    pub line: usize,
+    /// The column at which the span starts, starting from 1
+    pub column: usize,
+}
+impl JsonSpan {
+    fn new(codemap: &CodeMap, file_id: SourceId, range: core::ops::Range<usize>) -> Option<Self> {
+        let file = codemap.get(file_id).ok()?;
+        let location = file.location(ByteIndex(range.start as u32)).ok()?;
+        Some(Self {
+            file: file.name().to_string(),
+            start: range.start,
+            end: Some(range.end),
+            line: location.line.to_usize() + 1,
+            column: location.column.to_usize() + 1,
+        })
+    }
+}
+
+/// An [Emitter] converting each diagnostic emitted through a [DiagnosticsHandler] to a
+/// [JsonDiagnostic], which is passed to the provided sink, e.g. to print it to stdout.
+///
+/// As the [DiagnosticsHandler] renders diagnostics to text before handing them to its emitter, the
+/// diagnostics are parsed back from their rendering in the [DisplayStyle::Medium] style, which the
+/// handler must be configured with, see [JsonEmitter::config]. This keeps the severity, code,
+/// message and notes of the diagnostics, as well as the locations of their primary labels, but
+/// not the messages of the labels, nor the ends of their spans. The diagnostics emitted without a
+/// code are given the stable code of their message, see [diagnostic_code].
+///
+/// [DiagnosticsHandler]: miden_diagnostics::DiagnosticsHandler
+pub struct JsonEmitter {
+    codemap: Arc<CodeMap>,
+    sink: Box<dyn Fn(JsonDiagnostic) + Send + Sync>,
+}
+impl JsonEmitter {
+    /// Creates an emitter passing each diagnostic to `sink`, whose spans are resolved against the
+    /// provided [CodeMap]
+    pub fn new(
+        codemap: Arc<CodeMap>,
+        sink: impl Fn(JsonDiagnostic) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            codemap,
+            sink: Box::new(sink),
+        }
+    }
+
+    /// Creates an emitter printing each diagnostic to stdout as a single line of JSON
+    pub fn stdout(codemap: Arc<CodeMap>) -> Self {
+        Self::new(codemap, |diagnostic| println!("{}", diagnostic.to_json()))
+    }
+
+    /// Returns `config` with the display style expected by a [JsonEmitter]
+    pub fn config(mut config: DiagnosticsConfig) -> DiagnosticsConfig {
+        config.display.display_style = DisplayStyle::Medium;
+        config
+    }
+
+    /// Parses a diagnostic rendered in the [DisplayStyle::Medium] style, which starts with a header
+    /// for each of its primary labels, e.g. `file.air:4:11: error[E0206]: name already in use`,
+    /// or with a single header without location, followed by its notes, e.g. `= note`.
+    fn parse(&self, rendered: &str) -> JsonDiagnostic {
+        let mut header = None;
+        let mut labels = vec![];
+        let mut notes: Vec<String> = vec![];
+        for line in rendered.lines() {
+            if let Some(note) = line.strip_prefix(" = ") {
+                notes.push(note.to_string());
+            } else if let Some(continued) = line.strip_prefix("   ") {
+                if let Some(note) = notes.last_mut() {
+                    note.push('\n');
+                    note.push_str(continued);
+                }
+            } else if let Some((locus, severity, code, message)) = parse_header(line) {
+                if let Some(span) = locus.and_then(|locus| self.span(locus)) {
+                    labels.push(JsonLabel {
+                        primary: true,
+                        message: String::new(),
+                        span,
+                    });
+                }
+                header.get_or_insert((severity, code, message));
+            }
+        }
+
+        let (severity, code, message) = header.unwrap_or(("note", None, rendered.trim()));
+        Self::diagnostic(severity, code, message, labels, notes)
+    }
+
+    fn diagnostic(
+        severity: &'static str,
+        code: Option<&str>,
+        message: &str,
+        labels: Vec<JsonLabel>,
+        notes: Vec<String>,
+    ) -> JsonDiagnostic {
+        JsonDiagnostic {
+            severity,
+            code: code
+                .or_else(|| diagnostic_code(message))
+                .map(|code| code.to_string()),
+            message: message.to_string(),
+            span: labels.first().map(|label| label.span.clone()),
+            labels,
+            notes,
+        }
+    }
+
+    /// Resolves the location of a header, i.e. the file name, and the line and column starting
+    /// from 1, against the [CodeMap] of this emitter
+    fn span(&self, (name, line, column): (&str, usize, usize)) -> Option<JsonSpan> {
+        // the names of virtual files, e.g. the ones of sources parsed from a string, are rendered
+        // in angle brackets
+        let filename = match name
+            .strip_prefix('<')
+            .and_then(|name| name.strip_suffix('>'))
+        {
+            Some(name) => FileName::virtual_(name.to_string()),
+            None => FileName::real(name),
+        };
+        let file_id = self.codemap.get_file_id(&filename)?;
+        let file = self.codemap.get(file_id).ok()?;
+        let line_start = file
+            .line_start(LineIndex(line.checked_sub(1)? as u32))
+            .ok()?
+            .to_usize();
+        let column_offset = file.source()[line_start..]
+            .char_indices()
+            .nth(column.checked_sub(1)?)
+            .map_or(0, |(offset, _)| offset);
+        Some(JsonSpan {
+            file: name.to_string(),
+            start: line_start + column_offset,
+            end: None,
+            line,
+            column,
+        })
+    }
+}
+impl Emitter for JsonEmitter {
+    fn buffer(&self) -> Buffer {
+        Buffer::no_color()
+    }
+
+    fn print(&self, buffer: Buffer) -> std::io::Result<()> {
+        let rendered = String::from_utf8_lossy(buffer.as_slice());
+        (self.sink)(self.parse(&rendered));
+        Ok(())
+    }
+}
+
+type Header<'a> = (
+    Option<(&'a str, usize, usize)>,
+    &'static str,
+    Option<&'a str>,
+    &'a str,
+);
+
+/// Parses the header of a diagnostic, i.e. its optional location, severity, optional code and
+/// message, e.g. `file.air:4:11: error[E0206]: name already in use`
+fn parse_header(line: &str) -> Option<Header<'_>> {
+    if let Some((severity, code, message)) = parse_severity(line) {
+        return Some((None, severity, code, message));
+    }
+    line.match_indices(": ").find_map(|(index, _)| {
+        let locus = parse_locus(&line[..index])?;
+        let (severity, code, message) = parse_severity(&line[index + 2..])?;
+        Some((Some(locus), severity, code, message))
+    })
+}
+
+fn parse_severity(header: &str) -> Option<(&'static str, Option<&str>, &str)> {
+    ["bug", "error", "warning", "note", "help"]
+        .into_iter()
+        .find_map(|severity| {
+            let rest = header.strip_prefix(severity)?;
+            let (code, rest) = match rest.strip_prefix('[') {
+                Some(rest) => {
+                    let (code, rest) = rest.split_once(']')?;
+                    (Some(code), rest)
+                }
+                None => (None, rest),
+            };
+            Some((severity, code, rest.strip_prefix(": ")?))
+        })
+}
+
+fn parse_locus(locus: &str) -> Option<(&str, usize, usize)> {
+    let mut parts = locus.rsplitn(3, ':');
+    let column = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    Some((parts.next()?, line, column))
+}
+
+/// Returns the stable code of the diagnostics emitted with the provided message, which are built
+/// directly on the [DiagnosticsHandler] rather than converted from an error carrying its own code.
+///
+/// The codes of the diagnostics emitted during semantic analysis start from `E0500`, and those of
+/// the diagnostics emitted by the passes on the [crate::Air] from `E0600`.
+///
+/// [DiagnosticsHandler]: miden_diagnostics::DiagnosticsHandler
+pub fn diagnostic_code(message: &str) -> Option<&'static str> {
+    let code = match message {
+        "invalid binding in list comprehension" => "E0500",
+        "invalid iterable" => "E0501",
+        "invalid callee" => "E0502",
+        "invalid expression" => "E0503",
+        "invalid assertion" => "E0504",
+        "invalid variable access" => "E0505",
+        "reference to undefined function" => "E0506",
+        "reference to undefined variable" => "E0507",
+        "invalid call" => "E0508",
+        "invalid evaluator function argument" => "E0509",
+        "declaration conflicts with an imported item" => "E0510",
+        "declaration shadowed" => "E0511",
+        "invalid constraint" => "E0512",
+        "type mismatch" => "E0513",
+        "invalid alias declaration" => "E0514",
+        "invalid row offset" => "E0515",
+        "invalid access" => "E0516",
+        "redundant import" => "E0517",
+        "duplicate parameter declaration" => "E0518",
+        "unbound parameter" => "E0519",
+        "undefined parameter" => "E0520",
+        message if message.starts_with("a value was bound to the undeclared parameter") => "E0521",
+        "division by zero" => "E0522",
+        "unknown trace width" => "E0523",
+        "invalid constant table" => "E0524",
+        "unknown constant table format" => "E0525",
+        "invalid comprehension filter" => "E0526",
+        "conflicting module definitions" => "E0527",
+        "invalid module declaration" => "E0528",
+        "missing trace_columns section" => "E0529",
+        "missing main trace declaration" => "E0530",
+        message if message.starts_with("declaring random_values requires") => "E0531",
+        "duplicate module import" => "E0532",
+        "redundant item import" => "E0533",
+        "redundant module import" => "E0534",
+        "constant identifiers must be uppercase ASCII characters, e.g. FOO" => "E0535",
+        "invalid constant" => "E0536",
+        "invalid periodic column declaration" => "E0537",
+        "multiple random_values declarations" => "E0538",
+        "invalid trace binding declaration" => "E0539",
+        "at least one boundary constraint must be declared" => "E0540",
+        "at least one integrity constraint must be declared" => "E0541",
+        message if message.starts_with("invalid ") && message.ends_with(" declaration") => "E0542",
+        message if message.starts_with("periodic column conflicts with") => "E0543",
+        "constraint degree exceeds the maximum supported by the target blowup factor" => "E0600",
+        "constraint degree approaches the maximum supported by the target blowup factor" => "E0601",
+        "trace column only referenced in the next row" => "E0602",
+        "unused constant" => "E0603",
+        "unused public input" => "E0604",
+        "constraint quotient exceeds the degree bound of the composition polynomial" => "E0605",
+        "too many constraints" => "E0606",
+        "redundant constraint" => "E0607",
+        "assertion failed" => "E0608",
+        "invalid boundary constraint" => "E0609",
+        "invalid integrity constraint" => "E0610",
+        "overlapping boundary constraints" => "E0611",
+        "auxiliary constraint without random values" => "E0612",
+        _ => return None,
+    };
+    Some(code)
+}
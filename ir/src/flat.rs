@@ -0,0 +1,222 @@
+use std::fmt::Write;
+
+use crate::{
+    graph::NodeIndex, Air, CodeGenerator, ConstraintDomain, ConstraintRoot, Operation,
+    RandomValueAccess, TraceAccess, Value,
+};
+
+/// This code generator re-emits an [Air] as an AirScript program in which every constraint is
+/// fully expanded, i.e. without `let` bindings, comprehensions, evaluators, functions or named
+/// constants, which are all substituted while building the IR.
+///
+/// The declarations of the trace columns, public inputs, periodic columns and random values are
+/// kept, along with the doc strings of the columns and inputs, so the emitted program compiles to
+/// the same constraints, and each constraint is emitted on its own line, the integrity
+/// constraints of the main trace first. This is meant for the review of the constraints, and to
+/// cross-check the IR against the source program.
+#[derive(Default)]
+pub struct FlatAirScript;
+impl FlatAirScript {
+    /// Create a new instance of this code generator
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+impl CodeGenerator for FlatAirScript {
+    type Output = String;
+
+    fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        let mut out = String::new();
+        writeln!(out, "def {}", ir.name())?;
+
+        writeln!(out, "\ntrace_columns:")?;
+        for segment in ir.trace_columns.iter() {
+            writeln!(out, "    {segment}")?;
+        }
+
+        writeln!(out, "\npublic_inputs:")?;
+        for input in ir.public_inputs() {
+            match input.doc() {
+                Some(doc) => writeln!(out, "    {}: [{}] \"{doc}\"", input.name, input.size)?,
+                None => writeln!(out, "    {}: [{}]", input.name, input.size)?,
+            }
+        }
+
+        if !ir.periodic_columns.is_empty() {
+            writeln!(out, "\nperiodic_columns:")?;
+            for (name, column) in ir.periodic_columns.iter() {
+                let values = column
+                    .values
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>();
+                writeln!(out, "    {}: [{}]", name.item, values.join(", "))?;
+            }
+        }
+
+        if let Some(random_values) = ir.random_values.as_ref() {
+            writeln!(out, "\nrandom_values:")?;
+            writeln!(out, "    {random_values}")?;
+        }
+
+        let segments = 0..ir.trace_segment_widths.len();
+        writeln!(out, "\nboundary_constraints:")?;
+        for segment in segments.clone() {
+            for constraint in ir.boundary_constraints(segment) {
+                writeln!(out, "    {}", boundary_constraint(ir, constraint)?)?;
+            }
+        }
+
+        writeln!(out, "\nintegrity_constraints:")?;
+        for segment in segments {
+            for constraint in ir.integrity_constraints(segment) {
+                writeln!(out, "    {}", integrity_constraint(ir, constraint))?;
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// The precedence of an operation, which determines where parentheses are needed
+fn precedence(op: &Operation) -> u8 {
+    match op {
+        Operation::Value(_) => 4,
+        Operation::Exp(_, _) | Operation::Inv(_) => 3,
+        Operation::Mul(_, _) => 2,
+        Operation::Add(_, _) | Operation::Sub(_, _) => 1,
+    }
+}
+
+/// Returns the `enf` statement of a boundary constraint, which must be of the form
+/// `column - value`, or `column` when the expected value is zero
+fn boundary_constraint(ir: &Air, constraint: &ConstraintRoot) -> anyhow::Result<String> {
+    let graph = ir.constraint_graph();
+    let boundary = match constraint.domain() {
+        ConstraintDomain::FirstRow => "first",
+        ConstraintDomain::LastRow => "last",
+        domain => anyhow::bail!("invalid boundary constraint domain {domain}"),
+    };
+    let (column, value) = match graph.node(constraint.node_index()).op() {
+        Operation::Sub(lhs, rhs) => (lhs, expr(ir, rhs)),
+        _ => (constraint.node_index(), "0".to_string()),
+    };
+    match graph.node(column).op() {
+        Operation::Value(Value::TraceAccess(access)) if access.row_offset == 0 => Ok(format!(
+            "enf {}.{boundary} = {value}",
+            trace_access(ir, access)
+        )),
+        _ => anyhow::bail!("boundary constraints must constrain a single trace column"),
+    }
+}
+
+/// Returns the `enf` statement of an integrity constraint
+fn integrity_constraint(ir: &Air, constraint: &ConstraintRoot) -> String {
+    let graph = ir.constraint_graph();
+    let index = constraint.node_index();
+    let statement = match graph.node(index).op() {
+        Operation::Sub(lhs, rhs) => format!("enf {} = {}", expr(ir, lhs), expr(ir, rhs)),
+        Operation::Mul(lhs, selector) => match graph.node(lhs).op() {
+            Operation::Sub(lhs, rhs) => format!(
+                "enf {} = {} when {}",
+                expr(ir, lhs),
+                expr(ir, rhs),
+                expr(ir, selector)
+            ),
+            _ => format!("enf {} = 0", expr(ir, index)),
+        },
+        _ => format!("enf {} = 0", expr(ir, index)),
+    };
+
+    match constraint.exemptions() {
+        0 => statement,
+        rows => format!("{statement} except last {rows}"),
+    }
+}
+
+/// Returns the expression of the subgraph rooted at `index`, with the parentheses needed to
+/// preserve its structure when it is parsed again
+fn expr(ir: &Air, index: &NodeIndex) -> String {
+    let op = ir.constraint_graph().node(index).op();
+    let operand = |index: &NodeIndex, min_precedence: u8| {
+        let operand = expr(ir, index);
+        if precedence(ir.constraint_graph().node(index).op()) < min_precedence {
+            format!("({operand})")
+        } else {
+            operand
+        }
+    };
+
+    // binary operators are left-associative, so a right operand of the same precedence is
+    // parenthesized
+    match op {
+        Operation::Value(value) => self::value(ir, value),
+        Operation::Add(lhs, rhs) => format!("{} + {}", operand(lhs, 1), operand(rhs, 2)),
+        Operation::Sub(lhs, rhs) => format!("{} - {}", operand(lhs, 1), operand(rhs, 2)),
+        Operation::Mul(lhs, rhs) => format!("{} * {}", operand(lhs, 2), operand(rhs, 3)),
+        Operation::Exp(lhs, exp) => format!("{}^{exp}", operand(lhs, 4)),
+        Operation::Inv(inverted) => match ir.constraint_graph().node(inverted).op() {
+            Operation::Exp(lhs, exp) if *exp > 1 => format!("{}^(-{exp})", operand(lhs, 4)),
+            _ => format!("{}^(-1)", operand(inverted, 4)),
+        },
+    }
+}
+
+/// Returns the name of the provided value
+pub(crate) fn value(ir: &Air, value: &Value) -> String {
+    match value {
+        Value::Constant(value) => value.to_string(),
+        Value::TraceAccess(access) => trace_access(ir, access),
+        Value::PeriodicColumn(access) => access.name.item.to_string(),
+        Value::PublicInput(access) => format!("{}[{}]", access.name, access.index),
+        Value::RandomValue(element) => match ir.random_value_binding(*element) {
+            Some(RandomValueAccess { name, index, .. }) => {
+                let binding = ir
+                    .random_values
+                    .as_ref()
+                    .and_then(|rv| rv.bindings.iter().find(|binding| binding.name == name))
+                    .expect("random value bindings are declared");
+                if binding.is_scalar() {
+                    name.to_string()
+                } else {
+                    format!("{name}[{index}]")
+                }
+            }
+            None => random_value(ir, *element),
+        },
+    }
+}
+
+/// Returns the name of the column accessed by `access`, through the binding which declares it
+fn trace_access(ir: &Air, access: &TraceAccess) -> String {
+    let segment = &ir.trace_columns[access.segment];
+    let column = segment
+        .bindings
+        .iter()
+        .find(|binding| (binding.offset..binding.offset + binding.size).contains(&access.column))
+        .and_then(|binding| Some((binding.name?, binding.offset, binding.is_scalar())));
+    let name = match column {
+        Some((name, _, true)) => name.to_string(),
+        Some((name, offset, false)) => format!("{name}[{}]", access.column - offset),
+        None => format!("{}[{}]", segment.name, access.column),
+    };
+    format!("{name}{}", "'".repeat(access.row_offset))
+}
+
+/// Returns the access of the element `index` of the random values array, through the stage which
+/// contains it, if the random values are drawn in stages
+fn random_value(ir: &Air, index: usize) -> String {
+    let rv = ir
+        .random_values
+        .as_ref()
+        .expect("random values are declared");
+    match rv
+        .stages
+        .iter()
+        .find(|stage| (stage.offset..stage.offset + stage.size).contains(&index))
+    {
+        Some(stage) => format!("{}[{}]", stage.name, index - stage.offset),
+        None => format!("{}[{index}]", rv.name),
+    }
+}
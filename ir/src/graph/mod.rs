@@ -1,4 +1,7 @@
-use std::collections::BTreeMap;
+mod visit;
+pub use self::visit::{ValueCollector, Visit, VisitContext};
+
+use std::collections::HashMap;
 
 use crate::ir::*;
 
@@ -6,8 +9,15 @@ use crate::ir::*;
 ///
 /// The raw value of this identifier is an index in the `nodes` vector
 /// of the [AlgebraicGraph] struct.
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NodeIndex(usize);
+impl NodeIndex {
+    /// Returns the raw index of the node in the `nodes` vector of its [AlgebraicGraph]
+    #[inline]
+    pub const fn as_usize(&self) -> usize {
+        self.0
+    }
+}
 impl core::ops::Add<usize> for NodeIndex {
     type Output = NodeIndex;
 
@@ -23,6 +33,19 @@ impl core::ops::Add<usize> for &NodeIndex {
     }
 }
 
+/// An error raised when an [AlgebraicGraph] violates its structural invariants
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GraphError {
+    #[error("node {node} refers to node {child}, but the graph only has {num_nodes} nodes")]
+    ChildOutOfRange {
+        node: usize,
+        child: usize,
+        num_nodes: usize,
+    },
+    #[error("node {node} refers to node {child}, which does not precede it in the graph")]
+    ChildAfterParent { node: usize, child: usize },
+}
+
 /// A node in the [AlgebraicGraph]
 #[derive(Debug, Clone)]
 pub struct Node {
@@ -30,6 +53,12 @@ pub struct Node {
     op: Operation,
 }
 impl Node {
+    /// Creates a new node representing the given [Operation]
+    #[inline]
+    pub(crate) const fn new(op: Operation) -> Self {
+        Self { op }
+    }
+
     /// Get the underlying [Operation] represented by this node
     #[inline]
     pub const fn op(&self) -> &Operation {
@@ -70,69 +99,173 @@ impl AlgebraicGraph {
         self.nodes.len()
     }
 
-    /// Returns the degree of the subgraph which has the specified node as its tip.
-    pub fn degree(&self, index: &NodeIndex) -> IntegrityConstraintDegree {
-        let mut cycles = BTreeMap::default();
-        let base = self.accumulate_degree(&mut cycles, index);
+    /// Returns an iterator over the nodes of the graph, along with their indices.
+    ///
+    /// Passes which need to visit every node of the graph should use this rather than assuming
+    /// how nodes are laid out, as the indices yielded here are always valid for this graph, e.g.
+    /// for use with [AlgebraicGraph::node]. Nodes are yielded in increasing order of their index,
+    /// so the operands of a node are always yielded before the node itself.
+    pub fn nodes_indexed(&self) -> impl ExactSizeIterator<Item = (NodeIndex, &Node)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeIndex(index), node))
+    }
 
-        if cycles.is_empty() {
-            IntegrityConstraintDegree::new(base)
-        } else {
-            IntegrityConstraintDegree::with_cycles(base, cycles.values().copied().collect())
+    /// Returns the indices of the children of the node with the specified index, i.e. the operands
+    /// of its operation.
+    pub fn children(&self, index: &NodeIndex) -> [Option<NodeIndex>; 2] {
+        match self.node(index).op() {
+            Operation::Value(_) => [None, None],
+            Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) | Operation::Mul(lhs, rhs) => {
+                [Some(*lhs), Some(*rhs)]
+            }
+            Operation::Exp(lhs, _) | Operation::Inv(lhs) => [Some(*lhs), None],
+        }
+    }
+
+    /// Checks that the graph is well-formed, i.e. that the children of every node are nodes of the
+    /// graph which precede it.
+    ///
+    /// Nodes are only ever inserted after their operands, so a valid graph is always in topological
+    /// order, which also guarantees that it has no cycles. Passes which rebuild the graph and remap
+    /// node indices can use this to catch indices which were left dangling.
+    pub fn validate(&self) -> Result<(), GraphError> {
+        let num_nodes = self.nodes.len();
+        for (index, _) in self.nodes_indexed() {
+            for child in self.children(&index).into_iter().flatten() {
+                if child.0 >= num_nodes {
+                    return Err(GraphError::ChildOutOfRange {
+                        node: index.0,
+                        child: child.0,
+                        num_nodes,
+                    });
+                }
+                if child.0 >= index.0 {
+                    return Err(GraphError::ChildAfterParent {
+                        node: index.0,
+                        child: child.0,
+                    });
+                }
+            }
         }
+
+        Ok(())
     }
 
-    /// TODO: docs
+    /// Returns the operand `a` of the node with the specified index if it checks that `a` is
+    /// boolean in the canonical form `a^2 - a`, to which [crate::passes::NormalizeBooleanChecks]
+    /// rewrites the equivalent forms of such checks.
+    pub fn boolean_check(&self, index: &NodeIndex) -> Option<NodeIndex> {
+        let Operation::Sub(lhs, rhs) = *self.node(index).op() else {
+            return None;
+        };
+        match *self.node(&lhs).op() {
+            Operation::Exp(operand, 2) if operand == rhs => Some(operand),
+            _ => None,
+        }
+    }
+
+    /// Returns the degree of the subgraph which has the specified node as its tip.
+    pub fn degree(&self, index: &NodeIndex) -> IntegrityConstraintDegree {
+        let degree = self.accumulate_degree(index);
+        IntegrityConstraintDegree::with_cycles(degree.base(), degree.cycles().to_vec())
+    }
+
+    /// Infers the trace segment and the domain of the subgraph which has the specified node as
+    /// its tip, i.e. the latest segment and the most restrictive domain of the values it accesses,
+    /// starting from `default_domain`.
     pub fn node_details(
         &self,
         index: &NodeIndex,
         default_domain: ConstraintDomain,
     ) -> Result<(TraceSegmentId, ConstraintDomain), ConstraintError> {
-        // recursively walk the subgraph and infer the trace segment and domain
-        match self.node(index).op() {
-            Operation::Value(value) => match value {
-                Value::Constant(_) => Ok((DEFAULT_SEGMENT, default_domain)),
-                Value::PeriodicColumn(_) => {
-                    assert!(
-                        !default_domain.is_boundary(),
-                        "unexpected access to periodic column in boundary constraint"
-                    );
-                    // the default domain for [IntegrityConstraints] is `EveryRow`
-                    Ok((DEFAULT_SEGMENT, ConstraintDomain::EveryRow))
-                }
-                Value::PublicInput(_) => {
-                    assert!(
-                        !default_domain.is_integrity(),
-                        "unexpected access to public input in integrity constraint"
-                    );
-                    Ok((DEFAULT_SEGMENT, default_domain))
-                }
-                Value::RandomValue(_) => Ok((AUX_SEGMENT, default_domain)),
-                Value::TraceAccess(trace_access) => {
-                    let domain = if default_domain.is_boundary() {
-                        assert_eq!(
-                            trace_access.row_offset, 0,
-                            "unexpected trace offset in boundary constraint"
+        // walk the subgraph bottom-up, and infer the trace segment and domain of each node from
+        // those of its children
+        self.fold(
+            index,
+            |op, children: &[(TraceSegmentId, ConstraintDomain)]| {
+                let Operation::Value(value) = op else {
+                    let (mut trace_segment, mut domain) = children[0];
+                    for (child_segment, child_domain) in &children[1..] {
+                        trace_segment = trace_segment.max(*child_segment);
+                        domain = domain.merge(*child_domain)?;
+                    }
+                    return Ok((trace_segment, domain));
+                };
+                match value {
+                    Value::Constant(_) => Ok((DEFAULT_SEGMENT, default_domain)),
+                    Value::PeriodicColumn(_) => {
+                        assert!(
+                            !default_domain.is_boundary(),
+                            "unexpected access to periodic column in boundary constraint"
                         );
-                        default_domain
-                    } else {
-                        ConstraintDomain::from_offset(trace_access.row_offset)
-                    };
+                        // the default domain for [IntegrityConstraints] is `EveryRow`
+                        Ok((DEFAULT_SEGMENT, ConstraintDomain::EveryRow))
+                    }
+                    // public inputs are row-invariant, so they don't restrict the domain of integrity
+                    // constraints, in which they are only accessed when enabled
+                    Value::PublicInput(_) => Ok((DEFAULT_SEGMENT, default_domain)),
+                    Value::RandomValue(_) => Ok((AUX_SEGMENT, default_domain)),
+                    Value::TraceAccess(trace_access) => {
+                        let domain = if default_domain.is_boundary() {
+                            assert_eq!(
+                                trace_access.row_offset, 0,
+                                "unexpected trace offset in boundary constraint"
+                            );
+                            default_domain
+                        } else {
+                            ConstraintDomain::from_offset(trace_access.row_offset)
+                        };
 
-                    Ok((trace_access.segment, domain))
+                        Ok((trace_access.segment, domain))
+                    }
                 }
             },
-            Operation::Add(lhs, rhs) | Operation::Sub(lhs, rhs) | Operation::Mul(lhs, rhs) => {
-                let (lhs_segment, lhs_domain) = self.node_details(lhs, default_domain)?;
-                let (rhs_segment, rhs_domain) = self.node_details(rhs, default_domain)?;
-
-                let trace_segment = lhs_segment.max(rhs_segment);
-                let domain = lhs_domain.merge(rhs_domain)?;
+        )
+    }
 
-                Ok((trace_segment, domain))
+    /// Computes a value for the subgraph which has the specified node as its tip, by evaluating
+    /// `eval` on each of its nodes with the values already computed for their children, in order.
+    ///
+    /// The subgraph is walked bottom-up without recursion, so that arbitrarily deep graphs cannot
+    /// overflow the stack, and nodes shared within the subgraph are only evaluated once. Only the
+    /// values of the nodes of the subgraph are stored, so that the cost of a fold doesn't depend
+    /// on the size of the whole graph. The walk stops at the first error returned by `eval`.
+    pub(crate) fn fold<T, E, F>(&self, index: &NodeIndex, mut eval: F) -> Result<T, E>
+    where
+        T: Clone,
+        F: FnMut(&Operation, &[T]) -> Result<T, E>,
+    {
+        let mut values = HashMap::<NodeIndex, T>::new();
+        let mut stack = vec![(*index, false)];
+        while let Some((node_index, children_visited)) = stack.pop() {
+            if values.contains_key(&node_index) {
+                continue;
             }
-            Operation::Exp(lhs, _) => self.node_details(lhs, default_domain),
+            let children = self.children(&node_index);
+            if !children_visited {
+                stack.push((node_index, true));
+                // push the children in reverse order, so that the left-most child is visited first
+                for child in children.into_iter().rev().flatten() {
+                    if !values.contains_key(&child) {
+                        stack.push((child, false));
+                    }
+                }
+                continue;
+            }
+            // the children are always evaluated before their parent
+            let children = children
+                .into_iter()
+                .flatten()
+                .map(|child| values[&child].clone())
+                .collect::<Vec<_>>();
+            let value = eval(self.node(&node_index).op(), &children)?;
+            values.insert(node_index, value);
         }
+        Ok(values
+            .remove(index)
+            .expect("the tip of the subgraph is evaluated"))
     }
 
     /// Insert the operation and return its node index. If an identical node already exists, return
@@ -152,41 +285,32 @@ impl AlgebraicGraph {
         )
     }
 
-    /// Recursively accumulates the base degree and the cycle lengths of the periodic columns.
-    fn accumulate_degree(
-        &self,
-        cycles: &mut BTreeMap<QualifiedIdentifier, usize>,
-        index: &NodeIndex,
-    ) -> usize {
-        // recursively walk the subgraph and compute the degree from the operation and child nodes
-        match self.node(index).op() {
-            Operation::Value(value) => match value {
-                Value::Constant(_) | Value::RandomValue(_) | Value::PublicInput(_) => 0,
-                Value::TraceAccess(_) => 1,
-                Value::PeriodicColumn(pc) => {
-                    cycles.insert(pc.name, pc.cycle);
-                    0
-                }
-            },
-            Operation::Add(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                let rhs_base = self.accumulate_degree(cycles, rhs);
-                lhs_base.max(rhs_base)
-            }
-            Operation::Sub(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                let rhs_base = self.accumulate_degree(cycles, rhs);
-                lhs_base.max(rhs_base)
-            }
-            Operation::Mul(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                let rhs_base = self.accumulate_degree(cycles, rhs);
-                lhs_base + rhs_base
-            }
-            Operation::Exp(lhs, rhs) => {
-                let lhs_base = self.accumulate_degree(cycles, lhs);
-                lhs_base * rhs
-            }
+    /// Accumulates the base degree and the cycle lengths of the periodic columns.
+    fn accumulate_degree(&self, index: &NodeIndex) -> IntegrityConstraintDegree {
+        // walk the subgraph bottom-up, and compute the degree of each node from its operation and
+        // the degrees of its children
+        let degree = self.fold(index, |op, children: &[IntegrityConstraintDegree]| {
+            let degree = match op {
+                Operation::Value(value) => match value {
+                    Value::Constant(_) | Value::RandomValue(_) | Value::PublicInput(_) => {
+                        IntegrityConstraintDegree::constant()
+                    }
+                    Value::TraceAccess(_) => IntegrityConstraintDegree::new(1),
+                    Value::PeriodicColumn(pc) => {
+                        IntegrityConstraintDegree::periodic(pc.name, pc.cycle)
+                    }
+                },
+                Operation::Add(..) => children[0].combined(&children[1], BinaryOp::Add),
+                Operation::Sub(..) => children[0].combined(&children[1], BinaryOp::Sub),
+                Operation::Mul(..) => children[0].combined(&children[1], BinaryOp::Mul),
+                Operation::Exp(_, exp) => children[0].exp(*exp),
+                Operation::Inv(_) => children[0].inv(),
+            };
+            Ok::<_, core::convert::Infallible>(degree)
+        });
+        match degree {
+            Ok(degree) => degree,
+            Err(never) => match never {},
         }
     }
 }
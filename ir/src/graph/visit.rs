@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use crate::ir::*;
+
+use super::{AlgebraicGraph, NodeIndex};
+
+/// A [VisitContext] holds the state of a traversal of an [AlgebraicGraph], and is notified of
+/// every node visited by one of the traversals provided by [Visit].
+pub trait VisitContext {
+    /// Called once for each node visited during a traversal
+    fn visit(&mut self, graph: &AlgebraicGraph, node_index: NodeIndex);
+}
+
+/// This trait provides traversals of an [AlgebraicGraph], and is implemented for every
+/// [VisitContext].
+///
+/// Nodes shared between several subgraphs are only visited once per traversal.
+pub trait Visit: VisitContext {
+    /// Visits every node reachable from `roots` in post-order, i.e. the children of a node are
+    /// always visited before the node itself.
+    fn visit_postorder<I>(&mut self, graph: &AlgebraicGraph, roots: I)
+    where
+        I: IntoIterator<Item = NodeIndex>,
+    {
+        let mut visited = vec![false; graph.num_nodes()];
+        let mut stack = vec![];
+        for root in roots {
+            if visited[root.0] {
+                continue;
+            }
+            stack.push((root, false));
+            while let Some((node_index, children_visited)) = stack.pop() {
+                if children_visited {
+                    self.visit(graph, node_index);
+                    continue;
+                }
+                if visited[node_index.0] {
+                    continue;
+                }
+                visited[node_index.0] = true;
+                stack.push((node_index, true));
+                // push the children in reverse order, so that the left-most child is visited first
+                for child in graph.children(&node_index).into_iter().rev().flatten() {
+                    if !visited[child.0] {
+                        stack.push((child, false));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Visits every node reachable from the roots of the constraints of `air` in post-order,
+    /// starting with the boundary constraints of each trace segment.
+    fn visit_constraints(&mut self, air: &Air) {
+        let roots = (0..air.trace_segment_widths.len())
+            .flat_map(|segment| {
+                air.boundary_constraints(segment)
+                    .iter()
+                    .chain(air.integrity_constraints(segment))
+            })
+            .map(|constraint| *constraint.node_index());
+        self.visit_postorder(air.constraint_graph(), roots);
+    }
+}
+impl<T: VisitContext + ?Sized> Visit for T {}
+
+/// A [VisitContext] which collects the distinct [Value]s referenced by the visited nodes, in the
+/// order in which they were first visited.
+#[derive(Debug, Default)]
+pub struct ValueCollector {
+    values: Vec<Value>,
+    /// The values collected so far, to skip the duplicates without scanning `values`
+    seen: HashSet<Value>,
+}
+impl ValueCollector {
+    /// Returns the values collected so far
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    /// Consumes this collector, returning the collected values
+    pub fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+}
+impl VisitContext for ValueCollector {
+    fn visit(&mut self, graph: &AlgebraicGraph, node_index: NodeIndex) {
+        if let Operation::Value(value) = graph.node(&node_index).op() {
+            if self.seen.insert(*value) {
+                self.values.push(*value);
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use core::fmt;
+use std::collections::BTreeMap;
 
 use crate::graph::{AlgebraicGraph, NodeIndex};
 
@@ -35,6 +36,12 @@ pub struct Constraints {
     integrity_constraints: Vec<Vec<ConstraintRoot>>,
     /// A directed acyclic graph which represents all of the constraints and their subexpressions.
     graph: AlgebraicGraph,
+    /// The element of a named constant from which the value of each constant node was propagated,
+    /// if it is known.
+    ///
+    /// This is only used to refer to the constant by name in generated code. The nodes themselves
+    /// are plain [Value::Constant]s, so that equal constants share a node whatever their origin.
+    constant_names: BTreeMap<NodeIndex, ConstantAccess>,
 }
 impl Constraints {
     /// Constructs a new [Constraints] graph from the given parts
@@ -47,6 +54,7 @@ impl Constraints {
             graph,
             boundary_constraints,
             integrity_constraints,
+            constant_names: BTreeMap::new(),
         }
     }
 
@@ -98,15 +106,22 @@ impl Constraints {
         &self.integrity_constraints[trace_segment]
     }
 
-    /// Inserts a new constraint against `trace_segment`, using the provided `root` and `domain`
+    /// Inserts a new constraint against `trace_segment`, using the provided `root` and `domain`,
+    /// derived from the source constraint at `span`
     pub fn insert_constraint(
         &mut self,
         trace_segment: TraceSegmentId,
         root: NodeIndex,
         domain: ConstraintDomain,
+        span: SourceSpan,
     ) {
-        let root = ConstraintRoot::new(root, domain);
-        if domain.is_boundary() {
+        self.insert_constraint_root(trace_segment, ConstraintRoot::new(root, domain, span));
+    }
+
+    /// Inserts a new constraint against `trace_segment`, whose domain and other properties are
+    /// given by the provided [ConstraintRoot].
+    pub fn insert_constraint_root(&mut self, trace_segment: TraceSegmentId, root: ConstraintRoot) {
+        if root.domain().is_boundary() {
             if self.boundary_constraints.len() <= trace_segment {
                 self.boundary_constraints.resize(trace_segment + 1, vec![]);
             }
@@ -130,20 +145,106 @@ impl Constraints {
     pub fn graph_mut(&mut self) -> &mut AlgebraicGraph {
         &mut self.graph
     }
+
+    /// Returns the element of a named constant from which the value of the constant node at
+    /// `index` was propagated, if it is known.
+    ///
+    /// A node which is also used for a literal, or for the elements of several named constants, has
+    /// no name.
+    pub fn constant_name(&self, index: &NodeIndex) -> Option<&ConstantAccess> {
+        self.constant_names.get(index)
+    }
+
+    /// Records that the value of the constant node at `index` was propagated from the element of a
+    /// named constant described by `access`.
+    pub(crate) fn set_constant_name(&mut self, index: NodeIndex, access: ConstantAccess) {
+        self.constant_names.insert(index, access);
+    }
+
+    /// Replaces the underlying [AlgebraicGraph], using `remap` to map each node of the current
+    /// graph to its index in the new graph, or to `None` if it is not in the new graph, which is
+    /// only allowed for nodes which are not the root of a constraint.
+    ///
+    /// In debug builds, this validates the new graph and the remapped roots, as a safety net for
+    /// the passes which rebuild the graph.
+    pub(crate) fn replace_graph<F>(&mut self, graph: AlgebraicGraph, remap: F)
+    where
+        F: Fn(&NodeIndex) -> Option<NodeIndex>,
+    {
+        for root in self
+            .boundary_constraints
+            .iter_mut()
+            .chain(self.integrity_constraints.iter_mut())
+            .flatten()
+        {
+            root.index = remap(&root.index).expect("constraint roots must be in the new graph");
+            debug_assert!(
+                root.index.as_usize() < graph.num_nodes(),
+                "constraint root {root:?} is out of range of the new graph"
+            );
+        }
+        if cfg!(debug_assertions) {
+            if let Err(err) = graph.validate() {
+                panic!("invalid constraint graph: {err}");
+            }
+        }
+        self.constant_names = core::mem::take(&mut self.constant_names)
+            .into_iter()
+            .filter_map(|(index, access)| Some((remap(&index)?, access)))
+            .collect();
+        self.graph = graph;
+    }
+
+    /// Retains only the constraints for which `f` returns true, called with the trace segment of
+    /// each constraint, the boundary constraints of every segment first, followed by the
+    /// integrity constraints.
+    ///
+    /// The nodes which are only reachable from the removed constraints are left in the graph, to
+    /// be removed by [crate::passes::DeadCodeElimination].
+    pub(crate) fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(TraceSegmentId, &ConstraintRoot) -> bool,
+    {
+        for constraints in [
+            &mut self.boundary_constraints,
+            &mut self.integrity_constraints,
+        ] {
+            for (segment, roots) in constraints.iter_mut().enumerate() {
+                roots.retain(|root| f(segment, root));
+            }
+        }
+    }
 }
 
 /// A [ConstraintRoot] represents the entry node of a subgraph within the [AlgebraicGraph]
 /// representing a constraint. It also contains the [ConstraintDomain] for the constraint, which is
-/// the domain against which the constraint should be applied.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// the domain against which the constraint should be applied, the number of rows at the end of the
+/// trace on which it is exempted from being enforced, and the location of the constraint in the
+/// source program.
+#[derive(Debug, Clone, PartialEq, Eq, Spanned)]
 pub struct ConstraintRoot {
     index: NodeIndex,
     domain: ConstraintDomain,
+    exemptions: usize,
+    #[span]
+    span: SourceSpan,
 }
 impl ConstraintRoot {
-    /// Creates a new [ConstraintRoot] with the specified entry index and row offset.
-    pub const fn new(index: NodeIndex, domain: ConstraintDomain) -> Self {
-        Self { index, domain }
+    /// Creates a new [ConstraintRoot] with the specified entry index, row offset, and the span of
+    /// the constraint in the source program from which it was derived.
+    pub const fn new(index: NodeIndex, domain: ConstraintDomain, span: SourceSpan) -> Self {
+        Self {
+            index,
+            domain,
+            exemptions: 0,
+            span,
+        }
+    }
+
+    /// Exempts this constraint from being enforced on the last `exemptions` rows of the trace.
+    pub const fn with_exemptions(mut self, exemptions: usize) -> Self {
+        self.exemptions = exemptions;
+        self
     }
 
     /// Returns the index of the entry node of the subgraph representing the constraint.
@@ -156,6 +257,12 @@ impl ConstraintRoot {
     pub const fn domain(&self) -> ConstraintDomain {
         self.domain
     }
+
+    /// Returns the number of rows at the end of the trace on which this constraint is not
+    /// enforced, as declared with `except last`, or zero if no rows were exempted.
+    pub const fn exemptions(&self) -> usize {
+        self.exemptions
+    }
 }
 
 /// [ConstraintDomain] corresponds to the domain over which a constraint is applied.
@@ -193,6 +300,21 @@ impl ConstraintDomain {
         matches!(self, Self::EveryRow | Self::EveryFrame(_))
     }
 
+    /// Returns the degree of the divisor of a constraint enforced on this domain over a trace of
+    /// the specified length, except on its last `exemptions` rows.
+    ///
+    /// The divisor vanishes on each row on which the constraint is enforced, so its degree is the
+    /// number of those rows: a single row for boundary constraints, and every row of the trace
+    /// for integrity constraints, except for the exempted rows, and the last rows from which a
+    /// frame would extend beyond the end of the trace.
+    pub fn divisor_degree(&self, trace_length: usize, exemptions: usize) -> usize {
+        match self {
+            Self::FirstRow | Self::LastRow => 1,
+            Self::EveryRow => trace_length.saturating_sub(exemptions),
+            Self::EveryFrame(size) => trace_length.saturating_sub(exemptions.max(size - 1)),
+        }
+    }
+
     /// Returns a [ConstraintDomain] corresponding to the given row offset.
     ///
     /// * `offset == 0` corresponds to every row
@@ -6,7 +6,9 @@
 //! The original code is available in the Winterfell library in the `air` crate:
 //! https://github.com/novifinancial/winterfell/blob/main/air/src/air/transition/degree.rs
 
-use super::MIN_CYCLE_LENGTH;
+use std::collections::BTreeMap;
+
+use super::{BinaryOp, QualifiedIdentifier, MIN_CYCLE_LENGTH};
 
 /// Degree descriptor of an integrity constraint.
 ///
@@ -19,6 +21,12 @@ use super::MIN_CYCLE_LENGTH;
 pub struct IntegrityConstraintDegree {
     base: usize,
     cycles: Vec<usize>,
+    /// The cycle length of each periodic column accessed by the expression, which is only known
+    /// for the degrees computed from the constraint graph.
+    ///
+    /// The values of a periodic column are described by a single polynomial, so each column
+    /// contributes its cycle once to [Self::cycles], however many times it is accessed.
+    columns: BTreeMap<QualifiedIdentifier, usize>,
 }
 
 impl IntegrityConstraintDegree {
@@ -44,6 +52,7 @@ impl IntegrityConstraintDegree {
         Self {
             base: degree,
             cycles: vec![],
+            columns: BTreeMap::new(),
         }
     }
 
@@ -75,6 +84,159 @@ impl IntegrityConstraintDegree {
         Self {
             base: base_degree,
             cycles,
+            columns: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the degree of a constant expression, i.e. with a base of zero and no cycles.
+    ///
+    /// This is only a valid degree for a subexpression of a constraint, which is why it is not
+    /// public.
+    pub(crate) const fn constant() -> Self {
+        Self {
+            base: 0,
+            cycles: vec![],
+            columns: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the degree of an expression which is not a polynomial of the trace columns, such as
+    /// the inverse of a trace column, whose base is the largest possible one, so that it exceeds
+    /// any degree supported by a backend.
+    pub(crate) const fn unbounded() -> Self {
+        Self {
+            base: usize::MAX,
+            cycles: vec![],
+            columns: BTreeMap::new(),
+        }
+    }
+
+    /// Returns true if this is the degree of an expression which is not a polynomial of the trace
+    /// columns, see [Self::unbounded].
+    pub fn is_unbounded(&self) -> bool {
+        self.base == usize::MAX
+    }
+
+    /// Returns the degree of an access of the periodic column `name` with the specified cycle
+    /// length, which has a base of zero, see [Self::constant].
+    pub(crate) fn periodic(name: QualifiedIdentifier, cycle: usize) -> Self {
+        Self {
+            base: 0,
+            cycles: vec![cycle],
+            columns: BTreeMap::from([(name, cycle)]),
+        }
+    }
+
+    /// Returns the degree of the result of applying the binary operation `op` to expressions of
+    /// degree `self` and `other`.
+    ///
+    /// - For additions, subtractions and equalities, the base is the maximum of the bases of the
+    ///   operands.
+    /// - For multiplications, the base is the sum of the bases of the operands.
+    ///
+    /// In both cases, the cycles are the union of the cycles of the operands, as each periodic
+    /// column contributes its cycle once, even when the values of the column are multiplied. The
+    /// cycles of periodic columns which are not known by name, i.e. given to [Self::with_cycles],
+    /// are merged so that each cycle length appears as many times as it does in the operand in
+    /// which it appears the most.
+    ///
+    /// # Panics
+    /// Panics if `op` is an exponentiation, whose degree is computed by [Self::exp].
+    pub fn combined(&self, other: &Self, op: BinaryOp) -> Self {
+        let base = match op {
+            BinaryOp::Add | BinaryOp::Sub | BinaryOp::Eq => self.base.max(other.base),
+            BinaryOp::Mul => self.base.saturating_add(other.base),
+            BinaryOp::Exp | BinaryOp::NegExp => {
+                panic!("the degree of an exponentiation is computed by `exp`")
+            }
+        };
+
+        let mut columns = self.columns.clone();
+        columns.extend(other.columns.iter().map(|(name, cycle)| (*name, *cycle)));
+
+        let mut unnamed = self.unnamed_cycles();
+        let mut extra = other.unnamed_cycles();
+        for cycle in unnamed.iter() {
+            if let Some(pos) = extra.iter().position(|c| c == cycle) {
+                extra.remove(pos);
+            }
+        }
+        unnamed.extend(extra);
+
+        Self {
+            base,
+            cycles: columns.values().copied().chain(unnamed).collect(),
+            columns,
+        }
+    }
+
+    /// Returns the degree of an expression of degree `self` raised to the power `exp`, which is
+    /// the degree of the product of `exp` such expressions.
+    pub fn exp(&self, exp: usize) -> Self {
+        if exp == 0 {
+            return Self::constant();
+        }
+        Self {
+            base: self.base.saturating_mul(exp),
+            cycles: self.cycles.clone(),
+            columns: self.columns.clone(),
+        }
+    }
+
+    /// Returns the cycles of the periodic columns which are not known by name.
+    fn unnamed_cycles(&self) -> Vec<usize> {
+        let mut cycles = self.cycles.clone();
+        for cycle in self.columns.values() {
+            if let Some(pos) = cycles.iter().position(|c| c == cycle) {
+                cycles.remove(pos);
+            }
+        }
+        cycles
+    }
+
+    /// Returns the degree of the inverse of an expression of degree `self`.
+    ///
+    /// The inverse of a constant expression is constant, but the inverse of any other expression
+    /// is not a polynomial, so its degree is treated conservatively as [Self::unbounded].
+    pub fn inv(&self) -> Self {
+        if self.base == 0 && self.cycles.is_empty() {
+            Self::constant()
+        } else {
+            Self::unbounded()
+        }
+    }
+
+    /// Returns the degree of the polynomial described by this descriptor over an execution trace
+    /// of the specified length, see [Self::get_evaluation_degree].
+    pub fn effective(&self, trace_length: usize) -> usize {
+        self.get_evaluation_degree(trace_length)
+    }
+
+    /// Computes a degree to which this degree description expands in the context of execution
+    /// trace of the specified length.
+    ///
+    /// The expanded degree is computed as follows:
+    ///
+    /// $$
+    /// b \cdot (n - 1) + \sum_{i = 0}^{k - 1}{\frac{n \cdot (c_i - 1)}{c_i}}
+    /// $$
+    ///
+    /// where: $b$ is the base degree, $n$ is the `trace_length`, $c_i$ is a cycle length of
+    /// periodic column $i$, and $k$ is the total number of periodic columns for this degree
+    /// descriptor.
+    ///
+    /// Thus, evaluation degree of a transition constraint which involves multiplication of two
+    /// trace columns and one periodic column with a period length of 32 steps when evaluated over
+    /// an execution trace of 64 steps would be:
+    ///
+    /// $$
+    /// 2 \cdot (64 - 1) + \frac{64 \cdot (32 - 1)}{32} = 126 + 62 = 188
+    /// $$
+    pub fn get_evaluation_degree(&self, trace_length: usize) -> usize {
+        let mut result = self.base.saturating_mul(trace_length - 1);
+        for cycle_length in self.cycles.iter() {
+            result = result.saturating_add((trace_length / cycle_length) * (cycle_length - 1));
         }
+        result
     }
 }
@@ -0,0 +1,219 @@
+use crate::graph::{AlgebraicGraph, NodeIndex, Visit, VisitContext};
+
+use super::*;
+
+/// The canonical hash of an [Air], or of a subgraph of its [AlgebraicGraph]
+pub type CanonicalHash = [u8; 32];
+
+/// Computes a hash of an [Air] which depends only on its structure, and not on the order in which
+/// its nodes were inserted in the [AlgebraicGraph], nor on the order in which its constraints were
+/// declared.
+///
+/// Each node is hashed from its operation and the hashes of its operands, so that identical
+/// subgraphs hash identically wherever they are located in the graph. The constraints of each
+/// segment are then hashed as a set, by sorting the hashes of their roots.
+///
+/// Source locations are not part of the hash.
+///
+/// The nodes are hashed bottom-up by a post-order traversal of the graph, so that the hash of
+/// arbitrarily deep expressions is computed without recursion.
+pub(super) struct CanonicalHasher {
+    /// The hash of each node of the graph, as it is computed
+    nodes: Vec<Option<CanonicalHash>>,
+}
+impl CanonicalHasher {
+    pub fn new(graph: &AlgebraicGraph) -> Self {
+        Self {
+            nodes: vec![None; graph.num_nodes()],
+        }
+    }
+
+    /// Returns the canonical hash of the provided [Air]
+    pub fn hash_air(mut self, air: &Air) -> CanonicalHash {
+        let mut hasher = blake3::Hasher::new();
+        write_str(&mut hasher, air.name());
+
+        write_usize(&mut hasher, air.trace_segment_widths.len());
+        for width in air.trace_segment_widths.iter() {
+            write_usize(&mut hasher, *width as usize);
+        }
+
+        write_usize(&mut hasher, air.periodic_columns.len());
+        for (name, column) in air.periodic_columns.iter() {
+            write_str(&mut hasher, &name.to_string());
+            write_values(&mut hasher, &column.values);
+        }
+
+        write_usize(&mut hasher, air.constants.len());
+        for (name, constant) in air.constants.iter() {
+            write_str(&mut hasher, &name.to_string());
+            match &constant.value {
+                ConstantExpr::Scalar(value) => {
+                    write_tag(&mut hasher, 0);
+                    write_u64(&mut hasher, *value);
+                }
+                ConstantExpr::Vector(values) => {
+                    write_tag(&mut hasher, 1);
+                    write_values(&mut hasher, values);
+                }
+                ConstantExpr::Matrix(rows) => {
+                    write_tag(&mut hasher, 2);
+                    write_usize(&mut hasher, rows.len());
+                    for row in rows.iter() {
+                        write_values(&mut hasher, row);
+                    }
+                }
+            }
+        }
+
+        write_usize(&mut hasher, air.public_inputs.len());
+        for public_input in air.public_inputs.values() {
+            write_str(&mut hasher, public_input.name.as_str());
+            write_usize(&mut hasher, public_input.size);
+        }
+
+        write_usize(&mut hasher, air.num_random_values as usize);
+        write_usize(&mut hasher, air.num_random_values_per_stage.len());
+        for num_random_values in air.num_random_values_per_stage.iter() {
+            write_usize(&mut hasher, *num_random_values as usize);
+        }
+
+        self.visit_constraints(air);
+        for segment in 0..air.trace_segment_widths.len() {
+            let boundary_constraints = self.hash_constraints(air.boundary_constraints(segment));
+            hasher.update(&boundary_constraints);
+            let integrity_constraints = self.hash_constraints(air.integrity_constraints(segment));
+            hasher.update(&integrity_constraints);
+        }
+
+        hasher.finalize().into()
+    }
+
+    /// Hashes the provided constraints as a set, i.e. independently of their order
+    fn hash_constraints(&self, constraints: &[ConstraintRoot]) -> CanonicalHash {
+        let mut roots = constraints
+            .iter()
+            .map(|constraint| {
+                let mut hasher = blake3::Hasher::new();
+                match constraint.domain() {
+                    ConstraintDomain::FirstRow => write_tag(&mut hasher, 0),
+                    ConstraintDomain::LastRow => write_tag(&mut hasher, 1),
+                    ConstraintDomain::EveryRow => write_tag(&mut hasher, 2),
+                    ConstraintDomain::EveryFrame(size) => {
+                        write_tag(&mut hasher, 3);
+                        write_usize(&mut hasher, size);
+                    }
+                }
+                write_usize(&mut hasher, constraint.exemptions());
+                hasher.update(&self.hash_node(constraint.node_index()));
+                hasher.finalize().into()
+            })
+            .collect::<Vec<CanonicalHash>>();
+        roots.sort_unstable();
+
+        let mut hasher = blake3::Hasher::new();
+        write_usize(&mut hasher, roots.len());
+        for root in roots.iter() {
+            hasher.update(root);
+        }
+        hasher.finalize().into()
+    }
+
+    /// Returns the structural hash of the subgraph which has the specified node as its tip
+    ///
+    /// The node is expected to have been hashed by the traversal of the graph.
+    fn hash_node(&self, index: &NodeIndex) -> CanonicalHash {
+        self.nodes[index.as_usize()].expect("nodes are hashed before they are referenced")
+    }
+}
+impl VisitContext for CanonicalHasher {
+    /// Hashes the node from its operation and the hashes of its operands, which are visited first
+    fn visit(&mut self, graph: &AlgebraicGraph, node_index: NodeIndex) {
+        let mut hasher = blake3::Hasher::new();
+        match graph.node(&node_index).op() {
+            Operation::Value(value) => {
+                write_tag(&mut hasher, 0);
+                write_value(&mut hasher, value);
+            }
+            Operation::Add(lhs, rhs) => {
+                write_tag(&mut hasher, 1);
+                hasher.update(&self.hash_node(lhs));
+                hasher.update(&self.hash_node(rhs));
+            }
+            Operation::Sub(lhs, rhs) => {
+                write_tag(&mut hasher, 2);
+                hasher.update(&self.hash_node(lhs));
+                hasher.update(&self.hash_node(rhs));
+            }
+            Operation::Mul(lhs, rhs) => {
+                write_tag(&mut hasher, 3);
+                hasher.update(&self.hash_node(lhs));
+                hasher.update(&self.hash_node(rhs));
+            }
+            Operation::Exp(lhs, exponent) => {
+                write_tag(&mut hasher, 4);
+                hasher.update(&self.hash_node(lhs));
+                write_usize(&mut hasher, *exponent);
+            }
+            Operation::Inv(operand) => {
+                write_tag(&mut hasher, 5);
+                hasher.update(&self.hash_node(operand));
+            }
+        }
+
+        self.nodes[node_index.as_usize()] = Some(hasher.finalize().into());
+    }
+}
+
+fn write_value(hasher: &mut blake3::Hasher, value: &Value) {
+    match value {
+        Value::Constant(value) => {
+            write_tag(hasher, 0);
+            write_u64(hasher, *value);
+        }
+        Value::TraceAccess(access) => {
+            write_tag(hasher, 2);
+            write_usize(hasher, access.segment);
+            write_usize(hasher, access.column);
+            write_usize(hasher, access.row_offset);
+        }
+        Value::PeriodicColumn(access) => {
+            write_tag(hasher, 3);
+            write_str(hasher, &access.name.to_string());
+            write_usize(hasher, access.cycle);
+        }
+        Value::PublicInput(access) => {
+            write_tag(hasher, 4);
+            write_str(hasher, access.name.as_str());
+            write_usize(hasher, access.index);
+        }
+        Value::RandomValue(index) => {
+            write_tag(hasher, 5);
+            write_usize(hasher, *index);
+        }
+    }
+}
+
+fn write_tag(hasher: &mut blake3::Hasher, tag: u8) {
+    hasher.update(&[tag]);
+}
+
+fn write_values(hasher: &mut blake3::Hasher, values: &[u64]) {
+    write_usize(hasher, values.len());
+    for value in values.iter() {
+        write_u64(hasher, *value);
+    }
+}
+
+fn write_str(hasher: &mut blake3::Hasher, value: &str) {
+    write_usize(hasher, value.len());
+    hasher.update(value.as_bytes());
+}
+
+fn write_usize(hasher: &mut blake3::Hasher, value: usize) {
+    write_u64(hasher, value as u64);
+}
+
+fn write_u64(hasher: &mut blake3::Hasher, value: u64) {
+    hasher.update(&value.to_le_bytes());
+}
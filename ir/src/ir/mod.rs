@@ -1,19 +1,22 @@
 mod constraints;
 mod degree;
+mod hash;
 mod operation;
 mod trace;
 mod value;
 
 pub use self::constraints::{ConstraintDomain, ConstraintError, ConstraintRoot, Constraints};
 pub use self::degree::IntegrityConstraintDegree;
+pub use self::hash::CanonicalHash;
 pub use self::operation::Operation;
 pub use self::trace::TraceAccess;
-pub use self::value::{PeriodicColumnAccess, PublicInputAccess, Value};
+pub use self::value::{PeriodicColumnAccess, PublicInputAccess, RandomValueAccess, Value};
 
 pub use air_parser::{
     ast::{
-        AccessType, Boundary, Identifier, PeriodicColumn, PublicInput, QualifiedIdentifier,
-        TraceSegmentId,
+        AccessType, BinaryOp, Boundary, Constant, ConstantAccess, ConstantExpr, Identifier,
+        PeriodicColumn, PublicInput, QualifiedIdentifier, RandBinding, RandomValues, TraceBinding,
+        TraceSegment, TraceSegmentId,
     },
     Symbol,
 };
@@ -31,7 +34,7 @@ use std::collections::BTreeMap;
 
 use miden_diagnostics::{SourceSpan, Spanned};
 
-use crate::graph::AlgebraicGraph;
+use crate::graph::{AlgebraicGraph, NodeIndex};
 
 /// The intermediate representation of a complete AirScript program
 ///
@@ -48,16 +51,39 @@ pub struct Air {
     /// The widths (number of columns) of each segment of the trace, in segment order (i.e. the
     /// index in this vector matches the index of the segment in the program).
     pub trace_segment_widths: Vec<u16>,
+    /// The declarations of the columns of each segment of the trace, in segment order, which name
+    /// the columns of the trace.
+    ///
+    /// These are taken from the [air_parser::ast::Program], without the segments which have no
+    /// columns, so that they match [Air::trace_segment_widths].
+    pub trace_columns: Vec<TraceSegment>,
     /// The periodic columns referenced by this program.
     ///
     /// These are taken straight from the [air_parser::ast::Program] without modification.
     pub periodic_columns: BTreeMap<QualifiedIdentifier, PeriodicColumn>,
+    /// The named constants referenced by this program.
+    ///
+    /// These are taken straight from the [air_parser::ast::Program] without modification.
+    pub constants: BTreeMap<QualifiedIdentifier, Constant>,
+    /// The named constants declared in the root module of this program which are never referenced.
+    ///
+    /// These are taken straight from the [air_parser::ast::Program] without modification.
+    pub unused_constants: BTreeMap<QualifiedIdentifier, Constant>,
     /// The public inputs referenced by this program.
     ///
     /// These are taken straight from the [air_parser::ast::Program] without modification.
     pub public_inputs: BTreeMap<Identifier, PublicInput>,
     /// The total number of elements in the random values array
     pub num_random_values: u16,
+    /// The number of random values drawn in each stage, in order, which sum to
+    /// [Air::num_random_values].
+    ///
+    /// Programs which don't declare staged random values draw them all in a single stage.
+    pub num_random_values_per_stage: Vec<u16>,
+    /// The declaration of the random values array, which names its elements, if any.
+    ///
+    /// This is taken straight from the [air_parser::ast::Program] without modification.
+    pub random_values: Option<RandomValues>,
     /// The constraints enforced by this program, in their algebraic graph representation.
     pub constraints: Constraints,
 }
@@ -80,9 +106,14 @@ impl Air {
         Self {
             name,
             trace_segment_widths: vec![],
+            trace_columns: vec![],
             periodic_columns: Default::default(),
+            constants: Default::default(),
+            unused_constants: Default::default(),
             public_inputs: Default::default(),
             num_random_values: 0,
+            num_random_values_per_stage: vec![],
+            random_values: None,
             constraints: Default::default(),
         }
     }
@@ -93,8 +124,13 @@ impl Air {
         self.name.as_str()
     }
 
+    /// Returns the public inputs in the order in which they are declared, which is the order in
+    /// which the generated code lays them out, e.g. as the arguments of their constructor or as
+    /// the elements they are serialized to.
     pub fn public_inputs(&self) -> impl Iterator<Item = &PublicInput> + '_ {
-        self.public_inputs.values()
+        let mut public_inputs = self.public_inputs.values().collect::<Vec<_>>();
+        public_inputs.sort_by_key(|input| input.span.start());
+        public_inputs.into_iter()
     }
 
     pub fn periodic_columns(&self) -> impl Iterator<Item = &PeriodicColumn> + '_ {
@@ -124,7 +160,37 @@ impl Air {
         self.constraints.integrity_constraint_degrees(trace_segment)
     }
 
-    /// Return an [Iterator] over the validity constraints for the given trace segment
+    /// Return the integrity constraints for the given trace segment grouped by their
+    /// [IntegrityConstraintDegree], e.g. for backends which apply a different divisor to the
+    /// constraints of each degree.
+    ///
+    /// The groups are ordered by increasing base degree, then by their periodic cycles, and the
+    /// constraints of each group are in declaration order.
+    pub fn integrity_constraints_by_degree(
+        &self,
+        trace_segment: TraceSegmentId,
+    ) -> Vec<(IntegrityConstraintDegree, Vec<&ConstraintRoot>)> {
+        let constraints = self.constraints.integrity_constraints(trace_segment);
+        let degrees = self.constraints.integrity_constraint_degrees(trace_segment);
+
+        let mut groups: Vec<(IntegrityConstraintDegree, Vec<&ConstraintRoot>)> = vec![];
+        for (constraint, degree) in constraints.iter().zip(degrees) {
+            match groups
+                .iter_mut()
+                .find(|(group_degree, _)| *group_degree == degree)
+            {
+                Some((_, group)) => group.push(constraint),
+                None => groups.push((degree, vec![constraint])),
+            }
+        }
+        groups.sort_by(|(lhs, _), (rhs, _)| {
+            (lhs.base(), lhs.cycles()).cmp(&(rhs.base(), rhs.cycles()))
+        });
+        groups
+    }
+
+    /// Return an [Iterator] over the validity constraints for the given trace segment, i.e. the
+    /// integrity constraints which only reference the current row, in declaration order
     pub fn validity_constraints(
         &self,
         trace_segment: TraceSegmentId,
@@ -135,7 +201,8 @@ impl Air {
             .filter(|constraint| matches!(constraint.domain(), ConstraintDomain::EveryRow))
     }
 
-    /// Return an [Iterator] over the transition constraints for the given trace segment
+    /// Return an [Iterator] over the transition constraints for the given trace segment, i.e. the
+    /// integrity constraints which reference the next row, in declaration order
     pub fn transition_constraints(
         &self,
         trace_segment: TraceSegmentId,
@@ -146,12 +213,42 @@ impl Air {
             .filter(|constraint| matches!(constraint.domain(), ConstraintDomain::EveryFrame(_)))
     }
 
+    /// Returns a hash of this [Air] which is stable across compilations, suitable for caching the
+    /// output of code generation, or for checking two programs for equivalence.
+    ///
+    /// The hash is computed structurally from the constraint roots, so it is invariant to the
+    /// order in which nodes were inserted in the [AlgebraicGraph] and to the order in which the
+    /// constraints of a segment were declared. Source locations do not contribute to the hash.
+    pub fn canonical_hash(&self) -> CanonicalHash {
+        hash::CanonicalHasher::new(self.constraint_graph()).hash_air(self)
+    }
+
     /// Return a reference to the raw [AlgebraicGraph] corresponding to the constraints
     #[inline]
     pub fn constraint_graph(&self) -> &AlgebraicGraph {
         self.constraints.graph()
     }
 
+    /// Returns the element of a named constant from which the value of the constant node at
+    /// `index` was propagated, if it is known, see [Constraints::constant_name]
+    #[inline]
+    pub fn constant_name(&self, index: &NodeIndex) -> Option<&ConstantAccess> {
+        self.constraints.constant_name(index)
+    }
+
+    /// Returns the access through a named binding of the element of the random values array at
+    /// index `element`, if it is bound by one of the bindings of [Air::random_values].
+    ///
+    /// This is used by the backends to refer to random values by the name of their binding.
+    pub fn random_value_binding(&self, element: usize) -> Option<RandomValueAccess> {
+        self.random_values
+            .as_ref()?
+            .bindings
+            .iter()
+            .find(|binding| (binding.offset..binding.offset + binding.size).contains(&element))
+            .map(|binding| RandomValueAccess::new(binding.name, element - binding.offset, element))
+    }
+
     /// Return a mutable reference to the raw [AlgebraicGraph] corresponding to the constraints
     #[inline]
     pub fn constraint_graph_mut(&mut self) -> &mut AlgebraicGraph {
@@ -23,6 +23,11 @@ pub enum Operation {
     /// it may be possible to support non-constant exponents, but it is not
     /// supported at this time.
     Exp(NodeIndex, usize),
+    /// Evaluates to the field inverse of its operand (given as a node in the graph).
+    ///
+    /// This is only built from `a^(-n)` when the inverses are enabled with
+    /// [crate::passes::AstToAir::with_inverses], as not all backends support it.
+    Inv(NodeIndex),
 }
 impl Operation {
     /// Corresponds to the binding power of this [Operation]
@@ -4,7 +4,7 @@ use super::*;
 ///
 /// Values are either constant, or evaluated at runtime using the context
 /// provided to an AirScript program (i.e. random values, public inputs, etc.).
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 pub enum Value {
     /// A constant value.
     Constant(u64),
@@ -21,7 +21,7 @@ pub enum Value {
 }
 
 /// Represents an access of a [PeriodicColumn], similar in nature to [TraceAccess]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct PeriodicColumnAccess {
     pub name: QualifiedIdentifier,
     pub cycle: usize,
@@ -33,7 +33,7 @@ impl PeriodicColumnAccess {
 }
 
 /// Represents an access of a [PublicInput], similar in nature to [TraceAccess]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct PublicInputAccess {
     /// The name of the public input to access
     pub name: Identifier,
@@ -45,3 +45,26 @@ impl PublicInputAccess {
         Self { name, index }
     }
 }
+
+/// Represents an access of an element of the `random_values` array through one of its named
+/// bindings, e.g. `y[2]` with `random_values` declared as `alphas: [x, y[14], z]`
+///
+/// See [Air::random_value_binding].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RandomValueAccess {
+    /// The name of the binding to access
+    pub name: Identifier,
+    /// The index of the element in the binding, which is 0 for a binding of a single element
+    pub index: usize,
+    /// The index of the element in the `random_values` array
+    pub element: usize,
+}
+impl RandomValueAccess {
+    pub const fn new(name: Identifier, index: usize, element: usize) -> Self {
+        Self {
+            name,
+            index,
+            element,
+        }
+    }
+}
@@ -1,13 +1,23 @@
 mod codegen;
+mod diagnostics;
+mod flat;
 mod graph;
 mod ir;
 pub mod passes;
+mod pipeline;
+mod ssa;
 #[cfg(test)]
 mod tests;
 
 pub use self::codegen::CodeGenerator;
-pub use self::graph::{AlgebraicGraph, Node, NodeIndex};
+pub use self::diagnostics::{diagnostic_code, JsonDiagnostic, JsonEmitter, JsonLabel, JsonSpan};
+pub use self::flat::FlatAirScript;
+pub use self::graph::{
+    AlgebraicGraph, GraphError, Node, NodeIndex, ValueCollector, Visit, VisitContext,
+};
 pub use self::ir::*;
+pub use self::pipeline::{Pipeline, PipelineOptions};
+pub use self::ssa::SsaForm;
 
 use miden_diagnostics::{Diagnostic, ToDiagnostic};
 
@@ -22,13 +32,26 @@ pub enum CompileError {
     #[error("compilation failed, see diagnostics for more information")]
     Failed,
 }
+impl CompileError {
+    /// Returns the stable code identifying this kind of error in diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Parse(err) => err.code(),
+            Self::SemanticAnalysis(err) => err.code(),
+            Self::InvalidConstraint(_) => "E0400",
+            Self::Failed => "E0401",
+        }
+    }
+}
 impl ToDiagnostic for CompileError {
     fn to_diagnostic(self) -> Diagnostic {
-        match self {
+        let code = self.code();
+        let diagnostic = match self {
             Self::Parse(err) => err.to_diagnostic(),
             Self::SemanticAnalysis(err) => err.to_diagnostic(),
             Self::InvalidConstraint(err) => Diagnostic::error().with_message(err.to_string()),
             Self::Failed => Diagnostic::error().with_message(self.to_string()),
-        }
+        };
+        diagnostic.with_code(code)
     }
 }
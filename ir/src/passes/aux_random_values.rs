@@ -0,0 +1,62 @@
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{graph::ValueCollector, ir::*, CompileError, Visit};
+
+/// This pass reports the integrity constraints against auxiliary trace segments which do not
+/// reference any random value, as warnings.
+///
+/// The auxiliary segments are built from random values provided by the verifier, so a constraint
+/// which does not reference any of them is usually misplaced, and could be enforced against the
+/// main trace segment instead, e.g. by constraining the main trace columns it is derived from.
+///
+/// The segment of each constraint is the one inferred when it was inserted in the [Air], i.e.
+/// the latest segment of the values it references. Boundary constraints are not considered, as
+/// auxiliary columns are commonly initialized to constants.
+pub struct AuxRandomValues<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+}
+impl<'a> AuxRandomValues<'a> {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
+        Self { diagnostics }
+    }
+}
+impl<'p> Pass for AuxRandomValues<'p> {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        for segment in (0..air.trace_segment_widths.len()).filter(|segment| *segment != 0) {
+            for constraint in air.integrity_constraints(segment) {
+                let mut collector = ValueCollector::default();
+                collector.visit_postorder(air.constraint_graph(), [*constraint.node_index()]);
+                let random = collector
+                    .values()
+                    .iter()
+                    .any(|value| matches!(value, Value::RandomValue(_)));
+                if random {
+                    continue;
+                }
+                self.diagnostics
+                    .diagnostic(Severity::Warning)
+                    .with_message("auxiliary constraint without random values")
+                    .with_primary_label(
+                        constraint.span(),
+                        format!(
+                            "this constraint is enforced against the `{}` trace segment, but does not reference any random value",
+                            air.trace_columns[segment].name
+                        ),
+                    )
+                    .with_note(
+                        "Constraints which do not reference random values can usually be enforced against the main trace segment.",
+                    )
+                    .emit();
+            }
+        }
+
+        Ok(air)
+    }
+}
@@ -0,0 +1,188 @@
+use std::mem;
+
+use air_pass::Pass;
+
+use crate::{
+    graph::{AlgebraicGraph, NodeIndex},
+    ir::*,
+    CompileError, Visit, VisitContext,
+};
+
+use super::DeadCodeElimination;
+
+/// This pass rebuilds long chains of additions or multiplications of the constraint graph into
+/// balanced trees, so that the depth of a chain of `n` operands is reduced from `n - 1` to
+/// `ceil(log2(n))`, which bounds the recursion depth of the passes and code generators walking
+/// very large constraints.
+///
+/// Addition and multiplication are both associative and commutative in the field, so regrouping the
+/// operands of a chain preserves the value of the expression, as well as its degree. Subtraction
+/// is not associative, so it ends a chain. A chain only extends through the nodes which have no
+/// other parent than the next node of the chain, so that subexpressions shared with other
+/// expressions are left as they are, and the operands of a chain keep their order from left to
+/// right.
+///
+/// The balanced nodes are inserted in the graph like any other node, so they reuse identical
+/// existing nodes, and the nodes of the original chains are removed by [DeadCodeElimination].
+pub struct BalanceExpressions {
+    /// The minimum number of operands of a chain for it to be rebuilt
+    min_chain_length: usize,
+}
+impl Default for BalanceExpressions {
+    fn default() -> Self {
+        Self {
+            min_chain_length: Self::DEFAULT_MIN_CHAIN_LENGTH,
+        }
+    }
+}
+impl BalanceExpressions {
+    /// The default minimum number of operands of a chain for it to be rebuilt, below which the
+    /// depth of the chain is small enough to be left as written
+    pub const DEFAULT_MIN_CHAIN_LENGTH: usize = 8;
+
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only rebuilds the chains with at least the specified number of operands, which must be at
+    /// least 3, since a chain of 2 operands is already balanced.
+    pub fn with_min_chain_length(mut self, min_chain_length: usize) -> Self {
+        assert!(
+            min_chain_length >= 3,
+            "chains of less than 3 operands are already balanced"
+        );
+        self.min_chain_length = min_chain_length;
+        self
+    }
+}
+impl Pass for BalanceExpressions {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, mut air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let mut postorder = Postorder::default();
+        postorder.visit_constraints(&air);
+        let chained = chained_nodes(&air);
+
+        let mut graph = mem::take(air.constraint_graph_mut());
+        let mut remapped: Vec<Option<NodeIndex>> = vec![None; graph.num_nodes()];
+        let remap = |remapped: &[Option<NodeIndex>], index: &NodeIndex| {
+            remapped[index.as_usize()].expect("children must be visited before their parents")
+        };
+
+        for node_index in postorder.0 {
+            let op = *graph.node(&node_index).op();
+            let is_chain_root = matches!(op, Operation::Add(..) | Operation::Mul(..))
+                && !chained[node_index.as_usize()];
+            if is_chain_root {
+                let operands = chain_operands(&graph, &chained, node_index)
+                    .into_iter()
+                    .map(|operand| remap(&remapped, &operand))
+                    .collect::<Vec<_>>();
+                if operands.len() >= self.min_chain_length {
+                    remapped[node_index.as_usize()] = Some(balance(&mut graph, &op, &operands));
+                    continue;
+                }
+            }
+
+            let op = match op {
+                Operation::Value(value) => Operation::Value(value),
+                Operation::Add(lhs, rhs) => {
+                    Operation::Add(remap(&remapped, &lhs), remap(&remapped, &rhs))
+                }
+                Operation::Sub(lhs, rhs) => {
+                    Operation::Sub(remap(&remapped, &lhs), remap(&remapped, &rhs))
+                }
+                Operation::Mul(lhs, rhs) => {
+                    Operation::Mul(remap(&remapped, &lhs), remap(&remapped, &rhs))
+                }
+                Operation::Exp(lhs, rhs) => Operation::Exp(remap(&remapped, &lhs), rhs),
+                Operation::Inv(operand) => Operation::Inv(remap(&remapped, &operand)),
+            };
+            remapped[node_index.as_usize()] = Some(graph.insert_node(op));
+        }
+
+        air.constraints
+            .replace_graph(graph, |index| remapped[index.as_usize()]);
+
+        DeadCodeElimination::new().run(air)
+    }
+}
+
+/// A [VisitContext] which records the nodes reachable from the constraints in post-order.
+#[derive(Default)]
+struct Postorder(Vec<NodeIndex>);
+impl VisitContext for Postorder {
+    fn visit(&mut self, _graph: &AlgebraicGraph, node_index: NodeIndex) {
+        self.0.push(node_index);
+    }
+}
+
+/// Returns, for each node of the constraint graph, whether it is an inner node of a chain, i.e.
+/// whether it is the operand of a single node performing the same operation, and is not the root
+/// of a constraint.
+fn chained_nodes(air: &Air) -> Vec<bool> {
+    let graph = air.constraint_graph();
+    let mut uses = vec![0usize; graph.num_nodes()];
+    let mut parent_ops = vec![None; graph.num_nodes()];
+    for (index, node) in graph.nodes_indexed() {
+        for child in graph.children(&index).into_iter().flatten() {
+            uses[child.as_usize()] += 1;
+            parent_ops[child.as_usize()] = Some(mem::discriminant(node.op()));
+        }
+    }
+    for segment in 0..air.trace_segment_widths.len() {
+        for constraint in air
+            .boundary_constraints(segment)
+            .iter()
+            .chain(air.integrity_constraints(segment))
+        {
+            uses[constraint.node_index().as_usize()] += 1;
+        }
+    }
+
+    graph
+        .nodes_indexed()
+        .map(|(index, node)| {
+            matches!(node.op(), Operation::Add(..) | Operation::Mul(..))
+                && uses[index.as_usize()] == 1
+                && parent_ops[index.as_usize()] == Some(mem::discriminant(node.op()))
+        })
+        .collect()
+}
+
+/// Returns the operands of the chain rooted at the specified node, from left to right.
+fn chain_operands(graph: &AlgebraicGraph, chained: &[bool], root: NodeIndex) -> Vec<NodeIndex> {
+    let mut operands = vec![];
+    let mut stack = vec![root];
+    while let Some(index) = stack.pop() {
+        if index != root && !chained[index.as_usize()] {
+            operands.push(index);
+            continue;
+        }
+        // push the right operand first, so that the left operand is visited first
+        let [lhs, rhs] = graph.children(&index);
+        stack.extend(rhs);
+        stack.extend(lhs);
+    }
+    operands
+}
+
+/// Inserts a balanced tree of operations of the same kind as `op` over the provided operands,
+/// which are split in halves recursively, and returns the index of its root.
+fn balance(graph: &mut AlgebraicGraph, op: &Operation, operands: &[NodeIndex]) -> NodeIndex {
+    if let [operand] = operands {
+        return *operand;
+    }
+    let (lhs, rhs) = operands.split_at(operands.len() / 2);
+    let lhs = balance(graph, op, lhs);
+    let rhs = balance(graph, op, rhs);
+    match op {
+        Operation::Add(..) => graph.insert_node(Operation::Add(lhs, rhs)),
+        Operation::Mul(..) => graph.insert_node(Operation::Mul(lhs, rhs)),
+        _ => unreachable!("only additions and multiplications are balanced"),
+    }
+}
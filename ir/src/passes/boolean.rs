@@ -0,0 +1,124 @@
+use std::mem;
+
+use air_pass::Pass;
+
+use crate::{
+    graph::{AlgebraicGraph, NodeIndex},
+    ir::*,
+    CompileError,
+};
+
+use super::DeadCodeElimination;
+
+/// This pass rewrites the constraints which check that a value is boolean into a single canonical
+/// form, `a^2 - a`, so that equivalent checks written differently share the same nodes in the
+/// constraint graph, and boolean checks can be identified with [AlgebraicGraph::boolean_check].
+///
+/// The following forms are recognized, where `a` is any expression:
+///
+/// - `a^2 - a` and `a * a - a`
+/// - `a * (a - 1)` and `(a - 1) * a`
+/// - `a - a^2`, `a - a * a`, `a * (1 - a)` and `(1 - a) * a`, which are the negation of the forms
+///   above, and are equivalent to them as constraints
+///
+/// Each of them may also be compared to zero, e.g. `enf a * (a - 1) = 0`. Only the roots of
+/// constraints are rewritten, as the negated forms are not equivalent to the canonical form
+/// within a larger expression. Recognizing a form only looks at the few nodes at the root of a
+/// constraint, so its cost does not depend on the size of the constraint.
+#[derive(Default)]
+pub struct NormalizeBooleanChecks;
+impl NormalizeBooleanChecks {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+impl Pass for NormalizeBooleanChecks {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, mut air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let roots = (0..air.trace_segment_widths.len())
+            .flat_map(|segment| {
+                air.boundary_constraints(segment)
+                    .iter()
+                    .chain(air.integrity_constraints(segment))
+            })
+            .map(|constraint| *constraint.node_index())
+            .collect::<Vec<_>>();
+
+        let mut graph = mem::take(air.constraint_graph_mut());
+        // the canonical form of each root checking that a value is boolean, if any
+        let mut remapped = vec![None; graph.num_nodes()];
+        for root in roots {
+            if remapped[root.as_usize()].is_some() {
+                continue;
+            }
+            if let Some(operand) = boolean_check_operand(&graph, &root) {
+                let square = graph.insert_node(Operation::Exp(operand, 2));
+                let check = graph.insert_node(Operation::Sub(square, operand));
+                remapped[root.as_usize()] = Some(check);
+            }
+        }
+
+        air.constraints.replace_graph(graph, |index| {
+            Some(remapped[index.as_usize()].unwrap_or(*index))
+        });
+
+        DeadCodeElimination::new().run(air)
+    }
+}
+
+/// Returns the operand `a` of the constraint rooted at the specified node if it is any of the
+/// forms of a check that `a` is boolean recognized by [NormalizeBooleanChecks].
+fn boolean_check_operand(graph: &AlgebraicGraph, root: &NodeIndex) -> Option<NodeIndex> {
+    match *graph.node(root).op() {
+        // a constraint compared to zero, e.g. `enf a * (a - 1) = 0`
+        Operation::Sub(lhs, rhs) if is_constant(graph, &rhs, 0) => {
+            boolean_check_operand(graph, &lhs)
+        }
+        // a^2 - a, or a * a - a
+        Operation::Sub(lhs, rhs) if square_operand(graph, &lhs) == Some(rhs) => Some(rhs),
+        // a - a^2, or a - a * a
+        Operation::Sub(lhs, rhs) if square_operand(graph, &rhs) == Some(lhs) => Some(lhs),
+        // a * (a - 1), a * (1 - a), or the same with the operands swapped
+        Operation::Mul(lhs, rhs) => {
+            if complement_operand(graph, &rhs) == Some(lhs) {
+                Some(lhs)
+            } else if complement_operand(graph, &lhs) == Some(rhs) {
+                Some(rhs)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns `a` if the specified node is `a^2` or `a * a`.
+fn square_operand(graph: &AlgebraicGraph, index: &NodeIndex) -> Option<NodeIndex> {
+    match *graph.node(index).op() {
+        Operation::Exp(operand, 2) => Some(operand),
+        Operation::Mul(lhs, rhs) if lhs == rhs => Some(lhs),
+        _ => None,
+    }
+}
+
+/// Returns `a` if the specified node is `a - 1` or `1 - a`.
+fn complement_operand(graph: &AlgebraicGraph, index: &NodeIndex) -> Option<NodeIndex> {
+    match *graph.node(index).op() {
+        Operation::Sub(lhs, rhs) if is_constant(graph, &rhs, 1) => Some(lhs),
+        Operation::Sub(lhs, rhs) if is_constant(graph, &lhs, 1) => Some(rhs),
+        _ => None,
+    }
+}
+
+/// Returns true if the specified node is the provided constant.
+fn is_constant(graph: &AlgebraicGraph, index: &NodeIndex, value: u64) -> bool {
+    match graph.node(index).op() {
+        Operation::Value(Value::Constant(constant)) => *constant == value,
+        _ => false,
+    }
+}
@@ -0,0 +1,101 @@
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{ir::*, CompileError};
+
+/// This pass verifies that the quotient of every constraint by its divisor fits within the degree
+/// bound of the composition polynomial, for a given trace length and blowup factor, and reports
+/// each constraint which doesn't as an error.
+///
+/// The prover combines the quotients of all the constraints into the composition polynomial,
+/// which is evaluated over the low-degree extension of the trace domain, so its degree must be
+/// less than `n * b`, for a trace of length `n` and a blowup factor of `b`. The quotient of a
+/// constraint has the evaluation degree of its numerator over the trace, minus the degree of its
+/// divisor, which depends on the domain of the constraint: boundary constraints are divided by a
+/// polynomial of degree one, while integrity constraints are divided by the vanishing polynomial
+/// of the rows on which they are enforced, see [ConstraintDomain::divisor_degree].
+///
+/// Unlike [super::DegreeBudget], which warns about constraints approaching the maximum degree,
+/// this is a guard against constraints which would make the proofs invalid, so compilation fails
+/// once all the offending constraints are reported.
+pub struct CompositionDegree<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    trace_length: usize,
+    blowup_factor: usize,
+}
+impl<'a> CompositionDegree<'a> {
+    /// Create a new instance of this pass, targeting the provided trace length and blowup factor
+    ///
+    /// # Panics
+    /// Panics if the trace length is smaller than [MIN_CYCLE_LENGTH], or if the blowup factor is
+    /// smaller than two.
+    pub fn new(
+        diagnostics: &'a DiagnosticsHandler,
+        trace_length: usize,
+        blowup_factor: usize,
+    ) -> Self {
+        assert!(
+            trace_length >= MIN_CYCLE_LENGTH,
+            "trace length must be at least {MIN_CYCLE_LENGTH}, but was {trace_length}"
+        );
+        assert!(
+            blowup_factor >= 2,
+            "blowup factor must be at least 2, but was {blowup_factor}"
+        );
+        Self {
+            diagnostics,
+            trace_length,
+            blowup_factor,
+        }
+    }
+}
+impl<'p> Pass for CompositionDegree<'p> {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let trace_length = self.trace_length;
+        let bound = trace_length * self.blowup_factor - 1;
+
+        let mut failed = false;
+        for segment in 0..air.trace_segment_widths.len() {
+            let constraints = air
+                .boundary_constraints(segment)
+                .iter()
+                .chain(air.integrity_constraints(segment));
+            for constraint in constraints {
+                let numerator = air
+                    .constraint_graph()
+                    .degree(constraint.node_index())
+                    .get_evaluation_degree(trace_length);
+                let divisor = constraint
+                    .domain()
+                    .divisor_degree(trace_length, constraint.exemptions());
+                let quotient = numerator.saturating_sub(divisor);
+                if quotient <= bound {
+                    continue;
+                }
+
+                failed = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("constraint quotient exceeds the degree bound of the composition polynomial")
+                    .with_primary_label(
+                        constraint.span(),
+                        format!(
+                            "the numerator of this constraint has degree {numerator} and its divisor degree {divisor}, so its quotient has degree {quotient}, which exceeds the bound of {bound} for a trace of length {trace_length} and a blowup factor of {}",
+                            self.blowup_factor
+                        ),
+                    )
+                    .emit();
+            }
+        }
+
+        if failed {
+            Err(CompileError::Failed)
+        } else {
+            Ok(air)
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{ir::*, CompileError};
+
+/// This pass verifies that the number of constraints applied against each trace segment, i.e. its
+/// boundary and integrity constraints, is within the limit supported by a target backend, and
+/// reports each segment which exceeds its limit as an error.
+///
+/// Some proving backends cap the number of constraints they can evaluate, so this catches the AIRs
+/// which would not fit such a backend at compile time, rather than when generating proofs.
+pub struct ConstraintLimit<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    limits: Vec<Option<usize>>,
+}
+impl<'a> ConstraintLimit<'a> {
+    /// Create a new instance of this pass, where `limits[i]` is the maximum number of constraints
+    /// of the trace segment `i`, if any. Segments without a corresponding limit are unbounded.
+    pub fn new(diagnostics: &'a DiagnosticsHandler, limits: Vec<Option<usize>>) -> Self {
+        Self {
+            diagnostics,
+            limits,
+        }
+    }
+}
+impl<'p> Pass for ConstraintLimit<'p> {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let mut failed = false;
+        for (segment, limit) in self.limits.iter().enumerate() {
+            let Some(limit) = *limit else {
+                continue;
+            };
+            let mut constraints = air
+                .boundary_constraints(segment)
+                .iter()
+                .chain(air.integrity_constraints(segment));
+            let count = constraints.clone().count();
+            // the first constraint over the limit is reported
+            let Some(constraint) = constraints.nth(limit) else {
+                continue;
+            };
+
+            failed = true;
+            let name = air.trace_columns[segment].name;
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("too many constraints")
+                .with_primary_label(
+                    constraint.span(),
+                    format!("this constraint exceeds the limit of {limit} constraints of the {name} trace segment"),
+                )
+                .with_secondary_label(
+                    air.trace_columns[segment].span(),
+                    format!("{count} constraints are applied against this segment"),
+                )
+                .emit();
+        }
+
+        if failed {
+            Err(CompileError::Failed)
+        } else {
+            Ok(air)
+        }
+    }
+}
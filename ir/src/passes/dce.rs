@@ -0,0 +1,68 @@
+use std::mem;
+
+use air_pass::Pass;
+
+use crate::{
+    graph::{AlgebraicGraph, Node, NodeIndex},
+    ir::*,
+    CompileError, Visit, VisitContext,
+};
+
+/// This pass removes the nodes of the constraint graph which are not reachable from the root of
+/// any constraint, and compacts the remaining nodes.
+///
+/// Reachable nodes are visited in post-order, so the children of a node have always been moved to
+/// the compacted graph by the time the node itself is visited, and its operands can be remapped
+/// directly.
+#[derive(Default)]
+pub struct DeadCodeElimination {
+    /// The nodes of the compacted graph
+    nodes: Vec<Node>,
+    /// The index of each node of the original graph in the compacted graph, if reachable
+    remapped: Vec<Option<NodeIndex>>,
+}
+impl DeadCodeElimination {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the index in the compacted graph of a node which has already been visited
+    fn remap(&self, index: &NodeIndex) -> NodeIndex {
+        self.remapped[index.as_usize()].expect("children must be visited before their parents")
+    }
+}
+impl Pass for DeadCodeElimination {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, mut air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        self.nodes.clear();
+        self.remapped = vec![None; air.constraint_graph().num_nodes()];
+
+        self.visit_constraints(&air);
+
+        let graph = AlgebraicGraph::new(mem::take(&mut self.nodes));
+        air.constraints
+            .replace_graph(graph, |index| self.remapped[index.as_usize()]);
+
+        Ok(air)
+    }
+}
+impl VisitContext for DeadCodeElimination {
+    fn visit(&mut self, graph: &AlgebraicGraph, node_index: NodeIndex) {
+        let op = match graph.node(&node_index).op() {
+            Operation::Value(value) => Operation::Value(*value),
+            Operation::Add(lhs, rhs) => Operation::Add(self.remap(lhs), self.remap(rhs)),
+            Operation::Sub(lhs, rhs) => Operation::Sub(self.remap(lhs), self.remap(rhs)),
+            Operation::Mul(lhs, rhs) => Operation::Mul(self.remap(lhs), self.remap(rhs)),
+            Operation::Exp(lhs, rhs) => Operation::Exp(self.remap(lhs), *rhs),
+            Operation::Inv(operand) => Operation::Inv(self.remap(operand)),
+        };
+        let index = self.nodes.len();
+        self.nodes.push(Node::new(op));
+        self.remapped[node_index.as_usize()] = Some(NodeIndex::default() + index);
+    }
+}
@@ -0,0 +1,109 @@
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{ir::*, CompileError};
+
+/// This pass reports the integrity constraints whose degree approaches or exceeds the maximum
+/// degree supported by a target blowup factor, as warnings.
+///
+/// A blowup factor of `b` supports constraints of degree up to `b - 1`. The degree of a constraint
+/// is measured in units of the degree of a trace column, i.e. as its evaluation degree over a
+/// trace of length `n`, divided by `n - 1`, so that each periodic column contributes the degree of
+/// its polynomial for each of its cycles in the trace. Periodic columns contribute the most on the
+/// shortest trace accommodating all of them, which is assumed unless a longer trace length is
+/// provided.
+///
+/// Constraints using at least [DegreeBudget::WARNING_THRESHOLD] percent of the maximum degree are
+/// reported, so that authors can look for cheaper formulations before exceeding it.
+pub struct DegreeBudget<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    blowup_factor: usize,
+    trace_length: Option<usize>,
+}
+impl<'a> DegreeBudget<'a> {
+    /// The percentage of the maximum degree from which the degree of a constraint is reported
+    pub const WARNING_THRESHOLD: usize = 80;
+
+    /// Create a new instance of this pass, targeting the provided blowup factor
+    ///
+    /// # Panics
+    /// Panics if the blowup factor is smaller than two, as it would support no constraint.
+    pub fn new(diagnostics: &'a DiagnosticsHandler, blowup_factor: usize) -> Self {
+        assert!(
+            blowup_factor >= 2,
+            "blowup factor must be at least 2, but was {blowup_factor}"
+        );
+        Self {
+            diagnostics,
+            blowup_factor,
+            trace_length: None,
+        }
+    }
+
+    /// Sets the length of the trace over which the contribution of periodic columns to the degree
+    /// of constraints is computed.
+    ///
+    /// It is ignored if it is shorter than a periodic column.
+    pub fn with_trace_length(mut self, trace_length: usize) -> Self {
+        self.trace_length = Some(trace_length);
+        self
+    }
+}
+impl<'p> Pass for DegreeBudget<'p> {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let trace_length = air
+            .periodic_columns()
+            .map(|column| column.values.len())
+            .chain(self.trace_length)
+            .fold(MIN_CYCLE_LENGTH, usize::max);
+        let max_degree = self.blowup_factor - 1;
+        let budget = max_degree * (trace_length - 1);
+
+        for segment in 0..air.trace_segment_widths.len() {
+            for constraint in air.integrity_constraints(segment) {
+                let degree = air
+                    .constraint_graph()
+                    .degree(constraint.node_index())
+                    .get_evaluation_degree(trace_length);
+                let percent = degree * 100 / budget;
+                if percent < Self::WARNING_THRESHOLD {
+                    continue;
+                }
+
+                let message = if degree > budget {
+                    "constraint degree exceeds the maximum supported by the target blowup factor"
+                } else {
+                    "constraint degree approaches the maximum supported by the target blowup factor"
+                };
+                self.diagnostics
+                    .diagnostic(Severity::Warning)
+                    .with_message(message)
+                    .with_primary_label(
+                        constraint.span(),
+                        format!(
+                            "this constraint has degree {}, which is {percent}% of the maximum degree of {max_degree} supported by a blowup factor of {}",
+                            format_degree(degree, trace_length),
+                            self.blowup_factor
+                        ),
+                    )
+                    .emit();
+            }
+        }
+
+        Ok(air)
+    }
+}
+
+/// Formats an evaluation degree over a trace of the provided length in units of the degree of a
+/// trace column, with two decimals if it is not a whole number.
+fn format_degree(degree: usize, trace_length: usize) -> String {
+    if degree % (trace_length - 1) == 0 {
+        format!("{}", degree / (trace_length - 1))
+    } else {
+        format!("{:.2}", degree as f64 / (trace_length - 1) as f64)
+    }
+}
@@ -1,6 +1,28 @@
+mod aux_random_values;
+mod balance;
+mod boolean;
+mod composition_degree;
+mod constraint_limit;
+mod dce;
+mod degree_budget;
+mod next_row;
+mod scalar_multiples;
+mod subtraction;
 mod translate;
+mod unused;
 
+pub use self::aux_random_values::AuxRandomValues;
+pub use self::balance::BalanceExpressions;
+pub use self::boolean::NormalizeBooleanChecks;
+pub use self::composition_degree::CompositionDegree;
+pub use self::constraint_limit::ConstraintLimit;
+pub use self::dce::DeadCodeElimination;
+pub use self::degree_budget::DegreeBudget;
+pub use self::next_row::NextRowOnlyColumns;
+pub use self::scalar_multiples::MergeScalarMultiples;
+pub use self::subtraction::{LowerSubtraction, RaiseSubtraction, MINUS_ONE};
 pub use self::translate::AstToAir;
+pub use self::unused::UnusedDeclarations;
 
 use air_pass::Pass;
 
@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{graph::ValueCollector, ir::*, CompileError, Visit};
+
+/// This pass reports the trace columns which the integrity constraints only ever reference at a
+/// row offset, e.g. as `a'`, but never in the current row, as warnings.
+///
+/// Such a column is usually a sign of a missing reference to its current row, as the value of a
+/// column in the next row is normally constrained with respect to values in the current row.
+///
+/// The references are found by collecting the values of the integrity constraints, which keep
+/// the row offset of each trace access. Boundary constraints are not considered, as they
+/// constrain the first and last rows of a column rather than its current row.
+pub struct NextRowOnlyColumns<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+}
+impl<'a> NextRowOnlyColumns<'a> {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
+        Self { diagnostics }
+    }
+}
+impl<'p> Pass for NextRowOnlyColumns<'p> {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let mut collector = ValueCollector::default();
+        let roots = (0..air.trace_segment_widths.len())
+            .flat_map(|segment| air.integrity_constraints(segment))
+            .map(|constraint| *constraint.node_index());
+        collector.visit_postorder(air.constraint_graph(), roots);
+
+        // the (segment, column) pairs referenced in the current row, and at a row offset
+        let mut current = BTreeSet::new();
+        let mut offset = BTreeSet::new();
+        for value in collector.values() {
+            if let Value::TraceAccess(access) = value {
+                let column = (access.segment, access.column);
+                if access.row_offset == 0 {
+                    current.insert(column);
+                } else {
+                    offset.insert(column);
+                }
+            }
+        }
+
+        for segment in air.trace_columns.iter() {
+            for binding in segment.bindings.iter() {
+                for index in 0..binding.size {
+                    let column = (segment.id, binding.offset + index);
+                    if !offset.contains(&column) || current.contains(&column) {
+                        continue;
+                    }
+                    let name = match binding.name {
+                        Some(name) if binding.size == 1 => name.to_string(),
+                        Some(name) => format!("{name}[{index}]"),
+                        None => format!("{}[{}]", segment.name, column.1),
+                    };
+                    self.diagnostics
+                        .diagnostic(Severity::Warning)
+                        .with_message("trace column only referenced in the next row")
+                        .with_primary_label(
+                            binding.span(),
+                            format!(
+                                "`{name}` is only referenced at a row offset by the integrity constraints"
+                            ),
+                        )
+                        .with_note(
+                            "The value of a column in the next row is usually constrained with respect to values in the current row.",
+                        )
+                        .emit();
+                }
+            }
+        }
+
+        Ok(air)
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{
+    graph::{AlgebraicGraph, NodeIndex},
+    ir::*,
+    CompileError,
+};
+
+use super::DeadCodeElimination;
+
+/// This pass removes the constraints which are a nonzero constant multiple of another constraint
+/// of the same trace segment, domain and exemptions, e.g. `3 * (a - b)` when `a - b` is also
+/// enforced, and reports each of them as a warning.
+///
+/// Such constraints are satisfied by exactly the same traces, so only the first of them is kept.
+/// The ratio of two constraints is folded by stripping the constant factors of their roots, along
+/// with the comparisons to zero and the order of the operands of a subtraction, which only
+/// changes the sign. This mostly helps with machine-generated programs, as well as with the
+/// constraints duplicated by evaluators.
+pub struct MergeScalarMultiples<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+}
+impl<'a> MergeScalarMultiples<'a> {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
+        Self { diagnostics }
+    }
+}
+impl<'p> Pass for MergeScalarMultiples<'p> {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, mut air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let graph = air.constraint_graph();
+        let segments = 0..air.trace_segment_widths.len();
+
+        // the constraints are visited in the order expected by `Constraints::retain`
+        let roots = segments
+            .clone()
+            .flat_map(|segment| {
+                air.boundary_constraints(segment)
+                    .iter()
+                    .map(move |root| (segment, root))
+            })
+            .chain(segments.flat_map(|segment| {
+                air.integrity_constraints(segment)
+                    .iter()
+                    .map(move |root| (segment, root))
+            }));
+
+        let mut kept = BTreeMap::new();
+        let mut retained = vec![];
+        for (segment, root) in roots {
+            let key = (
+                segment,
+                root.domain(),
+                root.exemptions(),
+                Base::of(graph, root.node_index()),
+            );
+            match kept.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(root.span());
+                    retained.push(true);
+                }
+                Entry::Occupied(entry) => {
+                    self.diagnostics
+                        .diagnostic(Severity::Warning)
+                        .with_message("redundant constraint")
+                        .with_primary_label(
+                            root.span(),
+                            "this constraint is a scalar multiple of another constraint, and is removed",
+                        )
+                        .with_secondary_label(*entry.get(), "which is enforced here")
+                        .emit();
+                    retained.push(false);
+                }
+            }
+        }
+
+        if retained.iter().all(|retained| *retained) {
+            return Ok(air);
+        }
+
+        let mut retained = retained.into_iter();
+        air.constraints
+            .retain(|_, _| retained.next().expect("every constraint was visited"));
+
+        DeadCodeElimination::new().run(air)
+    }
+}
+
+/// The expression which remains of a constraint once its constant factors are stripped, which is
+/// the same for two constraints if one is a scalar multiple of the other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Base {
+    /// Any other expression, by the index of its node
+    Node(usize),
+    /// The subtraction of two nodes, by the indices of its operands in increasing order, as
+    /// `a - b` is `-1` times `b - a`
+    Difference(usize, usize),
+}
+impl Base {
+    /// Returns the base of the expression rooted at `index`
+    fn of(graph: &AlgebraicGraph, index: &NodeIndex) -> Self {
+        match *graph.node(index).op() {
+            Operation::Sub(lhs, rhs) if constant(graph, &rhs) == Some(0) => Self::of(graph, &lhs),
+            Operation::Mul(lhs, rhs) if nonzero(graph, &lhs) => Self::of(graph, &rhs),
+            Operation::Mul(lhs, rhs) if nonzero(graph, &rhs) => Self::of(graph, &lhs),
+            Operation::Sub(lhs, rhs) => {
+                let (lhs, rhs) = (lhs.as_usize(), rhs.as_usize());
+                Self::Difference(lhs.min(rhs), lhs.max(rhs))
+            }
+            _ => Self::Node(index.as_usize()),
+        }
+    }
+}
+
+/// Returns the value of the specified node, if it is a constant
+fn constant(graph: &AlgebraicGraph, index: &NodeIndex) -> Option<u64> {
+    match graph.node(index).op() {
+        Operation::Value(Value::Constant(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Returns true if the specified node is a nonzero constant, i.e. a factor which can be divided
+/// out of a constraint
+fn nonzero(graph: &AlgebraicGraph, index: &NodeIndex) -> bool {
+    matches!(constant(graph, index), Some(value) if value != 0)
+}
@@ -0,0 +1,131 @@
+use std::mem;
+
+use air_pass::Pass;
+
+use crate::{
+    graph::{AlgebraicGraph, NodeIndex},
+    ir::*,
+    CompileError,
+};
+
+use super::DeadCodeElimination;
+
+/// The field element `-1`, i.e. `modulus - 1` in the 64-bit field targeted by the code generators.
+pub const MINUS_ONE: u64 = 0xFFFFFFFF00000000;
+
+/// This pass rewrites every subtraction `a - b` of the constraint graph into the addition of the
+/// negation of its right operand, `a + (-1 * b)`, where `-1` is the constant [MINUS_ONE], so that
+/// the constraints only use additions and multiplications.
+///
+/// This is useful to backends without subtraction, as well as to the passes which are simpler
+/// when additions are the only linear operation, e.g. [super::BalanceExpressions] treats a
+/// subtraction as the end of a chain of additions. The rewrite preserves the value and the degree
+/// of the constraints, and is undone by [RaiseSubtraction] for the backends which do have
+/// subtraction.
+#[derive(Default)]
+pub struct LowerSubtraction;
+impl LowerSubtraction {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+impl Pass for LowerSubtraction {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        rewrite(air, |graph, op| match op {
+            Operation::Sub(lhs, rhs) => {
+                let minus_one = graph.insert_node(Operation::Value(Value::Constant(MINUS_ONE)));
+                let negated = graph.insert_node(Operation::Mul(minus_one, rhs));
+                Operation::Add(lhs, negated)
+            }
+            op => op,
+        })
+    }
+}
+
+/// This pass is the inverse of [LowerSubtraction], it rewrites every addition of a negated
+/// operand, `a + (-1 * b)`, back into the subtraction `a - b`.
+#[derive(Default)]
+pub struct RaiseSubtraction;
+impl RaiseSubtraction {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+impl Pass for RaiseSubtraction {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        rewrite(air, |graph, op| match op {
+            Operation::Add(lhs, rhs) => match negated_operand(graph, &rhs) {
+                Some(operand) => Operation::Sub(lhs, operand),
+                None => op,
+            },
+            op => op,
+        })
+    }
+}
+
+/// Rebuilds the constraint graph of `air`, replacing each operation by the one returned by
+/// `rewrite`, which is called with the operands of the operation already rewritten.
+fn rewrite<F>(mut air: Air, mut rewrite: F) -> Result<Air, CompileError>
+where
+    F: FnMut(&mut AlgebraicGraph, Operation) -> Operation,
+{
+    let mut graph = mem::take(air.constraint_graph_mut());
+    let ops = graph
+        .nodes_indexed()
+        .map(|(_, node)| *node.op())
+        .collect::<Vec<_>>();
+
+    // children always precede their parents, so they are remapped first
+    let mut remapped: Vec<NodeIndex> = Vec::with_capacity(ops.len());
+    for op in ops {
+        let op = match op {
+            Operation::Value(value) => Operation::Value(value),
+            Operation::Add(lhs, rhs) => {
+                Operation::Add(remapped[lhs.as_usize()], remapped[rhs.as_usize()])
+            }
+            Operation::Sub(lhs, rhs) => {
+                Operation::Sub(remapped[lhs.as_usize()], remapped[rhs.as_usize()])
+            }
+            Operation::Mul(lhs, rhs) => {
+                Operation::Mul(remapped[lhs.as_usize()], remapped[rhs.as_usize()])
+            }
+            Operation::Exp(lhs, rhs) => Operation::Exp(remapped[lhs.as_usize()], rhs),
+            Operation::Inv(operand) => Operation::Inv(remapped[operand.as_usize()]),
+        };
+        let op = rewrite(&mut graph, op);
+        remapped.push(graph.insert_node(op));
+    }
+
+    air.constraints
+        .replace_graph(graph, |index| Some(remapped[index.as_usize()]));
+
+    DeadCodeElimination::new().run(air)
+}
+
+/// Returns `b` if the specified node is `-1 * b`.
+fn negated_operand(graph: &AlgebraicGraph, index: &NodeIndex) -> Option<NodeIndex> {
+    match *graph.node(index).op() {
+        Operation::Mul(lhs, rhs) if is_minus_one(graph, &lhs) => Some(rhs),
+        _ => None,
+    }
+}
+
+/// Returns true if the specified node is the constant [MINUS_ONE].
+fn is_minus_one(graph: &AlgebraicGraph, index: &NodeIndex) -> bool {
+    matches!(
+        graph.node(index).op(),
+        Operation::Value(Value::Constant(MINUS_ONE))
+    )
+}
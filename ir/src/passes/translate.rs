@@ -1,20 +1,45 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use air_parser::ast;
 use air_pass::Pass;
 
-use miden_diagnostics::{DiagnosticsHandler, Severity, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
 
 use crate::{graph::NodeIndex, ir::*, CompileError};
 
 pub struct AstToAir<'a> {
     diagnostics: &'a DiagnosticsHandler,
+    inverses: bool,
+    integrity_public_inputs: bool,
 }
 impl<'a> AstToAir<'a> {
     /// Create a new instance of this pass
     #[inline]
     pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
-        Self { diagnostics }
+        Self {
+            diagnostics,
+            inverses: false,
+            integrity_public_inputs: false,
+        }
+    }
+
+    /// Enables the field inverses, i.e. the translation of `a^(-n)` to [Operation::Inv].
+    ///
+    /// They are disabled by default, as not all backends support them, in which case negative
+    /// exponents are rejected.
+    pub fn with_inverses(mut self, inverses: bool) -> Self {
+        self.inverses = inverses;
+        self
+    }
+
+    /// Enables the accesses to public inputs in integrity constraints, in which they are
+    /// row-invariant values, i.e. of degree 0.
+    ///
+    /// They are disabled by default, as public inputs usually only make sense against the first or
+    /// last row of the trace, in which case such accesses are rejected.
+    pub fn with_integrity_public_inputs(mut self, integrity_public_inputs: bool) -> Self {
+        self.integrity_public_inputs = integrity_public_inputs;
+        self
     }
 }
 impl<'p> Pass for AstToAir<'p> {
@@ -29,28 +54,66 @@ impl<'p> Pass for AstToAir<'p> {
         let trace_columns = program.trace_columns;
         let boundary_constraints = program.boundary_constraints;
         let integrity_constraints = program.integrity_constraints;
+        let assertions = program.assertions;
 
-        air.trace_segment_widths = trace_columns.iter().map(|ts| ts.size as u16).collect();
+        // an aux segment declared without columns is dropped, so that backends never have to
+        // handle a zero-width segment
+        air.trace_columns = trace_columns
+            .iter()
+            .filter(|ts| ts.id == 0 || ts.size > 0)
+            .cloned()
+            .collect();
+        air.trace_segment_widths = air.trace_columns.iter().map(|ts| ts.size as u16).collect();
         air.num_random_values = random_values.as_ref().map(|rv| rv.size as u16).unwrap_or(0);
+        air.num_random_values_per_stage = random_values
+            .as_ref()
+            .map(|rv| {
+                rv.stage_sizes()
+                    .into_iter()
+                    .map(|size| size as u16)
+                    .collect()
+            })
+            .unwrap_or_default();
+        air.random_values = random_values.clone();
         air.periodic_columns = program.periodic_columns;
+        air.constants = program.constants;
+        air.unused_constants = program.unused_constants;
         air.public_inputs = program.public_inputs;
 
         let mut builder = AirBuilder {
             diagnostics: self.diagnostics,
+            inverses: self.inverses,
+            integrity_public_inputs: self.integrity_public_inputs,
+            in_integrity_constraints: false,
             air: &mut air,
             random_values,
             trace_columns,
+            constant_accesses: program.constant_accesses,
+            constant_names: Default::default(),
             bindings: Default::default(),
+            exemptions: 0,
         };
 
+        for assertion in assertions.iter() {
+            builder.check_assertion(assertion)?;
+        }
+
         for bc in boundary_constraints.iter() {
             builder.build_boundary_constraint(bc)?;
         }
 
+        builder.in_integrity_constraints = true;
         for bc in integrity_constraints.iter() {
             builder.build_integrity_constraint(bc)?;
         }
 
+        let constant_names = builder.constant_names;
+        for (index, access) in constant_names {
+            if let Some(access) = access {
+                air.constraints.set_constant_name(index, access);
+            }
+        }
+
         Ok(air)
     }
 }
@@ -67,20 +130,62 @@ enum MemoizedBinding {
 
 struct AirBuilder<'a> {
     diagnostics: &'a DiagnosticsHandler,
+    /// Whether negative exponents are translated to field inverses
+    inverses: bool,
+    /// Whether public inputs may be accessed in integrity constraints
+    integrity_public_inputs: bool,
+    /// Whether the constraints currently being built are integrity constraints
+    in_integrity_constraints: bool,
     air: &'a mut Air,
     random_values: Option<ast::RandomValues>,
     trace_columns: Vec<ast::TraceSegment>,
+    constant_accesses: BTreeMap<SourceSpan, ast::ConstantAccess>,
+    /// The element of a named constant from which each constant node was propagated, or `None` if
+    /// the node is also used for a literal, or for the elements of several named constants
+    constant_names: BTreeMap<NodeIndex, Option<ast::ConstantAccess>>,
     bindings: HashMap<Identifier, MemoizedBinding>,
+    /// The number of rows exempted from the integrity constraints currently being built
+    exemptions: usize,
 }
 impl<'a> AirBuilder<'a> {
+    /// Checks that both sides of a compile-time assertion, which have been folded to constants,
+    /// are equal.
+    fn check_assertion(&self, assertion: &ast::Assertion) -> Result<(), CompileError> {
+        match (&assertion.lhs, &assertion.rhs) {
+            (ast::ScalarExpr::Const(lhs), ast::ScalarExpr::Const(rhs)) if lhs.item == rhs.item => {
+                Ok(())
+            }
+            (ast::ScalarExpr::Const(lhs), ast::ScalarExpr::Const(rhs)) => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("assertion failed")
+                    .with_primary_label(
+                        assertion.span(),
+                        format!("the left-hand side evaluates to {lhs}, but the right-hand side evaluates to {rhs}"),
+                    )
+                    .emit();
+                Err(CompileError::Failed)
+            }
+            (ast::ScalarExpr::Const(_), expr) | (expr, _) => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid assertion")
+                    .with_primary_label(expr.span(), "this is not a constant expression")
+                    .with_note("Assertions are evaluated when the program is compiled, so both of their sides must be constant expressions.")
+                    .emit();
+                Err(CompileError::Failed)
+            }
+        }
+    }
+
     fn build_boundary_constraint(&mut self, bc: &ast::Statement) -> Result<(), CompileError> {
         match bc {
             ast::Statement::Enforce(ast::ScalarExpr::Binary(ast::BinaryExpr {
+                span,
                 op: ast::BinaryOp::Eq,
                 ref lhs,
                 ref rhs,
-                ..
-            })) => self.build_boundary_equality(lhs, rhs),
+            })) => self.build_boundary_equality(*span, lhs, rhs),
             ast::Statement::Let(expr) => {
                 self.build_let(expr, |bldr, stmt| bldr.build_boundary_constraint(stmt))
             }
@@ -101,23 +206,34 @@ impl<'a> AirBuilder<'a> {
     fn build_integrity_constraint(&mut self, bc: &ast::Statement) -> Result<(), CompileError> {
         match bc {
             ast::Statement::Enforce(ast::ScalarExpr::Binary(ast::BinaryExpr {
+                span,
                 op: ast::BinaryOp::Eq,
                 ref lhs,
                 ref rhs,
-                ..
-            })) => self.build_integrity_equality(lhs, rhs, None),
+            })) => self.build_integrity_equality(*span, lhs, rhs, None),
             ast::Statement::EnforceIf(
                 ast::ScalarExpr::Binary(ast::BinaryExpr {
+                    span,
                     op: ast::BinaryOp::Eq,
                     ref lhs,
                     ref rhs,
-                    ..
                 }),
                 ref condition,
-            ) => self.build_integrity_equality(lhs, rhs, Some(condition)),
+            ) => self.build_integrity_equality(*span, lhs, rhs, Some(condition)),
             ast::Statement::Let(expr) => {
                 self.build_let(expr, |bldr, stmt| bldr.build_integrity_constraint(stmt))
             }
+            ast::Statement::Exempt(expr) => {
+                // Nested exemptions apply the largest number of exempted rows
+                let exemptions = self.exemptions;
+                self.exemptions = exemptions.max(expr.rows);
+                let result = expr
+                    .body
+                    .iter()
+                    .try_for_each(|stmt| self.build_integrity_constraint(stmt));
+                self.exemptions = exemptions;
+                result
+            }
             invalid => {
                 self.diagnostics
                     .diagnostic(Severity::Bug)
@@ -176,7 +292,7 @@ impl<'a> AirBuilder<'a> {
                     let mut nodes = vec![];
                     for value in values.iter().cloned() {
                         let value = value.try_into().unwrap();
-                        nodes.push(self.insert_scalar_expr(&value));
+                        nodes.push(self.insert_scalar_expr(&value)?);
                     }
                     self.bindings
                         .insert(expr.name, MemoizedBinding::Vector(nodes));
@@ -197,7 +313,7 @@ impl<'a> AirBuilder<'a> {
                                     let access = ast::ScalarExpr::SymbolAccess(
                                         access.access(AccessType::Index(i)).unwrap(),
                                     );
-                                    let node = self.insert_scalar_expr(&access);
+                                    let node = self.insert_scalar_expr(&access)?;
                                     cols.push(node);
                                 }
                                 nodes.push(cols);
@@ -206,7 +322,7 @@ impl<'a> AirBuilder<'a> {
                                 let mut cols = vec![];
                                 for elem in elems.iter().cloned() {
                                     let elem: ast::ScalarExpr = elem.try_into().unwrap();
-                                    let node = self.insert_scalar_expr(&elem);
+                                    let node = self.insert_scalar_expr(&elem)?;
                                     cols.push(node);
                                 }
                                 nodes.push(cols);
@@ -223,12 +339,12 @@ impl<'a> AirBuilder<'a> {
                 let values = values
                     .iter()
                     .map(|vs| vs.iter().map(|v| self.insert_scalar_expr(v)).collect())
-                    .collect();
+                    .collect::<Result<_, _>>()?;
                 self.bindings
                     .insert(expr.name, MemoizedBinding::Matrix(values));
             }
             ast::Expr::Binary(ref bexpr) => {
-                let value = self.insert_binary_expr(bexpr);
+                let value = self.insert_binary_expr(bexpr)?;
                 self.bindings
                     .insert(expr.name, MemoizedBinding::Scalar(value));
             }
@@ -236,6 +352,7 @@ impl<'a> AirBuilder<'a> {
                 match self.bindings.get(access.name.as_ref()) {
                     None => {
                         // Must be a reference to a declaration
+                        self.check_public_input_access(access)?;
                         let value = self.insert_symbol_access(access);
                         self.bindings
                             .insert(expr.name, MemoizedBinding::Scalar(value));
@@ -284,6 +401,7 @@ impl<'a> AirBuilder<'a> {
 
     fn build_boundary_equality(
         &mut self,
+        span: SourceSpan,
         lhs: &ast::ScalarExpr,
         rhs: &ast::ScalarExpr,
     ) -> Result<(), CompileError> {
@@ -326,7 +444,7 @@ impl<'a> AirBuilder<'a> {
 
         let lhs = self.insert_op(Operation::Value(Value::TraceAccess(trace_access)));
         // Insert the right-hand expression into the graph
-        let rhs = self.insert_scalar_expr(rhs);
+        let rhs = self.insert_scalar_expr(rhs)?;
         // Compare the inferred trace segment and domain of the operands
         let domain = access.boundary.into();
         {
@@ -361,20 +479,24 @@ impl<'a> AirBuilder<'a> {
         // Store the generated constraint
         self.air
             .constraints
-            .insert_constraint(trace_access.segment, root, domain);
+            .insert_constraint(trace_access.segment, root, domain, span);
 
         Ok(())
     }
 
     fn build_integrity_equality(
         &mut self,
+        span: SourceSpan,
         lhs: &ast::ScalarExpr,
         rhs: &ast::ScalarExpr,
         condition: Option<&ast::ScalarExpr>,
     ) -> Result<(), CompileError> {
-        let lhs = self.insert_scalar_expr(lhs);
-        let rhs = self.insert_scalar_expr(rhs);
-        let condition = condition.as_ref().map(|cond| self.insert_scalar_expr(cond));
+        let lhs = self.insert_scalar_expr(lhs)?;
+        let rhs = self.insert_scalar_expr(rhs)?;
+        let condition = condition
+            .as_ref()
+            .map(|cond| self.insert_scalar_expr(cond))
+            .transpose()?;
         let root = self.merge_equal_exprs(lhs, rhs, condition);
         // Get the trace segment and domain of the constraint.
         //
@@ -384,9 +506,10 @@ impl<'a> AirBuilder<'a> {
             .constraint_graph()
             .node_details(&root, ConstraintDomain::EveryRow)?;
         // Save the constraint information
+        let root = ConstraintRoot::new(root, domain, span).with_exemptions(self.exemptions);
         self.air
             .constraints
-            .insert_constraint(trace_segment, root, domain);
+            .insert_constraint_root(trace_segment, root);
 
         Ok(())
     }
@@ -405,32 +528,60 @@ impl<'a> AirBuilder<'a> {
         }
     }
 
-    fn insert_scalar_expr(&mut self, expr: &ast::ScalarExpr) -> NodeIndex {
+    fn insert_scalar_expr(&mut self, expr: &ast::ScalarExpr) -> Result<NodeIndex, CompileError> {
         match expr {
-            ast::ScalarExpr::Const(value) => {
-                self.insert_op(Operation::Value(Value::Constant(value.item)))
+            ast::ScalarExpr::Const(value) => match self.constant_accesses.get(&value.span()) {
+                // preserve the provenance of values propagated from named constants
+                Some(access) if access.value == value.item => {
+                    Ok(self.insert_named_constant(*access))
+                }
+                _ => Ok(self.insert_constant(value.item)),
+            },
+            ast::ScalarExpr::SymbolAccess(access) => {
+                self.check_public_input_access(access)?;
+                Ok(self.insert_symbol_access(access))
             }
-            ast::ScalarExpr::SymbolAccess(access) => self.insert_symbol_access(access),
             ast::ScalarExpr::Binary(expr) => self.insert_binary_expr(expr),
             ast::ScalarExpr::Call(_) | ast::ScalarExpr::BoundedSymbolAccess(_) => unreachable!(),
         }
     }
 
-    fn insert_binary_expr(&mut self, expr: &ast::BinaryExpr) -> NodeIndex {
-        if expr.op == ast::BinaryOp::Exp {
-            let lhs = self.insert_scalar_expr(expr.lhs.as_ref());
+    fn insert_binary_expr(&mut self, expr: &ast::BinaryExpr) -> Result<NodeIndex, CompileError> {
+        if expr.op.is_exp() {
+            let lhs = self.insert_scalar_expr(expr.lhs.as_ref())?;
             let ast::ScalarExpr::Const(rhs) = expr.rhs.as_ref() else {
                 unreachable!();
             };
-            return self.insert_op(Operation::Exp(lhs, rhs.item as usize));
+            if expr.op == ast::BinaryOp::Exp {
+                return Ok(self.insert_op(Operation::Exp(lhs, rhs.item as usize)));
+            }
+
+            if !self.inverses {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid expression")
+                    .with_primary_label(
+                        expr.span(),
+                        "negative exponents denote field inverses, which are not enabled",
+                    )
+                    .with_note("Field inverses are not supported by all backends, so they must be enabled explicitly.")
+                    .emit();
+                return Err(CompileError::Failed);
+            }
+            // `a^(-n)` is the inverse of `a^n`
+            let operand = match rhs.item {
+                1 => lhs,
+                exp => self.insert_op(Operation::Exp(lhs, exp as usize)),
+            };
+            return Ok(self.insert_op(Operation::Inv(operand)));
         }
 
-        let lhs = self.insert_scalar_expr(expr.lhs.as_ref());
-        let rhs = self.insert_scalar_expr(expr.rhs.as_ref());
+        let lhs = self.insert_scalar_expr(expr.lhs.as_ref())?;
+        let rhs = self.insert_scalar_expr(expr.rhs.as_ref())?;
         match expr.op {
-            ast::BinaryOp::Add => self.insert_op(Operation::Add(lhs, rhs)),
-            ast::BinaryOp::Sub => self.insert_op(Operation::Sub(lhs, rhs)),
-            ast::BinaryOp::Mul => self.insert_op(Operation::Mul(lhs, rhs)),
+            ast::BinaryOp::Add => Ok(self.insert_op(Operation::Add(lhs, rhs))),
+            ast::BinaryOp::Sub => Ok(self.insert_op(Operation::Sub(lhs, rhs))),
+            ast::BinaryOp::Mul => Ok(self.insert_op(Operation::Mul(lhs, rhs))),
             _ => unreachable!(),
         }
     }
@@ -460,7 +611,7 @@ impl<'a> AirBuilder<'a> {
                 // the random values array (generally the case), or the names of trace segments (e.g. `$main`)
                 if id.is_special() {
                     if let Some(rv) = self.random_value_access(access) {
-                        return self.insert_op(Operation::Value(Value::RandomValue(rv)));
+                        return self.insert_op(Operation::Value(rv));
                     }
 
                     // Must be a trace segment name
@@ -482,7 +633,7 @@ impl<'a> AirBuilder<'a> {
                 }
 
                 if let Some(random_value) = self.random_value_access(access) {
-                    return self.insert_op(Operation::Value(Value::RandomValue(random_value)));
+                    return self.insert_op(Operation::Value(random_value));
                 }
 
                 if let Some(public_input) = self.public_input_access(access) {
@@ -523,31 +674,64 @@ impl<'a> AirBuilder<'a> {
         }
     }
 
-    fn random_value_access(&self, access: &ast::SymbolAccess) -> Option<usize> {
+    /// Checks that `access` is not an access to a public input from an integrity constraint,
+    /// unless those have been enabled, see [AstToAir::with_integrity_public_inputs].
+    fn check_public_input_access(&self, access: &ast::SymbolAccess) -> Result<(), CompileError> {
+        if !self.in_integrity_constraints
+            || self.integrity_public_inputs
+            || !self.air.public_inputs.contains_key(access.name.as_ref())
+        {
+            return Ok(());
+        }
+
+        self.diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("invalid access")
+            .with_primary_label(access.span(), "cannot access public input here")
+            .with_note("It is not allowed to access public inputs in integrity constraints, unless they are enabled as row-invariant values.")
+            .emit();
+        Err(CompileError::Failed)
+    }
+
+    /// Returns the value of an access of the random values array, whether it is accessed directly,
+    /// through one of its stages, or through one of its bindings
+    fn random_value_access(&self, access: &ast::SymbolAccess) -> Option<Value> {
         let rv = self.random_values.as_ref()?;
         let id = access.name.as_ref();
         if rv.name == id {
             if let AccessType::Index(index) = access.access_type {
                 assert!(index < rv.size);
-                return Some(index);
+                return Some(Value::RandomValue(index));
             } else {
                 // This should have been caught earlier during compilation
                 unreachable!("invalid access to random values array: {:#?}", access);
             }
         }
 
+        // An access of a stage is an access of its slice of the random values array
+        if let Some(stage) = rv.stages.iter().find(|stage| stage.name == id) {
+            if let AccessType::Index(index) = access.access_type {
+                assert!(index < stage.size);
+                return Some(Value::RandomValue(stage.offset + index));
+            } else {
+                // This should have been caught earlier during compilation
+                unreachable!("invalid access to random values stage: {:#?}", access);
+            }
+        }
+
         // This must be a reference to a binding, if it is a random value access
         let binding = rv.bindings.iter().find(|rb| rb.name == id)?;
 
-        match access.access_type {
-            AccessType::Default if binding.size == 1 => Some(binding.offset),
-            AccessType::Index(extra) if binding.size > 1 => Some(binding.offset + extra),
+        let index = match access.access_type {
+            AccessType::Default if binding.size == 1 => 0,
+            AccessType::Index(index) if binding.size > 1 => index,
             // This should have been caught earlier during compilation
             _ => unreachable!(
                 "unexpected random value access type encountered during lowering: {:#?}",
                 access
             ),
-        }
+        };
+        Some(Value::RandomValue(binding.offset + index))
     }
 
     fn public_input_access(&self, access: &ast::SymbolAccess) -> Option<PublicInputAccess> {
@@ -613,7 +797,24 @@ impl<'a> AirBuilder<'a> {
     }
 
     fn insert_constant(&mut self, value: u64) -> NodeIndex {
-        self.insert_op(Operation::Value(Value::Constant(value)))
+        let index = self.insert_op(Operation::Value(Value::Constant(value)));
+        self.constant_names.insert(index, None);
+        index
+    }
+
+    /// Adds the value of an element of a named constant to the graph, and records the element it
+    /// was propagated from, unless its node is shared with another value.
+    fn insert_named_constant(&mut self, access: ast::ConstantAccess) -> NodeIndex {
+        let index = self.insert_op(Operation::Value(Value::Constant(access.value)));
+        self.constant_names
+            .entry(index)
+            .and_modify(|name| {
+                if *name != Some(access) {
+                    *name = None;
+                }
+            })
+            .or_insert(Some(access));
+        index
     }
 
     fn insert_constants(&mut self, values: &[u64]) -> Vec<NodeIndex> {
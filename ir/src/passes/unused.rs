@@ -0,0 +1,68 @@
+use std::collections::BTreeSet;
+
+use air_pass::Pass;
+use miden_diagnostics::{DiagnosticsHandler, Severity, Spanned};
+
+use crate::{graph::ValueCollector, ir::*, CompileError, Visit};
+
+/// This pass reports the named constants and public inputs declared in the root module of a
+/// program which are never referenced, as warnings.
+///
+/// Public inputs are found to be referenced by collecting the values of the constraint graph, as
+/// they are never folded away. Constants, on the other hand, are usually folded into other values
+/// by constant propagation, so unreferenced constants are identified during semantic analysis,
+/// and are reported from [Air::unused_constants].
+///
+/// Unused public inputs are especially worth reporting, as they are still part of the interface
+/// of the verifier.
+pub struct UnusedDeclarations<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+}
+impl<'a> UnusedDeclarations<'a> {
+    /// Create a new instance of this pass
+    #[inline]
+    pub fn new(diagnostics: &'a DiagnosticsHandler) -> Self {
+        Self { diagnostics }
+    }
+}
+impl<'p> Pass for UnusedDeclarations<'p> {
+    type Input<'a> = Air;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, air: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let mut collector = ValueCollector::default();
+        collector.visit_constraints(&air);
+        let referenced = collector
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                Value::PublicInput(access) => Some(access.name),
+                _ => None,
+            })
+            .collect::<BTreeSet<_>>();
+
+        for constant in air.unused_constants.values() {
+            self.diagnostics
+                .diagnostic(Severity::Warning)
+                .with_message("unused constant")
+                .with_primary_label(constant.span(), "this constant is never referenced")
+                .emit();
+        }
+
+        for public_input in air.public_inputs() {
+            if !referenced.contains(&public_input.name) {
+                self.diagnostics
+                    .diagnostic(Severity::Warning)
+                    .with_message("unused public input")
+                    .with_primary_label(
+                        public_input.span(),
+                        "this public input is never referenced by any constraint",
+                    )
+                    .emit();
+            }
+        }
+
+        Ok(air)
+    }
+}
@@ -0,0 +1,250 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use air_parser::{ast, transforms, Params};
+use air_pass::{Pass, PhaseTimings};
+use miden_diagnostics::{CodeMap, DiagnosticsHandler};
+
+use crate::{passes, Air, CompileError};
+
+/// The options of a [Pipeline], which select the values bound to the parameters of the compiled
+/// programs, the language features they may use, and the optional passes run on them.
+///
+/// All of the optional features and passes are disabled by default.
+#[derive(Debug, Default, Clone)]
+pub struct PipelineOptions {
+    /// The values bound to the parameters of the program, see [air_parser::parse_with_params]
+    pub params: Params,
+    /// Enables the field inverses, see [passes::AstToAir::with_inverses]
+    pub inverses: bool,
+    /// Permits accesses to public inputs in integrity constraints, see
+    /// [passes::AstToAir::with_integrity_public_inputs]
+    pub integrity_public_inputs: bool,
+    /// Runs the [passes::NormalizeBooleanChecks] pass
+    pub normalize_boolean_checks: bool,
+    /// Runs the [passes::MergeScalarMultiples] pass
+    pub merge_scalar_multiples: bool,
+    /// Runs the [passes::BalanceExpressions] pass
+    pub balance_expressions: bool,
+    /// Runs the [passes::DegreeBudget] pass against the given blowup factor
+    pub blowup_factor: Option<usize>,
+    /// Runs the [passes::CompositionDegree] pass against the given trace length and the blowup
+    /// factor given in [Self::blowup_factor], which is only run if both are set
+    pub trace_length: Option<usize>,
+    /// Runs the [passes::ConstraintLimit] pass against the given maximum number of constraints of
+    /// each trace segment, unless it is empty
+    pub constraint_limits: Vec<Option<usize>>,
+    /// Runs the [passes::AuxRandomValues] pass
+    pub check_aux_random_values: bool,
+}
+
+/// The sequence of passes compiling an AirScript program to an [Air], which is shared by all of
+/// the frontends of the compiler, e.g. the `airc` CLI and the `air!` macro, so that a program
+/// compiles to the same [Air] whichever way it is compiled.
+///
+/// The program is lowered to an [Air] by constant propagation, inlining, and translation, after
+/// which the unused declarations and the trace columns only referenced in the next row are
+/// reported. The optional passes selected by the [PipelineOptions] are then run in turn.
+///
+/// The time spent in each phase is recorded in the [PhaseTimings] of the pipeline.
+pub struct Pipeline<'a> {
+    diagnostics: &'a DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    options: PipelineOptions,
+    timings: PhaseTimings,
+}
+impl<'a> Pipeline<'a> {
+    /// Creates a new pipeline with the provided options
+    pub fn new(
+        diagnostics: &'a DiagnosticsHandler,
+        codemap: Arc<CodeMap>,
+        options: PipelineOptions,
+    ) -> Self {
+        Self {
+            diagnostics,
+            codemap,
+            options,
+            timings: PhaseTimings::new(),
+        }
+    }
+
+    /// Returns the time spent in each phase run by this pipeline so far, which is only recorded
+    /// with the `metrics` feature of `air-pass`
+    #[inline]
+    pub fn timings(&self) -> &PhaseTimings {
+        &self.timings
+    }
+
+    /// Parses the provided source, binding the parameters of the program to the values in the
+    /// options of this pipeline
+    pub fn parse(&self, source: &str) -> Result<ast::Program, CompileError> {
+        self.timings
+            .time("parse", || {
+                air_parser::parse_with_params(
+                    self.diagnostics,
+                    self.codemap.clone(),
+                    source,
+                    &self.options.params,
+                )
+            })
+            .map_err(CompileError::Parse)
+    }
+
+    /// Parses the file at the provided path, binding the parameters of the program to the values
+    /// in the options of this pipeline
+    pub fn parse_file<P: AsRef<Path>>(&self, path: P) -> Result<ast::Program, CompileError> {
+        self.timings
+            .time("parse", || {
+                air_parser::parse_file_with_params(
+                    self.diagnostics,
+                    self.codemap.clone(),
+                    path,
+                    &self.options.params,
+                )
+            })
+            .map_err(CompileError::Parse)
+    }
+
+    /// Parses the provided source, which may define several root modules, binding the parameters
+    /// of each of them to the values in the options of this pipeline
+    pub fn parse_programs(&self, source: &str) -> Result<ast::Programs, CompileError> {
+        self.timings
+            .time("parse", || {
+                air_parser::parse_programs_with_params(
+                    self.diagnostics,
+                    self.codemap.clone(),
+                    source,
+                    &self.options.params,
+                )
+            })
+            .map_err(CompileError::Parse)
+    }
+
+    /// Parses the file at the provided path, which may define several root modules, binding the
+    /// parameters of each of them to the values in the options of this pipeline
+    pub fn parse_programs_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<ast::Programs, CompileError> {
+        self.timings
+            .time("parse", || {
+                air_parser::parse_programs_file_with_params(
+                    self.diagnostics,
+                    self.codemap.clone(),
+                    path,
+                    &self.options.params,
+                )
+            })
+            .map_err(CompileError::Parse)
+    }
+
+    /// Parses and compiles the provided source to an [Air]
+    pub fn compile(&mut self, source: &str) -> Result<Air, CompileError> {
+        let program = self.parse(source)?;
+        self.run(program)
+    }
+
+    /// Parses and compiles the file at the provided path to an [Air]
+    pub fn compile_file<P: AsRef<Path>>(&mut self, path: P) -> Result<Air, CompileError> {
+        let program = self.parse_file(path)?;
+        self.run(program)
+    }
+
+    /// Parses the provided source, which may define several root modules, and compiles each of
+    /// them to an independent [Air], keyed by the name of its root module
+    pub fn compile_programs(
+        &mut self,
+        source: &str,
+    ) -> Result<BTreeMap<String, Air>, CompileError> {
+        let programs = self.parse_programs(source)?;
+        self.run_programs(programs)
+    }
+
+    /// Parses the file at the provided path, which may define several root modules, and compiles
+    /// each of them to an independent [Air], keyed by the name of its root module
+    pub fn compile_programs_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<BTreeMap<String, Air>, CompileError> {
+        let programs = self.parse_programs_file(path)?;
+        self.run_programs(programs)
+    }
+
+    fn run_programs(
+        &mut self,
+        programs: ast::Programs,
+    ) -> Result<BTreeMap<String, Air>, CompileError> {
+        programs
+            .into_iter()
+            .map(|(name, program)| Ok((name.as_str().to_string(), self.run(program)?)))
+            .collect()
+    }
+}
+impl<'p> Pass for Pipeline<'p> {
+    type Input<'a> = ast::Program;
+    type Output<'a> = Air;
+    type Error = CompileError;
+
+    fn run<'a>(&mut self, program: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let diagnostics = self.diagnostics;
+        let options = &self.options;
+        let timings = &self.timings;
+
+        let mut lowering = transforms::ConstantPropagation::new(diagnostics)
+            .timed("constant_propagation", timings)
+            .chain(transforms::Inlining::new(diagnostics).timed("inlining", timings))
+            .chain(
+                passes::AstToAir::new(diagnostics)
+                    .with_inverses(options.inverses)
+                    .with_integrity_public_inputs(options.integrity_public_inputs)
+                    .timed("ast_to_air", timings),
+            )
+            .chain(
+                passes::UnusedDeclarations::new(diagnostics).timed("unused_declarations", timings),
+            )
+            .chain(
+                passes::NextRowOnlyColumns::new(diagnostics)
+                    .timed("next_row_only_columns", timings),
+            );
+        let mut air = lowering.run(program)?;
+
+        if options.normalize_boolean_checks {
+            air = passes::NormalizeBooleanChecks::new()
+                .timed("normalize_boolean_checks", timings)
+                .run(air)?;
+        }
+        if options.merge_scalar_multiples {
+            air = passes::MergeScalarMultiples::new(diagnostics)
+                .timed("merge_scalar_multiples", timings)
+                .run(air)?;
+        }
+        if options.balance_expressions {
+            air = passes::BalanceExpressions::new()
+                .timed("balance_expressions", timings)
+                .run(air)?;
+        }
+        if let Some(blowup_factor) = options.blowup_factor {
+            air = passes::DegreeBudget::new(diagnostics, blowup_factor)
+                .timed("degree_budget", timings)
+                .run(air)?;
+        }
+        if let (Some(trace_length), Some(blowup_factor)) =
+            (options.trace_length, options.blowup_factor)
+        {
+            air = passes::CompositionDegree::new(diagnostics, trace_length, blowup_factor)
+                .timed("composition_degree", timings)
+                .run(air)?;
+        }
+        if !options.constraint_limits.is_empty() {
+            air = passes::ConstraintLimit::new(diagnostics, options.constraint_limits.clone())
+                .timed("constraint_limit", timings)
+                .run(air)?;
+        }
+        if options.check_aux_random_values {
+            air = passes::AuxRandomValues::new(diagnostics)
+                .timed("aux_random_values", timings)
+                .run(air)?;
+        }
+
+        Ok(air)
+    }
+}
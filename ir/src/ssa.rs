@@ -0,0 +1,115 @@
+use std::fmt::Write;
+
+use crate::{
+    flat, graph::NodeIndex, Air, AlgebraicGraph, CodeGenerator, ConstraintDomain, ConstraintRoot,
+    Operation, Value, Visit, VisitContext,
+};
+
+/// This code generator emits the constraint graph of an [Air] as a textual IR in static single
+/// assignment form, which can be adapted to the dialects of other compiler toolchains, e.g. MLIR.
+///
+/// Each node reachable from a constraint is assigned once to a numbered value, e.g.
+/// `%3 = mul %1, %2`, after the values of its operands, so a node shared between several
+/// constraints is only emitted once. The constraints follow, one per line, with the value of
+/// their root, the trace segment against which they are applied and their domain, e.g.
+/// `enf %7 on $main every_row except last 2`.
+///
+/// The leaves of the graph are named after the declarations they access, as in the flat
+/// AirScript emitted by [crate::FlatAirScript], e.g. `%0 = trace a'` or
+/// `%1 = public_input stack_inputs[0]`.
+#[derive(Default)]
+pub struct SsaForm;
+impl SsaForm {
+    /// Create a new instance of this code generator
+    #[inline]
+    pub fn new() -> Self {
+        Self
+    }
+}
+impl CodeGenerator for SsaForm {
+    type Output = String;
+
+    fn generate(&self, ir: &Air) -> anyhow::Result<Self::Output> {
+        let mut listing = Listing {
+            ir,
+            names: vec![None; ir.constraint_graph().num_nodes()],
+            next_name: 0,
+            out: String::new(),
+        };
+        writeln!(listing.out, "air {}", ir.name())?;
+        listing.visit_constraints(ir);
+
+        for segment in 0..ir.trace_segment_widths.len() {
+            for constraint in ir
+                .boundary_constraints(segment)
+                .iter()
+                .chain(ir.integrity_constraints(segment))
+            {
+                writeln!(listing.out, "{}", listing.constraint(segment, constraint))?;
+            }
+        }
+
+        Ok(listing.out)
+    }
+}
+
+/// The [VisitContext] which assigns a name to each visited node, and emits its assignment
+struct Listing<'a> {
+    ir: &'a Air,
+    /// The number of the value assigned to each node, if it was visited
+    names: Vec<Option<usize>>,
+    next_name: usize,
+    out: String,
+}
+impl<'a> Listing<'a> {
+    /// Returns the name of the value assigned to a node, which must have been visited
+    fn name(&self, index: &NodeIndex) -> String {
+        let name = self.names[index.as_usize()].expect("operands are visited before their users");
+        format!("%{name}")
+    }
+
+    /// Returns the statement enforcing `constraint` against the trace segment `segment`
+    fn constraint(&self, segment: usize, constraint: &ConstraintRoot) -> String {
+        let domain = match constraint.domain() {
+            ConstraintDomain::FirstRow => "first".to_string(),
+            ConstraintDomain::LastRow => "last".to_string(),
+            ConstraintDomain::EveryRow => "every_row".to_string(),
+            ConstraintDomain::EveryFrame(size) => format!("every_frame({size})"),
+        };
+        let statement = format!(
+            "enf {} on {} {domain}",
+            self.name(constraint.node_index()),
+            self.ir.trace_columns[segment].name
+        );
+        match constraint.exemptions() {
+            0 => statement,
+            rows => format!("{statement} except last {rows}"),
+        }
+    }
+}
+impl<'a> VisitContext for Listing<'a> {
+    fn visit(&mut self, graph: &AlgebraicGraph, node_index: NodeIndex) {
+        let assignment = match graph.node(&node_index).op() {
+            Operation::Value(value) => {
+                let kind = match value {
+                    Value::Constant(_) => "const",
+                    Value::TraceAccess(_) => "trace",
+                    Value::PeriodicColumn(_) => "periodic",
+                    Value::PublicInput(_) => "public_input",
+                    Value::RandomValue(_) => "random_value",
+                };
+                format!("{kind} {}", flat::value(self.ir, value))
+            }
+            Operation::Add(lhs, rhs) => format!("add {}, {}", self.name(lhs), self.name(rhs)),
+            Operation::Sub(lhs, rhs) => format!("sub {}, {}", self.name(lhs), self.name(rhs)),
+            Operation::Mul(lhs, rhs) => format!("mul {}, {}", self.name(lhs), self.name(rhs)),
+            Operation::Exp(lhs, exp) => format!("exp {}, {exp}", self.name(lhs)),
+            Operation::Inv(inverted) => format!("inv {}", self.name(inverted)),
+        };
+
+        let name = self.next_name;
+        self.next_name += 1;
+        self.names[node_index.as_usize()] = Some(name);
+        self.out.push_str(&format!("%{name} = {assignment}\n"));
+    }
+}
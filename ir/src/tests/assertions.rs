@@ -0,0 +1,90 @@
+use super::{compile, expect_diagnostic};
+
+#[test]
+fn assertions_hold() {
+    let source = "
+    def test
+    const WIDTH = 2
+    trace_columns:
+        main: [a, b[2], c]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf a.first = 0
+        enf p.first = 1
+    integrity_constraints:
+        enf a' = a + c
+        enf p' = p * $rand[0]
+    assertions:
+        assert NUM_MAIN_COLUMNS == 4
+        assert NUM_AUX_COLUMNS * WIDTH == WIDTH
+        assert 2^WIDTH == NUM_MAIN_COLUMNS";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn err_assertion_fails() {
+    // the trace shape no longer matches the assertion
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + b
+    assertions:
+        assert NUM_MAIN_COLUMNS == 9";
+
+    expect_diagnostic(source, "assertion failed");
+    expect_diagnostic(
+        source,
+        "the left-hand side evaluates to 3, but the right-hand side evaluates to 9",
+    );
+}
+
+#[test]
+fn err_assertion_references_trace_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + b
+    assertions:
+        assert a == 0";
+
+    expect_diagnostic(
+        source,
+        "only constants can be referenced in assertions, but `a` refers to trace column(s)",
+    );
+}
+
+#[test]
+fn err_assertion_not_constant() {
+    let source = "
+    def test
+    const A = [1, 2, 3]
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + b
+    assertions:
+        assert sum(A) == 6";
+
+    expect_diagnostic(source, "this is not a constant expression");
+}
@@ -0,0 +1,68 @@
+use air_pass::Pass;
+
+use crate::passes::AuxRandomValues;
+
+use super::Compiler;
+
+/// Compiles `source`, which is expected to succeed, and runs the [AuxRandomValues] pass,
+/// returning the diagnostic output
+fn check_aux_random_values(source: &str) -> String {
+    let compiler = Compiler::default();
+    let air = match compiler.compile(source) {
+        Ok(air) => air,
+        Err(err) => {
+            compiler.diagnostics.emit(err);
+            compiler.emitter.print_captured_to_stderr();
+            panic!("expected compilation to succeed");
+        }
+    };
+    AuxRandomValues::new(&compiler.diagnostics)
+        .run(air)
+        .expect("expected the pass to succeed");
+    compiler.emitter.captured()
+}
+
+#[test]
+fn aux_constraint_without_random_values() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+        enf p.first = 1
+    integrity_constraints:
+        enf a' = a + 1
+        enf p' = p * a";
+
+    let output = check_aux_random_values(source);
+    assert!(output.contains("auxiliary constraint without random values"));
+    assert!(output.contains("enforced against the `$aux` trace segment"));
+}
+
+#[test]
+fn aux_constraint_with_random_values() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+        enf p.first = 1
+    integrity_constraints:
+        enf a' = a + 1
+        enf p' = p * (a + $rand[0])";
+
+    let output = check_aux_random_values(source);
+    assert!(!output.contains("auxiliary constraint without random values"));
+}
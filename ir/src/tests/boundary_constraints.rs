@@ -50,3 +50,97 @@ fn err_bc_duplicate_last() {
 
     expect_diagnostic(source, "overlapping boundary constraints");
 }
+
+#[test]
+fn err_bc_next_row_access() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = a'
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "`a'` refers to the next row of column `a`");
+}
+
+#[test]
+fn err_ic_boundary_access() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + a.last";
+
+    expect_diagnostic(
+        source,
+        "the last row of column `a` cannot be referenced in an integrity constraint",
+    );
+}
+
+#[test]
+fn err_ic_boundary_access_in_let() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let b = a.first
+        enf clk' = clk + b";
+
+    expect_diagnostic(
+        source,
+        "only valid on the left-hand side of boundary constraints",
+    );
+}
+
+#[test]
+fn boundary_constraints_on_groups_of_columns() {
+    let source = "
+    def test
+    const INIT = [1, 2]
+    trace_columns:
+        main: [clk, stack[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf stack[0..2].first = stack_inputs[2..4]
+        enf stack[2..4].first = INIT
+        enf stack.last = stack_inputs[12..16]
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.boundary_constraints(0).len(), 8);
+}
+
+#[test]
+fn err_bc_group_length_mismatch() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, stack[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf stack.first = stack_inputs
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(
+        source,
+        "the sides of this constraint have different lengths",
+    );
+}
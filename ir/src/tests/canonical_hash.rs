@@ -0,0 +1,131 @@
+use super::compile;
+
+/// Compiles a program with the provided integrity constraints, and returns its canonical hash
+fn hash_with_constraints(integrity_constraints: &str) -> [u8; 32] {
+    let source = format!(
+        "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+        enf b.first = 1
+    integrity_constraints:
+{integrity_constraints}"
+    );
+
+    compile(&source)
+        .expect("compilation failed")
+        .canonical_hash()
+}
+
+#[test]
+fn constraint_order_does_not_change_hash() {
+    let hash = hash_with_constraints(
+        "
+        enf a' = a + b
+        enf b' = a * c
+        enf c^2 = c",
+    );
+    let reordered = hash_with_constraints(
+        "
+        enf c^2 = c
+        enf b' = a * c
+        enf a' = a + b",
+    );
+
+    assert_eq!(hash, reordered);
+}
+
+#[test]
+fn boundary_constraint_order_does_not_change_hash() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+        enf b.last = 1
+    integrity_constraints:
+        enf a' = a + b";
+    let reordered = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf b.last = 1
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf a' = a + b";
+
+    let hash = compile(source)
+        .expect("compilation failed")
+        .canonical_hash();
+    let reordered = compile(reordered)
+        .expect("compilation failed")
+        .canonical_hash();
+    assert_eq!(hash, reordered);
+}
+
+#[test]
+fn different_constraints_change_hash() {
+    let hash = hash_with_constraints(
+        "
+        enf a' = a + b
+        enf b' = a * c",
+    );
+
+    assert_ne!(
+        hash,
+        hash_with_constraints(
+            "
+        enf a' = a + c
+        enf b' = a * c"
+        )
+    );
+    assert_ne!(
+        hash,
+        hash_with_constraints(
+            "
+        enf a' = a + b
+        enf b' = a * c
+        enf c^2 = c"
+        )
+    );
+    // the same constraint, but applied against the current row rather than the next one
+    assert_ne!(
+        hash,
+        hash_with_constraints(
+            "
+        enf a = a + b
+        enf b' = a * c"
+        )
+    );
+    // the same constraint, but exempted from the last rows of the trace
+    assert_ne!(
+        hash,
+        hash_with_constraints(
+            "
+        enf a' = a + b except last 3
+        enf b' = a * c"
+        )
+    );
+}
+
+#[test]
+fn hash_is_stable() {
+    let constraints = "
+        enf a' = a + b
+        enf b' = a * c";
+
+    assert_eq!(
+        hash_with_constraints(constraints),
+        hash_with_constraints(constraints)
+    );
+}
@@ -0,0 +1,66 @@
+use air_pass::Pass;
+
+use crate::{passes::CompositionDegree, ConstraintDomain};
+
+use super::Compiler;
+
+/// Compiles `source`, which is expected to succeed, and runs the [CompositionDegree] pass for the
+/// provided trace length and blowup factor, returning whether it succeeded along with the
+/// diagnostic output
+fn check_degrees(source: &str, trace_length: usize, blowup_factor: usize) -> (bool, String) {
+    let compiler = Compiler::default();
+    let air = match compiler.compile(source) {
+        Ok(air) => air,
+        Err(err) => {
+            compiler.diagnostics.emit(err);
+            compiler.emitter.print_captured_to_stderr();
+            panic!("expected compilation to succeed");
+        }
+    };
+    let result =
+        CompositionDegree::new(&compiler.diagnostics, trace_length, blowup_factor).run(air);
+    (result.is_ok(), compiler.emitter.captured())
+}
+
+static SOURCE: &str = "
+def test
+trace_columns:
+    main: [a, b, c]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints:
+    enf a.first = stack_inputs[0]
+integrity_constraints:
+    enf b' = a^3
+    enf c' = a^4";
+
+#[test]
+fn over_degree_transition_constraint() {
+    let (succeeded, output) = check_degrees(SOURCE, 16, 2);
+    assert!(!succeeded);
+    assert!(output
+        .contains("constraint quotient exceeds the degree bound of the composition polynomial"));
+
+    // the quotient of `a^4` has degree 4 * 15 - 15, while `a^3` fits within the bound of 31
+    assert!(output.contains(
+        "the numerator of this constraint has degree 60 and its divisor degree 15, so its quotient has degree 45, which exceeds the bound of 31 for a trace of length 16 and a blowup factor of 2"
+    ));
+    assert_eq!(output.matches("constraint quotient exceeds").count(), 1);
+}
+
+#[test]
+fn constraints_within_composition_degree() {
+    let (succeeded, output) = check_degrees(SOURCE, 16, 4);
+    assert!(succeeded);
+    assert!(!output.contains("constraint quotient exceeds"));
+}
+
+#[test]
+fn divisor_degree_by_domain() {
+    assert_eq!(ConstraintDomain::FirstRow.divisor_degree(16, 0), 1);
+    assert_eq!(ConstraintDomain::LastRow.divisor_degree(16, 0), 1);
+    assert_eq!(ConstraintDomain::EveryRow.divisor_degree(16, 0), 16);
+    assert_eq!(ConstraintDomain::EveryRow.divisor_degree(16, 2), 14);
+    assert_eq!(ConstraintDomain::EveryFrame(2).divisor_degree(16, 0), 15);
+    assert_eq!(ConstraintDomain::EveryFrame(2).divisor_degree(16, 3), 13);
+}
@@ -1,3 +1,5 @@
+use crate::{Air, ConstantAccess, NodeIndex, Operation, Value, ValueCollector, Visit};
+
 use super::{compile, expect_diagnostic};
 
 #[test]
@@ -39,6 +41,67 @@ fn integrity_constraint_with_constants() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn constants_preserve_provenance() {
+    let source = "
+    def test
+    const A = 123
+    const C = [[1, 2, 3], [4, 5, 6]]
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk * C[1][2] + (A + 7)";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.constants.len(), 2);
+
+    let (a, c) = {
+        let mut names = air.constants.keys().copied();
+        (names.next().unwrap(), names.next().unwrap())
+    };
+    assert_eq!(
+        air.constant_name(&constant_node(&air, 6)),
+        Some(&ConstantAccess::new(c, Some(1), Some(2), 6))
+    );
+    // `A + 7` is folded, so the provenance of the folded value is lost
+    assert_eq!(air.constant_name(&constant_node(&air, 130)), None);
+    assert!(!air
+        .constraint_graph()
+        .nodes_indexed()
+        .any(|(index, _)| air.constant_name(&index).map(|access| access.name) == Some(a)));
+}
+
+#[test]
+fn constants_shared_with_literals_are_unnamed() {
+    let source = "
+    def test
+    const A = 5
+    const B = 7
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk * A + 5
+        enf clk' = clk * B + 3";
+
+    let air = compile(source).expect("compilation failed");
+
+    // the node of `A` is shared with the literal `5`, so it is emitted as a literal
+    assert_eq!(air.constant_name(&constant_node(&air, 5)), None);
+    let b = air.constants.keys().nth(1).copied().unwrap();
+    assert_eq!(
+        air.constant_name(&constant_node(&air, 7)),
+        Some(&ConstantAccess::new(b, None, None, 7))
+    );
+}
+
 #[test]
 fn invalid_matrix_constant() {
     let source = "
@@ -56,3 +119,104 @@ fn invalid_matrix_constant() {
 
     expect_diagnostic(source, "invalid matrix literal: mismatched dimensions");
 }
+
+#[test]
+fn json_constant_table() {
+    // the path of the table is resolved from the working directory, as the source is not a file
+    let source = r#"
+    def test
+    const RC = json("src/tests/input/round_constants.json")
+    trace_columns:
+        main: [clk, state[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + RC[0]
+        enf s' = s + c for (s, c) in (state[1..4], RC[1..4])"#;
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 4);
+
+    let mut collector = ValueCollector::default();
+    collector.visit_constraints(&air);
+    let values = collector.values();
+
+    let rc = *air.constants.keys().next().unwrap();
+    assert_eq!(
+        air.constant_name(&constant_node(&air, 5)),
+        Some(&ConstantAccess::new(rc, Some(0), None, 5))
+    );
+    // the elements bound by the comprehension are folded to their values
+    assert!(values.contains(&Value::Constant(18446744069414584320)));
+    // the file the table was loaded from is recorded, so that it can be tracked as a dependency
+    assert_eq!(
+        air.constants[&rc].table,
+        Some(
+            std::env::current_dir()
+                .unwrap()
+                .join("src/tests/input/round_constants.json")
+        )
+    );
+}
+
+#[test]
+fn err_json_constant_table_not_found() {
+    let source = r#"
+    def test
+    const RC = json("src/tests/input/missing.json")
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + RC[0]"#;
+
+    expect_diagnostic(source, "could not read");
+}
+
+#[test]
+fn err_json_constant_table_not_an_array() {
+    let source = r#"
+    def test
+    const RC = json("src/tests/input/not_a_table.json")
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + RC[0]"#;
+
+    expect_diagnostic(source, "is not a JSON array of field elements");
+}
+
+#[test]
+fn err_unknown_constant_table_format() {
+    let source = r#"
+    def test
+    const RC = csv("src/tests/input/round_constants.json")
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + RC[0]"#;
+
+    expect_diagnostic(source, "unknown constant table format");
+}
+
+/// Returns the index of the node of the constraint graph holding the provided constant.
+fn constant_node(air: &Air, value: u64) -> NodeIndex {
+    air.constraint_graph()
+        .nodes_indexed()
+        .find(|(_, node)| matches!(node.op(), Operation::Value(Value::Constant(c)) if *c == value))
+        .map(|(index, _)| index)
+        .expect("the constant is in the constraint graph")
+}
@@ -0,0 +1,60 @@
+use air_pass::Pass;
+
+use crate::passes::ConstraintLimit;
+
+use super::Compiler;
+
+/// Compiles `source`, which is expected to succeed, and runs the [ConstraintLimit] pass for the
+/// provided limits, returning whether it succeeded along with the diagnostic output
+fn check_limits(source: &str, limits: Vec<Option<usize>>) -> (bool, String) {
+    let compiler = Compiler::default();
+    let air = match compiler.compile(source) {
+        Ok(air) => air,
+        Err(err) => {
+            compiler.diagnostics.emit(err);
+            compiler.emitter.print_captured_to_stderr();
+            panic!("expected compilation to succeed");
+        }
+    };
+    let result = ConstraintLimit::new(&compiler.diagnostics, limits).run(air);
+    (result.is_ok(), compiler.emitter.captured())
+}
+
+static SOURCE: &str = "
+def test
+trace_columns:
+    main: [a, b, c]
+    aux: [p]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints:
+    enf a.first = stack_inputs[0]
+    enf p.first = 1
+integrity_constraints:
+    enf b' = a * b
+    enf c' = a + c
+    enf p' = p * a";
+
+#[test]
+fn err_constraint_limit_exceeded() {
+    let (succeeded, output) = check_limits(SOURCE, vec![Some(2)]);
+    assert!(!succeeded);
+    assert!(output.contains("too many constraints"));
+    assert!(output
+        .contains("this constraint exceeds the limit of 2 constraints of the $main trace segment"));
+    assert!(output.contains("3 constraints are applied against this segment"));
+}
+
+#[test]
+fn constraints_within_limits() {
+    let (succeeded, output) = check_limits(SOURCE, vec![Some(3), Some(2)]);
+    assert!(succeeded);
+    assert!(!output.contains("too many constraints"));
+
+    // segments without a limit are unbounded
+    let (succeeded, _) = check_limits(SOURCE, vec![None, Some(2)]);
+    assert!(succeeded);
+    let (succeeded, output) = check_limits(SOURCE, vec![None, Some(1)]);
+    assert!(!succeeded);
+    assert!(output.contains("the limit of 1 constraints of the $aux trace segment"));
+}
@@ -0,0 +1,225 @@
+use air_parser::ast::NamespacedIdentifier;
+use miden_diagnostics::SourceSpan;
+
+use crate::{BinaryOp, Identifier, IntegrityConstraintDegree, QualifiedIdentifier, Symbol};
+
+use super::compile;
+
+#[test]
+fn combine_additive_degrees() {
+    let lhs = IntegrityConstraintDegree::with_cycles(2, vec![8, 4]);
+    let rhs = IntegrityConstraintDegree::with_cycles(3, vec![4, 4, 16]);
+
+    // the base is the maximum of the bases, and the cycles are the union of the cycles
+    let expected = IntegrityConstraintDegree::with_cycles(3, vec![8, 4, 4, 16]);
+    assert_eq!(lhs.combined(&rhs, BinaryOp::Add), expected);
+    assert_eq!(lhs.combined(&rhs, BinaryOp::Sub), expected);
+    assert_eq!(lhs.combined(&rhs, BinaryOp::Eq), expected);
+
+    let degree = IntegrityConstraintDegree::new(2);
+    assert_eq!(degree.combined(&degree, BinaryOp::Add), degree);
+}
+
+#[test]
+fn combine_multiplicative_degrees() {
+    let lhs = IntegrityConstraintDegree::with_cycles(2, vec![8]);
+    let rhs = IntegrityConstraintDegree::with_cycles(3, vec![8, 4]);
+
+    // the bases are added, and the cycles are the union of the cycles
+    assert_eq!(
+        lhs.combined(&rhs, BinaryOp::Mul),
+        IntegrityConstraintDegree::with_cycles(5, vec![8, 4])
+    );
+    assert_eq!(
+        lhs.exp(3),
+        IntegrityConstraintDegree::with_cycles(6, vec![8])
+    );
+}
+
+#[test]
+fn combine_periodic_column_degrees() {
+    let k = IntegrityConstraintDegree::periodic(column("k"), 4);
+    let m = IntegrityConstraintDegree::periodic(column("m"), 4);
+
+    // each periodic column contributes its cycle once, however many times it is multiplied
+    assert_eq!(k.combined(&k, BinaryOp::Mul).cycles(), [4]);
+    assert_eq!(k.exp(2).cycles(), [4]);
+    // distinct periodic columns contribute their cycles even when they are equal
+    assert_eq!(k.combined(&m, BinaryOp::Mul).cycles(), [4, 4]);
+    assert_eq!(k.combined(&m, BinaryOp::Add).cycles(), [4, 4]);
+    assert_eq!(
+        k.combined(&m, BinaryOp::Mul)
+            .combined(&k, BinaryOp::Mul)
+            .cycles(),
+        [4, 4]
+    );
+}
+
+#[test]
+#[should_panic(expected = "the degree of an exponentiation is computed by `exp`")]
+fn combine_exponentiation_degrees() {
+    let degree = IntegrityConstraintDegree::new(1);
+    degree.combined(&degree, BinaryOp::Exp);
+}
+
+#[test]
+fn inverse_degrees() {
+    // the inverse of a constant is constant, any other inverse is not a polynomial
+    let constant = IntegrityConstraintDegree::constant();
+    assert_eq!(constant.inv(), constant);
+    let degree = IntegrityConstraintDegree::new(2).inv();
+    assert!(degree.is_unbounded());
+    assert!(IntegrityConstraintDegree::periodic(column("k"), 8)
+        .inv()
+        .is_unbounded());
+
+    // an unbounded degree stays unbounded when combined with other degrees
+    let other = IntegrityConstraintDegree::with_cycles(3, vec![8]);
+    assert!(degree.combined(&other, BinaryOp::Mul).is_unbounded());
+    assert!(degree.combined(&other, BinaryOp::Add).is_unbounded());
+    assert!(degree.exp(3).is_unbounded());
+    assert_eq!(degree.effective(16), usize::MAX);
+}
+
+#[test]
+fn effective_degree() {
+    // see the example in the documentation of `get_evaluation_degree`
+    let degree = IntegrityConstraintDegree::with_cycles(2, vec![32]);
+    assert_eq!(degree.effective(64), 188);
+    assert_eq!(IntegrityConstraintDegree::new(3).effective(16), 45);
+}
+
+#[test]
+fn constraint_degrees() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 0, 0, 0]
+        m: [1, 0, 0, 0, 0, 0, 0, 0]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf a' = a * b + k
+        enf b' = k * m * b^2";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        vec![
+            IntegrityConstraintDegree::with_cycles(2, vec![4]),
+            IntegrityConstraintDegree::with_cycles(2, vec![4, 8]),
+        ]
+    );
+}
+
+#[test]
+fn periodic_column_degrees() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 0, 0, 0]
+        m: [1, 1, 0, 0]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf a' = a * k * k
+        enf b' = b * k^2
+        enf a = k * m * b";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        vec![
+            IntegrityConstraintDegree::with_cycles(1, vec![4]),
+            IntegrityConstraintDegree::with_cycles(1, vec![4]),
+            IntegrityConstraintDegree::with_cycles(1, vec![4, 4]),
+        ]
+    );
+}
+
+#[test]
+fn constraints_by_degree() {
+    let source = "
+    def SimpleArithmetic
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a + a = 0
+        enf a - a = 0
+        enf a * a = 0
+        enf b + a = 0
+        enf b - a = 0
+        enf b * a = 0";
+
+    let air = compile(source).expect("compilation failed");
+    let constraints = air.integrity_constraints(0);
+    let groups = air.integrity_constraints_by_degree(0);
+
+    // the constraints of each degree keep their declaration order
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].0, IntegrityConstraintDegree::new(1));
+    assert_eq!(
+        groups[0].1,
+        [
+            &constraints[0],
+            &constraints[1],
+            &constraints[3],
+            &constraints[4]
+        ]
+    );
+    assert_eq!(groups[1].0, IntegrityConstraintDegree::new(2));
+    assert_eq!(groups[1].1, [&constraints[2], &constraints[5]]);
+}
+
+#[test]
+fn constraints_by_degree_with_cycles() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 0, 0, 0]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf b' = a * b * k
+        enf a' = a * b
+        enf b = k * a";
+
+    let air = compile(source).expect("compilation failed");
+    let degrees = air
+        .integrity_constraints_by_degree(0)
+        .into_iter()
+        .map(|(degree, group)| (degree, group.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        degrees,
+        vec![
+            (IntegrityConstraintDegree::with_cycles(1, vec![4]), 1),
+            (IntegrityConstraintDegree::new(2), 1),
+            (IntegrityConstraintDegree::with_cycles(2, vec![4]), 1),
+        ]
+    );
+}
+
+/// Returns the identifier of the periodic column `name` of the root module `test`.
+fn column(name: &str) -> QualifiedIdentifier {
+    QualifiedIdentifier::new(
+        Identifier::new(SourceSpan::UNKNOWN, Symbol::intern("test")),
+        NamespacedIdentifier::Binding(Identifier::new(SourceSpan::UNKNOWN, Symbol::intern(name))),
+    )
+}
@@ -0,0 +1,95 @@
+use air_pass::Pass;
+
+use crate::passes::DegreeBudget;
+
+use super::Compiler;
+
+/// Compiles `source`, which is expected to succeed, and returns the diagnostic output of the
+/// [DegreeBudget] pass targeting the provided blowup factor
+fn degree_warnings(source: &str, blowup_factor: usize) -> String {
+    let compiler = Compiler::default();
+    let result = compiler
+        .compile(source)
+        .and_then(|air| DegreeBudget::new(&compiler.diagnostics, blowup_factor).run(air));
+    if let Err(err) = result {
+        compiler.diagnostics.emit(err);
+        compiler.emitter.print_captured_to_stderr();
+        panic!("expected compilation to succeed");
+    }
+    compiler.emitter.captured()
+}
+
+/// Returns a program with a single integrity constraint of the provided degree
+fn source_with_degree(degree: usize) -> String {
+    format!(
+        "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf b' = a^{degree}"
+    )
+}
+
+#[test]
+fn degree_exceeding_blowup_factor() {
+    let output = degree_warnings(&source_with_degree(8), 8);
+    assert!(output.contains("constraint degree exceeds the maximum supported"));
+    assert!(output.contains(
+        "this constraint has degree 8, which is 114% of the maximum degree of 7 supported by a blowup factor of 8"
+    ));
+}
+
+#[test]
+fn degree_within_blowup_factor() {
+    let output = degree_warnings(&source_with_degree(4), 8);
+    assert!(!output.contains("constraint degree"));
+}
+
+#[test]
+fn degree_approaching_blowup_factor() {
+    let output = degree_warnings(&source_with_degree(6), 8);
+    assert!(output.contains("constraint degree approaches the maximum supported"));
+    assert!(output.contains("which is 85% of the maximum degree of 7"));
+
+    // the degree of 5 is below the threshold
+    let output = degree_warnings(&source_with_degree(5), 8);
+    assert!(!output.contains("constraint degree"));
+}
+
+#[test]
+fn degree_with_periodic_columns() {
+    // a periodic column of 4 rows contributes a degree of 1 over a trace of 4 rows, and of 6/7
+    // over a trace of 8 rows
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 1, 1, 0]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf b' = k * a^6";
+
+    let output = degree_warnings(source, 8);
+    assert!(output.contains("constraint degree approaches the maximum supported"));
+    assert!(output.contains("this constraint has degree 7, which is 100%"));
+
+    let compiler = Compiler::default();
+    let air = compiler.compile(source).unwrap();
+    DegreeBudget::new(&compiler.diagnostics, 8)
+        .with_trace_length(8)
+        .run(air)
+        .unwrap();
+    assert!(compiler
+        .emitter
+        .captured()
+        .contains("this constraint has degree 6.86, which is 97%"));
+}
@@ -0,0 +1,151 @@
+use std::sync::{Arc, Mutex};
+
+use miden_diagnostics::{CodeMap, DiagnosticsConfig, DiagnosticsHandler, Verbosity};
+
+use crate::{JsonDiagnostic, JsonEmitter, Pipeline};
+
+use super::Compiler;
+
+/// Compiles `source`, which is expected to fail, with a [JsonEmitter], and returns all of the
+/// diagnostics emitted, ending with the error failing the compilation
+fn json_diagnostics(source: &str) -> Vec<JsonDiagnostic> {
+    let codemap = Arc::new(CodeMap::new());
+    let emitted = Arc::new(Mutex::new(vec![]));
+    let sink = emitted.clone();
+    let emitter = Arc::new(JsonEmitter::new(codemap.clone(), move |diagnostic| {
+        sink.lock().unwrap().push(diagnostic)
+    }));
+    let config = JsonEmitter::config(DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        display: Default::default(),
+    });
+    let diagnostics = DiagnosticsHandler::new(config, codemap.clone(), emitter);
+
+    let err = Pipeline::new(&diagnostics, codemap, Default::default())
+        .compile(source)
+        .expect_err("expected compilation to fail");
+    diagnostics.emit(err);
+
+    let emitted = emitted.lock().unwrap();
+    emitted.clone()
+}
+
+#[test]
+fn json_diagnostic() {
+    let source = "
+    def test
+    const A = 1
+    const A = 2
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + A";
+
+    let compiler = Compiler::default();
+    let err = compiler
+        .compile(source)
+        .expect_err("expected compilation to fail");
+    assert_eq!(err.code(), "E0206");
+
+    let diagnostic = JsonDiagnostic::new(err, &compiler.codemap);
+    let span = r#"{"file":"<nofile>","start":40,"end":41,"line":4,"column":11}"#;
+    let expected = format!(
+        r#"{{"severity":"error","code":"E0206","message":"name already in use","span":{span},"labels":[{{"primary":true,"message":"conflicting definition occurs here","span":{span}}}],"notes":[]}}"#
+    );
+    assert_eq!(diagnostic.to_json(), expected);
+}
+
+#[test]
+fn json_diagnostic_of_syntax_error() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk
+    public_inputs:
+        stack_inputs: [16]";
+
+    let compiler = Compiler::default();
+    let err = compiler
+        .compile(source)
+        .expect_err("expected compilation to fail");
+
+    let diagnostic = JsonDiagnostic::new(err, &compiler.codemap);
+    assert_eq!(diagnostic.severity, "error");
+    assert_eq!(diagnostic.code.as_deref(), Some("E0103"));
+    assert_eq!(diagnostic.message, "unexpected token");
+    let span = diagnostic.span.expect("expected the span of the token");
+    assert_eq!((span.line, span.column), (5, 5));
+    assert_eq!(
+        &source[span.start..span.end.expect("expected the end of the span")],
+        "public_inputs"
+    );
+}
+
+#[test]
+fn json_emitted_diagnostics() {
+    // the undefined variable is reported by a diagnostic emitted during semantic analysis,
+    // ahead of the error failing the compilation
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + x";
+
+    let diagnostics = json_diagnostics(source);
+    assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+
+    let undefined = &diagnostics[0];
+    assert_eq!(undefined.severity, "error");
+    assert_eq!(undefined.code.as_deref(), Some("E0507"));
+    assert_eq!(undefined.message, "reference to undefined variable");
+    let span = undefined
+        .span
+        .clone()
+        .expect("expected the span of the variable");
+    assert_eq!((span.line, span.column, span.end), (10, 26, None));
+    assert_eq!(&source[span.start..=span.start], "x");
+    assert_eq!(undefined.labels.len(), 1);
+
+    let failed = &diagnostics[1];
+    assert_eq!(failed.code.as_deref(), Some("E0211"));
+    assert_eq!(failed.span, None);
+}
+
+#[test]
+fn json_emitted_diagnostic_notes() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias next_clk = clk'
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf next_clk' = clk + 2";
+
+    let diagnostics = json_diagnostics(source);
+    let invalid = &diagnostics[0];
+    assert_eq!(invalid.code.as_deref(), Some("E0515"));
+    assert_eq!(invalid.message, "invalid row offset");
+    assert_eq!(
+        invalid.span.as_ref().map(|span| (span.line, span.column)),
+        Some((11, 13))
+    );
+    assert_eq!(
+        invalid.notes,
+        ["Trace columns can only be accessed with an offset of at most 1."]
+    );
+}
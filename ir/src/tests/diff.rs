@@ -0,0 +1,141 @@
+use crate::{Operation, TraceAccess, Value};
+
+use super::{compile, expect_diagnostic};
+
+#[test]
+fn diff_over_column_group() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf diff(s) = clk";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let constraints = air.integrity_constraints(0);
+    assert_eq!(constraints.len(), 3);
+
+    // each constraint is `s[i + 1] - s[i] - clk`
+    let clk = Operation::Value(Value::TraceAccess(TraceAccess::new(0, 0, 0)));
+    for (i, constraint) in constraints.iter().enumerate() {
+        let Operation::Sub(difference, rhs) = graph.node(constraint.node_index()).op() else {
+            panic!("expected a subtraction");
+        };
+        assert_eq!(graph.node(rhs).op(), &clk);
+        let Operation::Sub(next, current) = graph.node(difference).op() else {
+            panic!("expected a subtraction");
+        };
+        let column = |col| Operation::Value(Value::TraceAccess(TraceAccess::new(0, col, 0)));
+        assert_eq!(graph.node(next).op(), &column(i + 2));
+        assert_eq!(graph.node(current).op(), &column(i + 1));
+    }
+}
+
+#[test]
+fn diff_over_slice_with_selector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf diff(s[1..4]) = 1 when clk";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 2);
+}
+
+#[test]
+fn diff_in_evaluator() {
+    let source = "
+    def test
+    ev is_increment([s[3]]):
+        enf diff(s) = 1
+    trace_columns:
+        main: [clk, s[3]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf is_increment([s])";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.integrity_constraints(0).len(), 2);
+}
+
+#[test]
+fn err_diff_over_scalar() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf diff(clk) = 1";
+
+    expect_diagnostic(source, "but this argument is a single column");
+}
+
+#[test]
+fn err_diff_over_non_trace_binding() {
+    let source = "
+    def test
+    const A = [1, 2, 3]
+    trace_columns:
+        main: [clk, s[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf diff(A) = clk";
+
+    expect_diagnostic(source, "but this argument is not a trace column");
+}
+
+#[test]
+fn err_diff_out_of_range() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf diff(s[2..6]) = 1";
+
+    expect_diagnostic(source, "invalid variable access");
+}
+
+#[test]
+fn err_diff_outside_of_constraint() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, s[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk = sum(diff(s))";
+
+    expect_diagnostic(
+        source,
+        "diff is only permitted on the left-hand side of an integrity constraint",
+    );
+}
@@ -0,0 +1,104 @@
+use crate::DEFAULT_SEGMENT;
+
+use super::{compile, expect_diagnostic};
+
+/// Returns the number of exempted rows of each integrity constraint of the main trace, in order
+fn main_exemptions(source: &str) -> Vec<usize> {
+    let air = compile(source).expect("compilation failed");
+    air.integrity_constraints(DEFAULT_SEGMENT)
+        .iter()
+        .map(|constraint| constraint.exemptions())
+        .collect()
+}
+
+#[test]
+fn exemptions() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk, a]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + 1 except last 4
+        enf a' = a + clk";
+
+    assert_eq!(main_exemptions(source), vec![4, 0]);
+}
+
+#[test]
+fn exemptions_of_comprehension() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk, a[3]]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf x' = x + 1 for x in a except last 3";
+
+    assert_eq!(main_exemptions(source), vec![0, 3, 3, 3]);
+}
+
+#[test]
+fn exemptions_of_evaluator_call() {
+    // the same evaluator is inlined both with and without exemptions, and the exemptions of a
+    // call apply to every constraint of the evaluator, unless they exempt more rows
+    let source = "
+    def test
+
+    ev advance([x, y]):
+        enf x' = x + 1
+        enf y' = y + x except last 6
+
+    trace_columns:
+        main: [a, b, c, d]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf advance([a, b])
+        enf advance([c, d]) except last 3";
+
+    assert_eq!(main_exemptions(source), vec![0, 6, 3, 6]);
+}
+
+#[test]
+fn err_exemptions_of_boundary_constraint() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf clk.last = 0 except last 3
+
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(
+        source,
+        "only integrity constraints may be exempted from rows of the trace",
+    );
+}
@@ -0,0 +1,144 @@
+use crate::{CodeGenerator, FlatAirScript};
+
+use super::compile;
+
+#[test]
+fn flat_reemission_substitutes_bindings() {
+    let source = "
+    def test
+
+    const TWO = 2
+    const MASKS = [1, 3]
+
+    trace_columns:
+        main: [clk, s, a[3]]
+        aux: [p]
+
+    public_inputs:
+        stack_inputs: [4]
+
+    periodic_columns:
+        k: [1, 0]
+
+    random_values:
+        rand: [alpha, betas[2]]
+
+    boundary_constraints:
+        enf clk.first = stack_inputs[1]
+        enf p.first = 1
+        enf p.last = alpha * betas[1]
+
+    ev is_binary([x]):
+        enf x^2 = x
+
+    integrity_constraints:
+        let next = clk + TWO
+        let sums = [x + 2 for x in a]
+        enf clk' = next * MASKS[0]
+        enf is_binary([s])
+        enf x' = y * MASKS[1] for (x, y) in (a, sums)
+        let gated = k * (a[2] - a[1])
+        enf gated = 0 when s
+        enf p' = p * (a[0] + $rand[2]) except last 2";
+
+    let air = compile(source).expect("compilation failed");
+    let flat = FlatAirScript::new().generate(&air).unwrap();
+    let expected = "def test
+
+trace_columns:
+    main: [clk, s, a[3]]
+    aux: [p]
+
+public_inputs:
+    stack_inputs: [4]
+
+periodic_columns:
+    k: [1, 0]
+
+random_values:
+    rand: [alpha, betas[2]]
+
+boundary_constraints:
+    enf clk.first = stack_inputs[1]
+    enf p.first = 1
+    enf p.last = alpha * betas[1]
+
+integrity_constraints:
+    enf clk' = (clk + 2) * 1
+    enf s^2 = s
+    enf a[0]' = (a[0] + 2) * 3
+    enf a[1]' = (a[1] + 2) * 3
+    enf a[2]' = (a[2] + 2) * 3
+    enf k * (a[2] - a[1]) = 0 when s
+    enf p' = p * (a[0] + betas[1]) except last 2
+";
+    assert_eq!(flat, expected);
+
+    // the re-emitted program compiles to the same flat constraints
+    let reemitted = compile(&flat).expect("re-emitted program failed to compile");
+    assert_eq!(FlatAirScript::new().generate(&reemitted).unwrap(), flat);
+    for segment in 0..2 {
+        assert_eq!(
+            air.integrity_constraints(segment).len(),
+            reemitted.integrity_constraints(segment).len()
+        );
+        assert_eq!(
+            air.boundary_constraints(segment).len(),
+            reemitted.boundary_constraints(segment).len()
+        );
+    }
+}
+
+#[test]
+fn flat_reemission_preserves_structure() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c]
+
+    public_inputs:
+        stack_inputs: [4]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a - (b - c) = (a * b)^2 * (c * (a + b))";
+
+    let air = compile(source).expect("compilation failed");
+    let flat = FlatAirScript::new().generate(&air).unwrap();
+    assert!(flat.contains("    enf a - (b - c) = (a * b)^2 * (c * (a + b))\n"));
+
+    let reemitted = compile(&flat).expect("re-emitted program failed to compile");
+    assert_eq!(air.canonical_hash(), reemitted.canonical_hash());
+}
+
+#[test]
+fn flat_reemission_keeps_doc_strings() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk "clock cycle", fmp[2] "free memory pointer", a]
+
+    public_inputs:
+        stack_inputs: [4] "initial stack"
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + 1"#;
+
+    let air = compile(source).expect("compilation failed");
+    let flat = FlatAirScript::new().generate(&air).unwrap();
+    assert!(flat.contains(r#"main: [clk "clock cycle", fmp[2] "free memory pointer", a]"#));
+    assert!(flat.contains(r#"stack_inputs: [4] "initial stack""#));
+
+    let reemitted = compile(&flat).expect("re-emitted program failed to compile");
+    assert_eq!(
+        reemitted.trace_columns[0].bindings[1].doc(),
+        Some("free memory pointer")
+    );
+}
@@ -0,0 +1,492 @@
+use air_pass::Pass;
+
+use crate::{
+    passes::{
+        BalanceExpressions, DeadCodeElimination, LowerSubtraction, NormalizeBooleanChecks,
+        RaiseSubtraction,
+    },
+    AlgebraicGraph, ConstraintDomain, GraphError, Node, NodeIndex, Operation, PeriodicColumnAccess,
+    PublicInputAccess, TraceAccess, Value, ValueCollector, Visit, VisitContext,
+};
+
+use super::compile;
+
+#[test]
+fn value_collector() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    periodic_columns:
+        k: [1, 0, 0, 0]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf clk.first = stack_inputs[1]
+        enf p.first = 1
+    integrity_constraints:
+        enf clk' = clk + k * 5
+        enf p' = p * ($rand[0] + a)";
+
+    let air = compile(source).expect("compilation failed");
+
+    let mut collector = ValueCollector::default();
+    collector.visit_constraints(&air);
+    let values = collector.into_values();
+
+    let k = air.periodic_columns.keys().next().copied().unwrap();
+    let stack_inputs = *air.public_inputs.keys().next().unwrap();
+    let expected = [
+        Value::TraceAccess(TraceAccess::new(0, 0, 0)),
+        Value::PublicInput(PublicInputAccess::new(stack_inputs, 1)),
+        Value::TraceAccess(TraceAccess::new(0, 0, 1)),
+        Value::PeriodicColumn(PeriodicColumnAccess::new(k, 4)),
+        Value::Constant(5),
+        Value::TraceAccess(TraceAccess::new(1, 0, 0)),
+        Value::Constant(1),
+        Value::TraceAccess(TraceAccess::new(1, 0, 1)),
+        Value::RandomValue(0),
+        Value::TraceAccess(TraceAccess::new(0, 1, 0)),
+    ];
+    assert_eq!(values.len(), expected.len(), "{values:#?}");
+    for value in expected.iter() {
+        assert!(values.contains(value), "expected {value:?} in {values:#?}");
+    }
+    // the values are collected in the order in which they are first visited
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn visit_postorder_visits_children_first() {
+    #[derive(Default)]
+    struct Order(Vec<crate::NodeIndex>);
+    impl VisitContext for Order {
+        fn visit(&mut self, graph: &crate::AlgebraicGraph, node_index: crate::NodeIndex) {
+            for child in graph.children(&node_index).into_iter().flatten() {
+                assert!(self.0.contains(&child));
+            }
+            assert!(!self.0.contains(&node_index));
+            self.0.push(node_index);
+        }
+    }
+
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        let x = a + b
+        enf a' = x * x
+        enf b' = x^2";
+
+    let air = compile(source).expect("compilation failed");
+
+    let mut order = Order::default();
+    order.visit_constraints(&air);
+    assert!(!order.0.is_empty());
+}
+
+#[test]
+fn dead_code_elimination_removes_orphans() {
+    #[derive(Default)]
+    struct Count(usize);
+    impl VisitContext for Count {
+        fn visit(&mut self, _graph: &crate::AlgebraicGraph, _node_index: crate::NodeIndex) {
+            self.0 += 1;
+        }
+    }
+
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + b
+        enf b' = a * b";
+
+    let mut air = compile(source).expect("compilation failed");
+    let degrees = air.integrity_constraint_degrees(0);
+    let mut reachable = Count::default();
+    reachable.visit_constraints(&air);
+
+    // add orphan nodes, one of which references a reachable node
+    let graph = air.constraint_graph_mut();
+    let orphan = graph.insert_node(Operation::Value(Value::Constant(12345)));
+    let a = graph.insert_node(Operation::Value(Value::TraceAccess(TraceAccess::new(
+        0, 0, 0,
+    ))));
+    graph.insert_node(Operation::Mul(orphan, a));
+    assert_eq!(air.constraint_graph().num_nodes(), reachable.0 + 2);
+
+    let air = DeadCodeElimination::new()
+        .run(air)
+        .expect("dead code elimination failed");
+
+    assert_eq!(air.constraint_graph().num_nodes(), reachable.0);
+    assert_eq!(air.integrity_constraint_degrees(0), degrees);
+    let mut collector = ValueCollector::default();
+    collector.visit_constraints(&air);
+    assert!(!collector.values().contains(&Value::Constant(12345)));
+    let mut count = Count::default();
+    count.visit_constraints(&air);
+    assert_eq!(count.0, reachable.0);
+}
+
+#[test]
+fn nodes_indexed() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf a' = a + b
+        enf b' = a * b^2";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    assert_eq!(graph.nodes_indexed().len(), graph.num_nodes());
+
+    let mut operations = Vec::with_capacity(graph.num_nodes());
+    for (index, node) in graph.nodes_indexed() {
+        assert_eq!(graph.node(&index).op(), node.op());
+        // operands are always yielded before the nodes which reference them
+        for child in graph.children(&index).into_iter().flatten() {
+            assert!(operations.len() > child.as_usize());
+        }
+        operations.push(*node.op());
+    }
+
+    // the graph can be reconstructed from the operations, in the order they were yielded
+    let rebuilt = AlgebraicGraph::new(operations.iter().map(|op| crate::Node::new(*op)).collect());
+    for constraint in air.integrity_constraints(0) {
+        assert_eq!(
+            rebuilt.degree(constraint.node_index()),
+            graph.degree(constraint.node_index())
+        );
+    }
+    let rebuilt_operations = rebuilt
+        .nodes_indexed()
+        .map(|(_, node)| *node.op())
+        .collect::<Vec<_>>();
+    assert_eq!(rebuilt_operations, operations);
+}
+
+/// Returns the number of operations on the longest path from the specified node to a leaf
+fn depth(graph: &AlgebraicGraph, index: &NodeIndex) -> usize {
+    graph
+        .children(index)
+        .into_iter()
+        .flatten()
+        .map(|child| depth(graph, &child) + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn balance_expressions_sum_has_logarithmic_depth() {
+    let terms = (0..16).map(|i| format!("a[{i}]")).collect::<Vec<_>>();
+    let source = format!(
+        "
+    def test
+    trace_columns:
+        main: [clk, a[16]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+    integrity_constraints:
+        enf clk' = {}
+        enf a[0]' = a[0] * a[1] * a[2] * (a[3] - a[4] - a[5])",
+        terms.join(" + ")
+    );
+
+    let air = compile(&source).expect("compilation failed");
+    let degrees = air.integrity_constraint_degrees(0);
+    let num_nodes = air.constraint_graph().num_nodes();
+    let root = *air.integrity_constraints(0)[0].node_index();
+    // the sum is subtracted from `clk'`, below a chain of 15 additions
+    assert_eq!(depth(air.constraint_graph(), &root), 16);
+
+    let air = BalanceExpressions::new()
+        .run(air)
+        .expect("balancing expressions failed");
+
+    let graph = air.constraint_graph();
+    let root = *air.integrity_constraints(0)[0].node_index();
+    // the 16 terms of the sum are added in a balanced tree of depth log2(16) = 4
+    assert_eq!(depth(graph, &root), 5);
+    assert_eq!(air.integrity_constraint_degrees(0), degrees);
+
+    // the chain of 3 multiplications is shorter than the default threshold, and is left as written
+    let root = *air.integrity_constraints(0)[1].node_index();
+    assert_eq!(depth(graph, &root), 4);
+
+    // the nodes of the original chain are removed, and replaced by as many balanced nodes
+    assert_eq!(graph.num_nodes(), num_nodes);
+}
+
+#[test]
+fn boolean_checks_normalize_to_one_node() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf a^2 = a
+        enf a * (a - 1) = 0
+        enf a - a * a = 0
+        enf b' = a * (b - 1)";
+
+    let air = compile(source).expect("compilation failed");
+    let roots = |air: &crate::Air| {
+        air.integrity_constraints(0)
+            .iter()
+            .map(|constraint| *constraint.node_index())
+            .collect::<Vec<_>>()
+    };
+    let before = roots(&air);
+    assert_ne!(before[0], before[1]);
+    assert_ne!(before[0], before[2]);
+
+    let air = NormalizeBooleanChecks::new()
+        .run(air)
+        .expect("normalizing boolean checks failed");
+
+    let graph = air.constraint_graph();
+    let after = roots(&air);
+    assert_eq!(after[0], after[1]);
+    assert_eq!(after[0], after[2]);
+    let operand = graph
+        .boolean_check(&after[0])
+        .expect("expected a boolean check");
+    assert_eq!(
+        graph.node(&operand).op(),
+        &Operation::Value(Value::TraceAccess(TraceAccess::new(0, 0, 0)))
+    );
+
+    // other constraints are left as they are
+    assert!(graph.boolean_check(&after[3]).is_none());
+    assert_eq!(air.integrity_constraint_degrees(0)[3].base(), 2);
+}
+
+#[test]
+fn validate_graph() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + b
+        enf b' = a * b";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.constraint_graph().validate(), Ok(()));
+
+    let a = Node::new(Operation::Value(Value::TraceAccess(TraceAccess::new(
+        0, 0, 0,
+    ))));
+    let index = |i| NodeIndex::default() + i;
+
+    // a child past the end of the graph
+    let graph = AlgebraicGraph::new(vec![
+        a.clone(),
+        Node::new(Operation::Add(index(0), index(2))),
+    ]);
+    assert_eq!(
+        graph.validate(),
+        Err(GraphError::ChildOutOfRange {
+            node: 1,
+            child: 2,
+            num_nodes: 2
+        })
+    );
+
+    // a child which does not precede its parent forms a cycle
+    let graph = AlgebraicGraph::new(vec![
+        a.clone(),
+        Node::new(Operation::Mul(index(0), index(2))),
+        Node::new(Operation::Exp(index(1), 2)),
+    ]);
+    assert_eq!(
+        graph.validate(),
+        Err(GraphError::ChildAfterParent { node: 1, child: 2 })
+    );
+
+    // a node referencing itself
+    let graph = AlgebraicGraph::new(vec![a, Node::new(Operation::Sub(index(1), index(0)))]);
+    assert_eq!(
+        graph.validate(),
+        Err(GraphError::ChildAfterParent { node: 1, child: 1 })
+    );
+}
+
+/// Evaluates the specified node in the 64-bit field, with the value of each trace access given
+/// by `trace`, and every other value being zero.
+fn evaluate(
+    graph: &AlgebraicGraph,
+    index: &NodeIndex,
+    trace: &impl Fn(&TraceAccess) -> u64,
+) -> u64 {
+    const MODULUS: u128 = 0xFFFFFFFF00000001;
+    let value = graph.fold(index, |op, children: &[u64]| {
+        let operand = |i: usize| children[i] as u128;
+        let value = match op {
+            Operation::Value(Value::Constant(value)) => *value as u128,
+            Operation::Value(Value::TraceAccess(access)) => trace(access) as u128,
+            Operation::Value(_) => 0,
+            Operation::Add(..) => operand(0) + operand(1),
+            Operation::Sub(..) => operand(0) + MODULUS - operand(1),
+            Operation::Mul(..) => operand(0) * operand(1),
+            Operation::Exp(_, exp) => (0..*exp).fold(1, |acc, _| acc * operand(0) % MODULUS),
+            Operation::Inv(_) => {
+                // the inverse is `x^(p - 2)`, computed by square-and-multiply
+                let mut base = operand(0);
+                let mut exp = MODULUS - 2;
+                let mut acc = 1;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        acc = acc * base % MODULUS;
+                    }
+                    base = base * base % MODULUS;
+                    exp >>= 1;
+                }
+                acc
+            }
+        };
+        Ok::<_, core::convert::Infallible>((value % MODULUS) as u64)
+    });
+    match value {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
+#[test]
+fn deep_graph_traversals() {
+    // a chain of 200000 multiplications, `((a * a') * a) * a' ...`, which is far deeper than a
+    // recursive traversal of the graph could walk on the stack of a test thread
+    const DEPTH: usize = 200_000;
+    let index = |i| NodeIndex::default() + i;
+    let mut nodes = vec![
+        Node::new(Operation::Value(Value::TraceAccess(TraceAccess::new(
+            0, 0, 0,
+        )))),
+        Node::new(Operation::Value(Value::TraceAccess(TraceAccess::new(
+            0, 0, 1,
+        )))),
+    ];
+    for i in 0..DEPTH {
+        let lhs = if i == 0 { index(0) } else { index(i + 1) };
+        nodes.push(Node::new(Operation::Mul(lhs, index(i % 2))));
+    }
+    let graph = AlgebraicGraph::new(nodes);
+    let tip = index(DEPTH + 1);
+
+    assert_eq!(graph.degree(&tip).base(), DEPTH + 1);
+    assert_eq!(
+        graph
+            .node_details(&tip, ConstraintDomain::EveryRow)
+            .expect("the domains of the chain are compatible"),
+        (0, ConstraintDomain::EveryFrame(2))
+    );
+    assert_eq!(evaluate(&graph, &tip, &|_| 1), 1);
+
+    // the canonical hash of an Air constrained by the chain is computed without recursion either
+    let hash = |root: NodeIndex| {
+        let mut air = crate::Air::new(crate::Identifier::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            crate::Symbol::intern("deep"),
+        ));
+        air.trace_segment_widths = vec![1];
+        *air.constraint_graph_mut() = graph.clone();
+        air.constraints.insert_constraint(
+            0,
+            root,
+            ConstraintDomain::EveryFrame(2),
+            miden_diagnostics::SourceSpan::UNKNOWN,
+        );
+        air.canonical_hash()
+    };
+    assert_ne!(hash(tip), hash(index(DEPTH)));
+}
+
+#[test]
+fn lower_and_raise_subtraction() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0] - 1
+    integrity_constraints:
+        enf a' = a - b
+        enf b' = (a - b) * (c - 7) - b^2
+        enf c' = c + 3";
+
+    let air = compile(source).expect("compilation failed");
+    let trace = |access: &TraceAccess| [11, 2, 5][access.column] + 100 * access.row_offset as u64;
+    let evaluations = |air: &crate::Air| {
+        let graph = air.constraint_graph();
+        air.boundary_constraints(0)
+            .iter()
+            .chain(air.integrity_constraints(0))
+            .map(|constraint| evaluate(graph, constraint.node_index(), &trace))
+            .collect::<Vec<_>>()
+    };
+    let has_subtraction = |air: &crate::Air| {
+        air.constraint_graph()
+            .nodes_indexed()
+            .any(|(_, node)| matches!(node.op(), Operation::Sub(..)))
+    };
+    let expected = evaluations(&air);
+    let degrees = air.integrity_constraint_degrees(0);
+    let num_nodes = air.constraint_graph().num_nodes();
+    assert!(has_subtraction(&air));
+
+    let air = LowerSubtraction::new()
+        .run(air)
+        .expect("lowering subtraction failed");
+    assert!(!has_subtraction(&air));
+    assert_eq!(air.constraint_graph().validate(), Ok(()));
+    assert_eq!(evaluations(&air), expected);
+    assert_eq!(air.integrity_constraint_degrees(0), degrees);
+
+    let air = RaiseSubtraction::new()
+        .run(air)
+        .expect("raising subtraction failed");
+    assert!(has_subtraction(&air));
+    assert!(
+        !air.constraint_graph()
+            .nodes_indexed()
+            .any(|(_, node)| node.op()
+                == &Operation::Value(Value::Constant(crate::passes::MINUS_ONE)))
+    );
+    assert_eq!(evaluations(&air), expected);
+    assert_eq!(air.integrity_constraint_degrees(0), degrees);
+    assert_eq!(air.constraint_graph().num_nodes(), num_nodes);
+}
@@ -1,4 +1,4 @@
-use super::super::compile;
+use super::super::{compile, expect_diagnostic};
 
 #[test]
 fn constraint_comprehension() {
@@ -33,3 +33,112 @@ fn ic_comprehension_with_selectors() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn ic_comprehension_over_main_column_count() {
+    let source = |main: &str| {
+        format!(
+            "
+    def test
+    trace_columns:
+        main: {main}
+        aux: [p]
+    const WIDTH = NUM_MAIN_COLUMNS
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf p.first = NUM_AUX_COLUMNS
+    integrity_constraints:
+        enf col' = col + i for (col, i) in ($main, 0..NUM_MAIN_COLUMNS)
+        enf p' = p * WIDTH"
+        )
+    };
+
+    // the comprehension adapts to the number of columns of the main trace
+    let air = compile(&source("[a, b, c]")).unwrap();
+    assert_eq!(air.integrity_constraints(0).len(), 3);
+
+    let air = compile(&source("[a, b[3], c]")).unwrap();
+    assert_eq!(air.integrity_constraints(0).len(), 5);
+}
+
+#[test]
+fn err_main_column_count_before_trace_columns() {
+    let source = "
+    def test
+    const WIDTH = NUM_MAIN_COLUMNS
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf a' = a + WIDTH";
+
+    expect_diagnostic(
+        source,
+        "the number of trace columns is only known after the trace_columns section",
+    );
+}
+
+#[test]
+fn ic_comprehension_with_constant_filter() {
+    let source = |mask: &str| {
+        format!(
+            "
+    def test
+    const MASK = {mask}
+    trace_columns:
+        main: [clk, c[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf c = 0 for (c, m) in (c, MASK) if m"
+        )
+    };
+
+    // only the constraints of the columns selected by the mask are generated
+    let air = compile(&source("[1, 0, 1, 1]")).unwrap();
+    assert_eq!(air.integrity_constraints(0).len(), 4);
+
+    let air = compile(&source("[0, 0, 0, 0]")).unwrap();
+    assert_eq!(air.integrity_constraints(0).len(), 1);
+}
+
+#[test]
+fn ic_comprehension_with_filter_over_range() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, c[8]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf c' = c + i for (c, i) in (c, 0..8) if i when clk";
+
+    // the constraint of the first column is omitted, and the selector still applies to the others
+    let air = compile(source).unwrap();
+    assert_eq!(air.integrity_constraints(0).len(), 7);
+}
+
+#[test]
+fn err_ic_comprehension_with_trace_filter() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, c[4], s[4]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf c = 0 for (c, s) in (c, s) if s";
+
+    expect_diagnostic(source, "this filter cannot be evaluated at compile time");
+}
@@ -1,3 +1,5 @@
+use crate::{ConstraintDomain, AUX_SEGMENT, DEFAULT_SEGMENT};
+
 use super::{compile, expect_diagnostic};
 
 mod comprehension;
@@ -85,3 +87,48 @@ fn err_non_const_exp_outside_lc() {
 
     expect_diagnostic(source, "expected exponent to be a constant");
 }
+
+#[test]
+fn validity_and_transition_constraints() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a, b]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [1]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf a^2 = a
+        enf b = a * clk
+        enf a' = a + b'
+        enf p = p * $rand[0]";
+
+    let air = compile(source).expect("compilation failed");
+
+    // constraints are classified by whether they reference the next row, in declaration order
+    let validity = air
+        .validity_constraints(DEFAULT_SEGMENT)
+        .cloned()
+        .collect::<Vec<_>>();
+    let transition = air
+        .transition_constraints(DEFAULT_SEGMENT)
+        .cloned()
+        .collect::<Vec<_>>();
+    let constraints = air.integrity_constraints(DEFAULT_SEGMENT);
+    assert_eq!(validity, [constraints[1].clone(), constraints[2].clone()]);
+    assert_eq!(transition, [constraints[0].clone(), constraints[3].clone()]);
+    assert!(validity
+        .iter()
+        .all(|constraint| constraint.domain() == ConstraintDomain::EveryRow));
+    assert!(transition
+        .iter()
+        .all(|constraint| constraint.domain() == ConstraintDomain::EveryFrame(2)));
+
+    assert_eq!(air.validity_constraints(AUX_SEGMENT).count(), 1);
+    assert_eq!(air.transition_constraints(AUX_SEGMENT).count(), 0);
+}
@@ -0,0 +1,89 @@
+use crate::{CodeGenerator, FlatAirScript, Operation};
+
+use super::{compile, expect_diagnostic, Compiler};
+
+static SOURCE: &str = "
+def test
+
+trace_columns:
+    main: [a, b]
+
+public_inputs:
+    stack_inputs: [16]
+
+boundary_constraints:
+    enf a.first = 1
+
+integrity_constraints:
+    enf b = a^(-1)
+    enf b' = a^(-3) + 2^(-1)
+";
+
+#[test]
+fn inverse_builds_inverse_node() {
+    let air = Compiler::default()
+        .inverses(true)
+        .try_compile(SOURCE)
+        .expect("compilation failed");
+    let graph = air.constraint_graph();
+    let constraints = air.integrity_constraints(0);
+
+    // `b - a^(-1)` is the subtraction of the inverse of `a`
+    let Operation::Sub(_, rhs) = graph.node(constraints[0].node_index()).op() else {
+        panic!("expected a subtraction");
+    };
+    let Operation::Inv(operand) = graph.node(rhs).op() else {
+        panic!("expected an inverse, got {:?}", graph.node(rhs).op());
+    };
+    assert!(matches!(graph.node(operand).op(), Operation::Value(_)));
+
+    // `a^(-3)` is the inverse of `a^3`
+    let inverses = graph
+        .nodes_indexed()
+        .filter_map(|(_, node)| match node.op() {
+            Operation::Inv(operand) => Some(*graph.node(operand).op()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(inverses.len(), 3);
+    assert!(inverses.iter().any(|op| matches!(op, Operation::Exp(_, 3))));
+}
+
+#[test]
+fn inverse_degree_is_unbounded() {
+    let air = Compiler::default()
+        .inverses(true)
+        .try_compile(SOURCE)
+        .expect("compilation failed");
+    let graph = air.constraint_graph();
+
+    for constraint in air.integrity_constraints(0) {
+        assert!(graph.degree(constraint.node_index()).is_unbounded());
+    }
+}
+
+#[test]
+fn inverse_is_reemitted() {
+    let air = Compiler::default()
+        .inverses(true)
+        .try_compile(SOURCE)
+        .expect("compilation failed");
+    let flat = FlatAirScript::new().generate(&air).unwrap();
+    assert!(flat.contains("enf b = a^(-1)"), "{flat}");
+    assert!(flat.contains("enf b' = a^(-3) + 2^(-1)"), "{flat}");
+
+    let reemitted = Compiler::default()
+        .inverses(true)
+        .try_compile(&flat)
+        .expect("compilation failed");
+    assert_eq!(reemitted.canonical_hash(), air.canonical_hash());
+}
+
+#[test]
+fn err_inverse_not_enabled() {
+    assert!(compile(SOURCE).is_err());
+    expect_diagnostic(
+        SOURCE,
+        "negative exponents denote field inverses, which are not enabled",
+    );
+}
@@ -1,3 +1,5 @@
+use crate::{TraceAccess, Value, ValueCollector, Visit, AUX_SEGMENT, DEFAULT_SEGMENT};
+
 use super::compile;
 
 #[test]
@@ -99,3 +101,60 @@ fn list_folding_in_lc() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn list_folding_on_trace_segments() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a[3]]
+        aux: [p, q]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [2]
+    boundary_constraints:
+        enf clk.first = 0
+        enf p.first = 1
+    integrity_constraints:
+        let x = sum([col for col in $main])
+        enf clk' = x
+        let y = prod([col + r for (col, r) in ($aux, $rand)])
+        enf p' = y";
+
+    let air = compile(source).expect("compilation failed");
+
+    // the comprehension over `$main` expands into an access to each column of the main segment
+    let mut collector = ValueCollector::default();
+    let root = *air.integrity_constraints(DEFAULT_SEGMENT)[0].node_index();
+    collector.visit_postorder(air.constraint_graph(), [root]);
+    let expected = [
+        Value::TraceAccess(TraceAccess::new(DEFAULT_SEGMENT, 0, 1)),
+        Value::TraceAccess(TraceAccess::new(DEFAULT_SEGMENT, 0, 0)),
+        Value::TraceAccess(TraceAccess::new(DEFAULT_SEGMENT, 1, 0)),
+        Value::TraceAccess(TraceAccess::new(DEFAULT_SEGMENT, 2, 0)),
+        Value::TraceAccess(TraceAccess::new(DEFAULT_SEGMENT, 3, 0)),
+    ];
+    assert_eq!(collector.values(), expected);
+
+    // the comprehension over `$aux` expands into an access to each column of the aux segment
+    let mut collector = ValueCollector::default();
+    let root = *air.integrity_constraints(AUX_SEGMENT)[0].node_index();
+    collector.visit_postorder(air.constraint_graph(), [root]);
+    let columns = collector
+        .values()
+        .iter()
+        .filter_map(|value| match value {
+            Value::TraceAccess(access) if access.row_offset == 0 => Some(*access),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        columns,
+        [
+            TraceAccess::new(AUX_SEGMENT, 0, 0),
+            TraceAccess::new(AUX_SEGMENT, 1, 0)
+        ]
+    );
+    assert_eq!(air.integrity_constraint_degrees(AUX_SEGMENT)[0].base(), 2);
+}
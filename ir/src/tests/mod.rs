@@ -1,33 +1,45 @@
 mod access;
+mod assertions;
+mod aux_random_values;
 mod boundary_constraints;
+mod canonical_hash;
+mod composition_degree;
 mod constant;
+mod constraint_limit;
+mod degree;
+mod degree_budget;
+mod diagnostics;
+mod diff;
 mod evaluators;
+mod exemptions;
+mod flat;
+mod graph;
 mod integrity_constraints;
+mod inverse;
 mod list_folding;
+mod next_row;
+mod params;
+mod programs;
 mod pub_inputs;
 mod random_values;
+mod scalar_multiples;
 mod selectors;
 mod source_sections;
+mod ssa;
 mod trace;
+mod unused;
 mod variables;
 
 pub use crate::CompileError;
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
-use air_pass::Pass;
 use miden_diagnostics::{CodeMap, DiagnosticsConfig, DiagnosticsHandler, Verbosity};
 
+use crate::{Pipeline, PipelineOptions};
+
 pub fn compile(source: &str) -> Result<crate::Air, ()> {
-    let compiler = Compiler::default();
-    match compiler.compile(source) {
-        Ok(air) => Ok(air),
-        Err(err) => {
-            compiler.diagnostics.emit(err);
-            compiler.emitter.print_captured_to_stderr();
-            Err(())
-        }
-    }
+    Compiler::default().try_compile(source)
 }
 
 #[track_caller]
@@ -51,10 +63,32 @@ pub fn expect_diagnostic(source: &str, expected: &str) {
     );
 }
 
+/// Compiles each of the root modules defined in `source` into an independent [crate::Air], keyed
+/// by name
+pub fn compile_programs(source: &str) -> Result<BTreeMap<String, crate::Air>, ()> {
+    let compiler = Compiler::default();
+    compiler.emit_on_err(compiler.compile_programs(source))
+}
+
+/// Compiles `source`, which is expected to succeed, and returns the diagnostic output
+#[track_caller]
+pub fn compile_with_warnings(source: &str) -> String {
+    let compiler = Compiler::default();
+    if let Err(err) = compiler.compile(source) {
+        compiler.diagnostics.emit(err);
+        compiler.emitter.print_captured_to_stderr();
+        panic!("expected compilation to succeed");
+    }
+    compiler.emitter.captured()
+}
+
 struct Compiler {
     codemap: Arc<CodeMap>,
     emitter: Arc<SplitEmitter>,
     diagnostics: Arc<DiagnosticsHandler>,
+    inverses: bool,
+    integrity_public_inputs: bool,
+    params: air_parser::Params,
 }
 impl Default for Compiler {
     fn default() -> Self {
@@ -80,19 +114,66 @@ impl Compiler {
             codemap,
             emitter,
             diagnostics,
+            inverses: false,
+            integrity_public_inputs: false,
+            params: Default::default(),
         }
     }
 
+    /// Enables the field inverses, see [crate::passes::AstToAir::with_inverses]
+    pub fn inverses(mut self, enabled: bool) -> Self {
+        self.inverses = enabled;
+        self
+    }
+
+    /// Permits accesses to public inputs in integrity constraints, see
+    /// [crate::passes::AstToAir::with_integrity_public_inputs]
+    pub fn integrity_public_inputs(mut self, enabled: bool) -> Self {
+        self.integrity_public_inputs = enabled;
+        self
+    }
+
+    /// Binds the parameters of the compiled programs to the values in `params`, see
+    /// [air_parser::parse_with_params]
+    pub fn params(mut self, params: &[(&str, u64)]) -> Self {
+        self.params = params
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        self
+    }
+
+    /// Compiles `source`, printing the diagnostics to stderr if compilation fails
+    pub fn try_compile(&self, source: &str) -> Result<crate::Air, ()> {
+        self.emit_on_err(self.compile(source))
+    }
+
     pub fn compile(&self, source: &str) -> Result<crate::Air, CompileError> {
-        air_parser::parse(&self.diagnostics, self.codemap.clone(), source)
-            .map_err(CompileError::Parse)
-            .and_then(|ast| {
-                let mut pipeline =
-                    air_parser::transforms::ConstantPropagation::new(&self.diagnostics)
-                        .chain(air_parser::transforms::Inlining::new(&self.diagnostics))
-                        .chain(crate::passes::AstToAir::new(&self.diagnostics));
-                pipeline.run(ast)
-            })
+        self.pipeline().compile(source)
+    }
+
+    pub fn compile_programs(
+        &self,
+        source: &str,
+    ) -> Result<BTreeMap<String, crate::Air>, CompileError> {
+        self.pipeline().compile_programs(source)
+    }
+
+    fn emit_on_err<T>(&self, result: Result<T, CompileError>) -> Result<T, ()> {
+        result.map_err(|err| {
+            self.diagnostics.emit(err);
+            self.emitter.print_captured_to_stderr();
+        })
+    }
+
+    fn pipeline(&self) -> Pipeline<'_> {
+        let options = PipelineOptions {
+            params: self.params.clone(),
+            inverses: self.inverses,
+            integrity_public_inputs: self.integrity_public_inputs,
+            ..Default::default()
+        };
+        Pipeline::new(&self.diagnostics, self.codemap.clone(), options)
     }
 }
 
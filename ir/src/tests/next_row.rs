@@ -0,0 +1,86 @@
+use air_pass::Pass;
+
+use crate::passes::NextRowOnlyColumns;
+
+use super::Compiler;
+
+/// Compiles `source`, which is expected to succeed, and runs the [NextRowOnlyColumns] pass,
+/// returning the diagnostic output
+fn check_next_row(source: &str) -> String {
+    let compiler = Compiler::default();
+    let air = match compiler.compile(source) {
+        Ok(air) => air,
+        Err(err) => {
+            compiler.diagnostics.emit(err);
+            compiler.emitter.print_captured_to_stderr();
+            panic!("expected compilation to succeed");
+        }
+    };
+    NextRowOnlyColumns::new(&compiler.diagnostics)
+        .run(air)
+        .expect("expected the pass to succeed");
+    compiler.emitter.captured()
+}
+
+#[test]
+fn column_only_in_next_row() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf a' = b * clk";
+
+    let output = check_next_row(source);
+    assert!(output.contains("trace column only referenced in the next row"));
+    assert!(output.contains("`a` is only referenced at a row offset"));
+    assert!(!output.contains("`clk`"));
+    assert!(!output.contains("`b`"));
+}
+
+#[test]
+fn column_of_group_only_in_next_row() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, c[3]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf c[0]' = c[0] + c[1]
+        enf c[2]' = clk";
+
+    let output = check_next_row(source);
+    assert!(output.contains("`c[2]` is only referenced at a row offset"));
+    assert!(!output.contains("`c[0]`"));
+    assert!(!output.contains("`c[1]`"));
+}
+
+#[test]
+fn column_in_current_and_next_row() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf a' = a * clk";
+
+    let output = check_next_row(source);
+    assert!(
+        !output.contains("trace column only referenced in the next row"),
+        "{output}"
+    );
+}
@@ -0,0 +1,122 @@
+use super::{compile, expect_diagnostic, Compiler};
+
+const SOURCE: &str = "
+    def test
+    param N: 2
+    trace_columns:
+        main: [clk, stack[N]]
+    public_inputs:
+        stack_inputs: [N]
+    boundary_constraints:
+        enf stack.first = stack_inputs
+    integrity_constraints:
+        enf clk' = clk + N
+        enf s' = s for s in stack[0..N]";
+
+#[test]
+fn params_default_value() {
+    let air = compile(SOURCE).expect("compilation failed");
+    assert_eq!(air.trace_segment_widths[0], 3);
+    assert_eq!(air.boundary_constraints(0).len(), 2);
+    assert_eq!(air.integrity_constraints(0).len(), 3);
+}
+
+#[test]
+fn params_bound_values() {
+    let small = Compiler::default()
+        .params(&[("N", 4)])
+        .try_compile(SOURCE)
+        .expect("compilation failed");
+    let large = Compiler::default()
+        .params(&[("N", 8)])
+        .try_compile(SOURCE)
+        .expect("compilation failed");
+
+    assert_eq!(small.trace_segment_widths[0], 5);
+    assert_eq!(small.boundary_constraints(0).len(), 4);
+    assert_eq!(small.integrity_constraints(0).len(), 5);
+    assert_eq!(large.trace_segment_widths[0], 9);
+    assert_eq!(large.boundary_constraints(0).len(), 8);
+    assert_eq!(large.integrity_constraints(0).len(), 9);
+}
+
+#[test]
+fn params_as_index_and_random_values_size() {
+    let source = "
+    def test
+    param N: 2
+    param K: 1
+    trace_columns:
+        main: [clk, stack[N]]
+        aux: [p]
+    public_inputs:
+        stack_inputs: [N]
+    random_values:
+        rand: [N]
+    boundary_constraints:
+        enf stack[K].first = stack_inputs[K]
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf p' = p * (stack[K] + $rand[K])";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn err_unbound_param() {
+    let source = "
+    def test
+    param N
+    trace_columns:
+        main: [clk, stack[N]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "unbound parameter");
+}
+
+#[test]
+fn err_undefined_param() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, stack[N]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "undefined parameter");
+}
+
+#[test]
+fn err_duplicate_param() {
+    let source = "
+    def test
+    param N: 2
+    param N: 4
+    trace_columns:
+        main: [clk, stack[N]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "duplicate parameter declaration");
+}
+
+#[test]
+fn err_undeclared_param_binding() {
+    assert!(Compiler::default()
+        .params(&[("M", 4)])
+        .try_compile(SOURCE)
+        .is_err());
+}
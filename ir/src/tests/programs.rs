@@ -0,0 +1,149 @@
+use super::{compile_programs, expect_diagnostic, Compiler};
+
+#[test]
+fn multiple_root_modules() {
+    let source = "
+    def alpha
+
+    use helpers::binary
+
+    trace_columns:
+        main: [a, b]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = inputs[0]
+
+    integrity_constraints:
+        enf binary([a])
+        enf b' = b + a
+
+    def beta
+
+    trace_columns:
+        main: [x, y, z]
+        aux: [p]
+
+    public_inputs:
+        outputs: [4]
+
+    random_values:
+        rand: [1]
+
+    boundary_constraints:
+        enf x.last = outputs[0]
+        enf p.first = 1
+
+    integrity_constraints:
+        enf x' = x + y
+        enf p' = p * (z + $rand[0])
+
+    mod helpers
+
+    ev binary([v]):
+        enf v^2 = v
+    ";
+
+    let airs = compile_programs(source).expect("compilation failed");
+    let names = airs.keys().map(|name| name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["alpha", "beta"]);
+
+    let alpha = &airs["alpha"];
+    assert_eq!(alpha.name(), "alpha");
+    assert_eq!(alpha.trace_segment_widths, [2]);
+    assert_eq!(alpha.public_inputs().count(), 1);
+    assert_eq!(alpha.num_boundary_constraints(0), 1);
+    assert_eq!(alpha.integrity_constraints(0).len(), 2);
+
+    let beta = &airs["beta"];
+    assert_eq!(beta.name(), "beta");
+    assert_eq!(beta.trace_segment_widths, [3, 1]);
+    assert_eq!(beta.num_random_values, 1);
+    assert_eq!(beta.num_boundary_constraints(0), 1);
+    assert_eq!(beta.num_boundary_constraints(1), 1);
+    assert_eq!(beta.integrity_constraints(0).len(), 1);
+    assert_eq!(beta.integrity_constraints(1).len(), 1);
+
+    // each AIR is compiled independently of the other
+    assert_ne!(alpha.canonical_hash(), beta.canonical_hash());
+}
+
+#[test]
+fn multiple_root_modules_with_params() {
+    // the parameters bound when compiling are visible to each of the root modules
+    let source = "
+    def alpha
+    param N: 2
+
+    trace_columns:
+        main: [a[N]]
+
+    public_inputs:
+        inputs: [N]
+
+    boundary_constraints:
+        enf a.first = inputs
+
+    integrity_constraints:
+        enf x' = x for x in a
+
+    def beta
+
+    trace_columns:
+        main: [b[N]]
+
+    public_inputs:
+        inputs: [N]
+
+    boundary_constraints:
+        enf b.first = inputs
+
+    integrity_constraints:
+        enf x' = x for x in b
+    ";
+
+    let compiler = Compiler::default().params(&[("N", 4)]);
+    let airs = compiler
+        .emit_on_err(compiler.compile_programs(source))
+        .expect("compilation failed");
+    assert_eq!(airs["alpha"].trace_segment_widths, [4]);
+    assert_eq!(airs["beta"].trace_segment_widths, [4]);
+}
+
+#[test]
+fn err_multiple_root_modules_single_program() {
+    // a source defining several root modules is not a single program
+    let source = "
+    def alpha
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = inputs[0]
+
+    integrity_constraints:
+        enf a' = a
+
+    def beta
+
+    trace_columns:
+        main: [b]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf b.first = inputs[0]
+
+    integrity_constraints:
+        enf b' = b
+    ";
+
+    expect_diagnostic(source, "unexpected token");
+}
@@ -1,4 +1,19 @@
-use super::compile;
+use crate::{ConstraintDomain, IntegrityConstraintDegree};
+
+use super::{compile, expect_diagnostic, Compiler};
+
+/// An AIR using a public input as a parameter of its integrity constraints
+static INTEGRITY_SOURCE: &str = "
+def test
+trace_columns:
+    main: [clk, a]
+public_inputs:
+    stack_inputs: [16]
+boundary_constraints:
+    enf clk.first = stack_inputs[0]
+integrity_constraints:
+    enf a = stack_inputs[1]
+    enf clk' = clk + stack_inputs[2] * a";
 
 #[test]
 fn bc_with_public_inputs() {
@@ -15,3 +30,56 @@ fn bc_with_public_inputs() {
 
     assert!(compile(source).is_ok());
 }
+
+#[test]
+fn public_inputs_in_declaration_order() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a]
+    public_inputs:
+        stack_outputs: [4]
+        program_hash: [4]
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+        enf clk.last = stack_outputs[0]
+        enf a.first = program_hash[0]
+    integrity_constraints:
+        enf clk' = clk + a";
+
+    let air = compile(source).expect("compilation failed");
+    let names = air
+        .public_inputs()
+        .map(|input| input.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["stack_outputs", "program_hash", "stack_inputs"]);
+}
+
+#[test]
+fn ic_with_public_inputs() {
+    let air = Compiler::default()
+        .integrity_public_inputs(true)
+        .try_compile(INTEGRITY_SOURCE)
+        .expect("compilation failed");
+    let constraints = air.integrity_constraints(0);
+    assert_eq!(constraints.len(), 2);
+    assert_eq!(constraints[0].domain(), ConstraintDomain::EveryRow);
+    assert_eq!(constraints[1].domain(), ConstraintDomain::EveryFrame(2));
+
+    // the public inputs are row-invariant values of degree 0, so `stack_inputs[2] * a` has the
+    // degree of `a`
+    assert_eq!(
+        air.integrity_constraint_degrees(0),
+        [
+            IntegrityConstraintDegree::new(1),
+            IntegrityConstraintDegree::new(1)
+        ]
+    );
+}
+
+#[test]
+fn err_ic_with_public_inputs_not_enabled() {
+    assert!(compile(INTEGRITY_SOURCE).is_err());
+    expect_diagnostic(INTEGRITY_SOURCE, "cannot access public input here");
+}
@@ -1,3 +1,5 @@
+use crate::{RandomValueAccess, Value, ValueCollector, Visit};
+
 use super::{compile, expect_diagnostic};
 
 #[test]
@@ -151,6 +153,26 @@ fn err_random_values_without_aux_cols() {
     );
 }
 
+#[test]
+fn err_random_values_with_empty_aux_trace() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: []
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [16]
+    boundary_constraints:
+        enf a.first = 2
+        enf a.last = 1
+    integrity_constraints:
+        enf a' = a + 1";
+
+    expect_diagnostic(source, "declaring random_values requires aux trace columns");
+}
+
 #[test]
 fn err_random_values_in_bc_against_main_cols() {
     let source = "
@@ -170,3 +192,120 @@ fn err_random_values_in_bc_against_main_cols() {
 
     expect_diagnostic(source, "Boundary constraints require both sides of the constraint to apply to the same trace segment");
 }
+
+#[test]
+fn random_values_binding_access_is_named() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: [c, d]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [betas[2], gamma, alphas[3]]
+    boundary_constraints:
+        enf c.first = betas[1] + $rand[4]
+        enf c.last = 1
+    integrity_constraints:
+        enf c' = alphas[2] * gamma";
+
+    let air = compile(source).expect("compilation failed");
+    let random_values = air.random_values.as_ref().unwrap();
+    assert_eq!(random_values.size, 6);
+    assert_eq!(random_values.bindings.len(), 3);
+
+    let mut collector = ValueCollector::default();
+    collector.visit_constraints(&air);
+    let values = collector.values();
+
+    // all accesses are lowered to the element of the random values array they refer to, and the
+    // binding of an element is looked up from its index
+    let binding = |name: &str| {
+        random_values
+            .bindings
+            .iter()
+            .find(|b| b.name == name)
+            .unwrap()
+    };
+    for (name, index, element) in [("betas", 1, 1), ("gamma", 0, 2), ("alphas", 2, 5)] {
+        assert!(values.contains(&Value::RandomValue(element)));
+        assert_eq!(
+            air.random_value_binding(element),
+            Some(RandomValueAccess::new(binding(name).name, index, element))
+        );
+    }
+    // a flat access of an element is named after the binding which declares it
+    assert!(values.contains(&Value::RandomValue(4)));
+    assert_eq!(
+        air.random_value_binding(4),
+        Some(RandomValueAccess::new(binding("alphas").name, 1, 4))
+    );
+}
+
+#[test]
+fn random_values_stages() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: [c, d]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        stage1: [alpha, betas[2]]
+        stage2: [gamma]
+    boundary_constraints:
+        enf c.first = betas[1] + $stage2[0]
+        enf c.last = 1
+    integrity_constraints:
+        enf c' = alpha * gamma + $stage1[2]";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.num_random_values, 4);
+    assert_eq!(air.num_random_values_per_stage, vec![3, 1]);
+
+    let mut collector = ValueCollector::default();
+    collector.visit_constraints(&air);
+    let values = collector.values();
+
+    // the stages are laid out one after the other in the random values array
+    let random_values = air.random_values.as_ref().unwrap();
+    let binding = |name: &str| {
+        random_values
+            .bindings
+            .iter()
+            .find(|b| b.name == name)
+            .unwrap()
+    };
+    for (name, index, element) in [("betas", 1, 2), ("alpha", 0, 0), ("gamma", 0, 3)] {
+        assert!(values.contains(&Value::RandomValue(element)));
+        assert_eq!(
+            air.random_value_binding(element),
+            Some(RandomValueAccess::new(binding(name).name, index, element))
+        );
+    }
+    assert!(values.contains(&Value::RandomValue(3)));
+    assert!(values.contains(&Value::RandomValue(2)));
+}
+
+#[test]
+fn random_values_single_stage() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b[12]]
+        aux: [c, d]
+    public_inputs:
+        stack_inputs: [16]
+    random_values:
+        rand: [16]
+    boundary_constraints:
+        enf c.first = $rand[10] * 2
+        enf c.last = 1
+    integrity_constraints:
+        enf c' = $rand[3] + 1";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.num_random_values_per_stage, vec![16]);
+}
@@ -0,0 +1,89 @@
+use air_pass::Pass;
+
+use crate::{passes::MergeScalarMultiples, CodeGenerator, FlatAirScript};
+
+use super::Compiler;
+
+/// Compiles `source` and runs the [MergeScalarMultiples] pass, which are expected to succeed, and
+/// returns the resulting [crate::Air] along with the diagnostic output
+fn merge(source: &str) -> (crate::Air, String) {
+    let compiler = Compiler::default();
+    let result = compiler
+        .compile(source)
+        .and_then(|air| MergeScalarMultiples::new(&compiler.diagnostics).run(air));
+    match result {
+        Ok(air) => (air, compiler.emitter.captured()),
+        Err(err) => {
+            compiler.diagnostics.emit(err);
+            compiler.emitter.print_captured_to_stderr();
+            panic!("expected compilation to succeed");
+        }
+    }
+}
+
+#[test]
+fn scalar_multiples_are_merged() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b, c]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf a - b = 0
+        enf c' = c * a
+        enf 3 * (a - b) = 0";
+
+    let (air, output) = merge(source);
+    assert_eq!(air.integrity_constraints(0).len(), 2);
+    assert!(output.contains("redundant constraint"));
+    assert!(output.contains("this constraint is a scalar multiple of another constraint"));
+
+    // the first of the constraints is kept
+    let flat = FlatAirScript::new().generate(&air).unwrap();
+    assert!(flat.contains("enf a - b = 0"), "{flat}");
+    assert!(!flat.contains("3 * (a - b)"), "{flat}");
+}
+
+#[test]
+fn negated_constraints_are_merged() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+    integrity_constraints:
+        enf a' = b
+        enf 2 * (b - a') = 0";
+
+    let (air, output) = merge(source);
+    assert_eq!(air.integrity_constraints(0).len(), 1);
+    assert!(output.contains("redundant constraint"));
+}
+
+#[test]
+fn distinct_constraints_are_kept() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = stack_inputs[0]
+        enf a.last = 0
+    integrity_constraints:
+        enf a' = b
+        enf a' = b when a
+        enf a' = 3 * b";
+
+    let (air, output) = merge(source);
+    assert_eq!(air.integrity_constraints(0).len(), 3);
+    assert_eq!(air.boundary_constraints(0).len(), 2);
+    assert!(!output.contains("redundant constraint"));
+}
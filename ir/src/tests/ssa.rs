@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+
+use crate::{CodeGenerator, SsaForm};
+
+use super::compile;
+
+#[test]
+fn ssa_long_trace() {
+    let source = "
+    def LongTrace
+
+    trace_columns:
+        main: [a, b, c, d, e, f, g, h, i]
+
+    public_inputs:
+        stack_inputs: [16]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a * b * c + d - e = 0";
+
+    let air = compile(source).expect("compilation failed");
+    let ssa = SsaForm::new().generate(&air).unwrap();
+    let expected = "air LongTrace
+%0 = trace a
+%1 = const 0
+%2 = sub %0, %1
+%3 = trace b
+%4 = mul %0, %3
+%5 = trace c
+%6 = mul %4, %5
+%7 = trace d
+%8 = add %6, %7
+%9 = trace e
+%10 = sub %8, %9
+%11 = sub %10, %1
+enf %2 on $main first
+enf %11 on $main every_row
+";
+    assert_eq!(ssa, expected);
+
+    // each node is assigned once, after the values of its operands
+    let mut defined = HashSet::new();
+    for line in ssa.lines().skip(1) {
+        if let Some(constraint) = line.strip_prefix("enf ") {
+            let (root, _) = constraint.split_once(' ').unwrap();
+            assert!(defined.contains(root), "undefined root in `{line}`");
+            continue;
+        }
+        let (name, op) = line.split_once(" = ").unwrap();
+        for operand in op
+            .split([' ', ','])
+            .filter(|operand| operand.starts_with('%'))
+        {
+            assert!(defined.contains(operand), "undefined operand in `{line}`");
+        }
+        assert!(
+            defined.insert(name.to_string()),
+            "`{name}` is assigned twice"
+        );
+    }
+    assert_eq!(defined.len(), air.constraint_graph().num_nodes());
+}
+
+#[test]
+fn ssa_names_values_and_domains() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk, a[2]]
+        aux: [p]
+
+    public_inputs:
+        stack_inputs: [4]
+
+    periodic_columns:
+        k: [1, 0]
+
+    random_values:
+        rand: [alpha, beta]
+
+    boundary_constraints:
+        enf clk.first = stack_inputs[1]
+        enf p.last = alpha
+
+    integrity_constraints:
+        enf a[1]' = k * a[0]^3
+        enf p' = p * (clk + beta) except last 2";
+
+    let air = compile(source).expect("compilation failed");
+    let ssa = SsaForm::new().generate(&air).unwrap();
+    for line in [
+        "= public_input stack_inputs[1]\n",
+        "= random_value alpha\n",
+        "= periodic k\n",
+        "= trace a[1]'\n",
+        "= exp %",
+        " on $main first\n",
+        " on $main every_frame(2)\n",
+        " on $aux last\n",
+        " on $aux every_frame(2) except last 2\n",
+    ] {
+        assert!(ssa.contains(line), "missing `{line}` in:\n{ssa}");
+    }
+}
@@ -1,3 +1,7 @@
+use std::collections::BTreeSet;
+
+use crate::{Value, ValueCollector, Visit, DEFAULT_SEGMENT};
+
 use super::{compile, expect_diagnostic};
 
 #[test]
@@ -38,6 +42,94 @@ fn trace_cols_groups() {
     assert!(compile(source).is_ok());
 }
 
+#[test]
+fn mixed_trace_column_declarations() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf fmp[1].first = 0
+    integrity_constraints:
+        enf clk' = clk + 1
+        enf fmp[0]' = fmp[0]
+        enf fmp[1]' = fmp[1]
+        enf ctx' = ctx
+        enf $main[3]^2 = $main[3]";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+    let columns = |root| {
+        let mut collector = ValueCollector::default();
+        collector.visit_postorder(graph, [root]);
+        collector
+            .values()
+            .iter()
+            .filter_map(|value| match value {
+                Value::TraceAccess(access) => Some(access.column),
+                _ => None,
+            })
+            .collect::<BTreeSet<_>>()
+    };
+
+    // clk -> 0, fmp[0] -> 1, fmp[1] -> 2, ctx -> 3
+    let boundary_columns = air
+        .boundary_constraints(DEFAULT_SEGMENT)
+        .iter()
+        .map(|constraint| columns(*constraint.node_index()))
+        .collect::<Vec<_>>();
+    assert_eq!(boundary_columns, vec![BTreeSet::from([2])]);
+    let integrity_columns = air
+        .integrity_constraints(DEFAULT_SEGMENT)
+        .iter()
+        .map(|constraint| columns(*constraint.node_index()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        integrity_columns,
+        [0, 1, 2, 3, 3]
+            .into_iter()
+            .map(|column| BTreeSet::from([column]))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn err_zero_size_trace_column_group() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[0], ctx]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(
+        source,
+        "a group of trace columns must contain at least one column",
+    );
+}
+
+#[test]
+fn err_duplicate_trace_column_names() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, fmp[2], clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "this conflicts with a previous declaration");
+}
+
 #[test]
 fn err_bc_column_undeclared() {
     let source = "
@@ -133,3 +225,211 @@ fn err_ic_trace_cols_group_used_as_scalar() {
 
     expect_diagnostic(source, "type mismatch");
 }
+
+#[test]
+fn trace_alias_resolves_to_column() {
+    use crate::{Operation, TraceAccess, Value};
+
+    let source = "
+    def test
+    trace_columns:
+        main: [a, clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias next_clk = clk'
+    alias b = a
+    boundary_constraints:
+        enf b.first = 0
+    integrity_constraints:
+        enf next_clk = clk + 1
+        enf b' = b";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+
+    let expected = [TraceAccess::new(0, 1, 1), TraceAccess::new(0, 0, 1)];
+    let constraints = air.integrity_constraints(0);
+    assert_eq!(constraints.len(), expected.len());
+    for (constraint, expected) in constraints.iter().zip(expected) {
+        let Operation::Sub(lhs, _) = graph.node(constraint.node_index()).op() else {
+            panic!("expected constraint root to be a subtraction");
+        };
+        assert_eq!(
+            graph.node(lhs).op(),
+            &Operation::Value(Value::TraceAccess(expected))
+        );
+    }
+
+    let Operation::Sub(lhs, _) = graph.node(air.boundary_constraints(0)[0].node_index()).op()
+    else {
+        panic!("expected constraint root to be a subtraction");
+    };
+    assert_eq!(
+        graph.node(lhs).op(),
+        &Operation::Value(Value::TraceAccess(TraceAccess::new(0, 0, 0)))
+    );
+}
+
+#[test]
+fn trace_alias_with_offset() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a[2], clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias second = a[1]
+    boundary_constraints:
+        enf second.first = 0
+    integrity_constraints:
+        enf second' = clk";
+
+    assert!(compile(source).is_ok());
+}
+
+#[test]
+fn trace_alias_with_offset_shares_node() {
+    use crate::{Operation, TraceAccess, Value};
+
+    let source = "
+    def test
+    trace_columns:
+        main: [a, clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias next_clk = clk'
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf next_clk = clk + 1
+        enf a' = clk' * a";
+
+    let air = compile(source).expect("compilation failed");
+    let graph = air.constraint_graph();
+
+    // `next_clk` and `clk'` are the same access, and so the same node of the graph
+    let next_clk = Operation::Value(Value::TraceAccess(TraceAccess::new(0, 1, 1)));
+    let nodes = graph
+        .nodes_indexed()
+        .filter(|(_, node)| node.op() == &next_clk)
+        .count();
+    assert_eq!(nodes, 1);
+
+    let constraints = air.integrity_constraints(0);
+    let Operation::Sub(next_clk, _) = graph.node(constraints[0].node_index()).op() else {
+        panic!("expected constraint root to be a subtraction");
+    };
+    let Operation::Sub(_, rhs) = graph.node(constraints[1].node_index()).op() else {
+        panic!("expected constraint root to be a subtraction");
+    };
+    let Operation::Mul(lhs, _) = graph.node(rhs).op() else {
+        panic!("expected a multiplication");
+    };
+    assert_eq!(lhs, next_clk);
+}
+
+#[test]
+fn err_trace_alias_offset_out_of_range() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias next_clk = clk'
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        enf next_clk' = clk + 2";
+
+    expect_diagnostic(source, "invalid row offset");
+}
+
+#[test]
+fn err_trace_alias_conflicts_with_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias clk = a'
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf clk = a + 1";
+
+    expect_diagnostic(source, "invalid alias declaration");
+}
+
+#[test]
+fn err_trace_alias_of_non_column() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias inputs = stack_inputs
+    boundary_constraints:
+        enf a.first = 0
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    expect_diagnostic(source, "expected a trace column");
+}
+
+#[test]
+fn err_trace_alias_with_offset_in_boundary_constraint() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, clk]
+    public_inputs:
+        stack_inputs: [16]
+    alias next_clk = clk'
+    boundary_constraints:
+        enf next_clk.first = 0
+    integrity_constraints:
+        enf next_clk = clk + 1";
+
+    expect_diagnostic(source, "invalid access of a trace column with offset");
+}
+
+#[test]
+fn empty_aux_trace_is_dropped() {
+    let source = "
+    def test
+    trace_columns:
+        main: [a, b]
+        aux: []
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf a.first = 1
+    integrity_constraints:
+        enf a' = a + b";
+
+    let air = compile(source).expect("compilation failed");
+    assert_eq!(air.trace_segment_widths, [2]);
+}
+
+#[test]
+fn err_trace_segment_slice_out_of_bounds() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk, a[3]]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = 0
+    integrity_constraints:
+        let x = sum([col for col in $main[0..5]])
+        enf clk' = x";
+
+    expect_diagnostic(
+        source,
+        "attempted to access an index which is out of bounds",
+    );
+}
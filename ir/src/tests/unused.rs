@@ -0,0 +1,80 @@
+use super::compile_with_warnings;
+
+#[test]
+fn unused_constant() {
+    let source = "
+    def test
+    const A = 123
+    const B = [1, 2, 3]
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+    integrity_constraints:
+        enf clk' = clk + A";
+
+    let output = compile_with_warnings(source);
+    assert!(output.contains("unused constant"));
+    assert!(output.contains("const B = [1, 2, 3]"));
+    assert!(!output.contains("const A = 123"));
+}
+
+#[test]
+fn folded_constant_is_used() {
+    let source = "
+    def test
+    const A = 2
+    const B = [1, 2, 3]
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+    integrity_constraints:
+        let x = A * B[1]
+        enf clk' = clk + x";
+
+    let output = compile_with_warnings(source);
+    assert!(!output.contains("unused constant"), "{output}");
+}
+
+#[test]
+fn unused_public_input() {
+    let source = "
+    def test
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+        program_hash: [4]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    let output = compile_with_warnings(source);
+    assert!(output.contains("unused public input"));
+    assert!(output.contains("program_hash: [4]"));
+    assert!(!output.contains("stack_inputs: [16]"));
+}
+
+#[test]
+fn used_declarations_are_not_reported() {
+    let source = "
+    def test
+    const A = 123
+    trace_columns:
+        main: [clk]
+    public_inputs:
+        stack_inputs: [16]
+    boundary_constraints:
+        enf clk.first = stack_inputs[0]
+    integrity_constraints:
+        enf clk' = clk + A";
+
+    let output = compile_with_warnings(source);
+    assert!(!output.contains("unused"), "{output}");
+}
@@ -10,10 +10,12 @@
 //! also effectively global:
 //!
 //! * `trace_columns`
+//! * `alias`
 //! * `public_inputs`
 //! * `random_values`
 //! * `boundary_constraints`
 //! * `integrity_constraints`
+//! * `assertions`
 //!
 //! All other declarations are module-scoped, and must be explicitly imported by a module which wishes
 //! to reference them. Not all items are importable however, only the following:
@@ -24,7 +26,7 @@
 //!
 //! There is no notion of public/private visiblity, so any declaration of the above types may be
 //! imported into another module, and "wildcard" imports will import all importable items.
-use std::{collections::HashSet, fmt};
+use std::{collections::HashSet, fmt, path::PathBuf};
 
 use miden_diagnostics::{SourceSpan, Spanned};
 
@@ -37,6 +39,10 @@ pub enum Declaration {
     Import(Span<Import>),
     /// A constant value declaration
     Constant(Constant),
+    /// An alias for an access to a trace column, e.g. `alias next_clk = clk'`
+    ///
+    /// Aliases may only appear in the root AirScript module, as they refer to trace columns
+    TraceAlias(TraceAlias),
     /// An evaluator function definition
     ///
     /// Evaluator functions can be defined in any module of the program
@@ -70,6 +76,11 @@ pub enum Declaration {
     /// There may only be one of these in the entire program, and it must
     /// appear in the root AirScript module, i.e. in a module declared with `def`
     IntegrityConstraints(Span<Vec<Statement>>),
+    /// An `assertions` section declaration
+    ///
+    /// There may only be one of these in the entire program, and it must
+    /// appear in the root AirScript module, i.e. in a module declared with `def`
+    Assertions(Span<Vec<Assertion>>),
 }
 
 /// Stores a constant's name and value. There are three types of constants:
@@ -83,11 +94,35 @@ pub struct Constant {
     pub span: SourceSpan,
     pub name: Identifier,
     pub value: ConstantExpr,
+    /// Whether the value contains a rational number `n/d`, which is evaluated to an element of
+    /// the 64-bit field with modulus 2^64 - 2^32 + 1 when the program is parsed
+    pub rational: bool,
+    /// The file from which the value was loaded, if this is a constant table, e.g.
+    /// `json("round_constants.json")`
+    pub table: Option<PathBuf>,
 }
 impl Constant {
     /// Returns a new instance of a [Constant]
     pub const fn new(span: SourceSpan, name: Identifier, value: ConstantExpr) -> Self {
-        Self { span, name, value }
+        Self {
+            span,
+            name,
+            value,
+            rational: false,
+            table: None,
+        }
+    }
+
+    /// Marks this constant as containing a rational number, see [Constant::rational]
+    pub const fn with_rational(mut self, rational: bool) -> Self {
+        self.rational = rational;
+        self
+    }
+
+    /// Records the file from which the value of this constant was loaded, see [Constant::table]
+    pub fn with_table(mut self, table: PathBuf) -> Self {
+        self.table = Some(table);
+        self
     }
 
     /// Gets the type of the value associated with this constant
@@ -102,6 +137,40 @@ impl PartialEq for Constant {
     }
 }
 
+/// A reference to a scalar element of a named [Constant], from which a constant value was
+/// propagated.
+///
+/// * Scalar: `A` has no index
+/// * Vector: `B[1]` has an index
+/// * Matrix: `C[1][2]` has an index (the row), and a column
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ConstantAccess {
+    /// The name of the referenced constant
+    pub name: QualifiedIdentifier,
+    /// The index of the element in a vector constant, or of the row in a matrix constant
+    pub index: Option<usize>,
+    /// The index of the column in a matrix constant
+    pub column: Option<usize>,
+    /// The value of the referenced element
+    pub value: u64,
+}
+impl ConstantAccess {
+    /// Returns a new instance of a [ConstantAccess]
+    pub const fn new(
+        name: QualifiedIdentifier,
+        index: Option<usize>,
+        column: Option<usize>,
+        value: u64,
+    ) -> Self {
+        Self {
+            name,
+            index,
+            column,
+            value,
+        }
+    }
+}
+
 /// Value of a constant. Constants can be of 3 value types:
 ///
 /// * Scalar: 123
@@ -259,6 +328,8 @@ pub struct PublicInput {
     pub span: SourceSpan,
     pub name: Identifier,
     pub size: usize,
+    /// The doc string of this input, e.g. `"program hash"` in `program_hash: [4] "program hash"`
+    pub doc: Option<Symbol>,
 }
 impl PublicInput {
     #[inline]
@@ -267,8 +338,21 @@ impl PublicInput {
             span,
             name,
             size: size.try_into().unwrap(),
+            doc: None,
         }
     }
+
+    /// Sets the doc string of this input
+    pub fn with_doc(mut self, doc: Option<Symbol>) -> Self {
+        self.doc = doc;
+        self
+    }
+
+    /// Returns the doc string of this input, if it has one
+    #[inline]
+    pub fn doc(&self) -> Option<&'static str> {
+        self.doc.map(Symbol::as_str)
+    }
 }
 impl Eq for PublicInput {}
 impl PartialEq for PublicInput {
@@ -307,6 +391,20 @@ impl PartialEq for PublicInput {
 /// one for `a`, and one for `b`, with sizes `1` and `12` respectively. The size of the overall
 /// [RandomValues] instance in that case would be `13`.
 ///
+/// Multi-stage AIRs draw their random values in stages, each after the prover commits to the
+/// trace built with the values of the previous stages. A `random_values` declaration with more
+/// than one array, like the following:
+///
+/// ```airscript
+/// random_values:
+///     stage1: [a, b]
+///     stage2: [c]
+/// ```
+///
+/// is equivalent to creating it with `RandomValues::with_stages`, which concatenates the arrays
+/// of the stages into a single array named `random_values`, binds the name of each stage to its
+/// slice of the array, and keeps the bindings of each stage. The size of the overall
+/// [RandomValues] instance in that case would be `3`.
 #[derive(Clone, Spanned)]
 pub struct RandomValues {
     #[span]
@@ -317,6 +415,9 @@ pub struct RandomValues {
     pub size: usize,
     /// Zero or more bindings for individual elements or groups of elements
     pub bindings: Vec<RandBinding>,
+    /// The bindings of the stages to their slice of the array, in the order the stages are
+    /// drawn, which is empty if the random values are drawn in a single stage
+    pub stages: Vec<RandBinding>,
 }
 impl RandomValues {
     /// Creates a new [RandomValues] array `size` elements
@@ -326,6 +427,7 @@ impl RandomValues {
             name,
             size,
             bindings: vec![],
+            stages: vec![],
         }
     }
 
@@ -352,13 +454,59 @@ impl RandomValues {
             name,
             size: offset,
             bindings,
+            stages: vec![],
+        }
+    }
+
+    /// Creates a new [RandomValues] array drawn in the given stages, in order
+    ///
+    /// The arrays of the stages are concatenated into a single array named `$random_values`,
+    /// the name of each stage is bound to its slice of that array, and the bindings of each
+    /// stage are offset accordingly.
+    pub fn with_stages(span: SourceSpan, stages: Vec<RandomValues>) -> Self {
+        let mut bindings = vec![];
+        let mut stage_bindings = Vec::with_capacity(stages.len());
+        let mut offset = 0;
+        for stage in stages.into_iter() {
+            stage_bindings.push(RandBinding::new(
+                stage.span,
+                stage.name,
+                stage.size,
+                offset,
+                Type::Vector(stage.size),
+            ));
+            bindings.extend(stage.bindings.into_iter().map(|binding| RandBinding {
+                offset: binding.offset + offset,
+                ..binding
+            }));
+            offset += stage.size;
+        }
+
+        Self {
+            span,
+            name: Identifier::new(span, Symbol::intern("$random_values")),
+            size: offset,
+            bindings,
+            stages: stage_bindings,
+        }
+    }
+
+    /// Returns the number of random values drawn in each stage, in order
+    pub fn stage_sizes(&self) -> Vec<usize> {
+        if self.stages.is_empty() {
+            vec![self.size]
+        } else {
+            self.stages.iter().map(|stage| stage.size).collect()
         }
     }
 }
 impl Eq for RandomValues {}
 impl PartialEq for RandomValues {
     fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.size == other.size && self.bindings == other.bindings
+        self.name == other.name
+            && self.size == other.size
+            && self.bindings == other.bindings
+            && self.stages == other.stages
     }
 }
 impl fmt::Debug for RandomValues {
@@ -367,21 +515,49 @@ impl fmt::Debug for RandomValues {
             .field("name", &self.name)
             .field("size", &self.size)
             .field("bindings", &self.bindings)
+            .field("stages", &self.stages)
             .finish()
     }
 }
 impl fmt::Display for RandomValues {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(name) = self.name.as_str().strip_prefix('$') {
-            write!(f, "{}: ", name)?;
-        } else {
-            write!(f, "{}: ", self.name)?;
+        if self.stages.is_empty() {
+            return fmt_random_values(f, self.name, self.size, &self.bindings);
         }
-        if self.bindings.is_empty() {
-            write!(f, "[{}]", self.size)
-        } else {
-            write!(f, "{}", DisplayList(self.bindings.as_slice()))
+
+        for (i, stage) in self.stages.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n    ")?;
+            }
+            let range = stage.offset..(stage.offset + stage.size);
+            let bindings = self
+                .bindings
+                .iter()
+                .filter(|binding| range.contains(&binding.offset))
+                .map(|binding| RandBinding {
+                    offset: binding.offset - stage.offset,
+                    ..*binding
+                })
+                .collect::<Vec<_>>();
+            fmt_random_values(f, stage.name, stage.size, &bindings)?;
         }
+        Ok(())
+    }
+}
+
+/// Formats a single array of a `random_values` declaration
+fn fmt_random_values(
+    f: &mut fmt::Formatter,
+    name: Identifier,
+    size: usize,
+    bindings: &[RandBinding],
+) -> fmt::Result {
+    let name = name.as_str();
+    write!(f, "{}: ", name.strip_prefix('$').unwrap_or(name))?;
+    if bindings.is_empty() {
+        write!(f, "[{}]", size)
+    } else {
+        write!(f, "{}", DisplayList(bindings))
     }
 }
 
@@ -523,3 +699,32 @@ impl PartialEq for EvaluatorFunction {
         self.name == other.name && self.params == other.params && self.body == other.body
     }
 }
+
+/// A compile-time assertion about the program, e.g. `assert NUM_MAIN_COLUMNS == 9`.
+///
+/// Both sides of an assertion must be constant expressions, which are evaluated when the program
+/// is compiled, and compilation fails if they are not equal.
+#[derive(Debug, Clone, Spanned)]
+pub struct Assertion {
+    #[span]
+    pub span: SourceSpan,
+    pub lhs: ScalarExpr,
+    pub rhs: ScalarExpr,
+}
+impl Assertion {
+    /// Creates a new assertion that `lhs` and `rhs` are equal.
+    pub const fn new(span: SourceSpan, lhs: ScalarExpr, rhs: ScalarExpr) -> Self {
+        Self { span, lhs, rhs }
+    }
+}
+impl Eq for Assertion {}
+impl PartialEq for Assertion {
+    fn eq(&self, other: &Self) -> bool {
+        self.lhs == other.lhs && self.rhs == other.rhs
+    }
+}
+impl fmt::Display for Assertion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "assert {} == {}", &self.lhs, &self.rhs)
+    }
+}
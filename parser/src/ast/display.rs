@@ -18,14 +18,6 @@ impl<'a, T: fmt::Display> fmt::Display for DisplayList<'a, T> {
     }
 }
 
-/// Displays an item surrounded by parentheses, e.g. `(foo)`
-pub struct DisplayParenthesized<T>(pub T);
-impl<T: fmt::Display> fmt::Display for DisplayParenthesized<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({})", &self.0)
-    }
-}
-
 /// Displays a slice of items surrounded by parentheses, e.g. `(foo)`
 pub struct DisplayTuple<'a, T>(pub &'a [T]);
 impl<'a, T: fmt::Display> fmt::Display for DisplayTuple<'a, T> {
@@ -67,7 +59,7 @@ pub struct DisplayStatement<'a> {
     pub indent: usize,
 }
 impl DisplayStatement<'_> {
-    const INDENT: &str = "    ";
+    const INDENT: &'static str = "    ";
 
     fn write_indent(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for _ in 0..self.indent {
@@ -96,6 +88,20 @@ impl<'a> fmt::Display for DisplayStatement<'a> {
             Statement::EnforceAll(ref expr) => {
                 write!(f, "enf {}", expr)
             }
+            Statement::Exempt(ref expr) => match expr.body.as_slice() {
+                [statement @ (Statement::Enforce(_)
+                | Statement::EnforceIf(_, _)
+                | Statement::EnforceAll(_))] => {
+                    write!(f, "{} except last {}", statement.display(0), expr.rows)
+                }
+                body => {
+                    writeln!(f, "except last {}:", expr.rows)?;
+                    for statement in body.iter() {
+                        writeln!(f, "{}", statement.display(self.indent + 1))?;
+                    }
+                    Ok(())
+                }
+            },
             Statement::Expr(ref expr) => write!(f, "{}", expr),
         }
     }
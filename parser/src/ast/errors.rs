@@ -12,6 +12,17 @@ pub enum InvalidExprError {
     #[error("expected scalar expression")]
     InvalidScalarExpr(SourceSpan),
 }
+impl InvalidExprError {
+    /// Returns the stable code identifying this kind of error in diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidExponent(_) => "E0300",
+            Self::NonConstantExponent(_) => "E0301",
+            Self::BoundedSymbolAccess(_) => "E0302",
+            Self::InvalidScalarExpr(_) => "E0303",
+        }
+    }
+}
 impl Eq for InvalidExprError {}
 impl PartialEq for InvalidExprError {
     fn eq(&self, other: &Self) -> bool {
@@ -21,7 +32,8 @@ impl PartialEq for InvalidExprError {
 impl ToDiagnostic for InvalidExprError {
     fn to_diagnostic(self) -> Diagnostic {
         let message = format!("{}", &self);
-        match self {
+        let code = self.code();
+        let diagnostic = match self {
             Self::InvalidExponent(span) => Diagnostic::error()
                 .with_message("invalid expression")
                 .with_labels(vec![
@@ -40,12 +52,17 @@ impl ToDiagnostic for InvalidExprError {
                 .with_message("invalid expression")
                 .with_labels(vec![
                     Label::primary(span.source_id(), span).with_message(message)
+                ])
+                .with_notes(vec![
+                    "The boundary accessors `.first` and `.last` are only valid on the left-hand side of boundary constraints"
+                        .to_string(),
                 ]),
             Self::InvalidScalarExpr(span) => Diagnostic::error()
                 .with_message("invalid expression")
                 .with_labels(vec![
                     Label::primary(span.source_id(), span).with_message(message)
                 ]),
-        }
+        };
+        diagnostic.with_code(code)
     }
 }
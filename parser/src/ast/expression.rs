@@ -162,7 +162,7 @@ impl QualifiedIdentifier {
         if self.module.name() == "$builtin" {
             match self.item {
                 NamespacedIdentifier::Function(id) => {
-                    matches!(id.name(), symbols::Sum | symbols::Prod)
+                    matches!(id.name(), symbols::Sum | symbols::Prod | symbols::Diff)
                 }
                 _ => false,
             }
@@ -434,7 +434,7 @@ pub enum ScalarExpr {
     ///
     /// 1. The call is the top-level expression of a constraint, and is to an evaluator function
     /// 2. The call is not the top-level expression of a constraint, and is to a pure function
-    /// that produces a scalar value type.
+    ///    that produces a scalar value type.
     ///
     /// If neither of the above are true, the call is invalid in a `ScalarExpr` context
     Call(Call),
@@ -545,7 +545,10 @@ impl fmt::Debug for BinaryExpr {
 }
 impl fmt::Display for BinaryExpr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} {} {}", &self.lhs, &self.op, &self.rhs)
+        match self.op {
+            BinaryOp::NegExp => write!(f, "{} ^ (-{})", &self.lhs, &self.rhs),
+            op => write!(f, "{} {} {}", &self.lhs, op, &self.rhs),
+        }
     }
 }
 
@@ -559,11 +562,20 @@ pub enum BinaryOp {
     Mul,
     /// Exponentiation
     Exp,
+    /// Exponentiation by the negation of the right operand, i.e. `a ^ (-n)`, which denotes the
+    /// field inverse of `a ^ n`
+    NegExp,
     /// Equality
     ///
     /// NOTE: This is only used in constraints to assert equality, it is invalid in other contexts
     Eq,
 }
+impl BinaryOp {
+    /// Returns true if this is an exponentiation, whose right operand must be a constant
+    pub fn is_exp(&self) -> bool {
+        matches!(self, Self::Exp | Self::NegExp)
+    }
+}
 impl fmt::Display for BinaryOp {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -571,6 +583,7 @@ impl fmt::Display for BinaryOp {
             Self::Sub => f.write_str("-"),
             Self::Mul => f.write_str("*"),
             Self::Exp => f.write_str("^"),
+            Self::NegExp => f.write_str("^-"),
             Self::Eq => f.write_str("="),
         }
     }
@@ -665,6 +678,10 @@ pub struct SymbolAccess {
     pub ty: Option<Type>,
 }
 impl SymbolAccess {
+    /// The largest row offset of a trace column access, i.e. only the current and next rows of
+    /// the trace can be accessed by a constraint.
+    pub const MAX_OFFSET: usize = 1;
+
     pub const fn new(
         span: SourceSpan,
         name: Identifier,
@@ -940,6 +957,12 @@ pub struct ListComprehension {
     pub iterables: Vec<Expr>,
     /// The expression which will be evaluated at each step of the comprehension
     pub body: Box<ScalarExpr>,
+    /// An optional filter evaluated at compile time at each iteration, which omits the
+    /// iterations for which it evaluates to zero (false).
+    ///
+    /// This is only valid when the comprehension is used as a constraint, and the filter
+    /// must fold to a constant once the bindings are known.
+    pub filter: Option<Box<ScalarExpr>>,
     /// An optional filter applied to the generator expression at each iteration, which
     /// skips values for which the selector evaluates to zero (false).
     ///
@@ -966,10 +989,17 @@ impl ListComprehension {
             bindings,
             iterables,
             body: Box::new(body),
+            filter: None,
             selector,
             ty: None,
         }
     }
+
+    /// Sets the compile-time filter of this comprehension.
+    pub fn with_filter(mut self, filter: Option<ScalarExpr>) -> Self {
+        self.filter = filter.map(Box::new);
+        self
+    }
 }
 impl Eq for ListComprehension {}
 impl PartialEq for ListComprehension {
@@ -977,6 +1007,7 @@ impl PartialEq for ListComprehension {
         self.bindings == other.bindings
             && self.iterables == other.iterables
             && self.body == other.body
+            && self.filter == other.filter
             && self.selector == other.selector
     }
 }
@@ -986,6 +1017,7 @@ impl fmt::Debug for ListComprehension {
             .field("bindings", &self.bindings)
             .field("iterables", &self.iterables)
             .field("body", self.body.as_ref())
+            .field("filter", &self.filter)
             .field("selector", &self.selector)
             .finish()
     }
@@ -1008,6 +1040,10 @@ impl fmt::Display for ListComprehension {
             )?;
         }
 
+        if let Some(filter) = self.filter.as_ref() {
+            write!(f, " if {}", filter)?;
+        }
+
         if let Some(selector) = self.selector.as_ref() {
             write!(f, " when {}", selector)
         } else {
@@ -1046,8 +1082,8 @@ pub struct Call {
     ///
     /// * Calls to evaluators produce no value, and thus have no type
     /// * When parsed, the callee has not yet been resolved, so we don't know the
-    /// type of the function being called. During semantic analysis, the callee is
-    /// resolved and this field is set to the result type of that function.
+    ///   type of the function being called. During semantic analysis, the callee is
+    ///   resolved and this field is set to the result type of that function.
     pub ty: Option<Type>,
 }
 impl Call {
@@ -1057,6 +1093,7 @@ impl Call {
         match callee.name() {
             symbols::Sum => Self::sum(span, args),
             symbols::Prod => Self::prod(span, args),
+            symbols::Diff => Self::diff(span, args),
             _ => Self {
                 span,
                 callee: ResolvableIdentifier::Unresolved(NamespacedIdentifier::Function(callee)),
@@ -1072,19 +1109,34 @@ impl Call {
         self.callee.is_builtin()
     }
 
+    /// Returns true if the callee is the `diff` builtin
+    #[inline]
+    pub fn is_diff(&self) -> bool {
+        self.is_builtin() && self.callee.as_ref().name() == crate::symbols::Diff
+    }
+
     /// Constructs a function call for the `sum` reducer/fold
     #[inline]
     pub fn sum(span: SourceSpan, args: Vec<Expr>) -> Self {
-        Self::new_builtin(span, "sum", args, Type::Felt)
+        Self::new_builtin(span, "sum", args, Some(Type::Felt))
     }
 
     /// Constructs a function call for the `prod` reducer/fold
     #[inline]
     pub fn prod(span: SourceSpan, args: Vec<Expr>) -> Self {
-        Self::new_builtin(span, "prod", args, Type::Felt)
+        Self::new_builtin(span, "prod", args, Some(Type::Felt))
+    }
+
+    /// Constructs a function call for the `diff` builtin, which produces the differences between
+    /// adjacent columns of a trace column group.
+    ///
+    /// The type of the result depends on the size of the group, and is set during semantic analysis.
+    #[inline]
+    pub fn diff(span: SourceSpan, args: Vec<Expr>) -> Self {
+        Self::new_builtin(span, "diff", args, None)
     }
 
-    fn new_builtin(span: SourceSpan, name: &str, args: Vec<Expr>, ty: Type) -> Self {
+    fn new_builtin(span: SourceSpan, name: &str, args: Vec<Expr>, ty: Option<Type>) -> Self {
         let builtin_module = Identifier::new(SourceSpan::UNKNOWN, Symbol::intern("$builtin"));
         let name = Identifier::new(span, Symbol::intern(name));
         let id = QualifiedIdentifier::new(builtin_module, NamespacedIdentifier::Function(name));
@@ -1092,7 +1144,7 @@ impl Call {
             span,
             callee: ResolvableIdentifier::Resolved(id),
             args,
-            ty: Some(ty),
+            ty,
         }
     }
 }
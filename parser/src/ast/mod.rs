@@ -40,6 +40,7 @@ use crate::{
 /// All of the details described in the documentation for [Program] and [Library]
 /// apply to their respective variants here.
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Source {
     /// The source code which was parsed produced a valid [Program],
     /// i.e. it contained a root module, and optionally, one or more
@@ -69,6 +70,16 @@ pub struct Program {
     pub name: Identifier,
     /// The set of used constants referenced in this program.
     pub constants: BTreeMap<QualifiedIdentifier, Constant>,
+    /// The named constants from which constant values in this program were propagated, keyed by
+    /// the span of the propagated value.
+    ///
+    /// This is populated by constant propagation, and preserves the provenance of constant values
+    /// which would otherwise be lost once they are folded into the program.
+    pub constant_accesses: BTreeMap<SourceSpan, ConstantAccess>,
+    /// The set of constants declared in the root module which are never referenced.
+    ///
+    /// These are not part of the program proper, but are kept so that they can be reported.
+    pub unused_constants: BTreeMap<QualifiedIdentifier, Constant>,
     /// The set of used evaluator functions referenced in this program.
     pub evaluators: BTreeMap<QualifiedIdentifier, EvaluatorFunction>,
     /// The set of used periodic columns referenced in this program.
@@ -94,6 +105,8 @@ pub struct Program {
     ///
     /// It is guaranteed that this is non-empty
     pub integrity_constraints: Vec<Statement>,
+    /// The compile-time assertions declared in the root module, if any
+    pub assertions: Vec<Assertion>,
 }
 impl Program {
     /// Creates a new, empty [Program].
@@ -114,6 +127,8 @@ impl Program {
         Self {
             name,
             constants: Default::default(),
+            constant_accesses: Default::default(),
+            unused_constants: Default::default(),
             evaluators: Default::default(),
             periodic_columns: Default::default(),
             public_inputs: Default::default(),
@@ -121,6 +136,7 @@ impl Program {
             trace_columns: vec![],
             boundary_constraints: vec![],
             integrity_constraints: vec![],
+            assertions: vec![],
         }
     }
 
@@ -164,8 +180,13 @@ impl Program {
                 if let Some(module) = library.get(&module_name) {
                     for import in module.imports.values() {
                         let import_module = modgraph.add_node(import.module());
-                        // If an attempt is made to import the root module, raise an error
-                        if import_module == root {
+                        // If an attempt is made to import the root module, or any other root
+                        // module defined alongside it, raise an error
+                        let is_root = library
+                            .get(&import_module)
+                            .map(|module| module.is_root())
+                            .unwrap_or(false);
+                        if import_module == root || is_root {
                             return Err(SemanticAnalysisError::RootImport(import.module().span()));
                         }
 
@@ -231,6 +252,10 @@ impl Program {
             if let Some(ic) = root_module.integrity_constraints.as_ref() {
                 program.integrity_constraints = ic.to_vec();
             }
+            // Make sure we move the assertions into the program
+            if let Some(assertions) = root_module.assertions.as_ref() {
+                program.assertions = assertions.to_vec();
+            }
             for evaluator in root_module.evaluators.values() {
                 root_nodes.push_back(QualifiedIdentifier::new(
                     root,
@@ -279,9 +304,93 @@ impl Program {
             }
         }
 
+        // Keep track of the constants declared in the root module which were never referenced
+        let root_module = library.get(&root).unwrap();
+        for (id, constant) in root_module.constants.iter() {
+            let qid = QualifiedIdentifier::new(root, NamespacedIdentifier::Binding(*id));
+            if !program.constants.contains_key(&qid) {
+                program.unused_constants.insert(qid, constant.clone());
+            }
+        }
+
         Ok(program)
     }
 }
+
+/// This represents the set of [Program]s defined in a single AirScript file, which may contain
+/// several root modules, as well as library modules shared between them.
+///
+/// Each root module is loaded into an independent [Program] from the same [Library], so a root
+/// module only ever sees the library modules it imports, and never another root module.
+#[derive(Debug, Default)]
+pub struct Programs {
+    programs: BTreeMap<ModuleId, Program>,
+}
+impl Programs {
+    /// Load a [Program] for each of the root modules in the given library.
+    ///
+    /// When called, it is expected that the library has had import resolution performed. Root
+    /// modules are required to have distinct names, which is checked when constructing the
+    /// library, and at least one root module is required.
+    pub fn load(
+        diagnostics: &DiagnosticsHandler,
+        library: Library,
+    ) -> Result<Self, SemanticAnalysisError> {
+        let mut roots = library
+            .modules
+            .values()
+            .filter(|module| module.is_root())
+            .map(|module| module.name)
+            .collect::<Vec<_>>();
+        if roots.is_empty() {
+            return Err(SemanticAnalysisError::MissingRoot);
+        }
+        roots.sort();
+
+        let mut programs = BTreeMap::default();
+        for root in roots {
+            let program = Program::load(diagnostics, root, library.clone())?;
+            programs.insert(root, program);
+        }
+
+        Ok(Self { programs })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.programs.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+
+    /// Returns the [Program] whose root module has the given name, if any
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&Program> {
+        self.programs
+            .iter()
+            .find(|(id, _)| id.as_str() == name)
+            .map(|(_, program)| program)
+    }
+
+    /// Returns an iterator over the [Program]s in this set, ordered by name
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&ModuleId, &Program)> + '_ {
+        self.programs.iter()
+    }
+}
+impl IntoIterator for Programs {
+    type Item = (ModuleId, Program);
+    type IntoIter = std::collections::btree_map::IntoIter<ModuleId, Program>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.programs.into_iter()
+    }
+}
+
 impl Eq for Program {}
 impl PartialEq for Program {
     fn eq(&self, other: &Self) -> bool {
@@ -294,6 +403,7 @@ impl PartialEq for Program {
             && self.trace_columns == other.trace_columns
             && self.boundary_constraints == other.boundary_constraints
             && self.integrity_constraints == other.integrity_constraints
+            && self.assertions == other.assertions
     }
 }
 impl fmt::Display for Program {
@@ -358,6 +468,14 @@ impl fmt::Display for Program {
         }
         f.write_str("\n")?;
 
+        if !self.assertions.is_empty() {
+            writeln!(f, "assertions:")?;
+            for assertion in self.assertions.iter() {
+                writeln!(f, "    {}", assertion)?;
+            }
+            f.write_str("\n")?;
+        }
+
         for (qid, evaluator) in self.evaluators.iter() {
             f.write_str("ev ")?;
             if qid.module == self.name {
@@ -386,7 +504,7 @@ impl fmt::Display for Program {
 /// Libraries are produced when parsing files which do not contain a root module. We defer merging
 /// the modules together until a root module is provided so that we can perform import resolution on
 /// the root module using the contents of the library.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Library {
     pub modules: HashMap<ModuleId, Module>,
 }
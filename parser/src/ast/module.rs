@@ -22,8 +22,10 @@ pub enum ModuleType {
     /// * public_inputs
     /// * random_values
     /// * trace_columns
+    /// * trace column aliases
     /// * boundary_constraints
     /// * integrity_constraints
+    /// * assertions
     ///
     /// However, they are allowed to define constants, functions, and the periodic_columns section.
     Library,
@@ -36,7 +38,7 @@ pub enum ModuleType {
 /// * Fields which are only allowed in root modules are empty/unset in library modules
 /// * Fields which must be present in root modules are guaranteed to be present in a root module
 /// * It is guaranteed that at least one boundary constraint and one integrity constraint are present
-/// in a root module
+///   in a root module
 /// * No duplicate module-level declarations were present
 /// * All globally-visible declarations are unique
 ///
@@ -45,7 +47,7 @@ pub enum ModuleType {
 /// partially validated here, in that we check for obviously overlapping imports, but cannot
 /// fully validate them until later. Likewise we do not validate constraints, look for invalid
 /// variable usages, etc.
-#[derive(Debug, Spanned)]
+#[derive(Debug, Clone, Spanned)]
 pub struct Module {
     #[span]
     pub span: SourceSpan,
@@ -58,8 +60,10 @@ pub struct Module {
     pub public_inputs: BTreeMap<Identifier, PublicInput>,
     pub random_values: Option<RandomValues>,
     pub trace_columns: Vec<TraceSegment>,
+    pub trace_aliases: BTreeMap<Identifier, TraceAlias>,
     pub boundary_constraints: Option<Span<Vec<Statement>>>,
     pub integrity_constraints: Option<Span<Vec<Statement>>>,
+    pub assertions: Option<Span<Vec<Assertion>>>,
 }
 impl Module {
     /// Constructs an empty module of the specified type, with the given span and name.
@@ -83,8 +87,10 @@ impl Module {
             public_inputs: Default::default(),
             random_values: None,
             trace_columns: vec![],
+            trace_aliases: Default::default(),
             boundary_constraints: None,
             integrity_constraints: None,
+            assertions: None,
         }
     }
 
@@ -118,6 +124,9 @@ impl Module {
                 Declaration::Constant(constant) => {
                     module.declare_constant(diagnostics, &mut names, constant)?;
                 }
+                Declaration::TraceAlias(alias) => {
+                    module.declare_trace_alias(diagnostics, &mut names, alias)?;
+                }
                 Declaration::EvaluatorFunction(evaluator) => {
                     module.declare_evaluator(diagnostics, &mut names, evaluator)?;
                 }
@@ -147,6 +156,9 @@ impl Module {
                 Declaration::IntegrityConstraints(statements) => {
                     module.declare_integrity_constraints(diagnostics, statements)?;
                 }
+                Declaration::Assertions(assertions) => {
+                    module.declare_assertions(diagnostics, assertions)?;
+                }
             }
         }
 
@@ -175,18 +187,33 @@ impl Module {
                 return Err(SemanticAnalysisError::MissingPublicInputs);
             }
 
-            if module.random_values.is_some()
-                && !module.trace_columns.iter().any(|ts| ts.name == "$aux")
+            if let Some(random_values) = module
+                .random_values
+                .as_ref()
+                .filter(|_| !module.trace_columns.iter().any(|ts| ts.name == "$aux"))
             {
                 diagnostics
                     .diagnostic(Severity::Error)
                     .with_message(
                         "declaring random_values requires an aux trace_columns declaration",
                     )
-                    .with_primary_label(
-                        module.random_values.as_ref().unwrap().span(),
-                        "this declaration is invalid",
-                    )
+                    .with_primary_label(random_values.span(), "this declaration is invalid")
+                    .emit();
+                return Err(SemanticAnalysisError::Invalid);
+            }
+
+            // random values can only be used to build aux columns, so an empty aux trace has none
+            if let Some((random_values, aux)) = module.random_values.as_ref().zip(
+                module
+                    .trace_columns
+                    .iter()
+                    .find(|ts| ts.name == "$aux" && ts.size == 0),
+            ) {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("declaring random_values requires aux trace columns")
+                    .with_primary_label(random_values.span(), "this declaration is invalid")
+                    .with_secondary_label(aux.span(), "because no aux column is declared here")
                     .emit();
                 return Err(SemanticAnalysisError::Invalid);
             }
@@ -470,6 +497,18 @@ impl Module {
             }
         }
 
+        for stage in rv.stages.iter() {
+            if let Some(prev) = names.replace(NamespacedIdentifier::Binding(stage.name)) {
+                conflicting_declaration(
+                    diagnostics,
+                    "random values stage",
+                    prev.span(),
+                    stage.name.span(),
+                );
+                return Err(SemanticAnalysisError::NameConflict(stage.name.span()));
+            }
+        }
+
         if let Some(prev) = self.random_values.replace(rv) {
             diagnostics
                 .diagnostic(Severity::Error)
@@ -518,6 +557,17 @@ impl Module {
                     );
                     return Err(SemanticAnalysisError::NameConflict(binding_name.span()));
                 }
+                if binding.size == 0 {
+                    diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid trace binding declaration")
+                        .with_primary_label(
+                            binding.span(),
+                            "a group of trace columns must contain at least one column",
+                        )
+                        .emit();
+                    return Err(SemanticAnalysisError::Invalid);
+                }
             }
         }
 
@@ -526,6 +576,28 @@ impl Module {
         Ok(())
     }
 
+    fn declare_trace_alias(
+        &mut self,
+        diagnostics: &DiagnosticsHandler,
+        names: &mut HashSet<NamespacedIdentifier>,
+        alias: TraceAlias,
+    ) -> Result<(), SemanticAnalysisError> {
+        let span = alias.span();
+        if self.is_library() {
+            invalid_section_in_library(diagnostics, "alias", span);
+            return Err(SemanticAnalysisError::RootSectionInLibrary(span));
+        }
+
+        if let Some(prev) = names.replace(NamespacedIdentifier::Binding(alias.name)) {
+            conflicting_declaration(diagnostics, "alias", prev.span(), alias.name.span());
+            return Err(SemanticAnalysisError::NameConflict(alias.name.span()));
+        }
+
+        assert_eq!(self.trace_aliases.insert(alias.name, alias), None);
+
+        Ok(())
+    }
+
     fn declare_boundary_constraints(
         &mut self,
         diagnostics: &DiagnosticsHandler,
@@ -586,6 +658,27 @@ impl Module {
         Ok(())
     }
 
+    fn declare_assertions(
+        &mut self,
+        diagnostics: &DiagnosticsHandler,
+        assertions: Span<Vec<Assertion>>,
+    ) -> Result<(), SemanticAnalysisError> {
+        let span = assertions.span();
+        if self.is_library() {
+            invalid_section_in_library(diagnostics, "assertions", span);
+            return Err(SemanticAnalysisError::RootSectionInLibrary(span));
+        }
+
+        if let Some(prev) = self.assertions.as_ref() {
+            conflicting_declaration(diagnostics, "assertions", prev.span(), span);
+            return Err(SemanticAnalysisError::Invalid);
+        }
+
+        self.assertions = Some(assertions);
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn is_root(&self) -> bool {
         !self.is_library()
@@ -625,8 +718,10 @@ impl PartialEq for Module {
             && self.public_inputs == other.public_inputs
             && self.random_values == other.random_values
             && self.trace_columns == other.trace_columns
+            && self.trace_aliases == other.trace_aliases
             && self.boundary_constraints == other.boundary_constraints
             && self.integrity_constraints == other.integrity_constraints
+            && self.assertions == other.assertions
     }
 }
 
@@ -60,6 +60,12 @@ pub enum Statement {
     /// Just like `Enforce`, except the constraint is contained in the body of a list comprehension,
     /// and must be enforced on every value produced by that comprehension.
     EnforceAll(ListComprehension),
+    /// Exempts the constraints in its body from being enforced on the last rows of the trace,
+    /// e.g. `enf x' = x + 1 except last 2`
+    ///
+    /// The parser produces this with a single constraint in its body, but the body may contain
+    /// a block of statements once that constraint has been expanded during inlining.
+    Exempt(Exempt),
 }
 impl Statement {
     /// Checks this statement to see if it contains any constraints
@@ -70,7 +76,9 @@ impl Statement {
     pub fn has_constraints(&self) -> bool {
         match self {
             Self::Enforce(_) | Self::EnforceIf(_, _) | Self::EnforceAll(_) => true,
-            Self::Let(Let { body, .. }) => body.iter().any(|s| s.has_constraints()),
+            Self::Let(Let { body, .. }) | Self::Exempt(Exempt { body, .. }) => {
+                body.iter().any(|s| s.has_constraints())
+            }
             Self::Expr(_) => false,
         }
     }
@@ -149,3 +157,37 @@ impl fmt::Debug for Let {
             .finish()
     }
 }
+
+/// An `except last` clause, which exempts the constraints in `body` from being enforced on the
+/// last `rows` rows of the trace.
+///
+/// Transition constraints can never be enforced on the last row of the trace, as it has no next
+/// row, so exempting a single row has no effect on them.
+#[derive(Clone, Spanned)]
+pub struct Exempt {
+    #[span]
+    pub span: SourceSpan,
+    /// The number of rows at the end of the trace on which the constraints are not enforced
+    pub rows: usize,
+    /// The exempted constraints
+    pub body: Vec<Statement>,
+}
+impl Exempt {
+    pub fn new(span: SourceSpan, rows: usize, body: Vec<Statement>) -> Self {
+        Self { span, rows, body }
+    }
+}
+impl Eq for Exempt {}
+impl PartialEq for Exempt {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.body == other.body
+    }
+}
+impl fmt::Debug for Exempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Exempt")
+            .field("rows", &self.rows)
+            .field("body", &self.body)
+            .finish()
+    }
+}
@@ -30,6 +30,8 @@ pub struct TraceSegment {
 }
 impl TraceSegment {
     /// Constructs a new [TraceSegment] given a span, segment id, name, and a vector of (Identifier, size) pairs.
+    ///
+    /// The bindings are undocumented, see [TraceSegment::with_docs].
     pub fn new(
         span: SourceSpan,
         id: TraceSegmentId,
@@ -70,6 +72,23 @@ impl TraceSegment {
         }
     }
 
+    /// Sets the doc strings of the bindings of this segment, in the order in which they are
+    /// declared.
+    pub fn with_docs(mut self, docs: Vec<Option<Symbol>>) -> Self {
+        assert_eq!(docs.len(), self.bindings.len());
+        for (binding, doc) in self.bindings.iter_mut().zip(docs) {
+            binding.doc = doc;
+        }
+        self
+    }
+
+    /// Returns the binding declaring `column`, if any.
+    pub fn binding(&self, column: TraceColumnIndex) -> Option<&TraceBinding> {
+        self.bindings
+            .iter()
+            .find(|binding| (binding.offset..binding.offset + binding.size).contains(&column))
+    }
+
     /// Returns true if `column` is constrained on `boundary`
     pub fn is_boundary_constrained(&self, column: TraceColumnIndex, boundary: Boundary) -> bool {
         self.boundary_constrained[column].is_constrained(boundary)
@@ -124,10 +143,20 @@ impl fmt::Display for TraceSegment {
                 write!(f, "{}: ", self.name)?;
             }
         }
-        if self.bindings.is_empty() {
+        if self.size == 0 {
+            f.write_str("[]")
+        } else if self.bindings.is_empty() {
             write!(f, "[{}]", self.size)
         } else {
-            write!(f, "{}", DisplayList(self.bindings.as_slice()))
+            let bindings = self
+                .bindings
+                .iter()
+                .map(|binding| match binding.doc() {
+                    Some(doc) => format!("{binding} \"{doc}\""),
+                    None => binding.to_string(),
+                })
+                .collect::<Vec<_>>();
+            write!(f, "{}", DisplayList(bindings.as_slice()))
         }
     }
 }
@@ -218,6 +247,8 @@ pub struct TraceBinding {
     pub size: usize,
     /// The effective type of this binding
     pub ty: Type,
+    /// The doc string of this binding, e.g. `"clock cycle"` in `main: [clk "clock cycle"]`
+    pub doc: Option<Symbol>,
 }
 impl TraceBinding {
     /// Creates a new trace binding.
@@ -236,9 +267,16 @@ impl TraceBinding {
             offset,
             size,
             ty,
+            doc: None,
         }
     }
 
+    /// Returns the doc string of this binding, if it has one
+    #[inline]
+    pub fn doc(&self) -> Option<&'static str> {
+        self.doc.map(Symbol::as_str)
+    }
+
     /// Returns a [Type] that describes what type of value this binding represents
     #[inline]
     pub fn ty(&self) -> Type {
@@ -301,6 +339,7 @@ impl fmt::Debug for TraceBinding {
             .field("offset", &self.offset)
             .field("size", &self.size)
             .field("ty", &self.ty)
+            .field("doc", &self.doc)
             .finish()
     }
 }
@@ -322,3 +361,35 @@ impl fmt::Display for TraceBinding {
         }
     }
 }
+
+/// Represents an alias for an access to a trace column, declared in the root module, e.g.
+/// `alias next_clk = clk'`.
+///
+/// Unlike a `let`-bound variable, an alias is visible in every constraint section of the root
+/// module. All references to the alias are resolved to the aliased trace column access during
+/// semantic analysis, so aliases do not survive past that point.
+#[derive(Debug, Clone, Spanned)]
+pub struct TraceAlias {
+    #[span]
+    pub span: SourceSpan,
+    /// The name of this alias
+    pub name: Identifier,
+    /// The trace column access which this alias refers to
+    pub target: SymbolAccess,
+}
+impl TraceAlias {
+    pub const fn new(span: SourceSpan, name: Identifier, target: SymbolAccess) -> Self {
+        Self { span, name, target }
+    }
+}
+impl Eq for TraceAlias {}
+impl PartialEq for TraceAlias {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.target == other.target
+    }
+}
+impl fmt::Display for TraceAlias {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "alias {} = {}", self.name, &self.target)
+    }
+}
@@ -181,12 +181,18 @@ pub trait VisitMut<T> {
     fn visit_mut_enforce_all(&mut self, expr: &mut ast::ListComprehension) -> ControlFlow<T> {
         self.visit_mut_list_comprehension(expr)
     }
+    fn visit_mut_exempt(&mut self, expr: &mut ast::Exempt) -> ControlFlow<T> {
+        visit_mut_exempt(self, expr)
+    }
     fn visit_mut_integrity_constraints(
         &mut self,
         exprs: &mut Vec<ast::Statement>,
     ) -> ControlFlow<T> {
         self.visit_mut_statement_block(exprs)
     }
+    fn visit_mut_assertion(&mut self, expr: &mut ast::Assertion) -> ControlFlow<T> {
+        visit_mut_assertion(self, expr)
+    }
     fn visit_mut_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<T> {
         visit_mut_expr(self, expr)
     }
@@ -225,7 +231,7 @@ pub trait VisitMut<T> {
     }
 }
 
-impl<'a, V, T> VisitMut<T> for &'a mut V
+impl<V, T> VisitMut<T> for &mut V
 where
     V: ?Sized + VisitMut<T>,
 {
@@ -295,6 +301,9 @@ where
     ) -> ControlFlow<T> {
         (**self).visit_mut_integrity_constraints(exprs)
     }
+    fn visit_mut_assertion(&mut self, expr: &mut ast::Assertion) -> ControlFlow<T> {
+        (**self).visit_mut_assertion(expr)
+    }
     fn visit_mut_enforce(&mut self, expr: &mut ast::ScalarExpr) -> ControlFlow<T> {
         (**self).visit_mut_enforce(expr)
     }
@@ -308,6 +317,9 @@ where
     fn visit_mut_enforce_all(&mut self, expr: &mut ast::ListComprehension) -> ControlFlow<T> {
         (**self).visit_mut_enforce_all(expr)
     }
+    fn visit_mut_exempt(&mut self, expr: &mut ast::Exempt) -> ControlFlow<T> {
+        (**self).visit_mut_exempt(expr)
+    }
     fn visit_mut_expr(&mut self, expr: &mut ast::Expr) -> ControlFlow<T> {
         (**self).visit_mut_expr(expr)
     }
@@ -381,10 +393,23 @@ where
             visitor.visit_mut_integrity_constraints(ic)?;
         }
     }
+    if let Some(assertions) = module.assertions.as_mut() {
+        for assertion in assertions.iter_mut() {
+            visitor.visit_mut_assertion(assertion)?;
+        }
+    }
 
     ControlFlow::Continue(())
 }
 
+pub fn visit_mut_assertion<V, T>(visitor: &mut V, expr: &mut ast::Assertion) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    visitor.visit_mut_scalar_expr(&mut expr.lhs)?;
+    visitor.visit_mut_scalar_expr(&mut expr.rhs)
+}
+
 pub fn visit_mut_import<V, T>(_visitor: &mut V, _expr: &mut ast::Import) -> ControlFlow<T>
 where
     V: ?Sized + VisitMut<T>,
@@ -529,10 +554,18 @@ where
             visitor.visit_mut_enforce_if(expr, selector)
         }
         ast::Statement::EnforceAll(ref mut expr) => visitor.visit_mut_enforce_all(expr),
+        ast::Statement::Exempt(ref mut expr) => visitor.visit_mut_exempt(expr),
         ast::Statement::Expr(ref mut expr) => visitor.visit_mut_expr(expr),
     }
 }
 
+pub fn visit_mut_exempt<V, T>(visitor: &mut V, expr: &mut ast::Exempt) -> ControlFlow<T>
+where
+    V: ?Sized + VisitMut<T>,
+{
+    visitor.visit_mut_statement_block(&mut expr.body)
+}
+
 pub fn visit_mut_let<V, T>(visitor: &mut V, expr: &mut ast::Let) -> ControlFlow<T>
 where
     V: ?Sized + VisitMut<T>,
@@ -608,6 +641,9 @@ where
     for iterable in expr.iterables.iter_mut() {
         visitor.visit_mut_expr(iterable)?;
     }
+    if let Some(filter) = expr.filter.as_deref_mut() {
+        visitor.visit_mut_scalar_expr(filter)?;
+    }
     if let Some(selector) = expr.selector.as_mut() {
         visitor.visit_mut_scalar_expr(selector)?;
     }
@@ -21,6 +21,18 @@ pub enum LexicalError {
     },
     #[error("encountered unexpected character '{found}'")]
     UnexpectedCharacter { start: SourceIndex, found: char },
+    #[error("unterminated string literal")]
+    UnterminatedString { span: SourceSpan },
+}
+impl LexicalError {
+    /// Returns the stable code identifying this kind of error in diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidInt { .. } => "E0001",
+            Self::UnexpectedCharacter { .. } => "E0002",
+            Self::UnterminatedString { .. } => "E0003",
+        }
+    }
 }
 impl PartialEq for LexicalError {
     fn eq(&self, other: &Self) -> bool {
@@ -32,6 +44,7 @@ impl PartialEq for LexicalError {
                 Self::UnexpectedCharacter { found: lhs, .. },
                 Self::UnexpectedCharacter { found: rhs, .. },
             ) => lhs == rhs,
+            (Self::UnterminatedString { .. }, Self::UnterminatedString { .. }) => true,
             _ => false,
         }
     }
@@ -40,7 +53,8 @@ impl ToDiagnostic for LexicalError {
     fn to_diagnostic(self) -> Diagnostic {
         use miden_diagnostics::Label;
 
-        match self {
+        let code = self.code();
+        let diagnostic = match self {
             Self::InvalidInt { span, ref reason } => Diagnostic::error()
                 .with_message("invalid integer literal")
                 .with_labels(vec![Label::primary(span.source_id(), span)
@@ -51,7 +65,12 @@ impl ToDiagnostic for LexicalError {
                     start.source_id(),
                     SourceSpan::new(start, start),
                 )]),
-        }
+            Self::UnterminatedString { span } => Diagnostic::error()
+                .with_message("unterminated string literal")
+                .with_labels(vec![Label::primary(span.source_id(), span)
+                    .with_message("expected a closing '\"' on this line")]),
+        };
+        diagnostic.with_code(code)
     }
 }
 
@@ -86,6 +105,9 @@ pub enum Token {
     FunctionIdent(Symbol),
     /// Integers should only contain numeric characters.
     Num(u64),
+    /// A string literal, e.g. the doc string of a trace column. The quotes are not part of the
+    /// symbol, and the string may not span multiple lines.
+    Str(Symbol),
 
     // DECLARATION KEYWORDS
     // --------------------------------------------------------------------------------------------
@@ -99,6 +121,10 @@ pub enum Token {
     Let,
     /// Used to declare constants in the AIR constraints module.
     Const,
+    /// Used to declare a parameter of the AIR, whose value is bound when it is compiled.
+    Param,
+    /// Used to declare an alternative name for a trace column access in the root module.
+    Alias,
     /// Used to declare trace columns section in the AIR constraints module.
     TraceColumns,
     /// Used to declare main trace columns.
@@ -128,10 +154,19 @@ pub enum Token {
     /// Marks the beginning of integrity constraints section in the constraints file.
     IntegrityConstraints,
 
+    // ASSERTION KEYWORDS
+    // --------------------------------------------------------------------------------------------
+    /// Marks the beginning of the assertions section in the constraints file.
+    Assertions,
+    /// Keyword to state a compile-time assertion, e.g. `assert NUM_MAIN_COLUMNS == 9`
+    Assert,
+
     // LIST COMPREHENSION KEYWORDS
     // --------------------------------------------------------------------------------------------
     For,
     In,
+    /// Filters the iterations of a constraint comprehension by a constant, e.g. `for x in xs if m`
+    If,
 
     // GENERAL KEYWORDS
     // --------------------------------------------------------------------------------------------
@@ -140,6 +175,15 @@ pub enum Token {
     Match,
     Case,
     When,
+    /// Keyword to exempt a constraint from being enforced on some rows, e.g. `except last 2`
+    Except,
+
+    // BUILTIN CONSTANTS
+    // --------------------------------------------------------------------------------------------
+    /// The number of columns of the main trace, as declared in the `trace_columns` section
+    NumMainColumns,
+    /// The number of columns of the aux trace, as declared in the `trace_columns` section
+    NumAuxColumns,
 
     // PUNCTUATION
     // --------------------------------------------------------------------------------------------
@@ -154,9 +198,11 @@ pub enum Token {
     LBracket,
     RBracket,
     Equal,
+    EqualEqual,
     Plus,
     Minus,
     Star,
+    Slash,
     Caret,
     Ampersand,
     Bar,
@@ -170,6 +216,8 @@ impl Token {
             "use" => Self::Use,
             "let" => Self::Let,
             "const" => Self::Const,
+            "param" => Self::Param,
+            "alias" => Self::Alias,
             "trace_columns" => Self::TraceColumns,
             "main" => Self::Main,
             "aux" => Self::Aux,
@@ -179,14 +227,18 @@ impl Token {
             "ev" => Self::Ev,
             "boundary_constraints" => Self::BoundaryConstraints,
             "integrity_constraints" => Self::IntegrityConstraints,
+            "assertions" => Self::Assertions,
+            "assert" => Self::Assert,
             "first" => Self::First,
             "last" => Self::Last,
             "for" => Self::For,
             "in" => Self::In,
+            "if" => Self::If,
             "enf" => Self::Enf,
             "match" => Self::Match,
             "case" => Self::Case,
             "when" => Self::When,
+            "except" => Self::Except,
             other => Self::Ident(Symbol::intern(other)),
         }
     }
@@ -220,6 +272,11 @@ impl PartialEq for Token {
                     return i == i2;
                 }
             }
+            Self::Str(s) => {
+                if let Self::Str(s2) = other {
+                    return s == s2;
+                }
+            }
             _ => return mem::discriminant(self) == mem::discriminant(other),
         }
         false
@@ -235,11 +292,14 @@ impl fmt::Display for Token {
             Self::DeclIdentRef(ref id) => write!(f, "{}", id),
             Self::FunctionIdent(ref id) => write!(f, "{}", id),
             Self::Num(ref i) => write!(f, "{}", i),
+            Self::Str(ref s) => write!(f, "\"{}\"", s),
             Self::Def => write!(f, "def"),
             Self::Mod => write!(f, "mod"),
             Self::Use => write!(f, "use"),
             Self::Let => write!(f, "let"),
             Self::Const => write!(f, "const"),
+            Self::Param => write!(f, "param"),
+            Self::Alias => write!(f, "alias"),
             Self::TraceColumns => write!(f, "trace_columns"),
             Self::Main => write!(f, "main"),
             Self::Aux => write!(f, "aux"),
@@ -251,12 +311,18 @@ impl fmt::Display for Token {
             Self::First => write!(f, "first"),
             Self::Last => write!(f, "last"),
             Self::IntegrityConstraints => write!(f, "integrity_constraints"),
+            Self::Assertions => write!(f, "assertions"),
+            Self::Assert => write!(f, "assert"),
             Self::For => write!(f, "for"),
             Self::In => write!(f, "in"),
+            Self::If => write!(f, "if"),
             Self::Enf => write!(f, "enf"),
             Self::Match => write!(f, "match"),
             Self::Case => write!(f, "case"),
             Self::When => write!(f, "when"),
+            Self::Except => write!(f, "except"),
+            Self::NumMainColumns => write!(f, "NUM_MAIN_COLUMNS"),
+            Self::NumAuxColumns => write!(f, "NUM_AUX_COLUMNS"),
             Self::Quote => write!(f, "'"),
             Self::Colon => write!(f, ":"),
             Self::ColonColon => write!(f, "::"),
@@ -268,9 +334,11 @@ impl fmt::Display for Token {
             Self::LBracket => write!(f, "["),
             Self::RBracket => write!(f, "]"),
             Self::Equal => write!(f, "="),
+            Self::EqualEqual => write!(f, "=="),
             Self::Plus => write!(f, "+"),
             Self::Minus => write!(f, "-"),
             Self::Star => write!(f, "*"),
+            Self::Slash => write!(f, "/"),
             Self::Caret => write!(f, "^"),
             Self::Ampersand => write!(f, "&"),
             Self::Bar => write!(f, "|"),
@@ -484,14 +552,19 @@ where
             ')' => pop!(self, Token::RParen),
             '[' => pop!(self, Token::LBracket),
             ']' => pop!(self, Token::RBracket),
-            '=' => pop!(self, Token::Equal),
+            '=' => match self.peek() {
+                '=' => pop2!(self, Token::EqualEqual),
+                _ => pop!(self, Token::Equal),
+            },
             '+' => pop!(self, Token::Plus),
             '-' => pop!(self, Token::Minus),
             '*' => pop!(self, Token::Star),
+            '/' => pop!(self, Token::Slash),
             '^' => pop!(self, Token::Caret),
             '&' => pop!(self, Token::Ampersand),
             '|' => pop!(self, Token::Bar),
             '!' => pop!(self, Token::Bang),
+            '"' => self.lex_string(),
             '$' => self.lex_special_identifier(),
             '0'..='9' => self.lex_number(),
             'a'..='z' => self.lex_keyword_or_ident(),
@@ -523,6 +596,25 @@ where
         Token::Comment
     }
 
+    fn lex_string(&mut self) -> Token {
+        let c = self.pop();
+        debug_assert!(c == '"');
+
+        loop {
+            match self.read() {
+                '"' => break,
+                '\n' | '\0' => {
+                    return Token::Error(LexicalError::UnterminatedString { span: self.span() })
+                }
+                _ => self.skip(),
+            }
+        }
+        self.skip();
+
+        let slice = self.slice();
+        Token::Str(Symbol::intern(&slice[1..slice.len() - 1]))
+    }
+
     #[inline]
     fn lex_special_identifier(&mut self) -> Token {
         let c = self.pop();
@@ -566,9 +658,13 @@ where
         self.skip_ident();
 
         if self.read() == '(' {
-            Token::FunctionIdent(Symbol::intern(self.slice()))
-        } else {
-            Token::Ident(Symbol::intern(self.slice()))
+            return Token::FunctionIdent(Symbol::intern(self.slice()));
+        }
+
+        match self.slice() {
+            "NUM_MAIN_COLUMNS" => Token::NumMainColumns,
+            "NUM_AUX_COLUMNS" => Token::NumAuxColumns,
+            other => Token::Ident(Symbol::intern(other)),
         }
     }
 
@@ -598,7 +694,7 @@ where
             Ok(i) => Token::Num(i),
             Err(err) => Token::Error(LexicalError::InvalidInt {
                 span: self.span(),
-                reason: err.kind().clone(),
+                reason: *err.kind(),
             }),
         }
     }
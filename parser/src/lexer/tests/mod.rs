@@ -55,6 +55,7 @@ fn expect_error_at_location(source: &str, expected: LexicalError, line: u32, col
             let span = miden_diagnostics::SourceSpan::new(*start, *start);
             codemap.location(&span).unwrap()
         }
+        LexicalError::UnterminatedString { span } => codemap.location(span).unwrap(),
     };
     assert_eq!(err, expected);
     assert_eq!(loc.line, LineIndex(line));
@@ -1,4 +1,6 @@
-use super::{expect_valid_tokenization, Symbol, Token};
+use miden_diagnostics::SourceSpan;
+
+use super::{expect_error_at_location, expect_valid_tokenization, LexicalError, Symbol, Token};
 
 #[test]
 fn pub_inputs_kw() {
@@ -30,3 +32,35 @@ public_inputs:
     ];
     expect_valid_tokenization(source, tokens);
 }
+
+#[test]
+fn pub_inputs_doc_strings() {
+    let source = r#"
+public_inputs:
+    program_hash: [4] "hash of the program""#;
+
+    let tokens = vec![
+        Token::PublicInputs,
+        Token::Colon,
+        Token::Ident(Symbol::intern("program_hash")),
+        Token::Colon,
+        Token::LBracket,
+        Token::Num(4),
+        Token::RBracket,
+        Token::Str(Symbol::intern("hash of the program")),
+    ];
+    expect_valid_tokenization(source, tokens);
+}
+
+#[test]
+fn err_unterminated_doc_string() {
+    let source = r#"
+public_inputs:
+    program_hash: [4] "hash of the program
+    stack_inputs: [12]"#;
+
+    let error = LexicalError::UnterminatedString {
+        span: SourceSpan::UNKNOWN,
+    };
+    expect_error_at_location(source, error, 2, 22);
+}
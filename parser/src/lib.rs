@@ -8,7 +8,7 @@ mod sema;
 pub mod symbols;
 pub mod transforms;
 
-pub use self::parser::{ParseError, Parser};
+pub use self::parser::{Params, ParseError, Parser};
 pub use self::sema::{LexicalScope, SemanticAnalysisError};
 pub use self::symbols::Symbol;
 
@@ -23,7 +23,21 @@ pub fn parse(
     codemap: Arc<CodeMap>,
     source: &str,
 ) -> Result<ast::Program, ParseError> {
-    let parser = Parser::new((), codemap);
+    parse_with_params(diagnostics, codemap, source, &Params::default())
+}
+
+/// Parses the provided source, binding its parameters to the values in `params`, and returns the
+/// AST.
+///
+/// Parameters without a value in `params` take their default value, and it is an error to bind a
+/// value to a parameter which is not declared, or to leave a parameter without a default unbound.
+pub fn parse_with_params(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: &str,
+    params: &Params,
+) -> Result<ast::Program, ParseError> {
+    let parser = Parser::new(params.clone(), codemap);
     match parser.parse_string::<ast::Program, _, _>(diagnostics, source) {
         Ok(ast) => Ok(ast),
         Err(ParseError::Lexer(err)) => {
@@ -40,7 +54,18 @@ pub fn parse_file<P: AsRef<Path>>(
     codemap: Arc<CodeMap>,
     source: P,
 ) -> Result<ast::Program, ParseError> {
-    let parser = Parser::new((), codemap);
+    parse_file_with_params(diagnostics, codemap, source, &Params::default())
+}
+
+/// Parses the provided file, binding its parameters to the values in `params`, and returns the
+/// AST, see [parse_with_params].
+pub fn parse_file_with_params<P: AsRef<Path>>(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: P,
+    params: &Params,
+) -> Result<ast::Program, ParseError> {
+    let parser = Parser::new(params.clone(), codemap);
     match parser.parse_file::<ast::Program, _, _>(diagnostics, source) {
         Ok(ast) => Ok(ast),
         Err(ParseError::Lexer(err)) => {
@@ -51,6 +76,67 @@ pub fn parse_file<P: AsRef<Path>>(
     }
 }
 
+/// Parses the provided source, which may define several root modules, and returns an [ast::Program] for
+/// each of them, keyed by name.
+///
+/// Library modules defined in the same source are shared by all of the root modules which import
+/// them. Defining two modules with the same name is an error.
+pub fn parse_programs(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: &str,
+) -> Result<ast::Programs, ParseError> {
+    parse_programs_with_params(diagnostics, codemap, source, &Params::default())
+}
+
+/// Parses the provided source, which may define several root modules, binding the parameters of
+/// each of them to the values in `params`, see [parse_programs] and [parse_with_params].
+pub fn parse_programs_with_params(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: &str,
+    params: &Params,
+) -> Result<ast::Programs, ParseError> {
+    let parser = Parser::new(params.clone(), codemap);
+    match parser.parse_string::<ast::Programs, _, _>(diagnostics, source) {
+        Ok(ast) => Ok(ast),
+        Err(ParseError::Lexer(err)) => {
+            diagnostics.emit(err);
+            Err(ParseError::Failed)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Parses the provided file, which may define several root modules, and returns an [ast::Program] for
+/// each of them, keyed by name.
+pub fn parse_programs_file<P: AsRef<Path>>(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: P,
+) -> Result<ast::Programs, ParseError> {
+    parse_programs_file_with_params(diagnostics, codemap, source, &Params::default())
+}
+
+/// Parses the provided file, which may define several root modules, binding the parameters of
+/// each of them to the values in `params`, see [parse_programs] and [parse_with_params].
+pub fn parse_programs_file_with_params<P: AsRef<Path>>(
+    diagnostics: &DiagnosticsHandler,
+    codemap: Arc<CodeMap>,
+    source: P,
+    params: &Params,
+) -> Result<ast::Programs, ParseError> {
+    let parser = Parser::new(params.clone(), codemap);
+    match parser.parse_file::<ast::Programs, _, _>(diagnostics, source) {
+        Ok(ast) => Ok(ast),
+        Err(ParseError::Lexer(err)) => {
+            diagnostics.emit(err);
+            Err(ParseError::Failed)
+        }
+        Err(err) => Err(err),
+    }
+}
+
 /// Parses the provided source string with a default [CodeMap] and [DiagnosticsHandler].
 ///
 /// This is primarily provided for use in tests, you should generally prefer [parse]
@@ -79,7 +165,7 @@ pub(crate) fn parse_module_from_file<P: AsRef<Path>>(
     codemap: Arc<CodeMap>,
     path: P,
 ) -> Result<ast::Module, ParseError> {
-    let parser = Parser::new((), codemap);
+    let parser = Parser::new(Default::default(), codemap);
     match parser.parse_file::<ast::Module, _, _>(diagnostics, path) {
         ok @ Ok(_) => ok,
         Err(ParseError::Lexer(err)) => {
@@ -98,7 +184,7 @@ pub(crate) fn parse_module(
     codemap: Arc<CodeMap>,
     source: Arc<miden_diagnostics::SourceFile>,
 ) -> Result<ast::Module, ParseError> {
-    let parser = Parser::new((), codemap);
+    let parser = Parser::new(Default::default(), codemap);
     match parser.parse::<ast::Module, _>(diagnostics, source) {
         ok @ Ok(_) => ok,
         Err(ParseError::Lexer(err)) => {
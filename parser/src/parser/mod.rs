@@ -14,20 +14,29 @@ lalrpop_mod!(
     "/parser/grammar.rs"
 );
 
-use std::sync::Arc;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use miden_diagnostics::{
-    CodeMap, Diagnostic, DiagnosticsHandler, Label, SourceIndex, SourceSpan, ToDiagnostic,
+    CodeMap, Diagnostic, DiagnosticsHandler, FileName, Label, Severity, SourceIndex, SourceSpan,
+    Spanned, ToDiagnostic,
 };
 use miden_parsing::{Scanner, Source};
 
 use crate::{
-    ast,
+    ast::{self, Identifier},
     lexer::{Lexed, Lexer, LexicalError, Token},
-    sema,
+    sema, Symbol,
 };
 
-pub type Parser = miden_parsing::Parser<()>;
+pub type Parser = miden_parsing::Parser<Params>;
+
+/// The values bound to the parameters of a program by name, e.g. `N` for `param N`, which
+/// override the default values of their declarations.
+pub type Params = BTreeMap<String, u64>;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -58,6 +67,21 @@ pub enum ParseError {
     #[error("parsing failed, see diagnostics for details")]
     Failed,
 }
+impl ParseError {
+    /// Returns the stable code identifying this kind of error in diagnostics.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Lexer(err) => err.code(),
+            Self::Analysis(err) => err.code(),
+            Self::FileError { .. } => "E0100",
+            Self::InvalidToken(_) => "E0101",
+            Self::UnexpectedEof { .. } => "E0102",
+            Self::UnrecognizedToken { .. } => "E0103",
+            Self::ExtraToken { .. } => "E0104",
+            Self::Failed => "E0105",
+        }
+    }
+}
 impl Eq for ParseError {}
 impl PartialEq for ParseError {
     fn eq(&self, other: &Self) -> bool {
@@ -122,7 +146,8 @@ impl From<lalrpop_util::ParseError<SourceIndex, Token, ParseError>> for ParseErr
 }
 impl ToDiagnostic for ParseError {
     fn to_diagnostic(self) -> Diagnostic {
-        match self {
+        let code = self.code();
+        let diagnostic = match self {
             Self::Lexer(err) => err.to_diagnostic(),
             Self::Analysis(err) => err.to_diagnostic(),
             Self::InvalidToken(start) => Diagnostic::error()
@@ -171,14 +196,15 @@ impl ToDiagnostic for ParseError {
                 .with_message("extraneous token")
                 .with_labels(vec![Label::primary(span.source_id(), span)]),
             err => Diagnostic::error().with_message(err.to_string()),
-        }
+        };
+        diagnostic.with_code(code)
     }
 }
 
 impl miden_parsing::Parse for ast::Source {
     type Parser = grammar::SourceParser;
     type Error = ParseError;
-    type Config = ();
+    type Config = Params;
     type Token = Lexed;
 
     fn root_file_error(source: std::io::Error, path: std::path::PathBuf) -> Self::Error {
@@ -195,7 +221,20 @@ impl miden_parsing::Parse for ast::Source {
     {
         let scanner = Scanner::new(source);
         let lexer = Lexer::new(scanner);
-        Self::parse_tokens(diagnostics, parser.codemap.clone(), lexer)
+        parse_with(
+            diagnostics,
+            &parser.config,
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &parser.codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    lexer,
+                )
+            },
+        )
     }
 
     fn parse_tokens<S: IntoIterator<Item = Lexed>>(
@@ -203,25 +242,27 @@ impl miden_parsing::Parse for ast::Source {
         codemap: Arc<CodeMap>,
         tokens: S,
     ) -> Result<Self, Self::Error> {
-        let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
-        match result {
-            Ok(ast) => {
-                if diagnostics.has_errors() {
-                    return Err(ParseError::Failed);
-                }
-                Ok(ast)
-            }
-            Err(lalrpop_util::ParseError::User { error }) => Err(error),
-            Err(err) => Err(err.into()),
-        }
+        parse_with(
+            diagnostics,
+            &Params::default(),
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    tokens,
+                )
+            },
+        )
     }
 }
 
 impl miden_parsing::Parse for ast::Program {
     type Parser = grammar::ProgramParser;
     type Error = ParseError;
-    type Config = ();
+    type Config = Params;
     type Token = Lexed;
 
     fn root_file_error(source: std::io::Error, path: std::path::PathBuf) -> Self::Error {
@@ -238,7 +279,20 @@ impl miden_parsing::Parse for ast::Program {
     {
         let scanner = Scanner::new(source);
         let lexer = Lexer::new(scanner);
-        Self::parse_tokens(diagnostics, parser.codemap.clone(), lexer)
+        parse_with(
+            diagnostics,
+            &parser.config,
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &parser.codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    lexer,
+                )
+            },
+        )
     }
 
     fn parse_tokens<S: IntoIterator<Item = Lexed>>(
@@ -246,25 +300,85 @@ impl miden_parsing::Parse for ast::Program {
         codemap: Arc<CodeMap>,
         tokens: S,
     ) -> Result<Self, Self::Error> {
-        let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
-        match result {
-            Ok(ast) => {
-                if diagnostics.has_errors() {
-                    return Err(ParseError::Failed);
-                }
-                Ok(ast)
-            }
-            Err(lalrpop_util::ParseError::User { error }) => Err(error),
-            Err(err) => Err(err.into()),
-        }
+        parse_with(
+            diagnostics,
+            &Params::default(),
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    tokens,
+                )
+            },
+        )
+    }
+}
+
+impl miden_parsing::Parse for ast::Programs {
+    type Parser = grammar::ProgramsParser;
+    type Error = ParseError;
+    type Config = Params;
+    type Token = Lexed;
+
+    fn root_file_error(source: std::io::Error, path: std::path::PathBuf) -> Self::Error {
+        ParseError::FileError { source, path }
+    }
+
+    fn parse<S>(
+        parser: &Parser,
+        diagnostics: &DiagnosticsHandler,
+        source: S,
+    ) -> Result<Self, Self::Error>
+    where
+        S: Source,
+    {
+        let scanner = Scanner::new(source);
+        let lexer = Lexer::new(scanner);
+        parse_with(
+            diagnostics,
+            &parser.config,
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &parser.codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    lexer,
+                )
+            },
+        )
+    }
+
+    fn parse_tokens<S: IntoIterator<Item = Lexed>>(
+        diagnostics: &DiagnosticsHandler,
+        codemap: Arc<CodeMap>,
+        tokens: S,
+    ) -> Result<Self, Self::Error> {
+        parse_with(
+            diagnostics,
+            &Params::default(),
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    tokens,
+                )
+            },
+        )
     }
 }
 
 impl miden_parsing::Parse for ast::Module {
     type Parser = grammar::AnyModuleParser;
     type Error = ParseError;
-    type Config = ();
+    type Config = Params;
     type Token = Lexed;
 
     fn root_file_error(source: std::io::Error, path: std::path::PathBuf) -> Self::Error {
@@ -281,7 +395,20 @@ impl miden_parsing::Parse for ast::Module {
     {
         let scanner = Scanner::new(source);
         let lexer = Lexer::new(scanner);
-        Self::parse_tokens(diagnostics, parser.codemap.clone(), lexer)
+        parse_with(
+            diagnostics,
+            &parser.config,
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &parser.codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    lexer,
+                )
+            },
+        )
     }
 
     fn parse_tokens<S: IntoIterator<Item = Lexed>>(
@@ -289,19 +416,291 @@ impl miden_parsing::Parse for ast::Module {
         codemap: Arc<CodeMap>,
         tokens: S,
     ) -> Result<Self, Self::Error> {
-        let mut next_var = 0;
-        let result = Self::Parser::new().parse(diagnostics, &codemap, &mut next_var, tokens);
-        match result {
-            Ok(ast) => {
-                if diagnostics.has_errors() {
-                    return Err(ParseError::Failed);
-                }
-                Ok(ast)
+        parse_with(
+            diagnostics,
+            &Params::default(),
+            |next_var, segment_widths, parameters| {
+                Self::Parser::new().parse(
+                    diagnostics,
+                    &codemap,
+                    next_var,
+                    segment_widths,
+                    parameters,
+                    tokens,
+                )
+            },
+        )
+    }
+}
+
+/// Runs `parse` with a fresh parser state, in which the parameters of the program are bound to
+/// `params`, and checks that each of them was declared once the parse succeeds.
+fn parse_with<T, F>(
+    diagnostics: &DiagnosticsHandler,
+    params: &Params,
+    parse: F,
+) -> Result<T, ParseError>
+where
+    F: FnOnce(
+        &mut usize,
+        &mut SegmentWidths,
+        &mut ParamScope,
+    ) -> Result<T, lalrpop_util::ParseError<SourceIndex, Token, ParseError>>,
+{
+    let mut next_var = 0;
+    let mut segment_widths = None;
+    let mut parameters = ParamScope::new(params.clone());
+    match parse(&mut next_var, &mut segment_widths, &mut parameters) {
+        Ok(ast) => {
+            parameters.check_bindings(diagnostics);
+            if diagnostics.has_errors() {
+                return Err(ParseError::Failed);
             }
-            Err(lalrpop_util::ParseError::User { error }) => Err(error),
-            Err(err) => Err(err.into()),
+            Ok(ast)
+        }
+        Err(lalrpop_util::ParseError::User { error }) => Err(error),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// The parameters declared so far in the source being parsed, along with the values bound to
+/// them when the program is compiled.
+///
+/// Parameters are resolved while parsing, so that they can be used anywhere a size is expected,
+/// and remain in scope in the rest of the source once declared.
+#[derive(Debug, Default)]
+pub struct ParamScope {
+    bound: Params,
+    declared: BTreeMap<Symbol, (SourceSpan, u64)>,
+}
+impl ParamScope {
+    fn new(bound: Params) -> Self {
+        Self {
+            bound,
+            declared: Default::default(),
+        }
+    }
+
+    /// Declares the parameter `name`, with an optional default value, and returns its value,
+    /// i.e. the value bound to it if any, or its default value.
+    pub(crate) fn declare(
+        &mut self,
+        diagnostics: &DiagnosticsHandler,
+        name: Identifier,
+        default: Option<u64>,
+        span: SourceSpan,
+    ) -> Result<u64, ParseError> {
+        if let Some((prev, _)) = self.declared.get(&name.name()) {
+            diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("duplicate parameter declaration")
+                .with_primary_label(span, "this parameter is already declared")
+                .with_secondary_label(*prev, "previously declared here")
+                .emit();
+            return Err(ParseError::Failed);
         }
+        let Some(value) = self.bound.get(name.as_str()).copied().or(default) else {
+            diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("unbound parameter")
+                .with_primary_label(
+                    span,
+                    format!("`{name}` has no default value, and no value was bound to it"),
+                )
+                .with_note(format!("Bind a value to this parameter when compiling the program, e.g. with the `--param {name}=<value>` option of the `airc` CLI."))
+                .emit();
+            return Err(ParseError::Failed);
+        };
+        self.declared.insert(name.name(), (span, value));
+        Ok(value)
+    }
+
+    /// Returns true if `name` is a parameter declared so far
+    pub(crate) fn is_declared(&self, name: Identifier) -> bool {
+        self.declared.contains_key(&name.name())
+    }
+
+    /// Returns the value of the parameter `name`, which must have been declared before its use
+    pub(crate) fn value(
+        &self,
+        diagnostics: &DiagnosticsHandler,
+        name: Identifier,
+    ) -> Result<u64, ParseError> {
+        match self.declared.get(&name.name()) {
+            Some((_, value)) => Ok(*value),
+            None => {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("undefined parameter")
+                    .with_primary_label(
+                        name.span(),
+                        format!("`{name}` is not a parameter declared before this point"),
+                    )
+                    .with_note("Sizes and range bounds may only refer to parameters declared with `param` earlier in the source.")
+                    .emit();
+                Err(ParseError::Failed)
+            }
+        }
+    }
+
+    /// Reports the values bound to parameters which were never declared, as they are likely to be
+    /// misspelled.
+    fn check_bindings(&self, diagnostics: &DiagnosticsHandler) {
+        for name in self.bound.keys() {
+            if !self
+                .declared
+                .keys()
+                .any(|declared| declared.as_str() == name)
+            {
+                diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message(format!(
+                        "a value was bound to the undeclared parameter `{name}`"
+                    ))
+                    .with_note(
+                        "Only the parameters declared with `param` in the program can be bound.",
+                    )
+                    .emit();
+            }
+        }
+    }
+}
+
+/// The number of columns of the main and aux trace segments declared in the module being parsed,
+/// if its `trace_columns` section has been parsed.
+pub(crate) type SegmentWidths = Option<[usize; 2]>;
+
+/// The modulus of the 64-bit field, in which the rational constants are evaluated
+pub(crate) const FIELD_MODULUS: u64 = 0xFFFF_FFFF_0000_0001;
+
+/// Evaluates the rational constant `numerator/denominator` to its canonical representative in the
+/// 64-bit field, i.e. the product of `numerator` by the inverse of `denominator`.
+pub(crate) fn field_quotient(
+    diagnostics: &DiagnosticsHandler,
+    numerator: u64,
+    denominator: u64,
+    span: SourceSpan,
+) -> Result<u64, ParseError> {
+    let modulus = FIELD_MODULUS as u128;
+    let mul = |a: u128, b: u128| a * b % modulus;
+    let denominator = denominator as u128 % modulus;
+    if denominator == 0 {
+        diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("division by zero")
+            .with_primary_label(
+                span,
+                "the denominator of this constant is zero in the field",
+            )
+            .emit();
+        return Err(ParseError::Failed);
+    }
+
+    // the inverse is `d^(p - 2)`, computed by square-and-multiply
+    let mut inverse = 1;
+    let mut base = denominator;
+    let mut exp = modulus - 2;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            inverse = mul(inverse, base);
+        }
+        base = mul(base, base);
+        exp >>= 1;
+    }
+    Ok(mul(numerator as u128 % modulus, inverse) as u64)
+}
+
+/// Resolves a builtin constant referencing the number of columns of a trace segment, e.g.
+/// `NUM_MAIN_COLUMNS`, against the `trace_columns` section declared so far in the module being
+/// parsed, if any.
+pub(crate) fn trace_segment_width(
+    diagnostics: &DiagnosticsHandler,
+    segment_widths: &SegmentWidths,
+    segment: ast::TraceSegmentId,
+    span: SourceSpan,
+) -> Result<u64, ParseError> {
+    match segment_widths {
+        Some(widths) => Ok(widths[segment] as u64),
+        None => {
+            diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("unknown trace width")
+                .with_primary_label(
+                    span,
+                    "the number of trace columns is only known after the trace_columns section",
+                )
+                .with_note("Builtin constants referencing the number of columns of a trace segment can only be used after the trace_columns section of the same module.")
+                .emit();
+            Err(ParseError::Failed)
+        }
+    }
+}
+
+/// Loads the elements of a constant table from the file at `path`, in the given `format`, e.g.
+/// `json("round_constants.json")` for a JSON array of field elements.
+///
+/// A relative path is resolved from the directory of the source file containing the table, or
+/// from the current working directory if the source was not read from a file. The elements are
+/// returned along with the resolved path of the file.
+pub(crate) fn constant_table(
+    diagnostics: &DiagnosticsHandler,
+    codemap: &Arc<CodeMap>,
+    format: Identifier,
+    path: Symbol,
+    span: SourceSpan,
+) -> Result<(Vec<u64>, PathBuf), ParseError> {
+    let invalid_table = |label: String| {
+        diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("invalid constant table")
+            .with_primary_label(span, label)
+            .emit();
+        ParseError::Failed
+    };
+
+    if format.as_str() != "json" {
+        diagnostics
+            .diagnostic(Severity::Error)
+            .with_message("unknown constant table format")
+            .with_primary_label(
+                format.span(),
+                format!("`{format}` is not a supported constant table format"),
+            )
+            .with_note("Constant tables are loaded from JSON files, e.g. `json(\"table.json\")`.")
+            .emit();
+        return Err(ParseError::Failed);
+    }
+
+    let source_dir = match codemap.name(span.source_id()) {
+        Ok(FileName::Real(file)) => file
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .to_path_buf(),
+        _ => std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+    };
+    let file = source_dir.join(path.as_str());
+    let contents = std::fs::read_to_string(&file)
+        .map_err(|err| invalid_table(format!("could not read `{}`: {err}", file.display())))?;
+    let elements = serde_json::from_str::<Vec<u64>>(&contents).map_err(|err| {
+        invalid_table(format!(
+            "`{}` is not a JSON array of field elements: {err}",
+            file.display()
+        ))
+    })?;
+
+    if elements.is_empty() {
+        return Err(invalid_table(format!("`{}` is empty", file.display())));
+    }
+    if let Some(index) = elements
+        .iter()
+        .position(|&element| element >= FIELD_MODULUS)
+    {
+        return Err(invalid_table(format!(
+            "the element at index {index} of `{}` is not in the field",
+            file.display()
+        )));
     }
+    Ok((elements, file))
 }
 
 #[cfg(test)]
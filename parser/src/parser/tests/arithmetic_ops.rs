@@ -242,6 +242,31 @@ fn non_const_exponentiation() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn negative_exponentiation() {
+    // the operation must be put into a source section, or parsing will fail
+    let source = "
+    mod test
+
+    ev test([clk]):
+        enf clk' * clk^(-1) = 1";
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.evaluators.insert(
+        ident!(test),
+        EvaluatorFunction::new(
+            SourceSpan::UNKNOWN,
+            ident!(test),
+            vec![trace_segment!(0, "%0", [(clk, 1)])],
+            vec![enforce!(eq!(
+                mul!(access!(clk, 1), neg_exp!(access!(clk), int!(1))),
+                int!(1)
+            ))],
+        ),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn err_ops_without_matching_closing_parens() {
     // the operation must be put into a source section, or parsing will fail
@@ -83,6 +83,62 @@ fn constants_matrices() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn constants_rationals() {
+    let source = "
+    mod test
+
+    const HALF = 1/2
+    const A = [3/3, 2/4, 1/3]
+    const B = [[0/7, 6/3]]";
+
+    // the inverse of 2 in the 64-bit field is (p + 1) / 2, and the inverse of 3 is (2p + 1) / 3
+    const HALF: u64 = 0x7FFF_FFFF_8000_0001;
+    const THIRD: u64 = 0xAAAA_AAAA_0000_0001;
+    assert_eq!(HALF * 2 % crate::parser::FIELD_MODULUS, 1);
+    assert_eq!(THIRD as u128 * 3 % crate::parser::FIELD_MODULUS as u128, 1);
+
+    let mut expected = Module::new(ModuleType::Library, SourceSpan::UNKNOWN, ident!(test));
+    expected.constants.insert(
+        ident!(HALF),
+        Constant::new(
+            SourceSpan::UNKNOWN,
+            ident!(HALF),
+            ConstantExpr::Scalar(HALF),
+        )
+        .with_rational(true),
+    );
+    expected.constants.insert(
+        ident!(A),
+        Constant::new(
+            SourceSpan::UNKNOWN,
+            ident!(A),
+            ConstantExpr::Vector(vec![1, HALF, THIRD]),
+        )
+        .with_rational(true),
+    );
+    expected.constants.insert(
+        ident!(B),
+        Constant::new(
+            SourceSpan::UNKNOWN,
+            ident!(B),
+            ConstantExpr::Matrix(vec![vec![0, 2]]),
+        )
+        .with_rational(true),
+    );
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_rational_constant_division_by_zero() {
+    let source = "
+    mod test
+
+    const A = 1/0";
+
+    ParseTest::new().expect_module_diagnostic(source, "the denominator of this constant is zero");
+}
+
 #[test]
 fn err_const_matrix_unequal_number_of_cols() {
     // This is invalid since the number of columns for the two rows are unequal. However this
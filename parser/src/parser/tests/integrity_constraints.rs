@@ -480,6 +480,48 @@ fn ic_comprehension_with_selectors() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn ic_comprehension_with_filter() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [s[2], a, b, c[4]]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf x = a + b for (x, i) in (c, 0..4) if i when s[0]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(s, 2), (a, 1), (b, 1), (c, 4)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce_all!(
+            lc!(((x, expr!(access!(c))), (i, range!(0..4))) => eq!(access!(x), add!(access!(a), access!(b))), when access!(s[0]))
+                .with_filter(Some(access!(i)))
+        )],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn ic_comprehension_with_evaluator_call() {
     let source = "
@@ -741,3 +783,92 @@ fn err_empty_integrity_constraints() {
         enf clk.first = 1";
     ParseTest::new().expect_unrecognized_token(source);
 }
+
+// EXEMPTIONS
+// ================================================================================================
+
+#[test]
+fn integrity_constraint_with_exemptions() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + 1 except last 4";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![Statement::Exempt(Exempt::new(
+            SourceSpan::UNKNOWN,
+            4,
+            vec![enforce!(eq!(access!(clk, 1), add!(access!(clk), int!(1))))],
+        ))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_exemptions_of_no_rows() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk' = clk + 1 except last 0";
+
+    ParseTest::new().expect_program_diagnostic(source, "at least one row must be exempted");
+}
+
+#[test]
+fn err_exemptions_of_boundary_constraint() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [clk]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0 except last 2
+
+    integrity_constraints:
+        enf clk' = clk + 1";
+
+    ParseTest::new().expect_program_diagnostic(
+        source,
+        "only integrity constraints may be exempted from rows of the trace",
+    );
+}
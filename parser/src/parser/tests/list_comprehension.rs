@@ -445,6 +445,53 @@ fn err_bc_lc_one_member_two_iterables() {
         .expect_module_diagnostic(source, "bindings and iterables lengths are mismatched");
 }
 
+#[test]
+fn ic_trace_segment_lc() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c[4]]
+        aux: [p, q]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        let x = [col for col in $main]
+        let y = [col for col in $aux[0..2]]
+        enf a = x[5] + y[1]";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 4)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(p, 1), (q, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            let_!(x = lc!(((col, expr!(access!("$main")))) => access!(col)).into() =>
+                  let_!(y = lc!(((col, expr!(slice!("$aux", 0..2)))) => access!(col)).into() =>
+                        enforce!(eq!(access!(a), add!(access!(x[5]), access!(y[1])))))),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
 #[test]
 fn err_bc_lc_two_members_one_iterables() {
     let source = "
@@ -286,6 +286,16 @@ macro_rules! slice {
         })
     };
 
+    ($name:literal, $range:expr) => {
+        ScalarExpr::SymbolAccess(SymbolAccess {
+            span: miden_diagnostics::SourceSpan::UNKNOWN,
+            name: ResolvableIdentifier::Unresolved(NamespacedIdentifier::Binding(ident!($name))),
+            access_type: AccessType::Slice($range),
+            offset: 0,
+            ty: None,
+        })
+    };
+
     ($name:ident, $range:expr, $ty:expr) => {
         ScalarExpr::SymbolAccess(SymbolAccess {
             span: miden_diagnostics::SourceSpan::UNKNOWN,
@@ -574,6 +584,17 @@ macro_rules! exp {
     };
 }
 
+macro_rules! neg_exp {
+    ($lhs:expr, $rhs:expr) => {
+        ScalarExpr::Binary(BinaryExpr::new(
+            miden_diagnostics::SourceSpan::UNKNOWN,
+            BinaryOp::NegExp,
+            $lhs,
+            $rhs,
+        ))
+    };
+}
+
 macro_rules! import_all {
     ($module:ident) => {
         Import::All {
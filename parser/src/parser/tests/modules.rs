@@ -107,3 +107,143 @@ fn modules_integration_test() {
     ParseTest::new()
         .expect_program_ast_from_file("src/parser/tests/input/import_example.air", expected);
 }
+
+#[test]
+fn multiple_root_modules() {
+    let source = "
+    def alpha
+
+    use helpers::binary
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf binary([a])
+
+    def beta
+
+    trace_columns:
+        main: [x, y]
+
+    public_inputs:
+        outputs: [4]
+
+    boundary_constraints:
+        enf x.last = outputs[0]
+
+    integrity_constraints:
+        enf x' = x + y
+
+    mod helpers
+
+    ev binary([v]):
+        enf v^2 = v
+    ";
+
+    let test = ParseTest::new();
+    let programs = match test.parse_programs(source) {
+        Ok(programs) => programs,
+        Err(err) => {
+            test.diagnostics.emit(err);
+            panic!("expected parsing to succeed, see diagnostics for details");
+        }
+    };
+    assert_eq!(programs.len(), 2);
+    let names = programs
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["alpha", "beta"]);
+
+    let alpha = programs.get("alpha").unwrap();
+    assert_eq!(alpha.name, ident!(alpha));
+    assert_eq!(alpha.trace_columns[0].size, 1);
+    assert!(alpha.public_inputs.contains_key(&ident!(inputs)));
+    assert_eq!(alpha.evaluators.len(), 1);
+
+    let beta = programs.get("beta").unwrap();
+    assert_eq!(beta.name, ident!(beta));
+    assert_eq!(beta.trace_columns[0].size, 2);
+    assert!(beta.public_inputs.contains_key(&ident!(outputs)));
+    assert!(!beta.public_inputs.contains_key(&ident!(inputs)));
+    assert!(beta.evaluators.is_empty());
+}
+
+#[test]
+fn err_multiple_root_modules_name_conflict() {
+    let source = "
+    def example
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a
+
+    def example
+
+    trace_columns:
+        main: [b]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf b.first = 0
+
+    integrity_constraints:
+        enf b' = b
+    ";
+
+    ParseTest::new().expect_programs_diagnostic(source, "conflicting module definitions");
+}
+
+#[test]
+fn err_import_of_other_root_module() {
+    let source = "
+    def alpha
+
+    trace_columns:
+        main: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a' = a
+
+    def beta
+
+    use alpha::*
+
+    trace_columns:
+        main: [b]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf b.first = 0
+
+    integrity_constraints:
+        enf b' = b
+    ";
+
+    ParseTest::new().expect_programs_diagnostic(source, "invalid import of root module");
+}
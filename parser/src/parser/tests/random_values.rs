@@ -169,22 +169,60 @@ fn err_random_values_empty_list() {
 }
 
 #[test]
-fn err_random_values_multiple_declaration() {
+fn random_values_stages() {
     let source = "
     def test
 
     trace_columns:
         main: [clk]
+        aux: [a]
 
     random_values:
-        rand: [12]
-        alphas: [a, b[2]]
+        stage1: [x, y[2]]
+        stage2: [4]
+        stage3: [z]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
 
     integrity_constraints:
         enf clk = 0";
 
-    ParseTest::new()
-        .expect_module_diagnostic(source, "only one declaration may appear in random_values");
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1)]));
+    expected
+        .trace_columns
+        .push(trace_segment!(1, "$aux", [(a, 1)]));
+    expected.random_values = Some(RandomValues::with_stages(
+        SourceSpan::UNKNOWN,
+        vec![
+            random_values!("$stage1", [(x, 1), (y, 2)]),
+            random_values!("$stage2", 4),
+            random_values!("$stage3", [(z, 1)]),
+        ],
+    ));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(clk), int!(0)))],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
 }
 
 #[test]
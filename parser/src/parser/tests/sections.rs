@@ -1,3 +1,7 @@
+use miden_diagnostics::{SourceSpan, Span};
+
+use crate::ast::*;
+
 use super::ParseTest;
 
 // SECTIONS
@@ -13,3 +17,66 @@ fn error_constraint_without_section() {
     "#;
     ParseTest::new().expect_unrecognized_token(source);
 }
+
+#[test]
+fn assertions_section() {
+    let source = "
+    def test
+
+    trace_columns:
+        main: [a, b, c]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a = 0
+
+    assertions:
+        assert NUM_MAIN_COLUMNS == 3
+        assert NUM_MAIN_COLUMNS * 2 == 2^3 - 2";
+
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(a, 1), (b, 1), (c, 1)]));
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(bounded_access!(a, Boundary::First), int!(0)))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(access!(a), int!(0)))],
+    ));
+    expected.assertions = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![
+            Assertion::new(SourceSpan::UNKNOWN, int!(3), int!(3)),
+            Assertion::new(
+                SourceSpan::UNKNOWN,
+                mul!(int!(3), int!(2)),
+                sub!(exp!(int!(2), int!(3)), int!(2)),
+            ),
+        ],
+    ));
+
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn error_assertions_in_library() {
+    let source = "
+    mod test
+
+    assertions:
+        assert 1 == 1";
+
+    ParseTest::new().expect_module_diagnostic(source, "invalid assertions declaration");
+}
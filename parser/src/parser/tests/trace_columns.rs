@@ -140,6 +140,72 @@ fn trace_columns_groups() {
     ParseTest::new().expect_module_ast(source, expected);
 }
 
+#[test]
+fn trace_column_aliases() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp, ctx]
+
+    alias next_clk = clk'
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf next_clk = clk + 1
+    "#;
+    let mut expected = Module::new(ModuleType::Root, SourceSpan::UNKNOWN, ident!(test));
+    expected
+        .trace_columns
+        .push(trace_segment!(0, "$main", [(clk, 1), (fmp, 1), (ctx, 1)]));
+    expected.trace_aliases.insert(
+        ident!(next_clk),
+        TraceAlias::new(
+            SourceSpan::UNKNOWN,
+            ident!(next_clk),
+            SymbolAccess::new(SourceSpan::UNKNOWN, ident!(clk), AccessType::Default, 1),
+        ),
+    );
+    expected.public_inputs.insert(
+        ident!(inputs),
+        PublicInput::new(SourceSpan::UNKNOWN, ident!(inputs), 2),
+    );
+    expected.boundary_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            bounded_access!(clk, Boundary::First),
+            int!(0)
+        ))],
+    ));
+    expected.integrity_constraints = Some(Span::new(
+        SourceSpan::UNKNOWN,
+        vec![enforce!(eq!(
+            access!(next_clk),
+            add!(access!(clk), int!(1))
+        ))],
+    ));
+    ParseTest::new().expect_module_ast(source, expected);
+}
+
+#[test]
+fn err_trace_column_alias_conflicts_with_column() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp, ctx]
+
+    alias fmp = clk'
+    "#;
+
+    ParseTest::new().expect_module_diagnostic(source, "invalid alias declaration");
+}
+
 #[test]
 fn err_empty_trace_columns() {
     let source = r#"
@@ -171,3 +237,163 @@ fn err_main_trace_cols_missing() {
     ParseTest::new()
         .expect_module_diagnostic(source, "declaration of main trace columns is required");
 }
+
+#[test]
+fn trace_columns_mixed_offsets() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp[2], ctx]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    let module = ParseTest::new()
+        .parse_module(source)
+        .expect("expected parsing to succeed");
+
+    let main = &module.trace_columns[0];
+    assert_eq!(main.size, 4);
+    let offsets = main
+        .bindings
+        .iter()
+        .map(|binding| (binding.name.unwrap(), binding.offset, binding.size))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        offsets,
+        vec![
+            (ident!(clk), 0, 1),
+            (ident!(fmp), 1, 2),
+            (ident!(ctx), 3, 1)
+        ]
+    );
+}
+
+#[test]
+fn err_trace_columns_zero_size_group() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk, fmp[0], ctx]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    ParseTest::new().expect_module_diagnostic(source, "invalid trace binding declaration");
+}
+
+#[test]
+fn trace_columns_empty_aux() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk]
+        aux: []
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    let module = ParseTest::new()
+        .parse_module(source)
+        .expect("expected parsing to succeed");
+
+    assert_eq!(module.trace_columns.len(), 2);
+    let aux = &module.trace_columns[1];
+    assert_eq!(aux.name, "$aux");
+    assert_eq!(aux.size, 0);
+    assert!(aux.bindings.is_empty());
+    assert_eq!(aux.to_string(), "aux: []");
+}
+
+#[test]
+fn err_trace_columns_empty_main() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: []
+        aux: [a]
+
+    public_inputs:
+        inputs: [2]
+
+    boundary_constraints:
+        enf a.first = 0
+
+    integrity_constraints:
+        enf a = 0
+    "#;
+    ParseTest::new()
+        .expect_module_diagnostic(source, "the main trace must declare at least one column");
+}
+
+#[test]
+fn trace_columns_doc_strings() {
+    let source = r#"
+    def test
+
+    trace_columns:
+        main: [clk "clock cycle", fmp[2] "free memory pointer", ctx]
+        aux: [p "running product"]
+
+    public_inputs:
+        inputs: [2] "initial stack"
+
+    boundary_constraints:
+        enf clk.first = 0
+
+    integrity_constraints:
+        enf clk = 0
+    "#;
+    let module = ParseTest::new()
+        .parse_module(source)
+        .expect("expected parsing to succeed");
+
+    let main = &module.trace_columns[0];
+    let docs = main
+        .bindings
+        .iter()
+        .map(|binding| binding.doc())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        docs,
+        [Some("clock cycle"), Some("free memory pointer"), None]
+    );
+    assert_eq!(
+        main.binding(2).and_then(|b| b.doc()),
+        Some("free memory pointer")
+    );
+    assert_eq!(
+        main.to_string(),
+        r#"main: [clk "clock cycle", fmp[2] "free memory pointer", ctx]"#
+    );
+    assert_eq!(
+        module.trace_columns[1].bindings[0].doc(),
+        Some("running product")
+    );
+    assert_eq!(
+        module.public_inputs[&ident!(inputs)].doc(),
+        Some("initial stack")
+    );
+}
@@ -4,7 +4,7 @@ use miden_diagnostics::{CodeMap, DiagnosticsConfig, DiagnosticsHandler, Emitter,
 use pretty_assertions::assert_eq;
 
 use crate::{
-    ast::{Module, Program},
+    ast::{Module, Program, Programs},
     parser::{ParseError, Parser},
 };
 
@@ -79,7 +79,7 @@ impl ParseTest {
             codemap.clone(),
             emitter.clone(),
         ));
-        let parser = Parser::new((), codemap);
+        let parser = Parser::new(Default::default(), codemap);
         Self {
             diagnostics,
             emitter,
@@ -114,6 +114,11 @@ impl ParseTest {
             .parse_string::<Program, _, _>(&self.diagnostics, source)
     }
 
+    pub fn parse_programs(&self, source: &str) -> Result<Programs, ParseError> {
+        self.parser
+            .parse_string::<Programs, _, _>(&self.diagnostics, source)
+    }
+
     // TEST METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -131,6 +136,20 @@ impl ParseTest {
         }
     }
 
+    #[track_caller]
+    pub fn expect_programs_diagnostic(&self, source: &str, expected: &str) {
+        if let Err(err) = self.parse_programs(source) {
+            self.diagnostics.emit(err);
+            assert!(
+                self.emitter.captured().contains(expected),
+                "expected diagnostic output to contain the string: '{}'",
+                expected
+            );
+        } else {
+            panic!("expected parsing to fail, but it succeeded");
+        }
+    }
+
     #[track_caller]
     pub fn expect_module_diagnostic(&self, source: &str, expected: &str) {
         if let Err(err) = self.parse_module(source) {
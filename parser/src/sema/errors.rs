@@ -34,6 +34,28 @@ pub enum SemanticAnalysisError {
     #[error("module is invalid, see diagnostics for details")]
     Invalid,
 }
+impl SemanticAnalysisError {
+    /// Returns the stable code identifying this kind of error in diagnostics.
+    ///
+    /// Codes are never reused, so that tools consuming diagnostics may rely on them.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingRoot => "E0200",
+            Self::MissingConstraints => "E0201",
+            Self::MissingPublicInputs => "E0202",
+            Self::MissingModule(_) => "E0203",
+            Self::RootSectionInLibrary(_) => "E0204",
+            Self::RootImport(_) => "E0205",
+            Self::NameConflict(_) => "E0206",
+            Self::ImportUndefined(_) => "E0207",
+            Self::ImportSelf(_) => "E0208",
+            Self::ImportConflict { .. } => "E0209",
+            Self::ImportFailed(_) => "E0210",
+            Self::InvalidExpr(err) => err.code(),
+            Self::Invalid => "E0211",
+        }
+    }
+}
 impl Eq for SemanticAnalysisError {}
 impl PartialEq for SemanticAnalysisError {
     fn eq(&self, other: &Self) -> bool {
@@ -50,7 +72,8 @@ impl PartialEq for SemanticAnalysisError {
 }
 impl ToDiagnostic for SemanticAnalysisError {
     fn to_diagnostic(self) -> Diagnostic {
-        match self {
+        let code = self.code();
+        let diagnostic = match self {
             Self::MissingRoot => Diagnostic::error().with_message("no root module found"),
             Self::MissingConstraints => Diagnostic::error().with_message("root module must contain both boundary_constraints and integrity_constraints sections"),
             Self::MissingPublicInputs => Diagnostic::error().with_message("root module must contain a public_inputs section"),
@@ -90,6 +113,7 @@ impl ToDiagnostic for SemanticAnalysisError {
                     .with_message("failed import occurred here")]),
             Self::InvalidExpr(err) => err.to_diagnostic(),
             Self::Invalid => Diagnostic::error().with_message("module is invalid, see diagnostics for details"),
-        }
+        };
+        diagnostic.with_code(code)
     }
 }
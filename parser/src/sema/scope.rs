@@ -21,9 +21,10 @@ pub type Env<K, V> = Box<HashMap<K, V>>;
 /// When searching for keys, the search begins in the current scope, and searches upwards
 /// in the scope tree until either the root is reached and the search terminates, or the
 /// key is found in some intervening scope.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub enum LexicalScope<K, V> {
     /// An empty scope, this is the default state in which all [LexicalScope] start
+    #[default]
     Empty,
     /// Represents a non-empty, top-level (root) scope
     Root(Env<K, V>),
@@ -31,11 +32,6 @@ pub enum LexicalScope<K, V> {
     /// the environment of the current scope.
     Nested(Box<LexicalScope<K, V>>, Env<K, V>),
 }
-impl<K, V> Default for LexicalScope<K, V> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
 impl<K, V> LexicalScope<K, V> {
     /// Returns true if this scope is empty
     pub fn is_empty(&self) -> bool {
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt, mem,
     ops::ControlFlow,
 };
@@ -46,6 +46,7 @@ impl fmt::Display for ConstraintMode {
 /// This pass is used to perform a variety of semantic analysis tasks in a single traversal of a module AST
 ///
 /// * Resolves all identifiers to their fully-qualified names, or raises appropriate errors if unable
+/// * Rewrites references to trace column aliases as accesses to the aliased columns
 /// * Warns/errors as appropriate when declarations/bindings shadow or conflict with previous declarations/bindings
 /// * Assigns binding context and type information to identifiers, or raises appropriate errors if unable
 /// * Performs type checking
@@ -64,13 +65,16 @@ pub struct SemanticAnalysis<'a> {
     imported: Imported,
     globals: HashMap<Identifier, BindingType>,
     locals: LexicalScope<NamespacedIdentifier, BindingType>,
-    referenced: HashMap<QualifiedIdentifier, DependencyType>,
+    aliases: HashMap<Identifier, SymbolAccess>,
+    /// Ordered, so that the dependency graph is built in the same order on every run
+    referenced: BTreeMap<QualifiedIdentifier, DependencyType>,
     current_module: Option<ModuleId>,
     constraint_mode: ConstraintMode,
     saw_random_values: bool,
     has_undefined_variables: bool,
     has_type_errors: bool,
     in_constraint_comprehension: bool,
+    in_assertion: bool,
 }
 impl<'a> SemanticAnalysis<'a> {
     /// Create a new instance of the semantic analyzer
@@ -89,6 +93,7 @@ impl<'a> SemanticAnalysis<'a> {
             imported,
             globals: Default::default(),
             locals: Default::default(),
+            aliases: Default::default(),
             referenced: Default::default(),
             current_module: None,
             constraint_mode: ConstraintMode::None,
@@ -96,6 +101,7 @@ impl<'a> SemanticAnalysis<'a> {
             has_undefined_variables: false,
             has_type_errors: false,
             in_constraint_comprehension: false,
+            in_assertion: false,
         }
     }
 
@@ -167,6 +173,13 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                     None
                 );
             }
+            for stage in rv.stages.iter().copied() {
+                assert_eq!(
+                    self.globals
+                        .insert(stage.name, BindingType::RandomValue(stage)),
+                    None
+                );
+            }
         }
 
         // Next, add all the top-level root module declarations as locals, if this is the root module
@@ -184,6 +197,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                             offset: 0,
                             size: segment.size,
                             ty: Type::Vector(segment.size),
+                            doc: None,
                         })
                     ),
                     None
@@ -199,6 +213,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                                 offset: binding.offset,
                                 size: binding.size,
                                 ty: binding.ty,
+                                doc: binding.doc,
                             })
                         ),
                         None
@@ -279,6 +294,14 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             );
         }
 
+        // Next, we validate any trace column aliases declared in the root module.
+        //
+        // Alias names are guaranteed not to conflict with other module-level declarations, but we
+        // must ensure that they refer to trace columns, which are only known at this point.
+        for alias in module.trace_aliases.values() {
+            self.declare_trace_alias(alias)?;
+        }
+
         // From this point forward, we use the standard visitor traversal to visit every node
         // which can reference an identifier, and rewrite any references to imported names to
         // use the fully-qualified identifier. Likewise, any time we visit an imported item, we
@@ -299,6 +322,12 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             }
         }
 
+        if let Some(assertions) = module.assertions.as_mut() {
+            for assertion in assertions.iter_mut() {
+                self.visit_mut_assertion(assertion)?;
+            }
+        }
+
         self.current_module = None;
 
         // We're done
@@ -335,6 +364,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
                         offset: trace_binding.offset,
                         size: trace_binding.size,
                         ty: trace_binding.ty,
+                        doc: trace_binding.doc,
                     }),
                 );
             }
@@ -401,6 +431,19 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         ControlFlow::Continue(())
     }
 
+    /// Visit a compile-time assertion, which may only reference constants
+    fn visit_mut_assertion(
+        &mut self,
+        assertion: &mut Assertion,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        self.in_assertion = true;
+        self.visit_mut_scalar_expr(&mut assertion.lhs)?;
+        self.visit_mut_scalar_expr(&mut assertion.rhs)?;
+        self.in_assertion = false;
+
+        ControlFlow::Continue(())
+    }
+
     /// Visit scalar constraints and ensure that they are valid semantically, and have correct types
     fn visit_mut_enforce(&mut self, expr: &mut ScalarExpr) -> ControlFlow<SemanticAnalysisError> {
         // Verify that constraints are permitted here
@@ -433,6 +476,26 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         result
     }
 
+    /// Exemptions are only meaningful for integrity constraints, as boundary constraints apply to
+    /// a single row, and at least one row must be exempted.
+    fn visit_mut_exempt(&mut self, expr: &mut Exempt) -> ControlFlow<SemanticAnalysisError> {
+        if self.constraint_mode.is_boundary() {
+            self.invalid_constraint(
+                expr.span(),
+                "only integrity constraints may be exempted from rows of the trace",
+            )
+            .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+        if expr.rows == 0 {
+            self.invalid_constraint(expr.span(), "at least one row must be exempted")
+                .emit();
+            return ControlFlow::Break(SemanticAnalysisError::Invalid);
+        }
+
+        self.visit_mut_statement_block(&mut expr.body)
+    }
+
     fn visit_mut_let(&mut self, expr: &mut Let) -> ControlFlow<SemanticAnalysisError> {
         // Visit the binding expression first
         self.visit_mut_expr(&mut expr.value)?;
@@ -498,7 +561,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             if let Some(expected_ty) = result_ty.replace(iterable_ty) {
                 if expected_ty != iterable_ty {
                     self.has_type_errors = true;
-                    self.type_mismatch(
+                    let _ = self.type_mismatch(
                         Some(&iterable_ty),
                         iterable.span(),
                         &expected_ty,
@@ -553,7 +616,10 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
             );
         }
 
-        // Visit the selector
+        // Visit the filter and the selector
+        if let Some(filter) = expr.filter.as_deref_mut() {
+            self.visit_mut_scalar_expr(filter)?;
+        }
         if let Some(selector) = expr.selector.as_mut() {
             self.visit_mut_scalar_expr(selector)?;
         }
@@ -655,7 +721,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         match (expr.lhs.ty(), expr.rhs.ty()) {
             (Ok(Some(lty)), Ok(Some(rty))) => {
                 if lty != rty {
-                    self.type_mismatch(
+                    let _ = self.type_mismatch(
                         Some(&lty),
                         expr.lhs.span(),
                         &rty,
@@ -676,14 +742,31 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         // Any access to a bounded symbol is to be considered invalid, because the only places
         // in which they are valid are explicitly checked in the handling of `visit_mut_enforce`
         // Visit the underlying access first
-        self.diagnostics
+        let diagnostic = self
+            .diagnostics
             .diagnostic(Severity::Error)
-            .with_message("invalid expression")
-            .with_primary_label(
-                expr.span(),
-                "references to column boundaries are not permitted here",
-            )
-            .emit();
+            .with_message("invalid expression");
+        if self.constraint_mode.is_integrity() {
+            // A boundary access in an integrity constraint would otherwise be mistaken for a
+            // constraint on the boundary row, so point at the column it refers to
+            diagnostic
+                .with_primary_label(
+                    expr.span(),
+                    format!(
+                        "the {} row of column `{}` cannot be referenced in an integrity constraint",
+                        expr.boundary, expr.column.name
+                    ),
+                )
+                .with_note("The boundary accessors `.first` and `.last` are only valid in boundary constraints.")
+                .emit();
+        } else {
+            diagnostic
+                .with_primary_label(
+                    expr.span(),
+                    "references to column boundaries are not permitted here",
+                )
+                .emit();
+        }
         ControlFlow::Break(SemanticAnalysisError::Invalid)
     }
 
@@ -691,6 +774,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         &mut self,
         expr: &mut SymbolAccess,
     ) -> ControlFlow<SemanticAnalysisError> {
+        self.resolve_trace_alias(expr)?;
         self.visit_mut_resolvable_identifier(&mut expr.name)?;
 
         let resolved_binding_ty = match self.resolvable_binding_type(&expr.name) {
@@ -707,23 +791,36 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
         //
         // * This is an invalid trace access with offset in a boundary constraint
         // * This is an invalid periodic column access in a boundary constraint
-        // * This is an invalid public input access in an integrity constraint
+        //
+        // NOTE: Public inputs may be enabled in integrity constraints, which is checked when
+        // building the constraint graph.
         match &resolved_binding_ty.item {
-            BindingType::TraceColumn(_) | BindingType::TraceParam(_) => {
-                if self.constraint_mode.is_boundary() && expr.offset > 0 {
-                    self.has_type_errors = true;
-                    self.diagnostics.diagnostic(Severity::Error)
-                        .with_message("invalid expression")
-                        .with_primary_label(expr.span(), "invalid access of a trace column with offset")
-                        .with_note("It is not allowed to access trace columns with an offset in boundary constraints.")
-                        .emit();
-                }
+            BindingType::TraceColumn(_) | BindingType::TraceParam(_)
+                if self.constraint_mode.is_boundary() && expr.offset > 0 =>
+            {
+                self.has_type_errors = true;
+                self.diagnostics.diagnostic(Severity::Error)
+                    .with_message("invalid expression")
+                    .with_primary_label(expr.span(), "invalid access of a trace column with offset")
+                    .with_note(format!("`{expr}` refers to the next row of column `{}`, but boundary constraints only apply to the first or last row of the trace.", expr.name))
+                    .with_note("It is not allowed to access trace columns with an offset in boundary constraints.")
+                    .emit();
             }
             ty @ BindingType::PeriodicColumn(_) if self.constraint_mode.is_boundary() => {
                 self.invalid_access_in_constraint(expr.span(), ty);
             }
-            ty @ BindingType::PublicInput(_) if self.constraint_mode.is_integrity() => {
-                self.invalid_access_in_constraint(expr.span(), ty);
+            BindingType::Constant(_) => (),
+            ty if self.in_assertion => {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid assertion")
+                    .with_primary_label(
+                        expr.span(),
+                        format!("only constants can be referenced in assertions, but `{}` refers to {ty}", expr.name),
+                    )
+                    .with_note("Assertions are evaluated when the program is compiled, so both of their sides must be constant expressions.")
+                    .emit();
             }
             _ => (),
         }
@@ -883,9 +980,91 @@ impl<'a> VisitMut<SemanticAnalysisError> for SemanticAnalysis<'a> {
 }
 
 impl<'a> SemanticAnalysis<'a> {
-    /// Validate arguments for builtin functions, which currently consist only of the sum/prod reducers
+    /// Visit a call to the `diff` builtin on the left-hand side of an integrity constraint, and
+    /// validate that it is applied to a group of at least two trace columns.
+    ///
+    /// Such a constraint is desugared into one constraint per pair of adjacent columns during
+    /// inlining.
+    fn visit_mut_diff(&mut self, call: &mut Call) -> ControlFlow<SemanticAnalysisError> {
+        for arg in call.args.iter_mut() {
+            self.visit_mut_expr(arg)?;
+        }
+
+        let arg = match call.args.as_slice() {
+            [arg] => arg,
+            _ => {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid call")
+                    .with_primary_label(
+                        call.span(),
+                        format!(
+                            "the callee expects a single argument, but got {}",
+                            call.args.len()
+                        ),
+                    )
+                    .emit();
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            }
+        };
+
+        match self.expr_binding_type(arg) {
+            Ok(BindingType::TraceColumn(tb) | BindingType::TraceParam(tb)) if tb.size > 1 => {
+                call.ty = Some(Type::Vector(tb.size - 1));
+                ControlFlow::Continue(())
+            }
+            Ok(BindingType::TraceColumn(_) | BindingType::TraceParam(_)) => {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid call")
+                    .with_primary_label(
+                        call.span(),
+                        "this function expects a group of at least two trace columns",
+                    )
+                    .with_secondary_label(arg.span(), "but this argument is a single column")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+            Ok(_) => {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid call")
+                    .with_primary_label(
+                        call.span(),
+                        "this function expects a group of trace columns",
+                    )
+                    .with_secondary_label(arg.span(), "but this argument is not a trace column")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+            Err(_) => {
+                // We've already raised a diagnostic for this when visiting the access expression
+                assert!(self.has_undefined_variables || self.has_type_errors);
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+        }
+    }
+
+    /// Validate arguments for builtin functions, which currently consist of the sum/prod reducers,
+    /// and `diff`, which is only valid on the left-hand side of an integrity constraint
     fn validate_call_to_builtin(&mut self, call: &Call) -> ControlFlow<SemanticAnalysisError> {
         match call.callee.as_ref().name() {
+            // Valid uses of diff are handled by `visit_mut_diff`
+            symbols::Diff => {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid call")
+                    .with_primary_label(
+                        call.span(),
+                        "diff is only permitted on the left-hand side of an integrity constraint",
+                    )
+                    .emit();
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            }
             // The known reducers - each takes a single argument, which must be an aggregate or comprehension
             symbols::Sum | symbols::Prod => {
                 match call.args.as_slice() {
@@ -1035,7 +1214,7 @@ impl<'a> SemanticAnalysis<'a> {
 
                         if size != param.size {
                             self.has_type_errors = true;
-                            self.type_mismatch(
+                            let _ = self.type_mismatch(
                                 Some(&Type::Vector(param.size)),
                                 arg.span(),
                                 &Type::Vector(size),
@@ -1054,7 +1233,7 @@ impl<'a> SemanticAnalysis<'a> {
                             param.size,
                             Type::Vector(param.size),
                         ));
-                        self.binding_mismatch(
+                        let _ = self.binding_mismatch(
                             &binding_ty,
                             arg.span(),
                             &expected,
@@ -1163,49 +1342,56 @@ impl<'a> SemanticAnalysis<'a> {
                         // Visit the expression operands
                         self.visit_mut_symbol_access(&mut access.column)?;
 
-                        // Ensure the referenced symbol was a trace column, and that it produces a scalar value
-                        let (found, segment) =
-                            match self.resolvable_binding_type(&access.column.name) {
-                                Ok(ty) => match ty.item.access(access.column.access_type.clone()) {
-                                    Ok(BindingType::TraceColumn(tb))
-                                    | Ok(BindingType::TraceParam(tb)) => {
-                                        if tb.is_scalar() {
-                                            (ty, tb.segment)
-                                        } else {
-                                            let inferred = tb.ty();
-                                            return self.type_mismatch(
-                                                Some(&inferred),
-                                                access.span(),
-                                                &Type::Felt,
-                                                ty.span(),
-                                                constraint_span,
-                                            );
-                                        }
-                                    }
-                                    Ok(aty) => {
-                                        let expected = BindingType::TraceColumn(TraceBinding::new(
-                                            constraint_span,
-                                            Identifier::new(constraint_span, symbols::Main),
-                                            0,
-                                            0,
-                                            1,
-                                            Type::Felt,
-                                        ));
-                                        return self.binding_mismatch(
-                                            &aty,
+                        // Ensure the referenced symbol was a trace column, and that it produces a scalar value,
+                        // unless it is a group of columns constrained against a vector
+                        let (found, segment) = match self
+                            .resolvable_binding_type(&access.column.name)
+                        {
+                            Ok(ty) => match ty.item.access(access.column.access_type.clone()) {
+                                Ok(BindingType::TraceColumn(tb))
+                                | Ok(BindingType::TraceParam(tb)) => {
+                                    if tb.is_scalar() {
+                                        (ty, tb.segment)
+                                    } else if matches!(
+                                        expr.rhs.as_ref(),
+                                        ScalarExpr::SymbolAccess(_)
+                                    ) {
+                                        return self.visit_mut_vector_boundary_constraint(tb, expr);
+                                    } else {
+                                        let inferred = tb.ty();
+                                        return self.type_mismatch(
+                                            Some(&inferred),
                                             access.span(),
-                                            &expected,
+                                            &Type::Felt,
                                             ty.span(),
                                             constraint_span,
                                         );
                                     }
-                                    _ => return ControlFlow::Break(SemanticAnalysisError::Invalid),
-                                },
-                                Err(_) => {
-                                    // We've already raised a diagnostic for the undefined variable
-                                    return ControlFlow::Break(SemanticAnalysisError::Invalid);
                                 }
-                            };
+                                Ok(aty) => {
+                                    let expected = BindingType::TraceColumn(TraceBinding::new(
+                                        constraint_span,
+                                        Identifier::new(constraint_span, symbols::Main),
+                                        0,
+                                        0,
+                                        1,
+                                        Type::Felt,
+                                    ));
+                                    return self.binding_mismatch(
+                                        &aty,
+                                        access.span(),
+                                        &expected,
+                                        ty.span(),
+                                        constraint_span,
+                                    );
+                                }
+                                _ => return ControlFlow::Break(SemanticAnalysisError::Invalid),
+                            },
+                            Err(_) => {
+                                // We've already raised a diagnostic for the undefined variable
+                                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+                            }
+                        };
 
                         // Validate that the symbol access produces a scalar value
                         //
@@ -1280,6 +1466,63 @@ impl<'a> SemanticAnalysis<'a> {
         }
     }
 
+    /// Visit a boundary constraint on a group of trace columns, e.g. `enf stack.first = stack_inputs`,
+    /// and validate that its right-hand side is a vector with one element per column of the group.
+    ///
+    /// Such a constraint is desugared into one constraint per column during inlining.
+    fn visit_mut_vector_boundary_constraint(
+        &mut self,
+        group: TraceBinding,
+        expr: &mut BinaryExpr,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        self.visit_mut_scalar_expr(expr.rhs.as_mut())?;
+        let ScalarExpr::SymbolAccess(ref access) = expr.rhs.as_ref() else {
+            unreachable!()
+        };
+        match access.ty {
+            Some(Type::Vector(len)) if len == group.size => (),
+            Some(Type::Vector(len)) => {
+                self.has_type_errors = true;
+                self.invalid_constraint(expr.span(), "the sides of this constraint have different lengths")
+                    .with_secondary_label(expr.lhs.span(), format!("this group has {} columns", group.size))
+                    .with_secondary_label(access.span(), format!("but this vector has {len} elements"))
+                    .with_note("A group of columns is constrained elementwise, so it must be constrained against a vector of the same length.")
+                    .emit();
+                return ControlFlow::Break(SemanticAnalysisError::Invalid);
+            }
+            Some(ref ty) => {
+                return self.type_mismatch(
+                    Some(ty),
+                    access.span(),
+                    &Type::Vector(group.size),
+                    expr.lhs.span(),
+                    expr.span(),
+                );
+            }
+            // If no type is known, a diagnostic is already emitted
+            None => return ControlFlow::Break(SemanticAnalysisError::Invalid),
+        }
+
+        // Random values are only valid in constraints against auxiliary trace segments
+        if group.segment == 0 && self.saw_random_values {
+            self.has_type_errors = true;
+            self.invalid_constraint(
+                expr.lhs.span(),
+                "this constrains columns in the main trace segment",
+            )
+            .with_secondary_label(
+                expr.rhs.span(),
+                "but this expression references random values",
+            )
+            .with_note(
+                "Constraints involving random values are only valid with auxiliary trace segments",
+            )
+            .emit();
+        }
+
+        ControlFlow::Continue(())
+    }
+
     fn visit_mut_integrity_constraint(
         &mut self,
         expr: &mut ScalarExpr,
@@ -1294,7 +1537,23 @@ impl<'a> SemanticAnalysis<'a> {
         //
         match expr {
             ScalarExpr::Binary(ref mut expr) if expr.op == BinaryOp::Eq => {
-                self.visit_mut_binary_expr(expr)
+                match expr.lhs.as_mut() {
+                    ScalarExpr::Call(ref mut call) if call.is_diff() => {
+                        self.visit_mut_diff(call)?;
+                        self.visit_mut_scalar_expr(expr.rhs.as_mut())?;
+                        match expr.rhs.ty() {
+                            Ok(Some(ty)) if ty != Type::Felt => self.type_mismatch(
+                                Some(&ty),
+                                expr.rhs.span(),
+                                &Type::Felt,
+                                call.span(),
+                                expr.span(),
+                            ),
+                            _ => ControlFlow::Continue(()),
+                        }
+                    }
+                    _ => self.visit_mut_binary_expr(expr),
+                }
             }
             ScalarExpr::Call(ref mut expr) => {
                 // Visit the call normally, so we can resolve the callee identifier
@@ -1322,7 +1581,7 @@ impl<'a> SemanticAnalysis<'a> {
                                         // and we will have already validated the reference
                                         let (import_id, module_id) = self.imported.get_key_value(&id).unwrap();
                                         let module = self.library.get(module_id).unwrap();
-                                        if module.evaluators.get(&id.id()).is_none() {
+                                        if !module.evaluators.contains_key(&id.id()) {
                                             self.invalid_constraint(id.span(), "calls in constraints must be to evaluator functions")
                                                 .with_secondary_label(import_id.span(), "the function imported here is not an evaluator")
                                                 .emit();
@@ -1453,6 +1712,102 @@ impl<'a> SemanticAnalysis<'a> {
         ControlFlow::Break(SemanticAnalysisError::Invalid)
     }
 
+    /// Validates that `alias` refers to a trace column, and makes it visible to the rest of the module
+    fn declare_trace_alias(&mut self, alias: &TraceAlias) -> ControlFlow<SemanticAnalysisError> {
+        let target = NamespacedIdentifier::Binding(*alias.target.name.as_ref());
+        match self.locals.get(&target) {
+            Some(BindingType::TraceColumn(_)) => {
+                self.aliases.insert(alias.name, alias.target.clone());
+                ControlFlow::Continue(())
+            }
+            Some(binding_ty) => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid alias declaration")
+                    .with_primary_label(
+                        alias.target.span(),
+                        format!("expected a trace column, but this is {binding_ty}"),
+                    )
+                    .with_note("Aliases may only refer to trace columns")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+            None => {
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid alias declaration")
+                    .with_primary_label(alias.target.span(), "this trace column is not defined")
+                    .emit();
+                ControlFlow::Break(SemanticAnalysisError::Invalid)
+            }
+        }
+    }
+
+    /// If `expr` refers to a trace column alias which is not shadowed by a local binding, rewrite it
+    /// as an access to the aliased trace column, combining the access type and row offset of both.
+    fn resolve_trace_alias(
+        &mut self,
+        expr: &mut SymbolAccess,
+    ) -> ControlFlow<SemanticAnalysisError> {
+        let ResolvableIdentifier::Unresolved(NamespacedIdentifier::Binding(name)) = expr.name
+        else {
+            return ControlFlow::Continue(());
+        };
+        if self
+            .locals
+            .get(&NamespacedIdentifier::Binding(name))
+            .is_some()
+        {
+            return ControlFlow::Continue(());
+        }
+        let Some(target) = self.aliases.get(&name) else {
+            return ControlFlow::Continue(());
+        };
+
+        let access_type = match (&expr.access_type, &target.access_type) {
+            (AccessType::Default, access_type) | (access_type, AccessType::Default) => {
+                access_type.clone()
+            }
+            _ => {
+                self.has_type_errors = true;
+                self.diagnostics
+                    .diagnostic(Severity::Error)
+                    .with_message("invalid variable access")
+                    .with_primary_label(expr.span(), "this alias cannot be indexed")
+                    .with_secondary_label(
+                        target.span(),
+                        "because it already refers to an indexed access here",
+                    )
+                    .emit();
+                return ControlFlow::Continue(());
+            }
+        };
+
+        let offset = expr.offset + target.offset;
+        if offset > SymbolAccess::MAX_OFFSET {
+            self.has_type_errors = true;
+            self.diagnostics
+                .diagnostic(Severity::Error)
+                .with_message("invalid row offset")
+                .with_primary_label(
+                    expr.span(),
+                    format!("this accesses the row at offset {offset} of the trace"),
+                )
+                .with_secondary_label(target.span(), "because this alias already has an offset")
+                .with_note(format!(
+                    "Trace columns can only be accessed with an offset of at most {}.",
+                    SymbolAccess::MAX_OFFSET
+                ))
+                .emit();
+            return ControlFlow::Continue(());
+        }
+
+        expr.name = target.name;
+        expr.access_type = access_type;
+        expr.offset = offset;
+        ControlFlow::Continue(())
+    }
+
     fn invalid_access_in_constraint(&mut self, span: SourceSpan, ty: &BindingType) {
         self.has_type_errors = true;
         let mode = self.constraint_mode;
@@ -1565,6 +1920,13 @@ impl<'a> SemanticAnalysis<'a> {
                         FunctionType::Function(vec![Type::Vector(usize::MAX)], Type::Felt);
                     Ok(Span::new(qid.span(), BindingType::Function(folder_ty)))
                 }
+                symbols::Diff => {
+                    let diff_ty = FunctionType::Function(
+                        vec![Type::Vector(usize::MAX)],
+                        Type::Vector(usize::MAX),
+                    );
+                    Ok(Span::new(qid.span(), BindingType::Function(diff_ty)))
+                }
                 name => unimplemented!("unsupported builtin: {}", name),
             }
         } else {
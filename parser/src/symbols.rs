@@ -24,6 +24,8 @@ pub mod predefined {
     pub const Sum: Symbol = Symbol::new(3);
     /// The symbol `prod`
     pub const Prod: Symbol = Symbol::new(4);
+    /// The symbol `diff`
+    pub const Diff: Symbol = Symbol::new(5);
 
     pub(super) const __SYMBOLS: &[(Symbol, &str)] = &[
         (Main, "$main"),
@@ -31,6 +33,7 @@ pub mod predefined {
         (Builtin, "$builtin"),
         (Sum, "sum"),
         (Prod, "prod"),
+        (Diff, "diff"),
     ];
 }
 
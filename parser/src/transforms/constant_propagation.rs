@@ -1,10 +1,10 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     ops::ControlFlow,
 };
 
 use air_pass::Pass;
-use miden_diagnostics::{DiagnosticsHandler, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, SourceSpan, Span, Spanned};
 
 use crate::{
     ast::{visit::VisitMut, *},
@@ -17,6 +17,9 @@ use crate::{
 /// value, replacing local variables bound to constants with the constant value, and folding
 /// constant expressions into constant values.
 ///
+/// Scalar values propagated from a named constant are given the span of the access they replace,
+/// and the provenance of each such value is recorded in [Program::constant_accesses].
+///
 /// It is expected that the provided [Program] has already been run through semantic analysis,
 /// so it will panic if it encounters invalid constructions to help catch bugs in the semantic
 /// analysis pass, should they exist.
@@ -27,6 +30,8 @@ pub struct ConstantPropagation<'a> {
     local: LexicalScope<Identifier, Span<ConstantExpr>>,
     /// The set of identifiers which are live (in use) in the current scope
     live: HashSet<Identifier>,
+    /// The named constants from which scalar values were propagated, by the span of the value
+    accesses: BTreeMap<SourceSpan, ConstantAccess>,
     in_constraint_comprehension: bool,
 }
 impl<'p> Pass for ConstantPropagation<'p> {
@@ -38,7 +43,10 @@ impl<'p> Pass for ConstantPropagation<'p> {
         self.global.reserve(program.constants.len());
 
         match self.run_visitor(&mut program) {
-            ControlFlow::Continue(()) => Ok(program),
+            ControlFlow::Continue(()) => {
+                program.constant_accesses.append(&mut self.accesses);
+                Ok(program)
+            }
             ControlFlow::Break(err) => Err(err),
         }
     }
@@ -50,10 +58,59 @@ impl<'a> ConstantPropagation<'a> {
             global: Default::default(),
             local: Default::default(),
             live: Default::default(),
+            accesses: Default::default(),
             in_constraint_comprehension: false,
         }
     }
 
+    /// Returns the value of the constant referenced by `name`, along with its span, and the
+    /// identifier of the constant declaration if `name` refers to a named constant
+    #[allow(clippy::type_complexity)]
+    fn constant_value(
+        &self,
+        name: &ResolvableIdentifier,
+    ) -> Option<(SourceSpan, ConstantExpr, Option<QualifiedIdentifier>)> {
+        match name {
+            // Possibly a reference to a constant declaration
+            ResolvableIdentifier::Resolved(ref qid) => self
+                .global
+                .get(qid)
+                .cloned()
+                .map(|s| (s.span(), s.item, Some(*qid))),
+            // Possibly a reference to a local bound to a constant
+            ResolvableIdentifier::Local(ref id) => self
+                .local
+                .get(id)
+                .cloned()
+                .map(|s| (s.span(), s.item, None)),
+            // Other identifiers cannot possibly be constant
+            _ => None,
+        }
+    }
+
+    /// Returns the span to give to the scalar `value` propagated from an access to a constant.
+    ///
+    /// If the access refers to a named constant, the span of the access is used, and the
+    /// provenance of the value is recorded. Otherwise, the span of the constant is used.
+    fn propagated_span(
+        &mut self,
+        span: SourceSpan,
+        access_span: SourceSpan,
+        name: Option<QualifiedIdentifier>,
+        index: Option<usize>,
+        column: Option<usize>,
+        value: u64,
+    ) -> SourceSpan {
+        match name {
+            Some(name) if access_span != SourceSpan::UNKNOWN => {
+                self.accesses
+                    .insert(access_span, ConstantAccess::new(name, index, column, value));
+                access_span
+            }
+            _ => span,
+        }
+    }
+
     fn run_visitor(&mut self, program: &mut Program) -> ControlFlow<SemanticAnalysisError> {
         // Record all of the constant declarations
         for (name, constant) in program.constants.iter() {
@@ -71,7 +128,14 @@ impl<'a> ConstantPropagation<'a> {
 
         // Visit all of the constraints
         self.visit_mut_boundary_constraints(&mut program.boundary_constraints)?;
-        self.visit_mut_integrity_constraints(&mut program.integrity_constraints)
+        self.visit_mut_integrity_constraints(&mut program.integrity_constraints)?;
+
+        // Fold the assertions, which are checked once the program is lowered
+        for assertion in program.assertions.iter_mut() {
+            self.visit_mut_assertion(assertion)?;
+        }
+
+        ControlFlow::Continue(())
     }
 
     fn try_fold_binary_expr(
@@ -100,26 +164,25 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
             ScalarExpr::Const(_) => ControlFlow::Continue(()),
             // Need to check if this access is to a constant value, and transform to a constant if so
             ScalarExpr::SymbolAccess(sym) => {
-                let constant_value = match sym.name {
-                    // Possibly a reference to a constant declaration
-                    ResolvableIdentifier::Resolved(ref qid) => {
-                        self.global.get(qid).cloned().map(|s| (s.span(), s.item))
-                    }
-                    // Possibly a reference to a local bound to a constant
-                    ResolvableIdentifier::Local(ref id) => {
-                        self.local.get(id).cloned().map(|s| (s.span(), s.item))
-                    }
-                    // Other identifiers cannot possibly be constant
-                    _ => None,
-                };
-                if let Some((span, constant_expr)) = constant_value {
+                let access_span = sym.span();
+                if let Some((span, constant_expr, name)) = self.constant_value(&sym.name) {
                     match constant_expr {
                         ConstantExpr::Scalar(value) => {
                             assert_eq!(sym.access_type, AccessType::Default);
+                            let span =
+                                self.propagated_span(span, access_span, name, None, None, value);
                             *expr = ScalarExpr::Const(Span::new(span, value));
                         }
                         ConstantExpr::Vector(value) => match sym.access_type {
                             AccessType::Index(idx) => {
+                                let span = self.propagated_span(
+                                    span,
+                                    access_span,
+                                    name,
+                                    Some(idx),
+                                    None,
+                                    value[idx],
+                                );
                                 *expr = ScalarExpr::Const(Span::new(span, value[idx]));
                             }
                             // This access cannot be resolved here, so we need to record the fact
@@ -130,6 +193,14 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                         },
                         ConstantExpr::Matrix(value) => match sym.access_type {
                             AccessType::Matrix(row, col) => {
+                                let span = self.propagated_span(
+                                    span,
+                                    access_span,
+                                    name,
+                                    Some(row),
+                                    Some(col),
+                                    value[row][col],
+                                );
                                 *expr = ScalarExpr::Const(Span::new(span, value[row][col]));
                             }
                             // This access cannot be resolved here, so we need to record the fact
@@ -173,23 +244,14 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
             //
             // We deal with symbol accesses directly, as they may evaluate to an aggregate constant
             Expr::SymbolAccess(ref mut access) => {
-                let constant_value = match access.name {
-                    // Possibly a reference to a constant declaration
-                    ResolvableIdentifier::Resolved(ref qid) => {
-                        self.global.get(qid).cloned().map(|s| (s.span(), s.item))
-                    }
-                    // Possibly a reference to a local bound to a constant
-                    ResolvableIdentifier::Local(ref id) => {
-                        self.local.get(id).cloned().map(|s| (s.span(), s.item))
-                    }
-                    // Other identifiers cannot possibly be constant
-                    _ => None,
-                };
-                if let Some((span, constant_expr)) = constant_value {
+                let access_span = access.span();
+                if let Some((span, constant_expr, name)) = self.constant_value(&access.name) {
                     match constant_expr {
-                        cexpr @ ConstantExpr::Scalar(_) => {
+                        ConstantExpr::Scalar(value) => {
                             assert_eq!(access.access_type, AccessType::Default);
-                            *expr = Expr::Const(Span::new(span, cexpr));
+                            let span =
+                                self.propagated_span(span, access_span, name, None, None, value);
+                            *expr = Expr::Const(Span::new(span, ConstantExpr::Scalar(value)));
                         }
                         ConstantExpr::Vector(value) => match access.access_type.clone() {
                             AccessType::Default => {
@@ -200,6 +262,14 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                                 *expr = Expr::Const(Span::new(span, ConstantExpr::Vector(vector)));
                             }
                             AccessType::Index(idx) => {
+                                let span = self.propagated_span(
+                                    span,
+                                    access_span,
+                                    name,
+                                    Some(idx),
+                                    None,
+                                    value[idx],
+                                );
                                 *expr =
                                     Expr::Const(Span::new(span, ConstantExpr::Scalar(value[idx])));
                             }
@@ -223,6 +293,14 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                                 ));
                             }
                             AccessType::Matrix(row, col) => {
+                                let span = self.propagated_span(
+                                    span,
+                                    access_span,
+                                    name,
+                                    Some(row),
+                                    Some(col),
+                                    value[row][col],
+                                );
                                 *expr = Expr::Const(Span::new(
                                     span,
                                     ConstantExpr::Scalar(value[row][col]),
@@ -524,6 +602,9 @@ impl<'a> VisitMut<SemanticAnalysisError> for ConstantPropagation<'a> {
                     self.visit_mut_list_comprehension(expr)?;
                     self.in_constraint_comprehension = false;
                 }
+                Statement::Exempt(ref mut expr) => {
+                    self.visit_mut_statement_block(&mut expr.body)?;
+                }
                 Statement::Expr(ref mut expr) => {
                     self.visit_mut_expr(expr)?;
                 }
@@ -581,13 +662,13 @@ pub(crate) fn try_fold_binary_expr(
                 Ok(exp) => l.item.checked_pow(exp),
                 Err(_) => return Err(InvalidExprError::InvalidExponent(expr.span())),
             },
-            // This op cannot be folded
-            BinaryOp::Eq => return Ok(None),
+            // These ops cannot be folded, the inverse of a constant is only defined in the field
+            BinaryOp::Eq | BinaryOp::NegExp => return Ok(None),
         };
         Ok(folded.map(|v| Span::new(expr.span(), v)))
     } else {
         // If we observe a non-constant power in an exponentiation operation, raise an error
-        if expr.op == BinaryOp::Exp && !expr.rhs.is_constant() {
+        if expr.op.is_exp() && !expr.rhs.is_constant() {
             Err(InvalidExprError::NonConstantExponent(expr.rhs.span()))
         } else {
             Ok(None)
@@ -4,7 +4,7 @@ use std::{
 };
 
 use air_pass::Pass;
-use miden_diagnostics::{DiagnosticsHandler, SourceSpan, Span, Spanned};
+use miden_diagnostics::{DiagnosticsHandler, Severity, SourceSpan, Span, Spanned};
 
 use crate::{
     ast::{visit::VisitMut, *},
@@ -19,12 +19,12 @@ use super::constant_propagation;
 /// * Monomorphizing and inlining evaluators/functions at their call sites
 /// * Unrolling constraint comprehensions into a sequence of scalar constraints
 /// * Unrolling list comprehensions into a tree of `let` statements which end in
-/// a vector expression (the implicit result of the tree). Each iteration of the
-/// unrolled comprehension is reified as a value and bound to a variable so that
-/// other transformations may refer to it directly.
+///   a vector expression (the implicit result of the tree). Each iteration of the
+///   unrolled comprehension is reified as a value and bound to a variable so that
+///   other transformations may refer to it directly.
 /// * Rewriting aliases of top-level declarations to refer to those declarations directly
 /// * Removing let-bound variables which are unused, which is also used to clean up
-/// after the aliasing rewrite mentioned above.
+///   after the aliasing rewrite mentioned above.
 ///
 /// The trickiest transformation comes with inlining the body of evaluators at their
 /// call sites, as evaluator parameter lists can arbitrarily destructure/regroup columns
@@ -67,6 +67,8 @@ pub struct Inlining<'a> {
     random_values: Option<RandomValues>,
     /// The public_inputs declaration
     public_inputs: BTreeMap<Identifier, PublicInput>,
+    /// The values of the constant declarations, which are still referenced by vector accesses
+    constants: HashMap<QualifiedIdentifier, ConstantExpr>,
     /// All local/global bindings in scope
     bindings: LexicalScope<Identifier, BindingType>,
     /// The values of all let-bound variables in scope
@@ -103,6 +105,12 @@ impl<'p> Pass for Inlining<'p> {
         self.random_values = program.random_values.clone();
         // And the public inputs
         self.public_inputs = program.public_inputs.clone();
+        // And the constants, as constant vectors may be constrained against groups of columns
+        self.constants = program
+            .constants
+            .iter()
+            .map(|(name, constant)| (*name, constant.value.clone()))
+            .collect();
 
         // Add all of the local bindings visible in the root module, except for
         // constants and periodic columns, which by this point have been rewritten
@@ -120,6 +128,7 @@ impl<'p> Pass for Inlining<'p> {
                     offset: 0,
                     size: segment.size,
                     ty: Type::Vector(segment.size),
+                    doc: None,
                 }),
             );
             for binding in segment.bindings.iter().copied() {
@@ -132,6 +141,7 @@ impl<'p> Pass for Inlining<'p> {
                         offset: binding.offset,
                         size: binding.size,
                         ty: binding.ty,
+                        doc: binding.doc,
                     }),
                 );
             }
@@ -152,6 +162,10 @@ impl<'p> Pass for Inlining<'p> {
                 self.bindings
                     .insert(binding.name, BindingType::RandomValue(binding));
             }
+            for stage in rv.stages.iter().copied() {
+                self.bindings
+                    .insert(stage.name, BindingType::RandomValue(stage));
+            }
         }
         // Public inputs..
         for input in program.public_inputs.values() {
@@ -183,6 +197,7 @@ impl<'a> Inlining<'a> {
             trace: vec![],
             random_values: None,
             public_inputs: Default::default(),
+            constants: Default::default(),
             bindings: Default::default(),
             let_bound: Default::default(),
             imported: Default::default(),
@@ -291,6 +306,12 @@ impl<'a> Inlining<'a> {
                 }
                 Ok(statements)
             }
+            // Exempted constraints are expanded in place, so that the exemption applies to all
+            // constraints in the expansion.
+            Statement::Exempt(mut expr) => {
+                self.expand_statement_block(&mut expr.body)?;
+                Ok(vec![Statement::Exempt(expr)])
+            }
             // Expression statements are introduced during inlining, and are always already expanded,
             // but they are recursively visited to apply rewrites
             Statement::Expr(mut expr) => {
@@ -303,21 +324,21 @@ impl<'a> Inlining<'a> {
     /// Let expressions are expanded using the following rules:
     ///
     /// * The let-bound expression is expanded first. If it expands to a statement block and
-    /// not an expression, the block is inlined in place of the let being expanded, and the
-    /// rest of the expansion takes place at the end of the block; replacing the last statement
-    /// in the block. If the last statement in the block was an expression, it is treated as
-    /// the let-bound value. If the last statement in the block was another `let` however, then
-    /// we recursively walk down the let tree until we reach the bottom, which must always be
-    /// an expression statement.
+    ///   not an expression, the block is inlined in place of the let being expanded, and the
+    ///   rest of the expansion takes place at the end of the block; replacing the last statement
+    ///   in the block. If the last statement in the block was an expression, it is treated as
+    ///   the let-bound value. If the last statement in the block was another `let` however, then
+    ///   we recursively walk down the let tree until we reach the bottom, which must always be
+    ///   an expression statement.
     ///
     /// * The body is expanded in-place after the previous step has been completed.
     ///
     /// * If a let-bound variable is an alias for a declaration, we replace all uses
-    /// of the variable with direct references to the declaration, making the let-bound variable
-    /// dead
+    ///   of the variable with direct references to the declaration, making the let-bound variable
+    ///   dead
     ///
     /// * If a let-bound variable is dead (i.e. has no references), then the let is elided,
-    /// by replacing it with the result of expanding its body
+    ///   by replacing it with the result of expanding its body
     fn expand_let(&mut self, expr: Let) -> Result<Vec<Statement>, SemanticAnalysisError> {
         let span = expr.span();
         let name = expr.name;
@@ -519,23 +540,133 @@ impl<'a> Inlining<'a> {
         match constraint {
             ScalarExpr::Binary(BinaryExpr {
                 op: BinaryOp::Eq,
-                mut lhs,
+                lhs,
                 mut rhs,
                 span,
-            }) => {
-                self.rewrite_scalar_expr(lhs.as_mut())?;
-                self.rewrite_scalar_expr(rhs.as_mut())?;
-                Ok(vec![Statement::Enforce(ScalarExpr::Binary(BinaryExpr {
-                    op: BinaryOp::Eq,
-                    lhs,
-                    rhs,
-                    span,
-                }))])
-            }
+            }) => match *lhs {
+                // Constraints on the differences of adjacent columns are expanded into one
+                // constraint per pair of columns
+                ScalarExpr::Call(mut call) if call.is_diff() => {
+                    assert_eq!(call.args.len(), 1);
+                    self.expand_diff_constraint(span, call.args.pop().unwrap(), *rhs)
+                }
+                // Boundary constraints on a group of columns are expanded into one constraint per
+                // column, against the corresponding element of the right-hand side
+                ScalarExpr::BoundedSymbolAccess(access)
+                    if access
+                        .column
+                        .ty
+                        .as_ref()
+                        .map_or(false, |ty| !ty.is_scalar()) =>
+                {
+                    self.expand_vector_boundary_constraint(span, access, *rhs)
+                }
+                mut lhs => {
+                    self.rewrite_scalar_expr(&mut lhs)?;
+                    self.rewrite_scalar_expr(rhs.as_mut())?;
+                    Ok(vec![Statement::Enforce(ScalarExpr::Binary(BinaryExpr {
+                        op: BinaryOp::Eq,
+                        lhs: Box::new(lhs),
+                        rhs,
+                        span,
+                    }))])
+                }
+            },
             invalid => unreachable!("unexpected constraint node: {:#?}", invalid),
         }
     }
 
+    /// Expand a constraint of the form `enf diff(s) = rhs` over a group of trace columns `s` of
+    /// size `n` into the `n - 1` constraints `enf s[i + 1] - s[i] = rhs`, for `i` in `0..n - 1`.
+    fn expand_diff_constraint(
+        &mut self,
+        span: SourceSpan,
+        group: Expr,
+        rhs: ScalarExpr,
+    ) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        let access = match group {
+            Expr::SymbolAccess(access) => access,
+            // Semantic analysis guarantees that the argument is a group of trace columns
+            invalid => panic!("invalid argument to diff builtin: {:#?}", invalid),
+        };
+        let size = match self.access_binding_type(&access) {
+            Ok(BindingType::TraceColumn(tb) | BindingType::TraceParam(tb)) => tb.size,
+            Ok(_) | Err(_) => unimplemented!(),
+        };
+
+        let mut statements = Vec::with_capacity(size - 1);
+        for i in 0..(size - 1) {
+            let column =
+                |idx| ScalarExpr::SymbolAccess(access.access(AccessType::Index(idx)).unwrap());
+            let difference = ScalarExpr::Binary(BinaryExpr::new(
+                span,
+                BinaryOp::Sub,
+                column(i + 1),
+                column(i),
+            ));
+            let constraint =
+                ScalarExpr::Binary(BinaryExpr::new(span, BinaryOp::Eq, difference, rhs.clone()));
+            statements.extend(self.expand_constraint(constraint)?);
+        }
+
+        Ok(statements)
+    }
+
+    /// Expand a boundary constraint of the form `enf s.first = v` over a group of trace columns `s`
+    /// and a vector `v` of size `n` into the `n` constraints `enf s[i].first = v[i]`, for `i` in `0..n`.
+    fn expand_vector_boundary_constraint(
+        &mut self,
+        span: SourceSpan,
+        group: BoundedSymbolAccess,
+        rhs: ScalarExpr,
+    ) -> Result<Vec<Statement>, SemanticAnalysisError> {
+        let vector = match rhs {
+            ScalarExpr::SymbolAccess(access) => access,
+            // Semantic analysis guarantees that the right-hand side is a vector access
+            invalid => panic!("invalid vector boundary constraint: {:#?}", invalid),
+        };
+        let size = match group.column.ty {
+            Some(Type::Vector(size)) => size,
+            ref invalid => panic!("invalid group of columns: {:#?}", invalid),
+        };
+
+        let mut statements = Vec::with_capacity(size);
+        for i in 0..size {
+            let column = group.column.access(AccessType::Index(i)).unwrap();
+            let element = self.vector_element(&vector, i);
+            let constraint = ScalarExpr::Binary(BinaryExpr::new(
+                span,
+                BinaryOp::Eq,
+                ScalarExpr::BoundedSymbolAccess(BoundedSymbolAccess::new(
+                    group.span(),
+                    column,
+                    group.boundary,
+                )),
+                element,
+            ));
+            statements.extend(self.expand_constraint(constraint)?);
+        }
+
+        Ok(statements)
+    }
+
+    /// Returns the element `index` of the vector accessed by `vector`, folded to its value if the
+    /// vector is a constant, as accesses to constants were only folded when they were scalar.
+    fn vector_element(&self, vector: &SymbolAccess, index: usize) -> ScalarExpr {
+        let element = vector.access(AccessType::Index(index)).unwrap();
+        let constant = match element.name {
+            ResolvableIdentifier::Resolved(ref qid) => self.constants.get(qid),
+            _ => None,
+        };
+        let value = match (constant, &element.access_type) {
+            (Some(ConstantExpr::Vector(elems)), AccessType::Index(idx)) => elems[*idx],
+            (Some(ConstantExpr::Matrix(rows)), AccessType::Matrix(row, col)) => rows[*row][*col],
+            (Some(invalid), _) => panic!("invalid access to constant: {:#?}", invalid),
+            (None, _) => return ScalarExpr::SymbolAccess(element),
+        };
+        ScalarExpr::Const(Span::new(element.span(), value))
+    }
+
     /// This function rewrites expressions which contain accesses for which rewrites have been registered.
     fn rewrite_expr(&mut self, expr: &mut Expr) -> Result<(), SemanticAnalysisError> {
         match expr {
@@ -599,9 +730,11 @@ impl<'a> Inlining<'a> {
                 self.rewrite_scalar_expr(lhs.as_mut())?;
                 self.rewrite_scalar_expr(rhs.as_mut())?;
                 match op {
-                    BinaryOp::Exp if !rhs.is_constant() => Err(SemanticAnalysisError::InvalidExpr(
-                        InvalidExprError::NonConstantExponent(rhs.span()),
-                    )),
+                    op if op.is_exp() && !rhs.is_constant() => {
+                        Err(SemanticAnalysisError::InvalidExpr(
+                            InvalidExprError::NonConstantExponent(rhs.span()),
+                        ))
+                    }
                     _ => Ok(()),
                 }
             }
@@ -626,9 +759,9 @@ impl<'a> Inlining<'a> {
     /// the expansion is, respectively:
     ///
     /// * A tree of let statements (using generated variables), where each let binds the value of a
-    /// single iteration of the comprehension. The body of the final let, and thus the effective value
-    /// of the entire tree, is a vector containing all of the bindings in the evaluation order of the
-    /// comprehension.
+    ///   single iteration of the comprehension. The body of the final let, and thus the effective value
+    ///   of the entire tree, is a vector containing all of the bindings in the evaluation order of the
+    ///   comprehension.
     /// * A flat list of constraint statements
     fn expand_comprehension(
         &mut self,
@@ -818,7 +951,31 @@ impl<'a> Inlining<'a> {
             return Err(err);
         }
 
-        // Next, handle comprehension filters/selectors as follows:
+        // Filters decide whether this iteration is expanded at all, so they are rewritten like the body,
+        // and must fold to a constant: the set of constraints cannot depend on the trace.
+        if let Some(mut filter) = lc.filter.as_deref().cloned() {
+            if let ControlFlow::Break(err) = visitor.visit_mut_scalar_expr(&mut filter) {
+                return Err(err);
+            }
+            match filter {
+                ScalarExpr::Const(value) if value.item == 0 => return Ok(vec![]),
+                ScalarExpr::Const(_) => (),
+                other => {
+                    self.diagnostics
+                        .diagnostic(Severity::Error)
+                        .with_message("invalid comprehension filter")
+                        .with_primary_label(
+                            other.span(),
+                            "this filter cannot be evaluated at compile time",
+                        )
+                        .with_note("The filter of a comprehension decides which constraints are generated, so it must be a constant for each iteration. Use `when` to select constraints using values of the trace.")
+                        .emit();
+                    return Err(SemanticAnalysisError::Invalid);
+                }
+            }
+        }
+
+        // Next, handle comprehension selectors as follows:
         //
         // 1. Selectors are evaluated in the same context as the body, so we must visit iterable references in the same way.
         // 2. If a selector has a constant value, we can elide the selector for this iteration. Furthermore, in situations where
@@ -911,6 +1068,9 @@ impl<'a> Inlining<'a> {
                 for binding in rv.bindings.iter().copied() {
                     eval_bindings.insert(binding.name, BindingType::RandomValue(binding));
                 }
+                for stage in rv.stages.iter().copied() {
+                    eval_bindings.insert(stage.name, BindingType::RandomValue(stage));
+                }
             }
 
             for segment in self.trace.iter() {
@@ -923,6 +1083,7 @@ impl<'a> Inlining<'a> {
                         offset: 0,
                         size: segment.size,
                         ty: Type::Vector(segment.size),
+                        doc: None,
                     }),
                 );
                 for binding in segment.bindings.iter().copied() {
@@ -935,6 +1096,7 @@ impl<'a> Inlining<'a> {
                             offset: binding.offset,
                             size: binding.size,
                             ty: binding.ty,
+                            doc: binding.doc,
                         }),
                     );
                 }
@@ -1171,20 +1333,22 @@ impl<'a> Inlining<'a> {
             // relative to that trace binding
             match self.access_binding_type(access).unwrap() {
                 BindingType::TraceColumn(tb) => {
-                    let original_binding = self.trace[tb.segment]
-                        .bindings
-                        .iter()
-                        .find(|b| b.name == tb.name)
-                        .unwrap();
-                    let (access_type, ty) = if original_binding.size == 1 {
+                    let segment = &self.trace[tb.segment];
+                    // accesses to a whole trace segment, e.g. `$main`, are relative to its first
+                    // column, and always access it as a vector
+                    let (original_offset, is_scalar) = if tb.name == Some(segment.name) {
+                        (0, false)
+                    } else {
+                        let original_binding =
+                            segment.bindings.iter().find(|b| b.name == tb.name).unwrap();
+                        (original_binding.offset, original_binding.size == 1)
+                    };
+                    let (access_type, ty) = if is_scalar {
                         (AccessType::Default, Type::Felt)
                     } else if tb.size == 1 {
-                        (
-                            AccessType::Index(tb.offset - original_binding.offset),
-                            Type::Felt,
-                        )
+                        (AccessType::Index(tb.offset - original_offset), Type::Felt)
                     } else {
-                        let start = tb.offset - original_binding.offset;
+                        let start = tb.offset - original_offset;
                         (
                             AccessType::Slice(start..(start + tb.size)),
                             Type::Vector(tb.size),
@@ -1444,6 +1608,7 @@ impl<'a> VisitMut<SemanticAnalysisError> for ApplyConstraintSelector<'a> {
                 ControlFlow::Continue(())
             }
             Statement::EnforceAll(_) => unreachable!(),
+            Statement::Exempt(ref mut expr) => self.visit_mut_exempt(expr),
             Statement::Expr(_) => ControlFlow::Continue(()),
         }
     }
@@ -1,5 +1,9 @@
 //! This crate is pulled in from the [Firefly](https://github.com/GetFirefly/firefly) compiler, licensed under Apache 2.0
 
+mod metrics;
+
+pub use self::metrics::{PhaseTiming, PhaseTimings, Timed};
+
 /// This trait represents anything that can be run as a pass.
 ///
 /// Passes operate on an input value, and return either the same type, or a new type, depending on the nature of the pass.
@@ -28,6 +32,15 @@ pub trait Pass {
     {
         Chain::new(self, pass)
     }
+
+    /// Records the time spent running this pass in the provided [PhaseTimings], as the phase of
+    /// the given name, if the `metrics` feature is enabled
+    fn timed<'t>(self, name: &'static str, timings: &'t PhaseTimings) -> Timed<'t, Self>
+    where
+        Self: Sized,
+    {
+        Timed::new(self, name, timings)
+    }
 }
 impl<P, T, U, E> Pass for &mut P
 where
@@ -0,0 +1,120 @@
+use core::time::Duration;
+#[cfg(feature = "metrics")]
+use std::{cell::RefCell, time::Instant};
+
+use super::Pass;
+
+/// The time spent in a single phase of the compilation, as recorded by [PhaseTimings].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseTiming {
+    /// The name of the phase, e.g. `parse` or the name of a pass
+    pub name: &'static str,
+    /// The time at which the phase started, relative to the creation of the [PhaseTimings]
+    pub start: Duration,
+    /// The time spent in the phase
+    pub duration: Duration,
+}
+
+/// Records the time spent in each phase of the compilation, e.g. parsing, each pass, and code
+/// generation, in the order in which the phases start.
+///
+/// Timings are only recorded when the `metrics` feature is enabled. Otherwise, this is a
+/// zero-sized type, timing a phase simply runs it, and no timings are ever reported.
+#[derive(Debug)]
+pub struct PhaseTimings {
+    #[cfg(feature = "metrics")]
+    created: Instant,
+    #[cfg(feature = "metrics")]
+    phases: RefCell<Vec<PhaseTiming>>,
+}
+impl Default for PhaseTimings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl PhaseTimings {
+    /// Creates an empty set of timings, relative to which the start of each phase is recorded
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "metrics")]
+            created: Instant::now(),
+            #[cfg(feature = "metrics")]
+            phases: RefCell::new(vec![]),
+        }
+    }
+
+    /// Runs the provided closure as the phase of the given name, recording the time spent in it
+    #[cfg(feature = "metrics")]
+    pub fn time<T>(&self, name: &'static str, phase: impl FnOnce() -> T) -> T {
+        // the phase is recorded before running it, so that phases nested in it are recorded
+        // after it, in the order in which they start
+        let start = Instant::now();
+        let index = {
+            let mut phases = self.phases.borrow_mut();
+            phases.push(PhaseTiming {
+                name,
+                start: start - self.created,
+                duration: Duration::ZERO,
+            });
+            phases.len() - 1
+        };
+        let result = phase();
+        self.phases.borrow_mut()[index].duration = start.elapsed();
+        result
+    }
+
+    /// Runs the provided closure as the phase of the given name
+    #[cfg(not(feature = "metrics"))]
+    #[inline(always)]
+    pub fn time<T>(&self, _name: &'static str, phase: impl FnOnce() -> T) -> T {
+        phase()
+    }
+
+    /// Returns the timings recorded so far, in the order in which the phases started
+    pub fn phases(&self) -> Vec<PhaseTiming> {
+        #[cfg(feature = "metrics")]
+        return self.phases.borrow().clone();
+        #[cfg(not(feature = "metrics"))]
+        vec![]
+    }
+
+    /// Returns a human-readable report of the timings recorded so far, with one line per phase
+    pub fn report(&self) -> String {
+        self.phases()
+            .iter()
+            .map(|phase| format!("{:<32}{:>12.3?}\n", phase.name, phase.duration))
+            .collect()
+    }
+}
+
+/// A pass which records the time spent running the wrapped pass in a [PhaseTimings], as the
+/// phase of the given name.
+///
+/// This is produced by [Pass::timed].
+pub struct Timed<'t, P> {
+    pass: P,
+    name: &'static str,
+    timings: &'t PhaseTimings,
+}
+impl<'t, P> Timed<'t, P> {
+    pub(super) fn new(pass: P, name: &'static str, timings: &'t PhaseTimings) -> Self {
+        Self {
+            pass,
+            name,
+            timings,
+        }
+    }
+}
+impl<'t, P, T, U, E> Pass for Timed<'t, P>
+where
+    P: for<'a> Pass<Input<'a> = T, Output<'a> = U, Error = E>,
+{
+    type Input<'a> = T;
+    type Output<'a> = U;
+    type Error = E;
+
+    fn run<'a>(&mut self, input: Self::Input<'a>) -> Result<Self::Output<'a>, Self::Error> {
+        let pass = &mut self.pass;
+        self.timings.time(self.name, || pass.run(input))
+    }
+}
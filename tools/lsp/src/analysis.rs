@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use air_ir::{Air, CompileError};
+use air_pass::Pass;
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, Hover, HoverContents, MarkupContent, MarkupKind,
+    NumberOrString, Position, Range,
+};
+use miden_diagnostics::{
+    term::{Config, DisplayStyle},
+    CaptureEmitter, CodeMap, DiagnosticsConfig, DiagnosticsHandler, Verbosity,
+};
+
+/// The name of the diagnostics source reported to the client
+const SOURCE: &str = "airscript";
+
+/// The result of the analysis of an AirScript program
+pub struct Analysis {
+    /// The errors and warnings reported while compiling the program, in the order in which they
+    /// were reported
+    pub diagnostics: Vec<Diagnostic>,
+    /// The IR of the program, if it compiled successfully
+    pub air: Option<Air>,
+}
+
+/// Compiles the AirScript program `source` to the IR, through the same passes as the `airc` CLI
+/// but without any of the backends, and collects the diagnostics reported along the way.
+pub fn analyze(source: &str) -> Analysis {
+    let codemap = Arc::new(CodeMap::new());
+    let emitter = Arc::new(CaptureEmitter::new());
+    let config = DiagnosticsConfig {
+        verbosity: Verbosity::Warning,
+        warnings_as_errors: false,
+        no_warn: false,
+        // each diagnostic is rendered as one line per primary label, see [diagnostic]
+        display: Config {
+            display_style: DisplayStyle::Short,
+            ..Default::default()
+        },
+    };
+    let diagnostics = DiagnosticsHandler::new(config, codemap.clone(), emitter.clone());
+
+    let air = air_parser::parse(&diagnostics, codemap, source)
+        .map_err(CompileError::Parse)
+        .and_then(|ast| {
+            let mut pipeline = air_parser::transforms::ConstantPropagation::new(&diagnostics)
+                .chain(air_parser::transforms::Inlining::new(&diagnostics))
+                .chain(air_ir::passes::AstToAir::new(&diagnostics))
+                .chain(air_ir::passes::UnusedDeclarations::new(&diagnostics))
+                .chain(air_ir::passes::NextRowOnlyColumns::new(&diagnostics));
+            pipeline.run(ast)
+        });
+    let air = match air {
+        Ok(air) => Some(air),
+        Err(err) => {
+            // the error failing the compilation only carries information of its own when nothing
+            // was reported before it
+            if !diagnostics.has_errors() {
+                diagnostics.emit(err);
+            }
+            None
+        }
+    };
+
+    let diagnostics = emitter
+        .captured()
+        .lines()
+        .filter_map(|line| diagnostic(source, line))
+        .collect();
+    Analysis { diagnostics, air }
+}
+
+/// Returns the description of the trace column or segment named by the identifier at `position`
+/// in `source`, whose IR is `air`, e.g. ``"`a`: column 0 of the `$main` trace segment"``.
+pub fn hover(air: &Air, source: &str, position: Position) -> Option<Hover> {
+    let (name, range) = identifier_at(source, position)?;
+
+    let mut description = None;
+    for segment in air.trace_columns.iter() {
+        if segment.name.as_str() == name {
+            description = Some(format!(
+                "`{name}`: trace segment {} of {} columns",
+                segment.id, segment.size
+            ));
+            break;
+        }
+        let binding = segment
+            .bindings
+            .iter()
+            .find(|binding| matches!(binding.name, Some(id) if id.as_str() == name));
+        if let Some(binding) = binding {
+            let columns = match binding.size {
+                1 => format!("column {}", binding.offset),
+                size => format!(
+                    "columns {} to {}",
+                    binding.offset,
+                    binding.offset + size - 1
+                ),
+            };
+            description = Some(format!(
+                "`{name}`: {columns} of the `{}` trace segment",
+                segment.name
+            ));
+            break;
+        }
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: description?,
+        }),
+        range: Some(range),
+    })
+}
+
+/// Converts a diagnostic rendered in the short display style, i.e.
+/// `<file>:<line>:<column>: <severity>[<code>]: <message>`, to a [Diagnostic] spanning the
+/// identifier at its location. The location is omitted for diagnostics without a primary label,
+/// which are reported at the start of the source.
+fn diagnostic(source: &str, line: &str) -> Option<Diagnostic> {
+    let (locus, header) = line.split_once(": ")?;
+    let (start, header) = match location(locus) {
+        Some(start) => (start, header),
+        None => (Position::default(), line),
+    };
+    let (kind, message) = header.split_once(": ")?;
+    let (severity, code) = match kind.split_once('[') {
+        Some((severity, code)) => (severity, Some(code.trim_end_matches(']'))),
+        None => (kind, None),
+    };
+    let severity = match severity {
+        "bug" | "error" => DiagnosticSeverity::ERROR,
+        "warning" => DiagnosticSeverity::WARNING,
+        "note" => DiagnosticSeverity::INFORMATION,
+        "help" => DiagnosticSeverity::HINT,
+        _ => return None,
+    };
+
+    let range = match identifier_at(source, start) {
+        Some((_, range)) if range.start == start => range,
+        _ => Range::new(start, Position::new(start.line, start.character + 1)),
+    };
+    Some(Diagnostic {
+        range,
+        severity: Some(severity),
+        code: code.map(|code| NumberOrString::String(code.to_string())),
+        source: Some(SOURCE.to_string()),
+        message: message.to_string(),
+        ..Default::default()
+    })
+}
+
+/// Parses the `<file>:<line>:<column>` location of a rendered diagnostic, whose lines and columns
+/// start from 1, to a [Position].
+fn location(locus: &str) -> Option<Position> {
+    let mut parts = locus.rsplitn(3, ':');
+    let column = parts.next()?.parse::<u32>().ok()?;
+    let line = parts.next()?.parse::<u32>().ok()?;
+    parts.next()?;
+    Some(Position::new(line.checked_sub(1)?, column.checked_sub(1)?))
+}
+
+/// Returns the identifier at `position` in `source`, along with its range.
+///
+/// NOTE: Columns are counted in characters, which matches the UTF-16 offsets of the protocol as
+/// long as the source is ASCII, as AirScript identifiers are.
+fn identifier_at(source: &str, position: Position) -> Option<(String, Range)> {
+    let is_identifier = |c: &char| c.is_ascii_alphanumeric() || *c == '_' || *c == '$';
+
+    let line = source.lines().nth(position.line as usize)?;
+    let column = position.character as usize;
+    let chars = line.chars().collect::<Vec<_>>();
+    if !chars.get(column).map_or(false, is_identifier) {
+        return None;
+    }
+    let start = chars[..column]
+        .iter()
+        .rposition(|c| !is_identifier(c))
+        .map_or(0, |i| i + 1);
+    let end = chars[column..]
+        .iter()
+        .position(|c| !is_identifier(c))
+        .map_or(chars.len(), |i| column + i);
+
+    let name = chars[start..end].iter().collect();
+    let range = Range::new(
+        Position::new(position.line, start as u32),
+        Position::new(position.line, end as u32),
+    );
+    Some((name, range))
+}
@@ -0,0 +1,5 @@
+mod analysis;
+mod server;
+
+pub use self::analysis::{analyze, hover, Analysis};
+pub use self::server::{run, ServerError};
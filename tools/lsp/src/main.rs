@@ -0,0 +1,9 @@
+use lsp_server::Connection;
+
+fn main() -> Result<(), air_lsp::ServerError> {
+    // the client talks to the server over stdin and stdout
+    let (connection, io_threads) = Connection::stdio();
+    air_lsp::run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use std::{collections::HashMap, error::Error};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{HoverRequest, Request as _},
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, HoverParams, HoverProviderCapability, PublishDiagnosticsParams,
+    SaveOptions, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions, Url,
+};
+
+use crate::analysis::{analyze, hover};
+
+/// The error type of the server, which stops on protocol and serialization errors
+pub type ServerError = Box<dyn Error + Sync + Send>;
+
+/// Runs the language server over `connection`, from the initialization handshake until the client
+/// shuts it down.
+///
+/// The diagnostics of a document are published when it is opened and when it is saved, and hovering
+/// the name of a trace column shows its index and segment.
+pub fn run(connection: Connection) -> Result<(), ServerError> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(
+            TextDocumentSyncOptions {
+                open_close: Some(true),
+                change: Some(TextDocumentSyncKind::FULL),
+                save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions {
+                    include_text: Some(false),
+                })),
+                ..Default::default()
+            },
+        )),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut server = Server {
+        connection: &connection,
+        documents: Default::default(),
+    };
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                server.handle_request(request)?;
+            }
+            Message::Notification(notification) => server.handle_notification(notification)?,
+            Message::Response(_) => (),
+        }
+    }
+    Ok(())
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    /// The text of the open documents, kept in sync with the client
+    documents: HashMap<Url, String>,
+}
+impl<'a> Server<'a> {
+    fn handle_request(&mut self, request: Request) -> Result<(), ServerError> {
+        let response = match request.method.as_str() {
+            HoverRequest::METHOD => {
+                let (id, params) = request.extract::<HoverParams>(HoverRequest::METHOD)?;
+                let document = params.text_document_position_params;
+                let hover = self
+                    .documents
+                    .get(&document.text_document.uri)
+                    .and_then(|text| {
+                        let air = analyze(text).air?;
+                        hover(&air, text, document.position)
+                    });
+                Response::new_ok(id, hover)
+            }
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request: {method}"),
+            ),
+        };
+        self.connection.sender.send(response.into())?;
+        Ok(())
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), ServerError> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidOpenTextDocumentParams>(DidOpenTextDocument::METHOD)?;
+                let uri = params.text_document.uri;
+                self.documents
+                    .insert(uri.clone(), params.text_document.text);
+                self.publish_diagnostics(uri)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidChangeTextDocumentParams>(DidChangeTextDocument::METHOD)?;
+                // the documents are synchronized in full, so the last change is the whole text
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(params.text_document.uri, change.text);
+                }
+                Ok(())
+            }
+            DidSaveTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidSaveTextDocumentParams>(DidSaveTextDocument::METHOD)?;
+                self.publish_diagnostics(params.text_document.uri)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params = notification
+                    .extract::<DidCloseTextDocumentParams>(DidCloseTextDocument::METHOD)?;
+                self.documents.remove(&params.text_document.uri);
+                // the diagnostics of a closed document are cleared
+                self.send_diagnostics(params.text_document.uri, vec![])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Analyzes the document at `uri`, and publishes its diagnostics
+    fn publish_diagnostics(&self, uri: Url) -> Result<(), ServerError> {
+        let Some(text) = self.documents.get(&uri) else {
+            return Ok(());
+        };
+        let diagnostics = analyze(text).diagnostics;
+        self.send_diagnostics(uri, diagnostics)
+    }
+
+    fn send_diagnostics(
+        &self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+    ) -> Result<(), ServerError> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(notification.into())?;
+        Ok(())
+    }
+}
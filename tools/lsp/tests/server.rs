@@ -0,0 +1,175 @@
+use std::thread;
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId};
+use lsp_types::{
+    notification::{DidOpenTextDocument, Exit, Initialized, Notification as _, PublishDiagnostics},
+    request::{HoverRequest, Initialize, Request as _, Shutdown},
+    DiagnosticSeverity, DidOpenTextDocumentParams, Hover, HoverContents, HoverParams,
+    InitializeParams, InitializedParams, Position, PublishDiagnosticsParams, Range,
+    TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams, Url,
+};
+
+static INVALID: &str = include_str!("fixtures/invalid.air");
+static VALID: &str = include_str!("fixtures/valid.air");
+
+/// A client of the language server, which runs in the background until the client is dropped
+struct Client {
+    connection: Connection,
+    server: Option<thread::JoinHandle<()>>,
+    next_id: i32,
+}
+impl Client {
+    /// Starts the server, and completes the initialization handshake
+    fn new() -> Self {
+        let (server, connection) = Connection::memory();
+        let server = thread::spawn(move || air_lsp::run(server).expect("server failed"));
+        let mut client = Self {
+            connection,
+            server: Some(server),
+            next_id: 0,
+        };
+
+        client.request(Initialize::METHOD, InitializeParams::default());
+        client.notify(Initialized::METHOD, InitializedParams {});
+        client
+    }
+
+    /// Sends a request, and returns the result of its response
+    fn request(&mut self, method: &str, params: impl serde::Serialize) -> serde_json::Value {
+        self.next_id += 1;
+        let id = RequestId::from(self.next_id);
+        let request = Request::new(id.clone(), method.to_string(), params);
+        self.connection.sender.send(request.into()).unwrap();
+        match self.connection.receiver.recv().unwrap() {
+            Message::Response(response) if response.id == id => {
+                assert!(response.error.is_none(), "{:?}", response.error);
+                response.result.unwrap_or_default()
+            }
+            message => panic!("expected a response to {method}, got {message:?}"),
+        }
+    }
+
+    fn notify(&self, method: &str, params: impl serde::Serialize) {
+        let notification = Notification::new(method.to_string(), params);
+        self.connection.sender.send(notification.into()).unwrap();
+    }
+
+    /// Opens the document `uri`, and returns the diagnostics published for it
+    fn open(&self, uri: &Url, text: &str) -> PublishDiagnosticsParams {
+        self.notify(
+            DidOpenTextDocument::METHOD,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    "airscript".to_string(),
+                    0,
+                    text.to_string(),
+                ),
+            },
+        );
+        match self.connection.receiver.recv().unwrap() {
+            Message::Notification(notification) => notification
+                .extract(PublishDiagnostics::METHOD)
+                .expect("expected published diagnostics"),
+            message => panic!("expected published diagnostics, got {message:?}"),
+        }
+    }
+}
+impl Drop for Client {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            return;
+        }
+        self.request(Shutdown::METHOD, ());
+        self.notify(Exit::METHOD, ());
+        self.server.take().unwrap().join().unwrap();
+    }
+}
+
+#[test]
+fn diagnostics_on_open() {
+    let client = Client::new();
+    let uri = Url::parse("file:///fixtures/invalid.air").unwrap();
+    let published = client.open(&uri, INVALID);
+
+    assert_eq!(published.uri, uri);
+    let [diagnostic] = &published.diagnostics[..] else {
+        panic!(
+            "expected a single diagnostic, got {:?}",
+            published.diagnostics
+        );
+    };
+    assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+    assert_eq!(diagnostic.message, "reference to undefined variable");
+    // `step` in `enf clk' = clk + step`
+    assert_eq!(
+        diagnostic.range,
+        Range::new(Position::new(12, 21), Position::new(12, 25))
+    );
+}
+
+#[test]
+fn no_diagnostics_on_open() {
+    let client = Client::new();
+    let uri = Url::parse("file:///fixtures/valid.air").unwrap();
+    let published = client.open(&uri, VALID);
+    assert!(
+        published.diagnostics.is_empty(),
+        "{:?}",
+        published.diagnostics
+    );
+}
+
+#[test]
+fn hover_trace_column() {
+    let mut client = Client::new();
+    let uri = Url::parse("file:///fixtures/valid.air").unwrap();
+    client.open(&uri, VALID);
+
+    let mut hover = |line, character| {
+        let result = client.request(
+            HoverRequest::METHOD,
+            HoverParams {
+                text_document_position_params: TextDocumentPositionParams::new(
+                    TextDocumentIdentifier::new(uri.clone()),
+                    Position::new(line, character),
+                ),
+                work_done_progress_params: Default::default(),
+            },
+        );
+        serde_json::from_value::<Option<Hover>>(result).unwrap()
+    };
+
+    // `fmp` in `enf fmp[1]' = fmp[1] + fmp[0] * clk`
+    let Some(Hover {
+        contents: HoverContents::Markup(contents),
+        range,
+    }) = hover(13, 20)
+    else {
+        panic!("expected a hover of fmp");
+    };
+    assert_eq!(
+        contents.value,
+        "`fmp`: columns 1 to 2 of the `$main` trace segment"
+    );
+    assert_eq!(
+        range,
+        Some(Range::new(Position::new(13, 18), Position::new(13, 21)))
+    );
+
+    // `clk` in `enf clk' = clk + 1`
+    let Some(Hover {
+        contents: HoverContents::Markup(contents),
+        ..
+    }) = hover(12, 8)
+    else {
+        panic!("expected a hover of clk");
+    };
+    assert_eq!(
+        contents.value,
+        "`clk`: column 0 of the `$main` trace segment"
+    );
+
+    // `enf` is not a trace column
+    assert_eq!(hover(12, 5), None);
+}